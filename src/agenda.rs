@@ -0,0 +1,176 @@
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use colored::*;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::io::{IsTerminal, Write};
+
+use crate::{get_tasks_from_cache, output, push_dirty_tasks, save_tasks_locally, template, App, Config, Task};
+
+// `agenda bob` (or `agenda WORK`) gathers what to bring to a meeting: open tasks for
+// that person or project, then anything else blocked or in review. Items talked about
+// can be tagged #discussed-YYYY-MM-DD afterwards; the tag replaces an older one.
+
+/// Statuses worth raising in any meeting
+const RAISE_STATUSES: [&str; 2] = ["blocked", "review"];
+
+static DISCUSSED_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s*#discussed-\d{4}-\d{2}-\d{2}\b").unwrap());
+
+pub struct Agenda<'a> {
+    /// "+bob" or the project's name
+    pub label: String,
+    pub topics: Vec<&'a Task>,
+    /// Blocked or in-review tasks not already among the topics
+    pub raise: Vec<&'a Task>,
+}
+
+/// What's open for `who`: a project when it names one, otherwise a person (their +name,
+/// or the name anywhere in the title)
+pub fn gather<'a>(config: &Config, tasks: &'a [Task], who: &str) -> Agenda<'a> {
+    let project = config.projects.keys().find(|p| p.eq_ignore_ascii_case(who));
+    let name = who.trim_start_matches('+');
+    let mentions = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(name))).unwrap();
+    let matches = |t: &Task| match project {
+        Some(project) => t.project.as_deref().is_some_and(|p| p.eq_ignore_ascii_case(project)),
+        None => t.person().is_some_and(|p| p.eq_ignore_ascii_case(name)) || mentions.is_match(&t.title),
+    };
+
+    let by_priority = |list: &mut Vec<&Task>| list.sort_by_key(|t| (config.priority_rank(t.priority.as_deref()), t.date.clone()));
+    let open = tasks.iter().filter(|t| !t.is_done());
+    let mut topics: Vec<&Task> = open.clone().filter(|t| matches(t)).collect();
+    let mut raise: Vec<&Task> = open
+        .filter(|t| !matches(t))
+        .filter(|t| t.status.as_deref().is_some_and(|s| RAISE_STATUSES.iter().any(|r| r.eq_ignore_ascii_case(s))))
+        .collect();
+    by_priority(&mut topics);
+    by_priority(&mut raise);
+
+    Agenda {
+        label: project.cloned().unwrap_or_else(|| format!("+{}", name)),
+        topics,
+        raise,
+    }
+}
+
+/// The title with its #discussed tag set to `day`
+pub fn mark_discussed(title: &str, day: NaiveDate) -> String {
+    format!("{} #discussed-{}", DISCUSSED_REGEX.replace_all(title, "").trim_end(), day.format("%Y-%m-%d"))
+}
+
+/// "1 3 4", "1,3" or "a" (all) as 0-based indexes below `count`
+fn parse_picks(input: &str, count: usize) -> Vec<usize> {
+    if input.trim().eq_ignore_ascii_case("a") {
+        return (0..count).collect();
+    }
+    let mut picks: Vec<usize> = input.split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(|n| n.parse::<usize>().ok())
+        .filter(|n| (1..=count).contains(n))
+        .map(|n| n - 1)
+        .collect();
+    picks.sort();
+    picks.dedup();
+    picks
+}
+
+pub fn cmd_agenda(app: &App, who: &str, markdown: bool) -> Result<()> {
+    let config = &app.config;
+    let tasks = get_tasks_from_cache(app)?;
+    let agenda = gather(config, &tasks, who);
+    let items: Vec<&Task> = agenda.topics.iter().chain(&agenda.raise).copied().collect();
+    let today = Local::now().date_naive();
+
+    if markdown {
+        println!("## Agenda: {} ({})\n", agenda.label, today.format("%Y-%m-%d"));
+        for task in &agenda.topics {
+            println!("- [ ] {}", task.title);
+        }
+        if !agenda.raise.is_empty() {
+            println!("\n### Blocked / in review\n");
+            for task in &agenda.raise {
+                println!("- [ ] {} ({})", task.title, task.status.as_deref().unwrap_or_default());
+            }
+        }
+        return Ok(());
+    }
+
+    if items.is_empty() {
+        println!("{}", format!("Nothing open for {}.", agenda.label).green());
+        return Ok(());
+    }
+
+    println!("{}", output::header(&format!("🗓  Agenda: {} ({} items)\n", agenda.label, items.len())));
+    let line = |n: usize, task: &Task| {
+        let mut meta: Vec<String> = [task.priority.clone(), task.project.clone(), task.status.clone()].into_iter().flatten().collect();
+        if !task.date.is_empty() {
+            meta.push(format!("due {}", template::day_label(&task.date, today, false)));
+        }
+        println!("  {}. {} {}", n, task.title, meta.join(" · ").dimmed());
+    };
+    for (idx, task) in agenda.topics.iter().enumerate() {
+        line(idx + 1, task);
+    }
+    if !agenda.raise.is_empty() {
+        println!("\n{}", "  Blocked / in review:".yellow());
+        for (idx, task) in agenda.raise.iter().enumerate() {
+            line(agenda.topics.len() + idx + 1, task);
+        }
+    }
+
+    if !std::io::stdin().is_terminal() || output::is_quiet() {
+        return Ok(());
+    }
+    print!("\n{} ", "Mark discussed (e.g. 1 3, a=all, Enter=none):".bold());
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    let picks = parse_picks(&input, items.len());
+    if picks.is_empty() {
+        return Ok(());
+    }
+
+    let discussed: Vec<Task> = picks.iter().map(|&i| {
+        let mut task = items[i].clone();
+        task.title = mark_discussed(&task.title, today);
+        task
+    }).collect();
+    save_tasks_locally(&app.db, config, &discussed)?;
+    push_dirty_tasks(app)?;
+    println!("{}", format!("🏷  Tagged {} as #discussed-{}", discussed.len(), today.format("%Y-%m-%d")).green());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gather_agenda() {
+        let config = crate::create_default_config();
+        let tasks = [
+            Task::parse_with_config("[2026-03-04][P2][WORK] Budget review +bob", "Inbox", None),
+            Task::parse_with_config("[2026-03-04][P1][LIFE] Return Bob's drill", "Inbox", None),
+            Task::parse_with_config("[2026-03-04][P0][WORK][blocked] Deploy waits on infra", "Inbox", None),
+            Task::parse_with_config("[2026-03-04][P1][WORK] Bobsled tickets", "Inbox", None),
+        ];
+        let titles = |list: &[&Task]| list.iter().map(|t| t.title.clone()).collect::<Vec<_>>();
+
+        let agenda = gather(&config, &tasks, "bob");
+        assert_eq!(agenda.label, "+bob");
+        assert_eq!(titles(&agenda.topics), vec!["Return Bob's drill", "Budget review +bob"]);
+        assert_eq!(titles(&agenda.raise), vec!["Deploy waits on infra"]);
+
+        let agenda = gather(&config, &tasks, "work");
+        assert_eq!(agenda.label, "WORK");
+        assert_eq!(agenda.topics.len(), 3);
+        assert!(agenda.raise.is_empty());
+    }
+
+    #[test]
+    fn test_mark_discussed() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        assert_eq!(mark_discussed("Budget +bob", day), "Budget +bob #discussed-2026-03-04");
+        assert_eq!(mark_discussed("Budget #discussed-2026-02-01 +bob", day), "Budget +bob #discussed-2026-03-04");
+        assert_eq!(parse_picks("3, 1 9 x", 4), vec![0, 2]);
+        assert_eq!(parse_picks("a", 2), vec![0, 1]);
+    }
+}
@@ -1,12 +1,15 @@
 use anyhow::{Context, Result, anyhow};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::logging::LoggedAsyncRequest;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AirtableConfig {
     pub api_key: String,
     pub base_id: String,
     pub table_name: String,
+    /// Let Airtable add select options it doesn't have yet, instead of rejecting the record
+    #[serde(default)]
+    pub typecast: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -41,8 +44,9 @@ pub struct AirtableFields {
     #[serde(rename = "CreatedDate")]
     pub created_date: Option<String>,
     
+    /// Text, or a collaborator ({"email": ...}) when the field is a user field
     #[serde(rename = "Assignee")]
-    pub assignee: Option<String>,
+    pub assignee: Option<serde_json::Value>,
     
     #[serde(rename = "Tags")]
     pub tags: Option<String>,
@@ -55,12 +59,14 @@ pub struct AirtableFields {
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 struct AirtableResponse {
     records: Vec<AirtableRecord>,
     offset: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 struct AirtableRecord {
     id: String,
     fields: AirtableFields,
@@ -68,14 +74,47 @@ struct AirtableRecord {
     created_time: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct SchemaResponse {
+    tables: Vec<SchemaTable>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SchemaTable {
+    id: String,
+    name: String,
+    fields: Vec<SchemaField>,
+}
+
+/// A field of the table, as the Metadata API describes it
+#[derive(Debug, Deserialize)]
+pub struct SchemaField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: String,
+    #[serde(default)]
+    pub options: Option<FieldOptions>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FieldOptions {
+    #[serde(default)]
+    pub choices: Vec<Choice>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Choice {
+    pub name: String,
+}
+
 pub struct AirtableClient {
     config: AirtableConfig,
-    client: reqwest::blocking::Client,
+    client: reqwest::Client,
 }
 
 impl AirtableClient {
     pub fn new(config: AirtableConfig) -> Result<Self> {
-        let client = reqwest::blocking::Client::new();
+        let client = reqwest::Client::new();
         Ok(Self { config, client })
     }
 
@@ -86,8 +125,58 @@ impl AirtableClient {
         )
     }
 
+    /// Read one record, to check the token can read the table
+    pub async fn check_access(&self) -> Result<()> {
+        let response = self
+            .client
+            .get(self.get_base_url())
+            .query(&[("maxRecords", "1")])
+            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .send_logged()
+            .await
+            .context("Failed to reach Airtable")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Airtable API error {}: {}", status, body));
+        }
+
+        Ok(())
+    }
+
+    /// The table's fields from the Metadata API (the token needs `schema.bases:read`)
+    pub async fn fetch_fields(&self) -> Result<Vec<SchemaField>> {
+        let url = format!("https://api.airtable.com/v0/meta/bases/{}/tables", self.config.base_id);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .send_logged()
+            .await
+            .context("Failed to fetch the Airtable schema")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Airtable schema error {}: {}", status, body));
+        }
+
+        let schema: SchemaResponse = response
+            .json()
+            .await
+            .context("Failed to parse Airtable schema")?;
+
+        schema.tables.into_iter()
+            .find(|t| t.name == self.config.table_name || t.id == self.config.table_name)
+            .map(|t| t.fields)
+            .ok_or_else(|| anyhow!("No table named {} in the Airtable base", self.config.table_name))
+    }
+
     /// Fetch all tasks from Airtable
-    pub fn fetch_all_tasks(&self) -> Result<Vec<AirtableTask>> {
+    #[allow(dead_code)]
+    pub async fn fetch_all_tasks(&self) -> Result<Vec<AirtableTask>> {
         let mut all_records = Vec::new();
         let mut offset: Option<String> = None;
 
@@ -101,17 +190,19 @@ impl AirtableClient {
                 .client
                 .get(&url)
                 .header("Authorization", format!("Bearer {}", self.config.api_key))
-                .send()
+                .send_logged()
+                .await
                 .context("Failed to fetch tasks from Airtable")?;
 
             if !response.status().is_success() {
                 let status = response.status();
-                let body = response.text().unwrap_or_default();
+                let body = response.text().await.unwrap_or_default();
                 return Err(anyhow!("Airtable API error {}: {}", status, body));
             }
 
             let airtable_response: AirtableResponse = response
                 .json()
+                .await
                 .context("Failed to parse Airtable response")?;
 
             for record in airtable_response.records {
@@ -131,11 +222,12 @@ impl AirtableClient {
     }
 
     /// Create a new task in Airtable
-    pub fn create_task(&self, fields: AirtableFields) -> Result<AirtableTask> {
+    pub async fn create_task(&self, fields: AirtableFields) -> Result<AirtableTask> {
         let url = self.get_base_url();
 
         let payload = serde_json::json!({
-            "fields": fields
+            "fields": fields,
+            "typecast": self.config.typecast,
         });
 
         let response = self
@@ -144,17 +236,19 @@ impl AirtableClient {
             .header("Authorization", format!("Bearer {}", self.config.api_key))
             .header("Content-Type", "application/json")
             .json(&payload)
-            .send()
+            .send_logged()
+            .await
             .context("Failed to create task in Airtable")?;
 
         if !response.status().is_success() {
             let status = response.status();
-            let body = response.text().unwrap_or_default();
+            let body = response.text().await.unwrap_or_default();
             return Err(anyhow!("Airtable create error {}: {}", status, body));
         }
 
         let record: AirtableRecord = response
             .json()
+            .await
             .context("Failed to parse Airtable create response")?;
 
         Ok(AirtableTask {
@@ -164,11 +258,12 @@ impl AirtableClient {
     }
 
     /// Update an existing task in Airtable
-    pub fn update_task(&self, task_id: &str, fields: AirtableFields) -> Result<AirtableTask> {
+    pub async fn update_task(&self, task_id: &str, fields: AirtableFields) -> Result<AirtableTask> {
         let url = format!("{}/{}", self.get_base_url(), task_id);
 
         let payload = serde_json::json!({
-            "fields": fields
+            "fields": fields,
+            "typecast": self.config.typecast,
         });
 
         let response = self
@@ -177,17 +272,19 @@ impl AirtableClient {
             .header("Authorization", format!("Bearer {}", self.config.api_key))
             .header("Content-Type", "application/json")
             .json(&payload)
-            .send()
+            .send_logged()
+            .await
             .context("Failed to update task in Airtable")?;
 
         if !response.status().is_success() {
             let status = response.status();
-            let body = response.text().unwrap_or_default();
+            let body = response.text().await.unwrap_or_default();
             return Err(anyhow!("Airtable update error {}: {}", status, body));
         }
 
         let record: AirtableRecord = response
             .json()
+            .await
             .context("Failed to parse Airtable update response")?;
 
         Ok(AirtableTask {
@@ -197,19 +294,21 @@ impl AirtableClient {
     }
 
     /// Delete a task from Airtable
-    pub fn delete_task(&self, task_id: &str) -> Result<()> {
+    #[allow(dead_code)]
+    pub async fn delete_task(&self, task_id: &str) -> Result<()> {
         let url = format!("{}/{}", self.get_base_url(), task_id);
 
         let response = self
             .client
             .delete(&url)
             .header("Authorization", format!("Bearer {}", self.config.api_key))
-            .send()
+            .send_logged()
+            .await
             .context("Failed to delete task from Airtable")?;
 
         if !response.status().is_success() {
             let status = response.status();
-            let body = response.text().unwrap_or_default();
+            let body = response.text().await.unwrap_or_default();
             return Err(anyhow!("Airtable delete error {}: {}", status, body));
         }
 
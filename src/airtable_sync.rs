@@ -1,27 +1,135 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
+use futures_util::{stream, StreamExt};
 use regex::Regex;
+use std::collections::{BTreeSet, HashMap};
 use once_cell::sync::Lazy;
-use crate::airtable::{AirtableClient, AirtableFields, AirtableTask};
-use crate::sync::{SyncManager, CachedTask};
+use crate::airtable::{AirtableClient, AirtableFields, SchemaField};
+use crate::sync::{content_hash, pick_assignee, SyncManager, CachedTask};
+use crate::privacy::PrivacyConfig;
+use crate::StatusOption;
 
 // Parse taskgarden format: [date][priority][project][status][@context]{time} title
+/// sync_state key: Google Task ID → hash of the fields last pushed to Airtable
+const HASH_KEY: &str = "airtable_hash_map";
+
 static TASK_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"\[([^\]]+)\]\[([^\]]+)\]\[([^\]]+)\](?:\[([^\]]+)\])?(?:\[@([^\]]+)\])?(?:\{([^}]+)\})?\s*(.+)").unwrap()
 });
 
-pub struct AirtableSync {
-    sync_manager: SyncManager,
+pub struct AirtableSync<'a> {
+    sync_manager: &'a SyncManager,
     airtable_client: AirtableClient,
+    statuses: &'a [StatusOption],
+    select_options: Option<SelectOptions>,
+    excluded_projects: Vec<String>,
+    privacy: PrivacyConfig,
+    assignees: HashMap<String, String>,
+    default_assignee: Option<String>,
+    /// The Assignee field's type from the schema, when known
+    assignee_type: Option<String>,
+}
+
+/// The table's single-select options, and how local values map onto them
+pub struct SelectOptions {
+    /// Field name → its existing options
+    choices: HashMap<String, Vec<String>>,
+    /// Field name → local value → Airtable option
+    map: HashMap<String, HashMap<String, String>>,
+    /// Values without an option are sent anyway (with typecast, Airtable adds them)
+    create: bool,
+}
+
+impl SelectOptions {
+    pub fn new(fields: Vec<SchemaField>, map: HashMap<String, HashMap<String, String>>, create: bool) -> Self {
+        let choices = fields.into_iter()
+            .filter(|f| f.field_type == "singleSelect")
+            .map(|f| (f.name, f.options.map(|o| o.choices.into_iter().map(|c| c.name).collect()).unwrap_or_default()))
+            .collect();
+        Self { choices, map, create }
+    }
+
+    /// The option to send for `value` in `field`, or None when there's none to send.
+    /// Values that can't be sent are added to `unmapped`.
+    fn resolve(&self, field: &str, value: Option<String>, unmapped: &mut BTreeSet<(String, String)>) -> Option<String> {
+        let value = value?;
+        let value = self.map.get(field).and_then(|m| m.get(&value)).cloned().unwrap_or(value);
+        let Some(choices) = self.choices.get(field) else {
+            return Some(value);
+        };
+        if let Some(choice) = choices.iter().find(|c| c.eq_ignore_ascii_case(&value)) {
+            return Some(choice.clone());
+        }
+        if self.create {
+            return Some(value);
+        }
+        unmapped.insert((field.to_string(), value));
+        None
+    }
+
+    fn apply(&self, mut fields: AirtableFields, unmapped: &mut BTreeSet<(String, String)>) -> AirtableFields {
+        fields.priority = self.resolve("Priority", fields.priority.take(), unmapped);
+        fields.project = self.resolve("Project", fields.project.take(), unmapped);
+        fields.status = self.resolve("Status", fields.status.take(), unmapped);
+        fields.context = self.resolve("Context", fields.context.take(), unmapped);
+        fields
+    }
 }
 
-impl AirtableSync {
-    pub fn new(sync_manager: SyncManager, airtable_client: AirtableClient) -> Self {
+impl<'a> AirtableSync<'a> {
+    pub fn new(sync_manager: &'a SyncManager, airtable_client: AirtableClient, statuses: &'a [StatusOption]) -> Self {
         Self {
             sync_manager,
             airtable_client,
+            statuses,
+            select_options: None,
+            excluded_projects: Vec::new(),
+            privacy: PrivacyConfig::default(),
+            assignees: HashMap::new(),
+            default_assignee: None,
+            assignee_type: None,
         }
     }
 
+    /// Assign pushed tasks by "+person" or project (emails for a user field), else to `default`
+    pub fn with_assignees(mut self, assignees: HashMap<String, String>, default: Option<String>, field_type: Option<String>) -> Self {
+        self.assignees = assignees;
+        self.default_assignee = default;
+        self.assignee_type = field_type;
+        self
+    }
+
+    fn assignee(&self, title: &str, project: Option<&str>) -> Option<serde_json::Value> {
+        let assignee = pick_assignee(&self.assignees, self.default_assignee.as_ref(), title, project)?;
+        Some(match self.assignee_type.as_deref() {
+            Some("singleCollaborator") => serde_json::json!({ "email": assignee }),
+            Some("multipleCollaborators") => serde_json::json!([{ "email": assignee }]),
+            _ => assignee.clone().into(),
+        })
+    }
+
+    /// Strip private fields and tags from pushed tasks, and skip excluded ones
+    pub fn with_privacy(mut self, privacy: PrivacyConfig) -> Self {
+        self.privacy = privacy;
+        self
+    }
+
+    /// Leave tasks in these projects out of the push
+    pub fn with_excluded_projects(mut self, projects: Vec<String>) -> Self {
+        self.excluded_projects = projects;
+        self
+    }
+
+    fn is_excluded(&self, task: &CachedTask) -> bool {
+        Self::parse_task_title(&task.title).project
+            .is_some_and(|project| self.excluded_projects.iter().any(|p| p.eq_ignore_ascii_case(&project)))
+    }
+
+    /// Check select values against the table's options before pushing
+    pub fn with_select_options(mut self, select_options: SelectOptions) -> Self {
+        self.select_options = Some(select_options);
+        self
+    }
+
     /// Parse a taskgarden formatted title into structured fields
     fn parse_task_title(title: &str) -> ParsedTask {
         if let Some(caps) = TASK_REGEX.captures(title) {
@@ -49,17 +157,18 @@ impl AirtableSync {
     }
 
     /// Convert a CachedTask to AirtableFields
-    fn to_airtable_fields(task: &CachedTask) -> AirtableFields {
+    fn to_airtable_fields(&self, task: &CachedTask) -> AirtableFields {
         let parsed = Self::parse_task_title(&task.title);
         
         // Extract tags from title (anything with #)
         let tags = extract_hashtags(&parsed.title);
+        let assignee = self.assignee(&task.title, parsed.project.as_deref());
         
         AirtableFields {
             title: parsed.title.clone(),
             priority: parsed.priority,
             project: parsed.project,
-            status: parsed.status.or_else(|| {
+            status: parsed.status.map(|status| self.airtable_status(status)).or_else(|| {
                 // Map Google Tasks status to our status
                 match task.status.as_str() {
                     "needsAction" => Some("needsTriage".to_string()),
@@ -71,21 +180,44 @@ impl AirtableSync {
             time_estimate: parsed.time_estimate,
             due_date: parsed.date.clone(),
             created_date: task.created.clone(),
-            assignee: None, // Can be set manually in Airtable or via config
+            assignee,
             tags: if tags.is_empty() { None } else { Some(tags.join(", ")) },
-            notes: task.links.clone(),
+            notes: Self::notes_field(task),
             completed: Some(task.status == "completed"),
         }
     }
 
-    /// Push all tasks from SQLite cache to Airtable
-    pub fn push_to_airtable(&self) -> Result<PushStats> {
+    /// The configured Airtable name for a status, or the status as is
+    fn airtable_status(&self, status: String) -> String {
+        self.statuses.iter()
+            .find(|s| s.name == status)
+            .and_then(|s| s.airtable.clone())
+            .unwrap_or(status)
+    }
+
+    /// The task's own notes, followed by its links
+    fn notes_field(task: &CachedTask) -> Option<String> {
+        let parts: Vec<&str> = [task.user_description.as_deref(), task.links.as_deref()]
+            .into_iter()
+            .flatten()
+            .filter(|p| !p.trim().is_empty())
+            .collect();
+        if parts.is_empty() { None } else { Some(parts.join("\n\n")) }
+    }
+
+    /// Push all tasks from SQLite cache to Airtable, `concurrency` requests at a time
+    /// (with `dry_run`, only print what would change). Records whose fields hash the same
+    /// as at the last push are skipped unless `force`.
+    pub async fn push_to_airtable(&self, dry_run: bool, force: bool, concurrency: usize) -> Result<PushStats> {
         let cached_tasks = self.sync_manager.get_all_cached_tasks()?;
         
         // Get existing Airtable task IDs (stored in sync state)
         let airtable_map = self.get_airtable_id_map()?;
+        let mut hashes = self.sync_manager.get_id_map(HASH_KEY)?;
+        let mut unmapped = BTreeSet::new();
         
         let mut stats = PushStats::default();
+        let mut pushes = Vec::new();
         
         for task in cached_tasks {
             // Skip completed tasks older than 7 days (optional filter)
@@ -93,36 +225,69 @@ impl AirtableSync {
                 // Could add date filtering here
                 continue;
             }
+            if self.is_excluded(&task) || self.privacy.excludes(&task) {
+                continue;
+            }
             
-            let airtable_fields = Self::to_airtable_fields(&task);
-            
-            if let Some(airtable_id) = airtable_map.get(&task.id) {
-                // Update existing record
-                match self.airtable_client.update_task(airtable_id, airtable_fields) {
-                    Ok(_) => stats.updated += 1,
-                    Err(e) => {
-                        eprintln!("Failed to update task {}: {}", task.id, e);
-                        stats.errors += 1;
-                    }
+            let mut airtable_fields = self.to_airtable_fields(&self.privacy.apply(&task));
+            if let Some(ref select_options) = self.select_options {
+                airtable_fields = select_options.apply(airtable_fields, &mut unmapped);
+            }
+            let hash = content_hash(&airtable_fields);
+            if !force && airtable_map.contains_key(&task.id) && hashes.get(&task.id) == Some(&hash) {
+                stats.unchanged += 1;
+                continue;
+            }
+
+            if dry_run {
+                let exists = airtable_map.contains_key(&task.id);
+                println!("  {} {}", if exists { "~ update" } else { "+ create" }, airtable_fields.title);
+                if exists { stats.updated += 1 } else { stats.created += 1 }
+                continue;
+            }
+
+            let airtable_id = airtable_map.get(&task.id).cloned();
+            pushes.push((task.id, airtable_id, airtable_fields, hash));
+        }
+        stats.unmapped = unmapped.into_iter().collect();
+
+        let client = &self.airtable_client;
+        let mut results = stream::iter(pushes)
+            .map(|(task_id, airtable_id, fields, hash)| async move {
+                let result = match airtable_id {
+                    // Update existing record
+                    Some(ref airtable_id) => client.update_task(airtable_id, fields).await,
+                    // Create new record
+                    None => client.create_task(fields).await,
+                };
+                (task_id, airtable_id.is_some(), result, hash)
+            })
+            .buffer_unordered(concurrency.max(1));
+
+        while let Some((task_id, existed, result, hash)) = results.next().await {
+            match result {
+                Ok(_) if existed => {
+                    hashes.insert(task_id, hash);
+                    stats.updated += 1;
                 }
-            } else {
-                // Create new record
-                match self.airtable_client.create_task(airtable_fields) {
-                    Ok(airtable_task) => {
-                        if let Some(id) = airtable_task.id {
-                            // Store mapping
-                            self.save_airtable_id(&task.id, &id)?;
-                            stats.created += 1;
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to create task {}: {}", task.id, e);
-                        stats.errors += 1;
+                Ok(airtable_task) => {
+                    if let Some(id) = airtable_task.id {
+                        // Store mapping
+                        self.save_airtable_id(&task_id, &id)?;
+                        hashes.insert(task_id, hash);
+                        stats.created += 1;
                     }
                 }
+                Err(e) => {
+                    eprintln!("Failed to {} task {}: {}", if existed { "update" } else { "create" }, task_id, e);
+                    stats.errors += 1;
+                }
             }
         }
-        
+
+        if !dry_run {
+            self.sync_manager.set_state(HASH_KEY, &serde_json::to_string(&hashes)?)?;
+        }
         Ok(stats)
     }
 
@@ -164,7 +329,11 @@ struct ParsedTask {
 pub struct PushStats {
     pub created: usize,
     pub updated: usize,
+    /// Skipped because nothing changed since the last push
+    pub unchanged: usize,
     pub errors: usize,
+    /// (field, value) pairs with no select option, left off the records
+    pub unmapped: Vec<(String, String)>,
 }
 
 /// Extract hashtags from a string
@@ -196,6 +365,68 @@ mod tests {
         assert_eq!(parsed.title, "Fix editor bug #DeepWork");
     }
 
+    #[test]
+    fn test_unchanged_tasks_are_skipped() {
+        let db = SyncManager::open(std::path::Path::new(":memory:")).unwrap();
+        let mut task = CachedTask::parse_from_emoji_string("[2026-01-27][P0][WORK] Ship it", "g1", "list");
+        task.status = "needsAction".into();
+        db.upsert_task(&task).unwrap();
+        db.set_state("airtable_id_map", r#"{"g1": "rec1"}"#).unwrap();
+        let client = AirtableClient::new(crate::airtable::AirtableConfig { api_key: String::new(), base_id: String::new(), table_name: String::new(), typecast: false }).unwrap();
+        let airtable_sync = AirtableSync::new(&db, client, &[]);
+        let push = |force| crate::block_on(airtable_sync.push_to_airtable(true, force, 4)).unwrap();
+
+        // Never pushed from here, so no hash yet
+        assert_eq!((push(false).updated, push(false).unchanged), (1, 0));
+
+        let hash = content_hash(&airtable_sync.to_airtable_fields(&task));
+        db.set_state(HASH_KEY, &serde_json::json!({ "g1": hash }).to_string()).unwrap();
+        assert_eq!((push(false).updated, push(false).unchanged), (0, 1));
+        assert_eq!(push(true).updated, 1);
+
+        task.title = "[2026-01-27][P1][WORK] Ship it".into();
+        db.upsert_task(&task).unwrap();
+        assert_eq!(push(false).updated, 1);
+    }
+
+    #[test]
+    fn test_select_options() {
+        let fields: Vec<SchemaField> = serde_json::from_value(serde_json::json!([
+            {"name": "Title", "type": "singleLineText"},
+            {"name": "Priority", "type": "singleSelect", "options": {"choices": [{"name": "Urgent"}, {"name": "P1"}]}},
+            {"name": "Project", "type": "singleSelect", "options": {"choices": [{"name": "Work"}]}},
+        ])).unwrap();
+        let map = HashMap::from([("Priority".to_string(), HashMap::from([("P0".to_string(), "Urgent".to_string())]))]);
+        let strict = SelectOptions::new(fields, map, false);
+        let mut unmapped = BTreeSet::new();
+
+        assert_eq!(strict.resolve("Priority", Some("P0".into()), &mut unmapped).as_deref(), Some("Urgent"));
+        assert_eq!(strict.resolve("Priority", Some("p1".into()), &mut unmapped).as_deref(), Some("P1"));
+        assert_eq!(strict.resolve("Project", Some("WORK".into()), &mut unmapped).as_deref(), Some("Work"));
+        // Not a select field in the schema
+        assert_eq!(strict.resolve("Context", Some("@home".into()), &mut unmapped).as_deref(), Some("@home"));
+        assert_eq!(strict.resolve("Priority", Some("P5".into()), &mut unmapped), None);
+        assert_eq!(unmapped.into_iter().collect::<Vec<_>>(), vec![("Priority".to_string(), "P5".to_string())]);
+
+        let creating = SelectOptions { create: true, ..strict };
+        let mut unmapped = BTreeSet::new();
+        assert_eq!(creating.resolve("Priority", Some("P5".into()), &mut unmapped).as_deref(), Some("P5"));
+        assert!(unmapped.is_empty());
+    }
+
+    #[test]
+    fn test_assignee_field() {
+        let db = SyncManager::open(std::path::Path::new(":memory:")).unwrap();
+        let client = AirtableClient::new(crate::airtable::AirtableConfig { api_key: String::new(), base_id: String::new(), table_name: String::new(), typecast: false }).unwrap();
+        let assignees = HashMap::from([("+sam".to_string(), "sam@example.com".to_string())]);
+        let task = CachedTask::parse_from_emoji_string("[2026-01-27][P1][WORK] Review deck +Sam", "g1", "list");
+
+        let airtable_sync = AirtableSync::new(&db, client, &[]).with_assignees(assignees, None, Some("singleCollaborator".into()));
+        assert_eq!(airtable_sync.to_airtable_fields(&task).assignee, Some(serde_json::json!({ "email": "sam@example.com" })));
+        let airtable_sync = AirtableSync { assignee_type: Some("singleLineText".into()), ..airtable_sync };
+        assert_eq!(airtable_sync.to_airtable_fields(&task).assignee, Some(serde_json::json!("sam@example.com")));
+    }
+
     #[test]
     fn test_extract_hashtags() {
         let text = "Fix bug #DeepWork #Urgent test #tag";
@@ -0,0 +1,367 @@
+use anyhow::{anyhow, Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::logging::LoggedRequest;
+use crate::{get_tasks_from_cache, push_dirty_tasks, update_task_locally, App, Config, Task};
+
+// Asana as a team-visibility target next to Airtable and ClickUp: `sync --asana` pushes
+// open tasks into one project, in a section per priority (created when missing), assigned
+// by +person through `assignees`. Tasks completed in Asana are completed here first, so a
+// teammate ticking one off isn't undone by the push that follows.
+
+/// sync_state key: Google task ID → Asana task gid
+const ID_MAP: &str = "asana_id_map";
+/// sync_state key: Google task ID → the section gid it was last put in
+const SECTION_MAP: &str = "asana_section_map";
+const API_URL: &str = "https://app.asana.com/api/1.0";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AsanaConfig {
+    pub enabled: bool,
+    /// Personal access token (Asana → My settings → Apps → Developer apps)
+    pub token: String,
+    /// gid of the project tasks are pushed to (the number in its URL)
+    pub project: String,
+    /// Priority name → section name; a priority not listed gets a section of its own name
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub sections: HashMap<String, String>,
+    /// Section for tasks without a priority
+    pub untriaged_section: String,
+    /// +person → Asana user gid or email
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub assignees: HashMap<String, String>,
+    /// Assignee for tasks without a +person ("me" for yourself); None leaves them unassigned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_assignee: Option<String>,
+}
+
+impl Default for AsanaConfig {
+    fn default() -> Self {
+        AsanaConfig {
+            enabled: false,
+            token: String::new(),
+            project: String::new(),
+            sections: HashMap::new(),
+            untriaged_section: "Untriaged".into(),
+            assignees: HashMap::new(),
+            default_assignee: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Named {
+    gid: String,
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Completion {
+    gid: String,
+    #[serde(default)]
+    completed: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct PushStats {
+    pub created: usize,
+    pub updated: usize,
+    pub errors: usize,
+}
+
+/// The section a task belongs in
+pub fn section_name<'a>(settings: &'a AsanaConfig, priority: Option<&'a str>) -> &'a str {
+    match priority {
+        Some(p) => settings.sections.get(p).map(String::as_str).unwrap_or(p),
+        None => &settings.untriaged_section,
+    }
+}
+
+/// The assignee for a task's +person (matched case-insensitively), else the default
+pub fn assignee<'a>(settings: &'a AsanaConfig, task: &Task) -> Option<&'a str> {
+    match task.person() {
+        Some(person) => settings.assignees.iter()
+            .find(|(name, _)| name.trim_start_matches('+').eq_ignore_ascii_case(person))
+            .map(|(_, user)| user.as_str()),
+        None => settings.default_assignee.as_deref(),
+    }
+}
+
+/// The task fields pushed to Asana; section and project membership are set separately
+pub fn asana_task(config: &Config, settings: &AsanaConfig, task: &Task) -> serde_json::Value {
+    let due_on = chrono::NaiveDate::parse_from_str(&task.date, "%Y-%m-%d").ok().map(|d| d.format("%Y-%m-%d").to_string());
+    let mut notes = task.notes.clone().unwrap_or_default();
+    let details: Vec<String> = [
+        task.project.as_ref().map(|p| format!("Project: {}", p)),
+        task.status.as_ref().filter(|s| !config.is_done_status(Some(s))).map(|s| format!("Status: {}", s)),
+        task.context.as_ref().map(|c| format!("Context: {}", c)),
+        task.time.as_ref().map(|t| format!("Estimate: {}", t)),
+    ].into_iter().flatten().collect();
+    if !details.is_empty() {
+        if !notes.is_empty() {
+            notes.push_str("\n\n");
+        }
+        notes.push_str(&details.join("\n"));
+    }
+    json!({
+        "name": task.title,
+        "notes": notes,
+        "due_on": due_on,
+        "completed": task.is_done(),
+        "assignee": assignee(settings, task),
+    })
+}
+
+struct Client {
+    http: reqwest::blocking::Client,
+    token: String,
+}
+
+impl Client {
+    fn send(&self, request: reqwest::blocking::RequestBuilder, what: &str) -> Result<serde_json::Value> {
+        let response = request.bearer_auth(&self.token).send_logged()
+            .with_context(|| format!("Failed to {} in Asana", what))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_default();
+            return Err(anyhow!("Asana {} error {}: {}", what, status, body));
+        }
+        let body: serde_json::Value = response.json().context("Failed to parse Asana response")?;
+        Ok(body)
+    }
+
+    /// Every page of a GET that returns `data` plus `next_page`
+    fn get_all(&self, path: &str, query: &[(&str, &str)], what: &str) -> Result<Vec<serde_json::Value>> {
+        let mut items = Vec::new();
+        let mut offset: Option<String> = None;
+        loop {
+            let mut request = self.http.get(format!("{}{}", API_URL, path)).query(query).query(&[("limit", "100")]);
+            if let Some(ref offset) = offset {
+                request = request.query(&[("offset", offset)]);
+            }
+            let page = self.send(request, what)?;
+            items.extend(page["data"].as_array().cloned().unwrap_or_default());
+            match page["next_page"]["offset"].as_str() {
+                Some(next) => offset = Some(next.to_string()),
+                None => return Ok(items),
+            }
+        }
+    }
+
+    fn sections(&self, project: &str) -> Result<Vec<Named>> {
+        let items = self.get_all(&format!("/projects/{}/sections", project), &[], "fetch sections")?;
+        Ok(items.into_iter().filter_map(|s| serde_json::from_value(s).ok()).collect())
+    }
+
+    fn create_section(&self, project: &str, name: &str) -> Result<Named> {
+        let body = self.send(
+            self.http.post(format!("{}/projects/{}/sections", API_URL, project)).json(&json!({ "data": { "name": name } })),
+            "create section",
+        )?;
+        Ok(serde_json::from_value(body["data"].clone())?)
+    }
+
+    fn completions(&self, project: &str) -> Result<Vec<Completion>> {
+        let items = self.get_all(&format!("/projects/{}/tasks", project), &[("opt_fields", "completed")], "fetch tasks")?;
+        Ok(items.into_iter().filter_map(|t| serde_json::from_value(t).ok()).collect())
+    }
+
+    fn create_task(&self, project: &str, fields: &serde_json::Value) -> Result<String> {
+        let mut data = fields.clone();
+        data["projects"] = json!([project]);
+        let body = self.send(self.http.post(format!("{}/tasks", API_URL)).json(&json!({ "data": data })), "create task")?;
+        body["data"]["gid"].as_str().map(str::to_string).context("Asana returned no task gid")
+    }
+
+    fn update_task(&self, gid: &str, fields: &serde_json::Value) -> Result<()> {
+        self.send(self.http.put(format!("{}/tasks/{}", API_URL, gid)).json(&json!({ "data": fields })), "update task")?;
+        Ok(())
+    }
+
+    fn move_to_section(&self, section: &str, gid: &str) -> Result<()> {
+        self.send(
+            self.http.post(format!("{}/sections/{}/addTask", API_URL, section)).json(&json!({ "data": { "task": gid } })),
+            "move task",
+        )?;
+        Ok(())
+    }
+}
+
+/// Complete open tasks whose Asana copies were completed; returns how many
+fn pull_completions(app: &App, client: &Client, settings: &AsanaConfig, tasks: &[Task]) -> Result<usize> {
+    let ids = app.db.get_id_map(ID_MAP)?;
+    let done: Vec<String> = client.completions(&settings.project)?.into_iter()
+        .filter(|c| c.completed)
+        .map(|c| c.gid)
+        .collect();
+
+    let mut completed = 0;
+    for task in tasks.iter().filter(|t| !t.is_done()) {
+        let Some(gid) = task.id.as_ref().and_then(|id| ids.get(id)) else { continue };
+        if done.contains(gid) {
+            let mut finished = task.clone();
+            finished.completed = true;
+            update_task_locally(app, &finished)?;
+            println!("{}", format!("✓ Completed in Asana: {}", task.title).green());
+            completed += 1;
+        }
+    }
+    if completed > 0 {
+        push_dirty_tasks(app)?;
+    }
+    Ok(completed)
+}
+
+/// Open tasks go to Asana, and finished ones whose Asana copy is still to be completed
+fn is_pushed(config: &Config, task: &Task, ids: &HashMap<String, String>) -> bool {
+    (!task.is_done() || task.id.as_ref().is_some_and(|id| ids.contains_key(id)))
+        && config.syncs_project(task.project.as_deref(), "asana")
+}
+
+fn push(app: &App, settings: &AsanaConfig, dry_run: bool) -> Result<PushStats> {
+    let config = &app.config;
+    let mut ids = app.db.get_id_map(ID_MAP)?;
+    let mut placed = app.db.get_id_map(SECTION_MAP)?;
+    let mut stats = PushStats::default();
+
+    let pushed = |t: &Task, ids: &HashMap<String, String>| is_pushed(config, t, ids);
+
+    if dry_run {
+        for task in get_tasks_from_cache(app)?.iter().filter(|t| pushed(t, &ids)) {
+            let exists = task.id.as_ref().is_some_and(|id| ids.contains_key(id));
+            if task.is_done() {
+                println!("  ✓ complete {}", task.title);
+                stats.updated += 1;
+                continue;
+            }
+            println!("  {} {} → {}", if exists { "~ update" } else { "+ create" }, task.title, section_name(settings, task.priority.as_deref()));
+            if exists { stats.updated += 1 } else { stats.created += 1 }
+        }
+        return Ok(stats);
+    }
+
+    let client = Client { http: reqwest::blocking::Client::new(), token: settings.token.clone() };
+    pull_completions(app, &client, settings, &get_tasks_from_cache(app)?)?;
+    let mut sections = client.sections(&settings.project)?;
+
+    let tasks: Vec<Task> = get_tasks_from_cache(app)?.into_iter().filter(|t| pushed(t, &ids)).collect();
+    for task in &tasks {
+        let Some(google_id) = task.id.clone() else { continue };
+        let fields = asana_task(config, settings, task);
+        let name = section_name(settings, task.priority.as_deref());
+        let result = (|| -> Result<()> {
+            if task.is_done() {
+                // Completed there too; the copy needs nothing more from us
+                client.update_task(&ids[&google_id], &fields)?;
+                ids.remove(&google_id);
+                placed.remove(&google_id);
+                app.db.set_state(ID_MAP, &serde_json::to_string(&ids)?)?;
+                app.db.set_state(SECTION_MAP, &serde_json::to_string(&placed)?)?;
+                stats.updated += 1;
+                return Ok(());
+            }
+            let section = match sections.iter().find(|s| s.name.eq_ignore_ascii_case(name)) {
+                Some(section) => section.gid.clone(),
+                None => {
+                    let section = client.create_section(&settings.project, name)?;
+                    let gid = section.gid.clone();
+                    sections.push(section);
+                    gid
+                }
+            };
+            match ids.get(&google_id) {
+                Some(gid) => {
+                    client.update_task(gid, &fields)?;
+                    stats.updated += 1;
+                }
+                None => {
+                    let gid = client.create_task(&settings.project, &fields)?;
+                    ids.insert(google_id.clone(), gid);
+                    app.db.set_state(ID_MAP, &serde_json::to_string(&ids)?)?;
+                    stats.created += 1;
+                }
+            }
+            if placed.get(&google_id) != Some(&section) {
+                client.move_to_section(&section, &ids[&google_id])?;
+                placed.insert(google_id.clone(), section);
+                app.db.set_state(SECTION_MAP, &serde_json::to_string(&placed)?)?;
+            }
+            Ok(())
+        })();
+        if let Err(e) = result {
+            eprintln!("Failed to sync task {} to Asana: {}", google_id, e);
+            stats.errors += 1;
+        }
+    }
+    Ok(stats)
+}
+
+pub fn sync_to_asana(app: &App, dry_run: bool) -> Result<()> {
+    let settings = &app.config.asana;
+    if !settings.enabled {
+        println!("{}", "ℹ️  Asana not enabled. Set 'asana.enabled' in config.json".yellow());
+        return Ok(());
+    }
+    if settings.token.is_empty() || settings.project.is_empty() {
+        anyhow::bail!("Asana needs asana.token and asana.project in config.json");
+    }
+
+    println!("{}", if dry_run { "🔎 Asana (dry run)" } else { "📤 Pushing tasks to Asana..." }.cyan());
+    let stats = push(app, settings, dry_run)?;
+
+    if dry_run {
+        println!("{}", format!("Would create {}, update {}", stats.created, stats.updated).cyan());
+    } else if stats.created > 0 || stats.updated > 0 {
+        println!("{}", format!("✓ Created: {}, Updated: {}, Errors: {}", stats.created, stats.updated, stats.errors).green());
+    } else {
+        println!("{}", "✓ No changes to push".dimmed());
+    }
+    if stats.errors > 0 {
+        println!("{}", format!("⚠️  {} tasks failed to sync", stats.errors).yellow());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asana_task() {
+        let config = crate::create_default_config();
+        let mut settings = AsanaConfig::default();
+        settings.sections.insert("P0".into(), "🔥 Now".into());
+        settings.assignees.insert("+sam".into(), "sam@example.com".into());
+        settings.default_assignee = Some("me".into());
+
+        assert_eq!(section_name(&settings, Some("P0")), "🔥 Now");
+        assert_eq!(section_name(&settings, Some("P2")), "P2");
+        assert_eq!(section_name(&settings, None), "Untriaged");
+
+        let mut task = Task::parse_with_config("[2026-03-04][P0][WORK]{45m} Review deck +Sam", "Inbox", Some(&config));
+        task.notes = Some("Slides in Drive".into());
+        let fields = asana_task(&config, &settings, &task);
+        assert_eq!(fields["due_on"], "2026-03-04");
+        assert_eq!(fields["assignee"], "sam@example.com");
+        assert_eq!(fields["completed"], false);
+        assert_eq!(fields["notes"], "Slides in Drive\n\nProject: WORK\nEstimate: 45m");
+
+        let mine = Task::parse_with_config("Call mom", "Inbox", Some(&config));
+        assert_eq!(assignee(&settings, &mine), Some("me"));
+        let unknown = Task::parse_with_config("Sync with +dana", "Inbox", Some(&config));
+        assert_eq!(assignee(&settings, &unknown), None);
+
+        // Finishing a task completes its Asana copy, if it has one
+        task.id = Some("g1".into());
+        task.completed = true;
+        assert_eq!(asana_task(&config, &settings, &task)["completed"], true);
+        let ids = HashMap::from([("g1".to_string(), "1200".to_string())]);
+        assert!(is_pushed(&config, &task, &ids));
+        assert!(!is_pushed(&config, &task, &HashMap::new()));
+    }
+}
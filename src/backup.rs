@@ -0,0 +1,322 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use crate::sync::SyncManager;
+use crate::BackupPolicy;
+
+// A backup is a .tar.gz holding a snapshot of cache.db and the config file. The
+// cache is copied with VACUUM INTO so writes still sitting in the WAL are included.
+// API keys and other credentials are blanked in the archived config, and dropped
+// from the snapshot's sync_state, unless asked for; restoring such a backup keeps
+// whatever credentials the current config and cache have.
+
+const PREFIX: &str = "thegarden-";
+const SUFFIX: &str = ".tar.gz";
+const CACHE_ENTRY: &str = "cache.db";
+const CONFIG_ENTRY: &str = "config.json";
+
+/// Where each secret sits in config.json; "*" is every entry of a list or map
+const SECRET_PATHS: [&[&str]; 8] = [
+    &["airtable", "api_key"],
+    &["clickup", "api_token"],
+    &["embeddings", "api_key"],
+    &["asana", "token"],
+    &["mqtt", "password"],
+    &["notifiers", "*", "access_token"],
+    &["notifiers", "*", "webhook_url"],
+    &["webhooks", "*", "headers", "*"],
+];
+
+/// sync_state entries holding credentials; in an encrypted cache they're sealed and kept
+const SECRET_STATE_KEYS: [&str; 2] = [crate::mstodo::TOKEN_STATE, crate::serve::WEBHOOK_KEY];
+
+/// Where backups go when no path is given
+pub fn default_dir() -> Result<PathBuf> {
+    Ok(crate::paths::data_dir()?.join("backups"))
+}
+
+fn archive_name() -> String {
+    format!("{}{}{}", PREFIX, Local::now().format("%Y%m%d-%H%M%S"), SUFFIX)
+}
+
+/// Write a backup to `dest` (a file, or a directory to put a timestamped one in).
+/// Returns the archive's path.
+pub fn create(db: &SyncManager, dest: &Path, include_secrets: bool) -> Result<PathBuf> {
+    let archive = if dest.is_dir() || dest.extension().is_none() {
+        fs::create_dir_all(dest).with_context(|| format!("Failed to create {}", dest.display()))?;
+        dest.join(archive_name())
+    } else {
+        dest.to_path_buf()
+    };
+
+    let scratch = std::env::temp_dir().join(format!("thegarden-backup-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&scratch)?;
+    let result = write_archive(db, &archive, &scratch, include_secrets);
+    let _ = fs::remove_dir_all(&scratch);
+    result?;
+    tracing::info!(archive = %archive.display(), include_secrets, "backup written");
+    Ok(archive)
+}
+
+fn write_archive(db: &SyncManager, archive: &Path, scratch: &Path, include_secrets: bool) -> Result<()> {
+    let snapshot = scratch.join(CACHE_ENTRY);
+    db.snapshot_to(&snapshot)?;
+    if !include_secrets {
+        SyncManager::strip_state(&snapshot, &SECRET_STATE_KEYS)?;
+    }
+
+    let file = File::create(archive).with_context(|| format!("Failed to create {}", archive.display()))?;
+    let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    tar.append_path_with_name(&snapshot, CACHE_ENTRY)?;
+
+    let config_path = crate::paths::config_path()?;
+    if config_path.exists() {
+        let mut config: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path)?)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        if !include_secrets {
+            strip_secrets(&mut config);
+        }
+        let contents = serde_json::to_vec_pretty(&config)?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o600);
+        header.set_mtime(Local::now().timestamp() as u64);
+        tar.append_data(&mut header, CONFIG_ENTRY, contents.as_slice())?;
+    }
+
+    tar.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Call `f` with each value at `path` and its JSON pointer
+fn for_each_secret(value: &mut serde_json::Value, path: &[&str], pointer: String, f: &mut dyn FnMut(&str, &mut serde_json::Value)) {
+    let Some((&step, rest)) = path.split_first() else {
+        if !value.is_null() {
+            f(&pointer, value);
+        }
+        return;
+    };
+    let escape = |key: &str| key.replace('~', "~0").replace('/', "~1");
+    match (step, value) {
+        ("*", serde_json::Value::Array(items)) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                for_each_secret(item, rest, format!("{}/{}", pointer, i), f);
+            }
+        }
+        ("*", serde_json::Value::Object(map)) => {
+            for (key, item) in map.iter_mut() {
+                for_each_secret(item, rest, format!("{}/{}", pointer, escape(key)), f);
+            }
+        }
+        (key, serde_json::Value::Object(map)) => {
+            if let Some(item) = map.get_mut(key) {
+                for_each_secret(item, rest, format!("{}/{}", pointer, escape(key)), f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Blank every secret that is set
+fn strip_secrets(config: &mut serde_json::Value) {
+    for path in SECRET_PATHS {
+        for_each_secret(config, path, String::new(), &mut |_, value| *value = serde_json::Value::String(String::new()));
+    }
+}
+
+/// Fill secrets that are blank in `restored` from the same place in `current`
+fn carry_over_secrets(restored: &mut serde_json::Value, current: &serde_json::Value) {
+    for path in SECRET_PATHS {
+        for_each_secret(restored, path, String::new(), &mut |pointer, value| {
+            if value.as_str() != Some("") {
+                return;
+            }
+            if let Some(secret) = current.pointer(pointer).filter(|v| v.as_str().is_some_and(|s| !s.is_empty())) {
+                *value = secret.clone();
+            }
+        });
+    }
+}
+
+/// Replace the cache (and config, if archived) with the archive's. The current state
+/// is backed up first; returns where that safety backup went.
+pub fn restore(archive: &Path) -> Result<PathBuf> {
+    let scratch = std::env::temp_dir().join(format!("thegarden-restore-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&scratch)?;
+    let result = restore_from(archive, &scratch);
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
+
+fn restore_from(archive: &Path, scratch: &Path) -> Result<PathBuf> {
+    let file = File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    let mut tar = tar::Archive::new(GzDecoder::new(file));
+    for entry in tar.entries().context("Not a thegarden backup")? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        if name == CACHE_ENTRY || name == CONFIG_ENTRY {
+            entry.unpack(scratch.join(&name))?;
+        }
+    }
+
+    let restored_cache = scratch.join(CACHE_ENTRY);
+    if !restored_cache.exists() {
+        anyhow::bail!("{} has no {}; is it a thegarden backup?", archive.display(), CACHE_ENTRY);
+    }
+    // Opening it checks it's a readable SQLite file and brings an older schema up to date
+    drop(SyncManager::open(&restored_cache).context("The archived cache is unusable")?);
+
+    let db_path = SyncManager::get_db_path()?;
+    let current = SyncManager::new()?;
+    current.carry_over_state(&restored_cache, &SECRET_STATE_KEYS)?;
+    let safety = create(&current, &default_dir()?, true)?;
+    drop(current);
+
+    for suffix in ["-wal", "-shm"] {
+        let _ = fs::remove_file(format!("{}{}", db_path.display(), suffix));
+    }
+    fs::copy(&restored_cache, &db_path).with_context(|| format!("Failed to write {}", db_path.display()))?;
+
+    let restored_config = scratch.join(CONFIG_ENTRY);
+    if restored_config.exists() {
+        let config_path = crate::paths::config_path()?;
+        let mut config: serde_json::Value = serde_json::from_str(&fs::read_to_string(&restored_config)?)?;
+        if let Some(current) = fs::read_to_string(&config_path).ok().and_then(|c| serde_json::from_str(&c).ok()) {
+            carry_over_secrets(&mut config, &current);
+        }
+        fs::write(&config_path, serde_json::to_string_pretty(&config)?)
+            .with_context(|| format!("Failed to write {}", config_path.display()))?;
+    }
+    Ok(safety)
+}
+
+/// Take today's automatic backup if there isn't one yet and prune old ones.
+/// Returns the new archive, if one was written.
+pub fn run_daily(db: &SyncManager, policy: &BackupPolicy) -> Result<Option<PathBuf>> {
+    let dir = match &policy.dir {
+        Some(dir) => dir.clone(),
+        None => default_dir()?,
+    };
+    let today = format!("{}{}", PREFIX, Local::now().format("%Y%m%d"));
+    let existing = archives_in(&dir);
+    if existing.iter().any(|name| name.starts_with(&today)) {
+        return Ok(None);
+    }
+
+    let archive = create(db, &dir, policy.include_secrets)?;
+    let mut names = archives_in(&dir);
+    names.sort();
+    let excess = names.len().saturating_sub(policy.keep.max(1));
+    for name in &names[..excess] {
+        fs::remove_file(dir.join(name))?;
+    }
+    Ok(Some(archive))
+}
+
+/// File names of the timestamped archives in `dir`
+fn archives_in(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries.filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.starts_with(PREFIX) && name.ends_with(SUFFIX))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_secrets_are_stripped_and_carried_over() {
+        let mut config = json!({
+            "google_account": "me@example.com",
+            "airtable": {"api_key": "key123", "base_id": "app1"},
+            "clickup": {"api_token": "tok456"},
+        });
+        strip_secrets(&mut config);
+        assert_eq!(config["airtable"]["api_key"], "");
+        assert_eq!(config["clickup"]["api_token"], "");
+        assert_eq!(config["airtable"]["base_id"], "app1");
+        assert!(config.get("embeddings").is_none());
+
+        let current = json!({"airtable": {"api_key": "newkey"}});
+        carry_over_secrets(&mut config, &current);
+        assert_eq!(config["airtable"]["api_key"], "newkey");
+        assert_eq!(config["clickup"]["api_token"], "");
+    }
+
+    #[test]
+    fn test_backup_drops_stored_tokens() {
+        let dir = std::env::temp_dir().join(format!("thegarden-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let db = SyncManager::open(&dir.join("cache.db")).unwrap();
+        db.set_state(crate::mstodo::TOKEN_STATE, r#"{"refresh_token":"M.R3_secret"}"#).unwrap();
+        db.set_state(crate::serve::WEBHOOK_KEY, r#"{"id":"wh1","secret":"hmac_secret"}"#).unwrap();
+        db.set_state("last_sync", "2026-03-04T10:00:00Z").unwrap();
+
+        let archived_cache = |include_secrets: bool| {
+            let archive = create(&db, &dir.join(format!("backup-{}.tar.gz", include_secrets)), include_secrets).unwrap();
+            let mut tar = tar::Archive::new(GzDecoder::new(File::open(archive).unwrap()));
+            let mut contents = Vec::new();
+            for entry in tar.entries().unwrap() {
+                let mut entry = entry.unwrap();
+                if entry.path().unwrap().to_string_lossy() == CACHE_ENTRY {
+                    std::io::Read::read_to_end(&mut entry, &mut contents).unwrap();
+                }
+            }
+            let path = dir.join(format!("restored-{}.db", include_secrets));
+            fs::write(&path, &contents).unwrap();
+            (SyncManager::open(&path).unwrap(), String::from_utf8_lossy(&contents).into_owned())
+        };
+
+        let (stripped, raw) = archived_cache(false);
+        assert_eq!(stripped.get_state(crate::mstodo::TOKEN_STATE).unwrap(), None);
+        assert_eq!(stripped.get_state(crate::serve::WEBHOOK_KEY).unwrap(), None);
+        assert_eq!(stripped.get_last_sync().unwrap().as_deref(), Some("2026-03-04T10:00:00Z"));
+        assert!(!raw.contains("M.R3_secret") && !raw.contains("hmac_secret"));
+        let (full, _) = archived_cache(true);
+        assert!(full.get_state(crate::mstodo::TOKEN_STATE).unwrap().is_some());
+
+        // Restoring the stripped cache keeps the token the current one has
+        db.carry_over_state(&dir.join("restored-false.db"), &SECRET_STATE_KEYS).unwrap();
+        assert!(stripped.get_state(crate::mstodo::TOKEN_STATE).unwrap().is_some());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_secrets_in_lists_are_stripped() {
+        let mut config = json!({
+            "asana": {"token": "asana-pat", "workspace": "1"},
+            "mqtt": {"host": "broker", "password": "hunter2"},
+            "notifiers": [
+                {"type": "discord", "webhook_url": "https://discord.com/api/webhooks/1/tok"},
+                {"type": "matrix", "homeserver": "https://matrix.org", "room_id": "!r:m", "access_token": "syt_abc"},
+            ],
+            "webhooks": [{"url": "https://example.com/hook", "headers": {"Authorization": "Bearer xyz"}}],
+        });
+        strip_secrets(&mut config);
+        let text = config.to_string();
+        for secret in ["asana-pat", "hunter2", "api/webhooks", "syt_abc", "Bearer"] {
+            assert!(!text.contains(secret), "{} left in {}", secret, text);
+        }
+        assert_eq!(config["notifiers"][1]["homeserver"], "https://matrix.org");
+        assert_eq!(config["webhooks"][0]["url"], "https://example.com/hook");
+
+        let current = json!({
+            "notifiers": [{"type": "discord", "webhook_url": "https://discord.com/api/webhooks/1/new"}],
+            "webhooks": [{"headers": {"Authorization": "Bearer new"}}],
+        });
+        carry_over_secrets(&mut config, &current);
+        assert_eq!(config["notifiers"][0]["webhook_url"], "https://discord.com/api/webhooks/1/new");
+        assert_eq!(config["notifiers"][1]["access_token"], "");
+        assert_eq!(config["webhooks"][0]["headers"]["Authorization"], "Bearer new");
+    }
+}
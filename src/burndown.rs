@@ -0,0 +1,161 @@
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use colored::*;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{get_tasks_from_cache, local_date, parse_time_to_minutes, start_of_week, App, Task};
+
+// Every sync stores a snapshot of open work per project (task count and estimated
+// minutes). `burndown` charts the last snapshot of each day, so the trend shows
+// whether the backlog is shrinking, and adds weekly completions from the change
+// log as velocity.
+
+const CHART_HEIGHT: usize = 8;
+const BARS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Snapshot the open tasks in the cache, taken at `at` (RFC3339)
+pub fn record(app: &App, at: &str) -> Result<()> {
+    let tasks = get_tasks_from_cache(app)?;
+    app.db.save_snapshot(at, &snapshot_rows(&tasks))
+}
+
+/// (project, open tasks, estimated minutes) per project, "" for tasks without one.
+/// Always at least one row, so a sync with nothing open still shows up as zero.
+fn snapshot_rows(tasks: &[Task]) -> Vec<(String, i64, i64)> {
+    let mut by_project: BTreeMap<String, (i64, i64)> = BTreeMap::new();
+    for task in tasks.iter().filter(|t| !t.is_done()) {
+        let entry = by_project.entry(task.project.clone().unwrap_or_default()).or_default();
+        entry.0 += 1;
+        entry.1 += task.time.as_deref().map_or(0, parse_time_to_minutes) as i64;
+    }
+    if by_project.is_empty() {
+        by_project.insert(String::new(), (0, 0));
+    }
+    by_project.into_iter().map(|(project, (open, minutes))| (project, open, minutes)).collect()
+}
+
+pub fn run(app: &App, project: Option<&str>, weeks: i64) -> Result<()> {
+    let today = Local::now().date_naive();
+    // Whole weeks, ending with this one
+    let first = app.config.week_start();
+    let week_start = |day: NaiveDate| start_of_week(day, first);
+    let start = week_start(today) - chrono::Duration::days((weeks.max(1) - 1) * 7);
+    let project = project.map(|p| p.to_uppercase());
+
+    // Last snapshot of each local day
+    let mut by_day: BTreeMap<NaiveDate, (i64, i64)> = BTreeMap::new();
+    for snapshot in app.db.get_snapshots(&start.format("%Y-%m-%d").to_string(), project.as_deref())? {
+        if let Some(day) = local_date(&snapshot.at) {
+            by_day.insert(day, (snapshot.open_tasks, snapshot.estimated_minutes));
+        }
+    }
+
+    let scope = project.clone().unwrap_or_else(|| "all projects".to_string());
+    println!("{}", format!("📉 Burndown: {}, last {} weeks\n", scope, weeks.max(1)).cyan().bold());
+    if by_day.is_empty() {
+        println!("{}", "No snapshots yet. One is taken at every sync.".dimmed());
+        return Ok(());
+    }
+
+    let days: Vec<NaiveDate> = start.iter_days().take_while(|d| *d <= today).collect();
+    let open: Vec<Option<f64>> = days.iter().map(|d| by_day.get(d).map(|(n, _)| *n as f64)).collect();
+    let hours: Vec<Option<f64>> = days.iter().map(|d| by_day.get(d).map(|(_, m)| *m as f64 / 60.0)).collect();
+
+    println!("{}", "Open tasks".bold());
+    print_chart(&open, &days);
+    println!("\n{}", "Estimated hours".bold());
+    print_chart(&hours, &days);
+
+    // Velocity: tasks completed per week (by their current project)
+    let projects: HashMap<String, Option<String>> = get_tasks_from_cache(app)?.into_iter()
+        .filter_map(|t| Some((t.id?, t.project)))
+        .collect();
+    let history = app.db.get_history_since(&start.format("%Y-%m-%d").to_string())?;
+    let mut done_per_week: BTreeMap<NaiveDate, usize> = (0..weeks.max(1))
+        .map(|w| (week_start(start + chrono::Duration::days(w * 7)), 0))
+        .collect();
+    for change in history.iter().filter(|c| c.field == "status" && c.new_value.as_deref() == Some("completed")) {
+        let in_project = project.is_none() || projects.get(&change.task_id).is_some_and(|p| p.as_deref() == project.as_deref());
+        if let (true, Some(day)) = (in_project, local_date(&change.at)) {
+            *done_per_week.entry(week_start(day)).or_default() += 1;
+        }
+    }
+    let weekly: Vec<String> = done_per_week.iter().map(|(week, n)| format!("{} {}", week.format("%b %-d"), n)).collect();
+    let average = done_per_week.values().sum::<usize>() as f64 / done_per_week.len().max(1) as f64;
+    println!("\n{} {}", "Done per week:".bold(), weekly.join(" · "));
+    println!("{}", format!("Velocity: {:.1} tasks/week", average).dimmed());
+    Ok(())
+}
+
+fn print_chart(values: &[Option<f64>], days: &[NaiveDate]) {
+    let max = values.iter().flatten().fold(0.0_f64, |a, b| a.max(*b));
+    for (row, line) in chart(values, CHART_HEIGHT).into_iter().enumerate() {
+        let label = match row {
+            0 if max.fract() == 0.0 => format!("{:>6.0}", max),
+            0 => format!("{:>6.1}", max),
+            r if r == CHART_HEIGHT - 1 => format!("{:>6}", 0),
+            _ => " ".repeat(6),
+        };
+        println!("{} │{}", label.dimmed(), line.cyan());
+    }
+    println!("{} └{}", " ".repeat(6), "─".repeat(values.len()));
+    if let (Some(first), Some(last)) = (days.first(), days.last()) {
+        let (first, last) = (first.format("%b %-d").to_string(), last.format("%b %-d").to_string());
+        let gap = values.len().saturating_sub(first.chars().count() + last.chars().count()).max(1);
+        println!("{}  {}{}{}", " ".repeat(6), first.dimmed(), " ".repeat(gap), last.dimmed());
+    }
+}
+
+/// Rows of a bar chart, top first: one column per value scaled to the largest,
+/// in eighth-block steps. Days without a snapshot are a dotted gap.
+fn chart(values: &[Option<f64>], height: usize) -> Vec<String> {
+    let max = values.iter().flatten().fold(0.0_f64, |a, b| a.max(*b));
+    let units: Vec<Option<usize>> = values.iter()
+        .map(|v| v.map(|v| if max > 0.0 { (v / max * (height * 8) as f64).round() as usize } else { 0 }))
+        .collect();
+    (0..height).rev()
+        .map(|row| units.iter()
+            .map(|u| match u {
+                Some(u) if *u >= (row + 1) * 8 => BARS[8],
+                Some(u) if *u > row * 8 => BARS[u - row * 8],
+                Some(_) => ' ',
+                None if row == 0 => '·',
+                None => ' ',
+            })
+            .collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_rows() {
+        let tasks = [
+            Task::parse_with_config("[2026-03-02][P1][WORK]{1h} Report", "Tasks", None),
+            Task::parse_with_config("[2026-03-02][P1][WORK]{30m} Review", "Tasks", None),
+            Task::parse_with_config("[2026-03-02][DONE][WORK]{2h} Shipped", "Tasks", None),
+            Task::parse_with_config("Loose end", "Tasks", None),
+        ];
+        assert_eq!(snapshot_rows(&tasks), vec![("".to_string(), 1, 0), ("WORK".to_string(), 2, 90)]);
+        assert_eq!(snapshot_rows(&[]), vec![("".to_string(), 0, 0)]);
+    }
+
+    #[test]
+    fn test_chart() {
+        let rows = chart(&[Some(4.0), Some(2.0), None, Some(1.0), Some(0.0)], 2);
+        assert_eq!(rows, vec!["█    ", "██·▄ "]);
+    }
+
+    #[test]
+    fn test_snapshots_sum_projects() {
+        let db = crate::sync::SyncManager::open(std::path::Path::new(":memory:")).unwrap();
+        db.save_snapshot("2026-03-02T10:00:00Z", &[("WORK".into(), 3, 120), ("LIFE".into(), 2, 30)]).unwrap();
+        db.save_snapshot("2026-03-03T10:00:00Z", &[("LIFE".into(), 1, 15)]).unwrap();
+        let all: Vec<i64> = db.get_snapshots("2026-03-01", None).unwrap().iter().map(|s| s.open_tasks).collect();
+        assert_eq!(all, vec![5, 1]);
+        let work: Vec<i64> = db.get_snapshots("2026-03-01", Some("WORK")).unwrap().iter().map(|s| s.open_tasks).collect();
+        assert_eq!(work, vec![3, 0]);
+    }
+}
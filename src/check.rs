@@ -0,0 +1,181 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::collections::BTreeSet;
+
+use crate::airtable::{self, AirtableClient};
+use crate::clickup::{self, ClickUpClient, RateLimit};
+use crate::{block_on, App, ClickUpConfig, Config};
+
+// `sync check`: one cheap authenticated call per provider, so an expired token or a list
+// the token lost access to shows up before a push, not halfway through one. Nothing is
+// read beyond a single record, the list metadata and the token's own user.
+
+#[derive(Debug, PartialEq)]
+enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+struct Check {
+    name: String,
+    status: Status,
+    detail: String,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, status: Status, detail: impl Into<String>) -> Self {
+        Check { name: name.into(), status, detail: detail.into() }
+    }
+}
+
+/// ClickUp lists a push can write to: the default list, the mapped ones and per-project ones
+fn clickup_lists(clickup_config: &ClickUpConfig, config: &Config) -> BTreeSet<String> {
+    clickup_config.list_id.iter()
+        .chain(clickup_config.list_mappings.iter().flat_map(|m| m.values()))
+        .chain(config.projects.values().filter_map(|p| p.clickup_list.as_ref()))
+        .cloned()
+        .collect()
+}
+
+fn describe_rate_limit(rate_limit: &RateLimit, now: i64) -> (Status, String) {
+    let mut detail = format!("{}/{} requests left this minute", rate_limit.remaining, rate_limit.limit);
+    if let Some(reset) = rate_limit.reset {
+        detail.push_str(&format!(", resets in {}s", (reset - now).max(0)));
+    }
+    // A full push needs a request or two per task
+    let status = if rate_limit.remaining < rate_limit.limit / 5 { Status::Warn } else { Status::Ok };
+    (status, detail)
+}
+
+fn check_google(app: &App) -> Check {
+    match app.google.list_ids() {
+        Ok(ids) => Check::new("Google Tasks", Status::Ok, format!("{}, {} lists", app.config.google_account, ids.len())),
+        Err(e) => Check::new("Google Tasks", Status::Fail, format!("{:#} (try `gog auth`)", e)),
+    }
+}
+
+async fn check_airtable(app: &App) -> Vec<Check> {
+    let airtable_config = match &app.config.airtable {
+        Some(cfg) if cfg.enabled => cfg,
+        _ => return vec![],
+    };
+    let client = match AirtableClient::new(airtable::AirtableConfig {
+        api_key: airtable_config.api_key.clone(),
+        base_id: airtable_config.base_id.clone(),
+        table_name: airtable_config.table_name.clone(),
+        typecast: airtable_config.create_options,
+    }) {
+        Ok(client) => client,
+        Err(e) => return vec![Check::new("Airtable", Status::Fail, format!("{:#}", e))],
+    };
+
+    if let Err(e) = client.check_access().await {
+        return vec![Check::new("Airtable", Status::Fail, format!("{:#}", e))];
+    }
+    // Airtable sends no rate-limit headers; the limit is 5 requests/s per base
+    let table = format!("{}/{} readable (limit 5 requests/s)", airtable_config.base_id, airtable_config.table_name);
+    let schema = match client.fetch_fields().await {
+        Ok(fields) => Check::new("Airtable schema", Status::Ok, format!("{} fields", fields.len())),
+        Err(e) => Check::new(
+            "Airtable schema",
+            Status::Warn,
+            format!("{:#} (add the schema.bases:read scope to check select options)", e),
+        ),
+    };
+    vec![Check::new("Airtable", Status::Ok, table), schema]
+}
+
+async fn check_clickup(app: &App) -> Vec<Check> {
+    let clickup_config = match &app.config.clickup {
+        Some(cfg) if cfg.enabled => cfg,
+        _ => return vec![],
+    };
+    let client = match ClickUpClient::new(clickup::ClickUpConfig {
+        api_token: clickup_config.api_token.clone(),
+        list_id: clickup_config.list_id.clone().unwrap_or_default(),
+    }) {
+        Ok(client) => client,
+        Err(e) => return vec![Check::new("ClickUp", Status::Fail, format!("{:#}", e))],
+    };
+
+    let mut checks = match client.get_user().await {
+        Ok((username, rate_limit)) => {
+            let (status, limit) = match rate_limit {
+                Some(rate_limit) => describe_rate_limit(&rate_limit, chrono::Utc::now().timestamp()),
+                None => (Status::Ok, "no rate-limit headers".into()),
+            };
+            vec![Check::new("ClickUp", status, format!("{}, {}", username, limit))]
+        }
+        // With a bad token every list check would fail the same way
+        Err(e) => return vec![Check::new("ClickUp", Status::Fail, format!("{:#}", e))],
+    };
+
+    for list_id in clickup_lists(clickup_config, &app.config) {
+        let name = format!("ClickUp list {}", list_id);
+        checks.push(match client.get_list_statuses(&list_id).await {
+            Ok(list) => Check::new(name, Status::Ok, format!("{} statuses", list.statuses.len())),
+            Err(e) => Check::new(name, Status::Fail, format!("{:#}", e)),
+        });
+    }
+    checks
+}
+
+pub fn cmd_sync_check(app: &App) -> Result<()> {
+    let mut checks = vec![check_google(app)];
+    let (airtable, clickup) = block_on(async { tokio::join!(check_airtable(app), check_clickup(app)) });
+    checks.extend(airtable);
+    checks.extend(clickup);
+
+    for check in &checks {
+        let marker = match check.status {
+            Status::Ok => "✓".green(),
+            Status::Warn => "!".yellow(),
+            Status::Fail => "✗".red(),
+        };
+        println!("  {} {}: {}", marker, check.name.bold(), check.detail);
+    }
+
+    let failed = checks.iter().filter(|c| c.status == Status::Fail).count();
+    if failed > 0 {
+        anyhow::bail!("{} provider checks failed", failed);
+    }
+    println!("{}", "✓ Credentials look good".green());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clickup_lists_and_rate_limit() {
+        let mut config: Config = serde_json::from_value(serde_json::json!({
+            "format": "[{date}][{priority}][{project}]{status}{context}{time} {title}",
+            "date_format": "%Y-%m-%d",
+            "google_account": "me@example.com",
+            "projects": {"WORK": {"description": "Work", "clickup_list": "300"}, "LIFE": "Personal"},
+        })).unwrap();
+        config.clickup = serde_json::from_value(serde_json::json!({
+            "api_token": "pk_1",
+            "list_id": "100",
+            "list_mappings": {"g1": "200", "g2": "100"},
+        })).unwrap();
+        let lists = clickup_lists(config.clickup.as_ref().unwrap(), &config);
+        assert_eq!(lists.into_iter().collect::<Vec<_>>(), ["100", "200", "300"]);
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-ratelimit-limit", "100".parse().unwrap());
+        headers.insert("x-ratelimit-remaining", "12".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "1000030".parse().unwrap());
+        let rate_limit = RateLimit::from_headers(&headers).unwrap();
+        assert_eq!(rate_limit, RateLimit { limit: 100, remaining: 12, reset: Some(1000030) });
+        assert_eq!(
+            describe_rate_limit(&rate_limit, 1000000),
+            (Status::Warn, "12/100 requests left this minute, resets in 30s".into())
+        );
+
+        headers.remove("x-ratelimit-remaining");
+        assert_eq!(RateLimit::from_headers(&headers), None);
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::{Context, Result, anyhow};
 use serde::{Deserialize, Serialize};
+use crate::logging::LoggedAsyncRequest;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ClickUpConfig {
@@ -30,6 +31,7 @@ pub struct CustomField {
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct ClickUpTaskResponse {
     pub id: String,
     pub name: String,
@@ -43,34 +45,73 @@ pub struct ClickUpTaskResponse {
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct ClickUpStatus {
     pub status: String,
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct ClickUpPriority {
     pub id: String,
     pub priority: String,
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct ClickUpTag {
     pub name: String,
 }
 
+/// A webhook as registered with ClickUp; `secret` signs every delivery
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClickUpWebhook {
+    pub id: String,
+    pub secret: String,
+    pub endpoint: String,
+}
+
+/// ClickUp's per-token request budget, from the X-RateLimit-* headers
+#[derive(Debug, PartialEq)]
+pub struct RateLimit {
+    pub limit: u64,
+    pub remaining: u64,
+    /// Unix time (seconds) when the budget refills
+    pub reset: Option<i64>,
+}
+
+impl RateLimit {
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name)?.to_str().ok()?.parse::<i64>().ok();
+        Some(RateLimit {
+            limit: header("x-ratelimit-limit")? as u64,
+            remaining: header("x-ratelimit-remaining")? as u64,
+            reset: header("x-ratelimit-reset"),
+        })
+    }
+}
+
+/// A list's statuses in board order (new tasks start in the first) and its space
+#[derive(Debug)]
+pub struct ListStatuses {
+    pub statuses: Vec<String>,
+    pub space_id: String,
+}
+
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct ClickUpListResponse {
     pub tasks: Vec<ClickUpTaskResponse>,
 }
 
 pub struct ClickUpClient {
     config: ClickUpConfig,
-    client: reqwest::blocking::Client,
+    client: reqwest::Client,
 }
 
 impl ClickUpClient {
     pub fn new(config: ClickUpConfig) -> Result<Self> {
-        let client = reqwest::blocking::Client::new();
+        let client = reqwest::Client::new();
         Ok(Self { config, client })
     }
 
@@ -79,7 +120,8 @@ impl ClickUpClient {
     }
 
     /// Fetch all tasks from a ClickUp list
-    pub fn fetch_all_tasks(&self) -> Result<Vec<ClickUpTaskResponse>> {
+    #[allow(dead_code)]
+    pub async fn fetch_all_tasks(&self) -> Result<Vec<ClickUpTaskResponse>> {
         let url = format!("{}/list/{}/task", self.get_base_url(), self.config.list_id);
 
         let response = self
@@ -90,24 +132,26 @@ impl ClickUpClient {
                 ("archived", "false"),
                 ("include_closed", "true"),
             ])
-            .send()
+            .send_logged()
+            .await
             .context("Failed to fetch tasks from ClickUp")?;
 
         if !response.status().is_success() {
             let status = response.status();
-            let body = response.text().unwrap_or_default();
+            let body = response.text().await.unwrap_or_default();
             return Err(anyhow!("ClickUp API error {}: {}", status, body));
         }
 
         let list_response: ClickUpListResponse = response
             .json()
+            .await
             .context("Failed to parse ClickUp response")?;
 
         Ok(list_response.tasks)
     }
 
     /// Create a new task in ClickUp
-    pub fn create_task(&self, list_id: &str, task: &ClickUpTask) -> Result<ClickUpTaskResponse> {
+    pub async fn create_task(&self, list_id: &str, task: &ClickUpTask) -> Result<ClickUpTaskResponse> {
         let url = format!("{}/list/{}/task", self.get_base_url(), list_id);
 
         let response = self
@@ -116,88 +160,240 @@ impl ClickUpClient {
             .header("Authorization", &self.config.api_token)
             .header("Content-Type", "application/json")
             .json(&task)
-            .send()
+            .send_logged()
+            .await
             .context("Failed to create task in ClickUp")?;
 
         if !response.status().is_success() {
             let status = response.status();
-            let body = response.text().unwrap_or_default();
+            let body = response.text().await.unwrap_or_default();
             return Err(anyhow!("ClickUp create error {}: {}", status, body));
         }
 
         let task_response: ClickUpTaskResponse = response
             .json()
+            .await
             .context("Failed to parse ClickUp create response")?;
 
         Ok(task_response)
     }
 
-    /// Update an existing task in ClickUp
-    pub fn update_task(&self, task_id: &str, task: &ClickUpTask) -> Result<ClickUpTaskResponse> {
+    /// Update an existing task in ClickUp; its assignees are added to the task's current ones
+    pub async fn update_task(&self, task_id: &str, task: &ClickUpTask) -> Result<ClickUpTaskResponse> {
         let url = format!("{}/task/{}", self.get_base_url(), task_id);
+        // Updates take assignees as changes, not as the full list
+        let mut body = serde_json::to_value(task)?;
+        body["assignees"] = serde_json::json!({ "add": task.assignees, "rem": [] });
 
         let response = self
             .client
             .put(&url)
             .header("Authorization", &self.config.api_token)
             .header("Content-Type", "application/json")
-            .json(&task)
-            .send()
+            .json(&body)
+            .send_logged()
+            .await
             .context("Failed to update task in ClickUp")?;
 
         if !response.status().is_success() {
             let status = response.status();
-            let body = response.text().unwrap_or_default();
+            let body = response.text().await.unwrap_or_default();
             return Err(anyhow!("ClickUp update error {}: {}", status, body));
         }
 
         let task_response: ClickUpTaskResponse = response
             .json()
+            .await
             .context("Failed to parse ClickUp update response")?;
 
         Ok(task_response)
     }
 
     /// Delete a task from ClickUp
-    pub fn delete_task(&self, task_id: &str) -> Result<()> {
+    #[allow(dead_code)]
+    pub async fn delete_task(&self, task_id: &str) -> Result<()> {
         let url = format!("{}/task/{}", self.get_base_url(), task_id);
 
         let response = self
             .client
             .delete(&url)
             .header("Authorization", &self.config.api_token)
-            .send()
+            .send_logged()
+            .await
             .context("Failed to delete task from ClickUp")?;
 
         if !response.status().is_success() {
             let status = response.status();
-            let body = response.text().unwrap_or_default();
+            let body = response.text().await.unwrap_or_default();
             return Err(anyhow!("ClickUp delete error {}: {}", status, body));
         }
 
         Ok(())
     }
 
-    /// Get available statuses for a list
-    pub fn get_list_statuses(&self) -> Result<Vec<String>> {
-        let url = format!("{}/list/{}", self.get_base_url(), self.config.list_id);
+    /// Fetch a single task
+    pub async fn get_task(&self, task_id: &str) -> Result<ClickUpTaskResponse> {
+        let url = format!("{}/task/{}", self.get_base_url(), task_id);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", &self.config.api_token)
+            .send_logged()
+            .await
+            .context("Failed to fetch task from ClickUp")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("ClickUp API error {}: {}", status, body));
+        }
+
+        response.json().await.context("Failed to parse ClickUp task")
+    }
+
+    /// The token's user name, and the rate limit left
+    pub async fn get_user(&self) -> Result<(String, Option<RateLimit>)> {
+        let url = format!("{}/user", self.get_base_url());
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", &self.config.api_token)
+            .send_logged()
+            .await
+            .context("Failed to reach ClickUp")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("ClickUp API error {}: {}", status, body));
+        }
+
+        #[derive(Deserialize)]
+        struct UserResponse {
+            user: User,
+        }
+
+        #[derive(Deserialize)]
+        struct User {
+            username: String,
+        }
+
+        let rate_limit = RateLimit::from_headers(response.headers());
+        let user: UserResponse = response.json().await.context("Failed to parse ClickUp user")?;
+        Ok((user.user.username, rate_limit))
+    }
+
+    /// ID of the first workspace ("team") the token can see
+    pub async fn get_team_id(&self) -> Result<String> {
+        let url = format!("{}/team", self.get_base_url());
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", &self.config.api_token)
+            .send_logged()
+            .await
+            .context("Failed to list ClickUp workspaces")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("ClickUp API error {}: {}", status, body));
+        }
+
+        #[derive(Deserialize)]
+        struct Teams {
+            teams: Vec<Team>,
+        }
+
+        #[derive(Deserialize)]
+        struct Team {
+            id: String,
+        }
+
+        let teams: Teams = response.json().await.context("Failed to parse ClickUp workspaces")?;
+        teams.teams.into_iter().next().map(|t| t.id).context("The ClickUp token has no workspaces")
+    }
+
+    /// Ask ClickUp to POST these events for the workspace to `endpoint`
+    pub async fn create_webhook(&self, team_id: &str, endpoint: &str, events: &[&str]) -> Result<ClickUpWebhook> {
+        let url = format!("{}/team/{}/webhook", self.get_base_url(), team_id);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", &self.config.api_token)
+            .json(&serde_json::json!({ "endpoint": endpoint, "events": events }))
+            .send_logged()
+            .await
+            .context("Failed to register ClickUp webhook")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("ClickUp webhook error {}: {}", status, body));
+        }
+
+        #[derive(Deserialize)]
+        struct Created {
+            webhook: Registered,
+        }
+
+        #[derive(Deserialize)]
+        struct Registered {
+            id: String,
+            secret: String,
+        }
+
+        let created: Created = response.json().await.context("Failed to parse ClickUp webhook response")?;
+        Ok(ClickUpWebhook { id: created.webhook.id, secret: created.webhook.secret, endpoint: endpoint.to_string() })
+    }
+
+    /// Remove a webhook registered with `create_webhook`
+    pub async fn delete_webhook(&self, webhook_id: &str) -> Result<()> {
+        let url = format!("{}/webhook/{}", self.get_base_url(), webhook_id);
+
+        let response = self
+            .client
+            .delete(&url)
+            .header("Authorization", &self.config.api_token)
+            .send_logged()
+            .await
+            .context("Failed to delete ClickUp webhook")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("ClickUp webhook error {}: {}", status, body));
+        }
+
+        Ok(())
+    }
+
+    /// Get available statuses for a list, and the space its tags live in
+    pub async fn get_list_statuses(&self, list_id: &str) -> Result<ListStatuses> {
+        let url = format!("{}/list/{}", self.get_base_url(), list_id);
 
         let response = self
             .client
             .get(&url)
             .header("Authorization", &self.config.api_token)
-            .send()
+            .send_logged()
+            .await
             .context("Failed to get list info from ClickUp")?;
 
         if !response.status().is_success() {
             let status = response.status();
-            let body = response.text().unwrap_or_default();
+            let body = response.text().await.unwrap_or_default();
             return Err(anyhow!("ClickUp API error {}: {}", status, body));
         }
 
         #[derive(Deserialize)]
         struct ListInfo {
             statuses: Vec<StatusInfo>,
+            space: SpaceInfo,
         }
 
         #[derive(Deserialize)]
@@ -205,10 +401,68 @@ impl ClickUpClient {
             status: String,
         }
 
+        #[derive(Deserialize)]
+        struct SpaceInfo {
+            id: String,
+        }
+
         let list_info: ListInfo = response
             .json()
+            .await
             .context("Failed to parse list info")?;
 
-        Ok(list_info.statuses.into_iter().map(|s| s.status).collect())
+        Ok(ListStatuses {
+            statuses: list_info.statuses.into_iter().map(|s| s.status).collect(),
+            space_id: list_info.space.id,
+        })
+    }
+
+    /// Names of the tags defined in a space
+    pub async fn get_space_tags(&self, space_id: &str) -> Result<Vec<String>> {
+        let url = format!("{}/space/{}/tag", self.get_base_url(), space_id);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", &self.config.api_token)
+            .send_logged()
+            .await
+            .context("Failed to get tags from ClickUp")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("ClickUp API error {}: {}", status, body));
+        }
+
+        #[derive(Deserialize)]
+        struct Tags {
+            tags: Vec<ClickUpTag>,
+        }
+
+        let tags: Tags = response.json().await.context("Failed to parse ClickUp tags")?;
+        Ok(tags.tags.into_iter().map(|t| t.name).collect())
+    }
+
+    /// Add a tag to a space
+    pub async fn create_space_tag(&self, space_id: &str, name: &str) -> Result<()> {
+        let url = format!("{}/space/{}/tag", self.get_base_url(), space_id);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", &self.config.api_token)
+            .json(&serde_json::json!({ "tag": { "name": name } }))
+            .send_logged()
+            .await
+            .context("Failed to create tag in ClickUp")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("ClickUp tag error {}: {}", status, body));
+        }
+
+        Ok(())
     }
 }
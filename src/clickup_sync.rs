@@ -1,30 +1,107 @@
 use anyhow::{Result};
+use futures_util::{stream, StreamExt};
 use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use once_cell::sync::Lazy;
-use crate::clickup::{ClickUpClient, ClickUpTask};
-use crate::sync::{SyncManager, CachedTask};
-use chrono::{DateTime, Utc};
+use crate::clickup::{ClickUpClient, ClickUpTask, ListStatuses};
+use crate::sync::{content_hash, pick_assignee, SyncManager, CachedTask};
+use chrono::DateTime;
+use crate::logging::LoggedAsyncCommand;
+use crate::privacy::PrivacyConfig;
+use crate::{Config, StatusOption};
 
 // Parse taskgarden format: [date][priority][project][status][@context]{time} title
+/// sync_state key: Google Task ID → hash of the task last pushed to ClickUp
+const HASH_KEY: &str = "clickup_hash_map";
+
 static TASK_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"\[([^\]]+)\]\[([^\]]+)\]\[([^\]]+)\](?:\[([^\]]+)\])?(?:\[@([^\]]+)\])?(?:\{([^}]+)\})?\s*(.+)").unwrap()
 });
 
-pub struct ClickUpSync {
-    sync_manager: SyncManager,
+pub struct ClickUpSync<'a> {
+    sync_manager: &'a SyncManager,
     clickup_client: ClickUpClient,
     list_mappings: std::collections::HashMap<String, String>,  // Google list ID -> ClickUp list ID
+    project_lists: std::collections::HashMap<String, String>,  // Project -> ClickUp list ID, wins over list_mappings
+    config: &'a Config,
+    marker_account: Option<String>,
+    excluded_projects: Vec<String>,
+    privacy: PrivacyConfig,
+    assignees: HashMap<String, u64>,
+    default_assignee: Option<u64>,
+}
+
+/// The ClickUp "synced" marker, and how it reads when its UTF-8 was decoded as cp1252 or Latin-1
+const MARKERS: [&str; 3] = ["🔃", "ðŸ”ƒ", "\u{f0}\u{9f}\u{94}\u{83}"];
+
+/// `title` without leading markers (keeping one intact marker if `keep_marker` and it had any),
+/// or None when that's the title as is
+fn strip_markers(title: &str, keep_marker: bool) -> Option<String> {
+    let mut rest = title;
+    let mut marked = false;
+    while let Some(stripped) = MARKERS.iter().find_map(|m| rest.trim_start().strip_prefix(m)) {
+        rest = stripped;
+        marked = true;
+    }
+    let cleaned = if marked && keep_marker { format!("🔃 {}", rest.trim_start()) } else { rest.trim_start().to_string() };
+    (marked && cleaned != title).then_some(cleaned)
+}
+
+/// Fix task titles carrying the synced marker: garbled ones always, intact ones unless
+/// `keep_marker`. The tasks are left dirty for the next push. Returns how many changed.
+pub fn clean_synced_markers(sync_manager: &SyncManager, keep_marker: bool) -> Result<usize> {
+    let cleaned: Vec<CachedTask> = sync_manager.get_all_cached_tasks()?
+        .into_iter()
+        .filter_map(|mut task| {
+            task.title = strip_markers(&task.title, keep_marker)?;
+            Some(task)
+        })
+        .collect();
+    sync_manager.upsert_tasks_locally(&cleaned)?;
+    Ok(cleaned.len())
 }
 
-impl ClickUpSync {
-    pub fn new(sync_manager: SyncManager, clickup_client: ClickUpClient, list_mappings: std::collections::HashMap<String, String>) -> Self {
+impl<'a> ClickUpSync<'a> {
+    pub fn new(sync_manager: &'a SyncManager, clickup_client: ClickUpClient, list_mappings: std::collections::HashMap<String, String>, project_lists: std::collections::HashMap<String, String>, config: &'a Config) -> Self {
         Self {
             sync_manager,
             clickup_client,
             list_mappings,
+            project_lists,
+            config,
+            marker_account: None,
+            excluded_projects: Vec::new(),
+            privacy: PrivacyConfig::default(),
+            assignees: HashMap::new(),
+            default_assignee: None,
         }
     }
 
+    /// Assign pushed tasks to ClickUp members by "+person" or project, else to `default`
+    pub fn with_assignees(mut self, assignees: HashMap<String, u64>, default: Option<u64>) -> Self {
+        self.assignees = assignees;
+        self.default_assignee = default;
+        self
+    }
+
+    /// Strip private fields and tags from pushed tasks, and skip excluded ones
+    pub fn with_privacy(mut self, privacy: PrivacyConfig) -> Self {
+        self.privacy = privacy;
+        self
+    }
+
+    /// Leave tasks in these projects out of the push
+    pub fn with_excluded_projects(mut self, projects: Vec<String>) -> Self {
+        self.excluded_projects = projects;
+        self
+    }
+
+    /// Prefix the Google Tasks titles of newly created tasks with 🔃, via this account
+    pub fn with_synced_marker(mut self, google_account: String) -> Self {
+        self.marker_account = Some(google_account);
+        self
+    }
+
     /// Parse a taskgarden formatted title into structured fields
     fn parse_task_title(title: &str) -> ParsedTask {
         if let Some(caps) = TASK_REGEX.captures(title) {
@@ -51,23 +128,39 @@ impl ClickUpSync {
         }
     }
 
-    /// Convert taskgarden priority to ClickUp priority (1=urgent, 2=high, 3=normal, 4=low)
-    fn priority_to_clickup(priority: Option<&str>) -> Option<u8> {
-        match priority {
-            Some("P0") => Some(1), // Urgent
-            Some("P1") => Some(2), // High
-            Some("P2") => Some(3), // Normal
-            Some("P3") => Some(4), // Low
-            Some("P5") => Some(4), // Delegate -> Low
-            _ => None,
+    /// ClickUp priority (1=urgent, 2=high, 3=normal, 4=low) for a priority, by its place in
+    /// the configured list: the first three map one to one, everything below is low
+    pub fn priority_to_clickup(config: &Config, priority: Option<&str>) -> Option<u8> {
+        let rank = config.priority_rank(priority);
+        if priority.is_none() || rank >= config.priorities.len() {
+            return None; // unknown or done
         }
+        Some(rank.min(3) as u8 + 1)
+    }
+
+    /// Convert a ClickUp priority ID back. A priority that already maps to it is kept, since
+    /// several (P3 and P5 by default) share low.
+    pub fn priority_from_clickup(config: &Config, id: &str, current: Option<&str>) -> Option<String> {
+        if Self::priority_to_clickup(config, current).is_some_and(|p| p.to_string() == id) {
+            return current.map(|p| p.to_string());
+        }
+        let rank = match id.parse::<usize>() {
+            Ok(id @ 1..=4) => id - 1,
+            _ => return current.map(|p| p.to_string()),
+        };
+        config.ranked_priorities().nth(rank).map(|p| p.name.clone()).or_else(|| current.map(|p| p.to_string()))
     }
 
     /// Convert taskgarden status to ClickUp status string
-    fn status_to_clickup(status: Option<&str>, google_status: &str) -> String {
+    /// ClickUp status for a task: the name configured for its status, else the built-in mapping
+    pub fn status_to_clickup(statuses: &[StatusOption], status: Option<&str>, google_status: &str) -> String {
         if google_status == "completed" {
             return "complete".to_string();
         }
+        let configured = status.and_then(|s| statuses.iter().find(|c| c.name == s));
+        if let Some(name) = configured.and_then(|c| c.clickup.clone()) {
+            return name;
+        }
 
         match status {
             Some("needsTriage") => "to do".to_string(),
@@ -79,6 +172,29 @@ impl ClickUpSync {
         }
     }
 
+    /// Whether a ClickUp status means the task is finished: a built-in closed status,
+    /// or the ClickUp name of a configured done status
+    pub fn is_closed_status(statuses: &[StatusOption], status: &str) -> bool {
+        statuses.iter().any(|c| c.done && c.clickup.as_deref().unwrap_or(&c.name).eq_ignore_ascii_case(status))
+            || matches!(status.to_lowercase().as_str(), "complete" | "closed" | "done")
+    }
+
+    /// Convert an open ClickUp status back to taskgarden's: configured names first, then
+    /// the built-in ones. "to do" keeps needsTriage (which also maps to it); statuses
+    /// taskgarden doesn't have leave the current one alone.
+    pub fn status_from_clickup(statuses: &[StatusOption], status: &str, current: Option<&str>) -> Option<String> {
+        if let Some(configured) = statuses.iter().find(|c| c.clickup.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(status))) {
+            return Some(configured.name.clone());
+        }
+        match status.to_lowercase().as_str() {
+            "in progress" => Some("progress".to_string()),
+            "review" => Some("review".to_string()),
+            "blocked" => Some("blocked".to_string()),
+            "to do" | "open" => current.filter(|s| *s == "needsTriage").map(|s| s.to_string()),
+            _ => current.map(|s| s.to_string()),
+        }
+    }
+
     /// Parse time estimate to milliseconds
     fn time_estimate_to_ms(time_str: Option<&str>) -> Option<i64> {
         time_str.and_then(|s| {
@@ -103,7 +219,7 @@ impl ClickUpSync {
     }
 
     /// Convert a CachedTask to ClickUpTask
-    fn to_clickup_task(task: &CachedTask) -> ClickUpTask {
+    fn to_clickup_task(&self, task: &CachedTask) -> ClickUpTask {
         let parsed = Self::parse_task_title(&task.title);
         
         // Extract tags from title (anything with #)
@@ -121,6 +237,9 @@ impl ClickUpSync {
 
         // Build description with metadata
         let mut description_parts = vec![parsed.title.clone()];
+        if let Some(notes) = task.user_description.as_deref().filter(|n| !n.trim().is_empty()) {
+            description_parts.push(format!("\n{}", notes));
+        }
         if let Some(ref links) = task.links {
             description_parts.push(format!("\n\nLinks: {}", links));
         }
@@ -132,25 +251,32 @@ impl ClickUpSync {
             id: None,
             name: parsed.title.clone(),
             description: Some(description_parts.join("\n")),
-            status: Some(Self::status_to_clickup(parsed.status.as_deref(), &task.status)),
-            priority: Self::priority_to_clickup(parsed.priority.as_deref()),
+            status: Some(Self::status_to_clickup(&self.config.statuses, parsed.status.as_deref(), &task.status)),
+            priority: Self::priority_to_clickup(self.config, parsed.priority.as_deref()),
             due_date: Self::date_to_timestamp(parsed.date.as_deref()),
             start_date: None,
             time_estimate: Self::time_estimate_to_ms(parsed.time_estimate.as_deref()),
             tags,
-            assignees: vec![], // Can be set based on config or manually in ClickUp
+            assignees: pick_assignee(&self.assignees, self.default_assignee.as_ref(), &task.title, parsed.project.as_deref())
+                .into_iter()
+                .copied()
+                .collect(),
             custom_fields: None,
         }
     }
 
-    /// Push all tasks from SQLite cache to ClickUp
-    pub fn push_to_clickup(&self) -> Result<PushStats> {
+    /// Push all tasks from SQLite cache to ClickUp, `concurrency` requests at a time
+    /// (with `dry_run`, only print what would change). Tasks that hash the same as at
+    /// the last push are skipped unless `force`.
+    pub async fn push_to_clickup(&self, dry_run: bool, force: bool, concurrency: usize) -> Result<PushStats> {
         let cached_tasks = self.sync_manager.get_all_cached_tasks()?;
         
         // Get existing ClickUp task IDs (stored in sync state)
         let clickup_map = self.get_clickup_id_map()?;
+        let mut hashes = self.sync_manager.get_id_map(HASH_KEY)?;
         
         let mut stats = PushStats::default();
+        let mut pushes = Vec::new();
         
         for task in cached_tasks {
             // Skip completed tasks
@@ -158,8 +284,15 @@ impl ClickUpSync {
                 continue;
             }
             
-            // Get the ClickUp list ID for this Google Task list
-            let clickup_list_id = match self.list_mappings.get(&task.list_id) {
+            // Get the ClickUp list ID for this task's project, else its Google Task list
+            let project = Self::parse_task_title(&task.title).project;
+            if project.as_ref().is_some_and(|project| self.excluded_projects.iter().any(|p| p.eq_ignore_ascii_case(project)))
+                || self.privacy.excludes(&task)
+            {
+                continue;
+            }
+            let project_list = project.and_then(|project| self.project_lists.get(&project));
+            let clickup_list_id = match project_list.or_else(|| self.list_mappings.get(&task.list_id)) {
                 Some(id) => id,
                 None => {
                     eprintln!("Warning: No ClickUp list mapping for Google list {}, skipping task {}", task.list_id, task.id);
@@ -168,40 +301,127 @@ impl ClickUpSync {
                 }
             };
             
-            let clickup_task = Self::to_clickup_task(&task);
-            
-            if let Some(clickup_id) = clickup_map.get(&task.id) {
-                // Update existing task
-                match self.clickup_client.update_task(clickup_id, &clickup_task) {
-                    Ok(_) => stats.updated += 1,
-                    Err(e) => {
-                        eprintln!("Failed to update task {}: {}", task.id, e);
-                        stats.errors += 1;
-                    }
+            let clickup_task = self.to_clickup_task(&self.privacy.apply(&task));
+            let hash = content_hash(&clickup_task);
+            if !force && clickup_map.contains_key(&task.id) && hashes.get(&task.id) == Some(&hash) {
+                stats.unchanged += 1;
+                continue;
+            }
+
+            if dry_run {
+                let exists = clickup_map.contains_key(&task.id);
+                println!("  {} {}", if exists { "~ update" } else { "+ create" }, clickup_task.name);
+                if exists { stats.updated += 1 } else { stats.created += 1 }
+                continue;
+            }
+
+            let clickup_id = clickup_map.get(&task.id).cloned();
+            pushes.push((task, clickup_list_id.clone(), clickup_id, clickup_task, hash));
+        }
+
+        self.prepare_lists(&mut pushes, concurrency).await;
+
+        let mut results = stream::iter(pushes)
+            .map(|(task, clickup_list_id, clickup_id, clickup_task, hash)| async move {
+                let result = match clickup_id {
+                    // Update existing task
+                    Some(ref clickup_id) => self.clickup_client.update_task(clickup_id, &clickup_task).await,
+                    // Create new task in the correct list
+                    None => self.clickup_client.create_task(&clickup_list_id, &clickup_task).await,
+                };
+                (task, clickup_list_id, clickup_id.is_some(), result, hash)
+            })
+            .buffer_unordered(concurrency.max(1));
+
+        while let Some((task, clickup_list_id, existed, result, hash)) = results.next().await {
+            match result {
+                Ok(_) if existed => {
+                    hashes.insert(task.id, hash);
+                    stats.updated += 1;
                 }
-            } else {
-                // Create new task in the correct list
-                match self.clickup_client.create_task(clickup_list_id, &clickup_task) {
-                    Ok(clickup_response) => {
-                        // Store mapping
-                        self.save_clickup_id(&task.id, &clickup_response.id)?;
-                        
-                        // Mark task as synced in Google Tasks with 🔃
-                        self.mark_task_synced(&task)?;
-                        
-                        stats.created += 1;
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to create task {} in list {}: {}", task.id, clickup_list_id, e);
-                        stats.errors += 1;
+                Ok(clickup_response) => {
+                    // Store mapping
+                    self.save_clickup_id(&task.id, &clickup_response.id)?;
+                    hashes.insert(task.id.clone(), hash);
+                    
+                    // Mark task as synced in Google Tasks with 🔃, if asked to
+                    if let Some(ref account) = self.marker_account {
+                        self.mark_task_synced(&task, account).await?;
                     }
+                    
+                    stats.created += 1;
+                }
+                Err(e) if existed => {
+                    eprintln!("Failed to update task {}: {}", task.id, e);
+                    stats.errors += 1;
+                }
+                Err(e) => {
+                    eprintln!("Failed to create task {} in list {}: {}", task.id, clickup_list_id, e);
+                    stats.errors += 1;
                 }
             }
         }
-        
+
+        if !dry_run {
+            self.sync_manager.set_state(HASH_KEY, &serde_json::to_string(&hashes)?)?;
+        }
         Ok(stats)
     }
 
+    /// Before pushing: give tasks whose status their list lacks the list's default status,
+    /// and add tags the lists' spaces don't have yet, so single creates don't fail on them.
+    /// Lists that can't be read are pushed as they are.
+    async fn prepare_lists(&self, pushes: &mut [(CachedTask, String, Option<String>, ClickUpTask, String)], concurrency: usize) {
+        let list_ids: BTreeSet<String> = pushes.iter().map(|(_, list_id, ..)| list_id.clone()).collect();
+        let fetched: Vec<(String, Result<ListStatuses>)> = stream::iter(list_ids)
+            .map(|list_id| async move {
+                let result = self.clickup_client.get_list_statuses(&list_id).await;
+                (list_id, result)
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+        let mut lists = HashMap::new();
+        for (list_id, result) in fetched {
+            match result {
+                Ok(list) => {
+                    lists.insert(list_id, list);
+                }
+                Err(e) => eprintln!("Warning: Couldn't read ClickUp list {}, statuses and tags unchecked: {}", list_id, e),
+            }
+        }
+
+        let mut fallbacks = BTreeMap::new();
+        let mut space_tags: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for (_, list_id, _, clickup_task, _) in pushes.iter_mut() {
+            let Some(list) = lists.get(list_id.as_str()) else {
+                continue;
+            };
+            if let Some(unknown) = use_known_status(clickup_task, &list.statuses) {
+                fallbacks.insert((list_id.clone(), unknown), clickup_task.status.clone().unwrap_or_default());
+            }
+            space_tags.entry(&list.space_id).or_default().extend(clickup_task.tags.iter().cloned());
+        }
+        for ((list_id, unknown), default) in fallbacks {
+            eprintln!("Warning: ClickUp list {} has no status \"{}\", using \"{}\" (set `clickup` on the status in config)", list_id, unknown, default);
+        }
+
+        for (space_id, tags) in space_tags {
+            let existing = match self.clickup_client.get_space_tags(space_id).await {
+                Ok(existing) => existing,
+                Err(e) => {
+                    eprintln!("Warning: Couldn't read ClickUp tags for space {}: {}", space_id, e);
+                    continue;
+                }
+            };
+            for tag in missing_tags(&tags, &existing) {
+                if let Err(e) = self.clickup_client.create_space_tag(space_id, &tag).await {
+                    eprintln!("Warning: Failed to create ClickUp tag {}: {}", tag, e);
+                }
+            }
+        }
+    }
+
     /// Get map of Google Task ID → ClickUp Task ID
     fn get_clickup_id_map(&self) -> Result<std::collections::HashMap<String, String>> {
         let json_str = self.sync_manager.get_state("clickup_id_map")?
@@ -225,7 +445,7 @@ impl ClickUpSync {
     }
     
     /// Mark a task as synced in Google Tasks by prepending 🔃
-    fn mark_task_synced(&self, task: &CachedTask) -> Result<()> {
+    async fn mark_task_synced(&self, task: &CachedTask, account: &str) -> Result<()> {
         // Skip if already marked
         if task.title.starts_with("🔃") {
             return Ok(());
@@ -236,8 +456,8 @@ impl ClickUpSync {
         
         // Use gog CLI to update the task
         // gog tasks update <tasklistId> <taskId> --title "..." --account ...
-        let output = std::process::Command::new("gog")
-            .args(&[
+        let output = tokio::process::Command::new("gog")
+            .args([
                 "tasks",
                 "update",
                 &task.list_id,
@@ -245,9 +465,10 @@ impl ClickUpSync {
                 "--title",
                 &new_title,
                 "--account",
-                "bryan@silvermineai.com",
+                account,
             ])
-            .output();
+            .logged_output()
+            .await;
         
         match output {
             Ok(result) if result.status.success() => {
@@ -286,9 +507,30 @@ struct ParsedTask {
 pub struct PushStats {
     pub created: usize,
     pub updated: usize,
+    /// Skipped because nothing changed since the last push
+    pub unchanged: usize,
     pub errors: usize,
 }
 
+/// Switch a status the list doesn't have to the list's default (its first status).
+/// Returns the status that was replaced.
+fn use_known_status(task: &mut ClickUpTask, statuses: &[String]) -> Option<String> {
+    let status = task.status.as_ref()?;
+    if statuses.iter().any(|s| s.eq_ignore_ascii_case(status)) {
+        return None;
+    }
+    let default = statuses.first()?.clone();
+    task.status.replace(default)
+}
+
+/// Tags not yet in the space; ClickUp keeps tag names lowercase
+fn missing_tags(tags: &BTreeSet<String>, existing: &[String]) -> BTreeSet<String> {
+    tags.iter()
+        .map(|t| t.to_lowercase())
+        .filter(|t| !existing.iter().any(|e| e.eq_ignore_ascii_case(t)))
+        .collect()
+}
+
 /// Extract hashtags from a string
 fn extract_hashtags(text: &str) -> Vec<String> {
     let mut tags = Vec::new();
@@ -318,12 +560,89 @@ mod tests {
         assert_eq!(parsed.title, "Fix editor bug #DeepWork");
     }
 
+    #[test]
+    fn test_known_statuses_and_tags() {
+        let mut task = ClickUpTask {
+            id: None,
+            name: "Ship it".into(),
+            description: None,
+            status: Some("Review".into()),
+            priority: None,
+            due_date: None,
+            start_date: None,
+            time_estimate: None,
+            tags: vec![],
+            assignees: vec![],
+            custom_fields: None,
+        };
+        let statuses = vec!["open".to_string(), "review".to_string(), "closed".to_string()];
+        assert_eq!(use_known_status(&mut task, &statuses), None);
+        task.status = Some("blocked".into());
+        assert_eq!(use_known_status(&mut task, &statuses).as_deref(), Some("blocked"));
+        assert_eq!(task.status.as_deref(), Some("open"));
+
+        let tags = BTreeSet::from(["DeepWork".to_string(), "WORK".to_string(), "@home".to_string()]);
+        let missing = missing_tags(&tags, &["work".to_string()]);
+        assert_eq!(missing.into_iter().collect::<Vec<_>>(), vec!["@home", "deepwork"]);
+    }
+
+    #[test]
+    fn test_strip_markers() {
+        assert_eq!(strip_markers("[2026-01-27][P1][LIFE] Call mom", false), None);
+        assert_eq!(strip_markers("🔃 [2026-01-27][P1][LIFE] Call mom", false).as_deref(), Some("[2026-01-27][P1][LIFE] Call mom"));
+        assert_eq!(strip_markers("🔃 Call mom", true), None);
+        assert_eq!(strip_markers("ðŸ”ƒ ðŸ”ƒ Call mom", true).as_deref(), Some("🔃 Call mom"));
+        assert_eq!(strip_markers("\u{f0}\u{9f}\u{94}\u{83} Call mom", false).as_deref(), Some("Call mom"));
+    }
+
     #[test]
     fn test_priority_conversion() {
-        assert_eq!(ClickUpSync::priority_to_clickup(Some("P0")), Some(1));
-        assert_eq!(ClickUpSync::priority_to_clickup(Some("P1")), Some(2));
-        assert_eq!(ClickUpSync::priority_to_clickup(Some("P2")), Some(3));
-        assert_eq!(ClickUpSync::priority_to_clickup(Some("P3")), Some(4));
+        let config = crate::create_default_config();
+        assert_eq!(ClickUpSync::priority_to_clickup(&config, Some("P0")), Some(1));
+        assert_eq!(ClickUpSync::priority_to_clickup(&config, Some("P1")), Some(2));
+        assert_eq!(ClickUpSync::priority_to_clickup(&config, Some("P2")), Some(3));
+        assert_eq!(ClickUpSync::priority_to_clickup(&config, Some("P3")), Some(4));
+        assert_eq!(ClickUpSync::priority_to_clickup(&config, Some("P9")), None);
+        assert_eq!(ClickUpSync::priority_to_clickup(&config, None), None);
+
+        let mut custom = crate::create_default_config();
+        custom.priorities = ["Now", "Soon", "Later", "Someday", "Never"].iter()
+            .map(|name| crate::Priority { name: name.to_string(), ..Default::default() })
+            .collect();
+        assert_eq!(ClickUpSync::priority_to_clickup(&custom, Some("Now")), Some(1));
+        assert_eq!(ClickUpSync::priority_to_clickup(&custom, Some("Later")), Some(3));
+        assert_eq!(ClickUpSync::priority_to_clickup(&custom, Some("Never")), Some(4));
+        assert_eq!(ClickUpSync::priority_to_clickup(&custom, Some("P0")), None);
+        assert_eq!(ClickUpSync::priority_from_clickup(&custom, "2", Some("Now")).as_deref(), Some("Soon"));
+        assert_eq!(ClickUpSync::priority_from_clickup(&custom, "4", Some("Now")).as_deref(), Some("Someday"));
+    }
+
+    #[test]
+    fn test_priority_and_status_round_trip() {
+        let config = crate::create_default_config();
+        for priority in ["P0", "P1", "P2", "P3", "P5"] {
+            let id = ClickUpSync::priority_to_clickup(&config, Some(priority)).unwrap().to_string();
+            assert_eq!(ClickUpSync::priority_from_clickup(&config, &id, Some(priority)).as_deref(), Some(priority));
+        }
+        for status in [None, Some("needsTriage"), Some("progress"), Some("review"), Some("blocked")] {
+            let remote = ClickUpSync::status_to_clickup(&[], status, "needsAction");
+            assert_eq!(ClickUpSync::status_from_clickup(&[], &remote, status).as_deref(), status);
+        }
+        assert!(ClickUpSync::is_closed_status(&[], &ClickUpSync::status_to_clickup(&[], None, "completed")));
+    }
+
+    #[test]
+    fn test_configured_status_names() {
+        let statuses = vec![
+            StatusOption { name: "doing".into(), clickup: Some("In Flight".into()), ..Default::default() },
+            StatusOption { name: "shipped".into(), clickup: Some("Released".into()), done: true, ..Default::default() },
+        ];
+        assert_eq!(ClickUpSync::status_to_clickup(&statuses, Some("doing"), "needsAction"), "In Flight");
+        assert_eq!(ClickUpSync::status_from_clickup(&statuses, "in flight", None).as_deref(), Some("doing"));
+        assert!(ClickUpSync::is_closed_status(&statuses, "released"));
+        assert!(!ClickUpSync::is_closed_status(&statuses, "in flight"));
+        // Statuses without a ClickUp name keep the built-in mapping
+        assert_eq!(ClickUpSync::status_to_clickup(&statuses, Some("review"), "needsAction"), "review");
     }
 
     #[test]
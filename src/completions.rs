@@ -0,0 +1,81 @@
+use anyhow::{anyhow, Result};
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::Shells;
+use std::collections::BTreeSet;
+
+use crate::sync::SyncManager;
+use crate::{get_config_path, Config};
+
+// Shell completion. The script printed by `completions <shell>` calls back into
+// the binary with COMPLETE=<shell> set, and clap_complete answers from the
+// candidate functions below. They run on every <TAB>, so they only read the
+// config file and the local cache: no sync, no network, nothing on stdout.
+
+/// Environment variable the registration script sets when asking for candidates
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// How many open tasks to offer when completing an ID
+const RECENT_TASK_LIMIT: usize = 50;
+
+/// Shells `completions` can print a script for
+pub const SHELLS: [&str; 5] = ["bash", "zsh", "fish", "elvish", "powershell"];
+
+/// Print the registration script for a shell
+pub fn print_script(shell: &str) -> Result<()> {
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(shell)
+        .ok_or_else(|| anyhow!("Unsupported shell '{}'", shell))?;
+    let exe = std::env::current_exe()?;
+    completer.write_registration(COMPLETE_VAR, "thegarden", "thegarden", &exe.to_string_lossy(), &mut std::io::stdout())?;
+    Ok(())
+}
+
+/// The config as it is on disk, without creating a default one
+fn read_config() -> Option<Config> {
+    let contents = std::fs::read_to_string(get_config_path().ok()?).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Short IDs of open tasks, most recently updated first, with the title as help
+pub fn task_ids() -> Vec<CompletionCandidate> {
+    let Ok(mut tasks) = SyncManager::new().and_then(|s| s.get_all_cached_tasks()) else {
+        return Vec::new();
+    };
+    tasks.retain(|t| t.status != "completed");
+    tasks.sort_by(|a, b| b.updated.cmp(&a.updated));
+
+    tasks.into_iter()
+        .take(RECENT_TASK_LIMIT)
+        .map(|t| CompletionCandidate::new(t.id.chars().take(8).collect::<String>()).help(Some(t.title.into())))
+        .collect()
+}
+
+/// Project names from the config
+pub fn projects() -> Vec<CompletionCandidate> {
+    let Some(config) = read_config() else {
+        return Vec::new();
+    };
+    let projects: BTreeSet<String> = config.projects.into_keys().collect();
+    projects.into_iter().map(CompletionCandidate::new).collect()
+}
+
+/// View names from the config
+pub fn views() -> Vec<CompletionCandidate> {
+    let Some(config) = read_config() else {
+        return Vec::new();
+    };
+    let views: BTreeSet<String> = config.views.into_keys().collect();
+    views.into_iter().map(CompletionCandidate::new).collect()
+}
+
+/// Contexts from the config plus any that tasks in the cache use
+pub fn contexts() -> Vec<CompletionCandidate> {
+    let mut contexts: BTreeSet<String> = read_config()
+        .map(|c| c.contexts.into_iter().collect())
+        .unwrap_or_default();
+    if let Ok(tasks) = SyncManager::new().and_then(|s| s.get_all_cached_tasks()) {
+        contexts.extend(tasks.into_iter().filter_map(|t| t.context).filter(|c| !c.is_empty()));
+    }
+    contexts.into_iter().map(CompletionCandidate::new).collect()
+}
@@ -0,0 +1,118 @@
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use colored::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{create_task_in_google, get_tasks_from_cache, output, parse_task_links, push_dirty_tasks, update_task_locally, App, Config, Task};
+
+// Delegated emails track themselves: after a sync, an open task linked to an email
+// and given the delegate priority gets a #FollowUp task due a few days out, with the
+// email link in its notes. Each task gets one follow-up, remembered in sync_state, so
+// finishing or deleting the follow-up doesn't bring it back.
+
+/// sync_state key: delegated task ID → its follow-up's ID
+const FOLLOW_UP_MAP: &str = "follow_up_id_map";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FollowUpPolicy {
+    pub enabled: bool,
+    /// The priority that means "delegated"
+    pub priority: String,
+    /// Days from delegating until the follow-up is due
+    pub days: i64,
+}
+
+impl Default for FollowUpPolicy {
+    fn default() -> Self {
+        FollowUpPolicy { enabled: false, priority: "P5".into(), days: 3 }
+    }
+}
+
+/// Open, linked to an email, and at the delegate priority
+pub fn is_delegated_email(policy: &FollowUpPolicy, task: &Task) -> bool {
+    !task.is_done()
+        && task.attachment_type.as_deref() == Some("email")
+        && task.priority.as_deref() == Some(policy.priority.as_str())
+}
+
+/// The follow-up for `task`: same list, project and context, due `days` after `today`
+pub fn follow_up_for(config: &Config, task: &Task, email: Option<&str>, today: NaiveDate) -> Task {
+    let mut follow_up = Task::parse_with_config(&format!("Follow up: {} #FollowUp", task.title), &task.list, Some(config));
+    follow_up.date = (today + chrono::Duration::days(config.delegation_follow_up.days)).format("%Y-%m-%d").to_string();
+    follow_up.project = task.project.clone();
+    follow_up.context = task.context.clone();
+    let mut notes = Vec::new();
+    if let Some(url) = email {
+        notes.push(format!("Waiting on a reply to: {}", url));
+    }
+    notes.push(format!("Delegated: {} ({})", task.title, task.short_id()));
+    follow_up.notes = Some(notes.join("\n"));
+    follow_up
+}
+
+/// Create follow-ups for delegated emails that don't have one yet; returns how many
+pub fn create_follow_ups(app: &App) -> Result<usize> {
+    let config = &app.config;
+    let policy = &config.delegation_follow_up;
+    if !policy.enabled || crate::sync::is_offline() {
+        return Ok(0);
+    }
+
+    let mut followed_up = app.db.get_id_map(FOLLOW_UP_MAP)?;
+    let today = Local::now().date_naive();
+    let mut created = 0;
+    for task in get_tasks_from_cache(app)?.iter().filter(|t| is_delegated_email(policy, t)) {
+        let (Some(task_id), Some(list_id)) = (task.id.as_deref(), task.list_id.as_deref()) else { continue };
+        if followed_up.contains_key(task_id) {
+            continue;
+        }
+
+        let links = app.db.get_task_by_id(task_id)?.and_then(|c| c.links).map(|l| parse_task_links(&l)).unwrap_or_default();
+        let email = links.iter()
+            .find(|(label, url)| label == "email" || url.contains("mail.google.com"))
+            .map(|(_, url)| url.as_str())
+            .or_else(|| task.notes.as_deref().and_then(crate::gmail::email_link));
+        let mut follow_up = follow_up_for(config, task, email, today);
+        let id = create_task_in_google(app, list_id, &follow_up.format(config), None)?;
+        follow_up.id = Some(id.clone());
+        follow_up.list_id = Some(list_id.to_string());
+        update_task_locally(app, &follow_up)?;
+
+        followed_up.insert(task_id.to_string(), id);
+        app.db.set_state(FOLLOW_UP_MAP, &serde_json::to_string(&followed_up)?)?;
+        created += 1;
+    }
+
+    if created > 0 {
+        push_dirty_tasks(app)?;
+        output::note(format!("📨 Added {} follow-up(s) for delegated emails", created).cyan());
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delegated_email_follow_up() {
+        let mut config = crate::create_default_config();
+        config.delegation_follow_up.days = 2;
+        let policy = &config.delegation_follow_up;
+
+        let mut task = Task::parse_with_config("[2026-03-04][P5][WORK][@email] Ask Sam for the specs", "Work", None);
+        task.id = Some("abcdef123456".into());
+        assert!(!is_delegated_email(policy, &task));
+        task.attachment_type = Some("email".into());
+        assert!(is_delegated_email(policy, &task));
+
+        let today = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let follow_up = follow_up_for(&config, &task, Some("https://mail.google.com/mail/#all/123"), today);
+        assert_eq!(follow_up.title, "Follow up: Ask Sam for the specs #FollowUp");
+        assert_eq!(follow_up.date, "2026-03-06");
+        assert_eq!(follow_up.priority.as_deref(), Some("P1"));
+        assert_eq!(follow_up.project.as_deref(), Some("WORK"));
+        assert_eq!(follow_up.notes.as_deref(), Some("Waiting on a reply to: https://mail.google.com/mail/#all/123\nDelegated: Ask Sam for the specs (abcdef12)"));
+    }
+}
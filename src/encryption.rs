@@ -0,0 +1,163 @@
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::logging::LoggedCommand;
+
+// Encryption of the cache's sensitive columns (titles, notes, links and their history) and
+// the credentials kept in sync_state.
+// Each value is sealed on its own with AES-256-GCM, so the rest of the schema (IDs, dates,
+// priorities) stays queryable. The key lives in the OS keychain: `security` on macOS,
+// `secret-tool` (libsecret) on Linux. THEGARDEN_CACHE_KEY, base64 of 32 bytes, overrides
+// it for machines without a keychain.
+
+/// Marks a sealed value; anything without it is plaintext, so half-converted rows still read
+pub const PREFIX: &str = "enc1:";
+
+const KEY_ENV: &str = "THEGARDEN_CACHE_KEY";
+const KEYCHAIN_SERVICE: &str = "thegarden";
+const KEYCHAIN_ACCOUNT: &str = "cache-key";
+
+pub struct Cipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl Cipher {
+    pub fn new(key: &[u8; 32]) -> Self {
+        let key = UnboundKey::new(&AES_256_GCM, key).expect("AES-256 takes a 32-byte key");
+        Cipher { key: LessSafeKey::new(key), rng: SystemRandom::new() }
+    }
+
+    pub fn encrypt(&self, plain: &str) -> String {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng.fill(&mut nonce).expect("system random source failed");
+        let mut sealed = plain.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut sealed)
+            .expect("AES-GCM sealing failed");
+        format!("{}{}", PREFIX, BASE64.encode([nonce.as_slice(), &sealed].concat()))
+    }
+
+    pub fn decrypt(&self, value: &str) -> Result<String> {
+        let Some(encoded) = value.strip_prefix(PREFIX) else {
+            return Ok(value.to_string());
+        };
+        let bytes = BASE64.decode(encoded).context("Corrupt encrypted value in the cache")?;
+        if bytes.len() < NONCE_LEN {
+            bail!("Corrupt encrypted value in the cache");
+        }
+        let (nonce, sealed) = bytes.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| anyhow!("Corrupt encrypted value in the cache"))?;
+        let mut sealed = sealed.to_vec();
+        let plain = self.key
+            .open_in_place(nonce, Aad::empty(), &mut sealed)
+            .map_err(|_| anyhow!("Can't decrypt the cache: the key doesn't match the one it was encrypted with"))?;
+        Ok(String::from_utf8(plain.to_vec())?)
+    }
+
+    pub fn encrypt_opt(&self, plain: Option<&str>) -> Option<String> {
+        plain.map(|plain| self.encrypt(plain))
+    }
+
+    pub fn decrypt_opt(&self, value: Option<String>) -> Result<Option<String>> {
+        value.map(|value| self.decrypt(&value)).transpose()
+    }
+}
+
+fn decode_key(encoded: &str) -> Result<[u8; 32]> {
+    let bytes = BASE64.decode(encoded.trim()).context("The cache key isn't valid base64")?;
+    bytes.try_into().map_err(|_| anyhow!("The cache key must be 32 bytes"))
+}
+
+/// The cache key from $THEGARDEN_CACHE_KEY or the keychain; with `create`, a new one is
+/// generated and stored in the keychain if there is none yet
+pub fn load_key(create: bool) -> Result<[u8; 32]> {
+    if let Ok(encoded) = std::env::var(KEY_ENV) {
+        return decode_key(&encoded).with_context(|| format!("Bad {}", KEY_ENV));
+    }
+    if let Some(encoded) = keychain_lookup()? {
+        return decode_key(&encoded);
+    }
+    if !create {
+        bail!("The cache is encrypted but its key isn't in the keychain (or {})", KEY_ENV);
+    }
+
+    let mut key = [0u8; 32];
+    SystemRandom::new().fill(&mut key).map_err(|_| anyhow!("System random source failed"))?;
+    keychain_store(&BASE64.encode(key))?;
+    Ok(key)
+}
+
+fn keychain_lookup() -> Result<Option<String>> {
+    let output = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["find-generic-password", "-s", KEYCHAIN_SERVICE, "-a", KEYCHAIN_ACCOUNT, "-w"])
+            .logged_output()
+    } else if cfg!(unix) {
+        Command::new("secret-tool")
+            .args(["lookup", "service", KEYCHAIN_SERVICE, "account", KEYCHAIN_ACCOUNT])
+            .logged_output()
+    } else {
+        bail!("No supported keychain on this system; set {} instead", KEY_ENV);
+    };
+    let output = output.with_context(|| format!("Failed to run the keychain tool (security on macOS, secret-tool on Linux); or set {}", KEY_ENV))?;
+    let key = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    // Both tools exit non-zero when there is no such item
+    Ok(Some(key).filter(|key| output.status.success() && !key.is_empty()))
+}
+
+/// Store the key, passing it on stdin so it never shows up in the process list or logs
+fn keychain_store(encoded: &str) -> Result<()> {
+    let (mut command, input) = if cfg!(target_os = "macos") {
+        let mut command = Command::new("security");
+        command.arg("-i");
+        let input = format!("add-generic-password -U -s {} -a {} -w {}\n", KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT, encoded);
+        (command, input)
+    } else if cfg!(unix) {
+        let mut command = Command::new("secret-tool");
+        command.args(["store", "--label=thegarden cache key", "service", KEYCHAIN_SERVICE, "account", KEYCHAIN_ACCOUNT]);
+        (command, encoded.to_string())
+    } else {
+        bail!("No supported keychain on this system; set {} instead", KEY_ENV);
+    };
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run the keychain tool (security on macOS, secret-tool on Linux); or set {}", KEY_ENV))?;
+    child.stdin.take().expect("piped stdin").write_all(input.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!("Failed to store the cache key in the keychain: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cipher_round_trip() {
+        let cipher = Cipher::new(&[7; 32]);
+        let sealed = cipher.encrypt("[2026-01-27][P1][WORK] Salary review");
+        assert!(sealed.starts_with(PREFIX) && !sealed.contains("Salary"));
+        assert_ne!(sealed, cipher.encrypt("[2026-01-27][P1][WORK] Salary review"));
+        assert_eq!(cipher.decrypt(&sealed).unwrap(), "[2026-01-27][P1][WORK] Salary review");
+
+        // Plaintext left from before encryption reads as is
+        assert_eq!(cipher.decrypt("Buy milk").unwrap(), "Buy milk");
+        assert_eq!(cipher.decrypt_opt(None).unwrap(), None);
+
+        assert!(Cipher::new(&[8; 32]).decrypt(&sealed).is_err());
+        assert_eq!(decode_key(&BASE64.encode([1u8; 32])).unwrap(), [1u8; 32]);
+        assert!(decode_key(&BASE64.encode([1u8; 16])).is_err());
+    }
+}
@@ -0,0 +1,322 @@
+use anyhow::{Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{create_task_in_google, get_tasks_from_cache, push_dirty_tasks_to_google, update_task_locally, App, Task, TaskFilter};
+
+// `export` writes every cached task with all it knows about it: the parsed title
+// fields, notes, dates, Google/Airtable/ClickUp IDs. It reads the cache as is (no
+// sync first), so stdout carries nothing but the data. A JSON export can be loaded
+// back with `import`, which updates tasks still in the cache and recreates the
+// rest in Google Tasks (they get new IDs there, in their old list if it still exists).
+
+pub const FORMATS: [&str; 3] = ["json", "csv", "org"];
+
+/// Columns, in order, for the CSV export
+const CSV_COLUMNS: [&str; 23] = [
+    "id", "unique_id", "list_id", "parent_id", "title", "name", "due_date", "priority", "project",
+    "status", "context", "time", "tags", "notes", "start_date", "scheduled_date", "created",
+    "updated", "completed", "bump_count", "links", "airtable_id", "clickup_id",
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ExportedTask {
+    pub id: String,
+    #[serde(default)]
+    pub unique_id: Option<String>,
+    #[serde(default)]
+    pub list_id: Option<String>,
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    /// The full title as stored in Google Tasks, prefix included
+    pub title: String,
+    /// The title without the [date][priority][project] prefix
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub time: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub scheduled_date: Option<String>,
+    /// RFC 3339, from Google
+    #[serde(default)]
+    pub created: Option<String>,
+    #[serde(default)]
+    pub updated: Option<String>,
+    /// Completed in Google Tasks
+    #[serde(default)]
+    pub completed: bool,
+    #[serde(default)]
+    pub bump_count: i64,
+    /// Google Tasks links (emails, docs) as returned by the API
+    #[serde(default)]
+    pub links: Option<serde_json::Value>,
+    #[serde(default)]
+    pub airtable_id: Option<String>,
+    #[serde(default)]
+    pub clickup_id: Option<String>,
+}
+
+/// Every cached task matching the filter, oldest first
+pub fn collect(app: &App, filter: &TaskFilter) -> Result<Vec<ExportedTask>> {
+    let tasks = get_tasks_from_cache(app)?;
+    let mut matching: Vec<&Task> = tasks.iter().collect();
+    filter.retain(&mut matching);
+    let mut exported = describe(app, &matching)?;
+    exported.sort_by(|a, b| a.created.cmp(&b.created).then_with(|| a.id.cmp(&b.id)));
+    Ok(exported)
+}
+
+/// The export record for each of these tasks that is in the cache, in the same order
+pub fn describe(app: &App, tasks: &[&Task]) -> Result<Vec<ExportedTask>> {
+    let cached: HashMap<String, _> = app.db.get_all_cached_tasks()?.into_iter().map(|t| (t.id.clone(), t)).collect();
+    let parents = app.db.get_parent_ids()?;
+    let bumps = app.db.get_bump_counts()?;
+    let airtable = app.db.get_id_map("airtable_id_map")?;
+    let clickup = app.db.get_id_map("clickup_id_map")?;
+
+    Ok(tasks.iter()
+        .filter_map(|task| {
+            let id = task.id.as_deref()?;
+            let row = cached.get(id)?;
+            Some(ExportedTask {
+                id: id.to_string(),
+                unique_id: Some(row.unique_id.clone()),
+                list_id: Some(row.list_id.clone()),
+                parent_id: parents.get(id).cloned(),
+                title: row.title.clone(),
+                name: task.title.clone(),
+                due_date: Some(task.date.clone()),
+                priority: task.priority.clone(),
+                project: task.project.clone(),
+                status: task.status.clone(),
+                context: task.context.clone(),
+                time: task.time.clone(),
+                tags: task.tags.clone(),
+                notes: task.notes.clone(),
+                start_date: task.start.clone(),
+                scheduled_date: row.scheduled_date.clone(),
+                created: row.created.clone(),
+                updated: Some(row.updated.clone()),
+                completed: row.status == "completed",
+                bump_count: bumps.get(id).copied().unwrap_or(0),
+                links: row.links.as_deref().and_then(|l| serde_json::from_str(l).ok()),
+                airtable_id: airtable.get(id).cloned(),
+                clickup_id: clickup.get(id).cloned(),
+            })
+        })
+        .collect())
+}
+
+pub fn to_json(tasks: &[ExportedTask]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(tasks)? + "\n")
+}
+
+/// One row per task; tags are space-separated and links stay JSON
+pub fn to_csv(tasks: &[ExportedTask]) -> Result<String> {
+    let mut out = CSV_COLUMNS.join(",");
+    out.push('\n');
+    for task in tasks {
+        // Same field order as CSV_COLUMNS
+        let value = serde_json::to_value(task)?;
+        let row: Vec<String> = CSV_COLUMNS.iter()
+            .map(|column| match &value[*column] {
+                serde_json::Value::Null => String::new(),
+                serde_json::Value::String(s) => csv_field(s),
+                serde_json::Value::Array(items) if *column == "tags" => {
+                    csv_field(&items.iter().filter_map(|t| t.as_str()).collect::<Vec<_>>().join(" "))
+                }
+                other => csv_field(&other.to_string()),
+            })
+            .collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Quote a CSV field if it needs it (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// How an import went
+#[derive(Debug, Default)]
+pub struct ImportStats {
+    pub updated: usize,
+    pub created: usize,
+    pub unchanged: usize,
+    /// Completed tasks that are no longer in the cache (not recreated)
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+/// Load a JSON export: tasks still in the cache get their title, due date, start
+/// date and notes back; open tasks that are gone are created again in Google Tasks.
+pub fn import_json(app: &App, contents: &str) -> Result<ImportStats> {
+    let records: Vec<ExportedTask> = serde_json::from_str(contents).context("Not a thegarden JSON export")?;
+    import_records(app, &records)
+}
+
+/// Apply export records to the cache and Google Tasks. A record marked completed
+/// completes its task; one marked open leaves a completed task alone.
+pub fn import_records(app: &App, records: &[ExportedTask]) -> Result<ImportStats> {
+    let mut stats = ImportStats::default();
+    // Google's lists, fetched the first time a task has to be recreated
+    let mut lists = None;
+
+    for record in records {
+        let result = match app.db.get_task_by_id(&record.id)? {
+            Some(row) => {
+                let was_completed = row.status == "completed";
+                let same = row.title == record.title
+                    && row.user_description == record.notes
+                    && row.start_date == record.start_date
+                    && (was_completed || !record.completed);
+                if same {
+                    stats.unchanged += 1;
+                    continue;
+                }
+                let mut task = restored_task(app, record, row.id, row.list_id);
+                task.completed |= was_completed;
+                update_task_locally(app, &task).map(|_| stats.updated += 1)
+            }
+            None if record.completed => {
+                stats.skipped += 1;
+                continue;
+            }
+            None => restore_list(app, &mut lists, record.list_id.as_deref()).and_then(|list_id| {
+                let id = create_task_in_google(app, &list_id, &record.title, None)?;
+                println!("  {} {}", "+".green(), record.title);
+                stats.created += 1;
+                if record.notes.is_some() || record.start_date.is_some() {
+                    let task = restored_task(app, record, id, list_id);
+                    update_task_locally(app, &task)?;
+                }
+                Ok(())
+            }),
+        };
+        if let Err(e) = result {
+            println!("  {} {}: {}", "❌".red(), record.title, e);
+            stats.failed += 1;
+        }
+    }
+
+    if stats.updated > 0 || stats.created > 0 {
+        push_dirty_tasks_to_google(app)?;
+    }
+    Ok(stats)
+}
+
+/// The list to recreate a task in: the one it was exported from if Google still has it,
+/// otherwise the default list
+fn restore_list(app: &App, lists: &mut Option<Vec<String>>, list_id: Option<&str>) -> Result<String> {
+    let Some(list_id) = list_id else {
+        return Ok("@default".to_string());
+    };
+    if lists.is_none() {
+        *lists = Some(app.google.list_ids()?);
+    }
+    let exists = lists.iter().flatten().any(|id| id == list_id);
+    Ok(if exists { list_id } else { "@default" }.to_string())
+}
+
+/// The task as the export describes it, under `id` in `list_id`
+fn restored_task(app: &App, record: &ExportedTask, id: String, list_id: String) -> Task {
+    let mut task = Task::parse_with_config(&record.title, "Tasks", Some(&app.config));
+    task.id = Some(id);
+    task.list_id = Some(list_id);
+    if let Some(ref due) = record.due_date {
+        task.date = due.clone();
+    }
+    task.notes = record.notes.clone();
+    task.start = record.start_date.clone();
+    task.completed = record.completed;
+    task
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ExportedTask {
+        serde_json::from_value(serde_json::json!({
+            "id": "abc",
+            "title": "[2026-01-21][P1][WORK] Send \"final\" report, today",
+            "name": "Send \"final\" report, today",
+            "tags": ["urgent", "email"],
+            "bump_count": 2,
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_csv_quotes_and_flattens() {
+        let csv = to_csv(&[sample()]).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next().unwrap().split(',').count(), CSV_COLUMNS.len());
+        assert_eq!(
+            lines.next().unwrap(),
+            "abc,,,,\"[2026-01-21][P1][WORK] Send \"\"final\"\" report, today\",\"Send \"\"final\"\" report, today\",,,,,,,urgent email,,,,,,false,2,,,"
+        );
+    }
+
+    #[test]
+    fn test_json_round_trips() {
+        let json = to_json(&[sample()]).unwrap();
+        let parsed: Vec<ExportedTask> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, vec![sample()]);
+    }
+
+    #[test]
+    fn test_import_recreates_tasks_in_their_list() {
+        let fake = crate::provider::fake::FakeProvider::new();
+        fake.add_list("work");
+        let app = App {
+            config: crate::create_default_config(),
+            db: crate::sync::SyncManager::open(std::path::Path::new(":memory:")).unwrap(),
+            google: Box::new(fake.clone()),
+        };
+        let record = |id: &str, title: &str, list_id: Option<&str>| ExportedTask {
+            id: id.into(),
+            title: title.into(),
+            list_id: list_id.map(|l| l.into()),
+            ..Default::default()
+        };
+        let records = [
+            record("gone1", "Kept list", Some("work")),
+            record("gone2", "Deleted list", Some("old")),
+            record("gone3", "No list", None),
+        ];
+        let stats = import_records(&app, &records).unwrap();
+        assert_eq!((stats.created, stats.failed), (3, 0));
+
+        let list_of = |title: &str| {
+            get_tasks_from_cache(&app).unwrap().into_iter()
+                .find(|t| t.title == title).unwrap()
+                .list_id.unwrap()
+        };
+        assert_eq!(list_of("Kept list"), "work");
+        assert_eq!(list_of("Deleted list"), "@default");
+        assert_eq!(list_of("No list"), "@default");
+    }
+}
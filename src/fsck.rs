@@ -0,0 +1,178 @@
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::sync::SyncManager;
+use crate::TASK_REGEX;
+
+// Consistency checks over cache.db. Everything here is local: nothing is pushed,
+// and `--repair` only touches bookkeeping (IDs, mappings, pair tables). A title
+// that lost its [date][P][PROJECT] prefix is reported but left for triage, since
+// rewriting it would change what is in Google Tasks.
+
+/// sync_state keys holding Google task ID → provider record ID maps
+const ID_MAPS: [(&str, &str); 4] = [
+    ("airtable_id_map", "Airtable"),
+    ("clickup_id_map", "ClickUp"),
+    ("asana_id_map", "Asana"),
+    ("mstodo_id_map", "Microsoft To Do"),
+];
+
+#[derive(Debug, PartialEq)]
+pub enum Problem {
+    /// The row has priority/project/duration set but the title doesn't parse
+    UnparsedTriagedTitle { task_id: String, title: String },
+    MissingUniqueId { task_id: String },
+    /// Several tasks share a unique_id; the first (by ID) keeps it
+    DuplicateUniqueId { unique_id: String, task_ids: Vec<String> },
+    /// A provider record mapping for a task that is no longer in the cache
+    OrphanedMapping { map_key: &'static str, provider: &'static str, task_id: String },
+    OrphanedDismissedPair { task_id_1: String, task_id_2: String },
+    OrphanedQueuedPair { task_id_1: String, task_id_2: String },
+}
+
+impl Problem {
+    /// Whether `repair` can fix this without touching task content
+    pub fn is_repairable(&self) -> bool {
+        !matches!(self, Problem::UnparsedTriagedTitle { .. })
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::UnparsedTriagedTitle { task_id, title } => {
+                write!(f, "{}: has triage fields but title doesn't parse: \"{}\" (re-triage it)", short(task_id), title)
+            }
+            Problem::MissingUniqueId { task_id } => write!(f, "{}: missing unique_id", short(task_id)),
+            Problem::DuplicateUniqueId { unique_id, task_ids } => write!(
+                f, "unique_id {} shared by {}",
+                unique_id, task_ids.iter().map(|id| short(id)).collect::<Vec<_>>().join(", ")
+            ),
+            Problem::OrphanedMapping { provider, task_id, .. } => {
+                write!(f, "{} mapping for deleted task {}", provider, short(task_id))
+            }
+            Problem::OrphanedDismissedPair { task_id_1, task_id_2 } => {
+                write!(f, "dismissed duplicate pair {} / {} references a deleted task", short(task_id_1), short(task_id_2))
+            }
+            Problem::OrphanedQueuedPair { task_id_1, task_id_2 } => {
+                write!(f, "queued merge pair {} / {} references a deleted task", short(task_id_1), short(task_id_2))
+            }
+        }
+    }
+}
+
+fn short(id: &str) -> String {
+    id.chars().take(8).collect()
+}
+
+/// Everything wrong with the cache, in the order the checks run
+pub fn check(db: &SyncManager) -> Result<Vec<Problem>> {
+    let mut problems = Vec::new();
+    let tasks = db.get_all_cached_tasks()?;
+    let task_ids: HashSet<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+
+    for task in &tasks {
+        let claims_triage = task.priority.is_some() || task.project.is_some() || task.duration.is_some();
+        if claims_triage && !TASK_REGEX.is_match(&task.title) {
+            problems.push(Problem::UnparsedTriagedTitle { task_id: task.id.clone(), title: task.title.clone() });
+        }
+    }
+
+    let mut by_unique_id: HashMap<String, Vec<String>> = HashMap::new();
+    for (task_id, unique_id) in db.get_unique_ids()? {
+        match unique_id.filter(|u| !u.is_empty()) {
+            Some(unique_id) => by_unique_id.entry(unique_id).or_default().push(task_id),
+            None => problems.push(Problem::MissingUniqueId { task_id }),
+        }
+    }
+    let mut duplicates: Vec<Problem> = by_unique_id.into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .map(|(unique_id, task_ids)| Problem::DuplicateUniqueId { unique_id, task_ids })
+        .collect();
+    duplicates.sort_by_key(|p| p.to_string());
+    problems.extend(duplicates);
+
+    for (map_key, provider) in ID_MAPS {
+        let mut orphaned: Vec<String> = db.get_id_map(map_key)?.into_keys()
+            .filter(|id| !task_ids.contains(id.as_str()))
+            .collect();
+        orphaned.sort();
+        problems.extend(orphaned.into_iter().map(|task_id| Problem::OrphanedMapping { map_key, provider, task_id }));
+    }
+
+    let orphaned = |(a, b): &(String, String)| !task_ids.contains(a.as_str()) || !task_ids.contains(b.as_str());
+    for (task_id_1, task_id_2) in db.get_dismissed_pairs()?.into_iter().filter(orphaned) {
+        problems.push(Problem::OrphanedDismissedPair { task_id_1, task_id_2 });
+    }
+    for (task_id_1, task_id_2) in db.get_merge_queue()?.into_iter().filter(orphaned) {
+        problems.push(Problem::OrphanedQueuedPair { task_id_1, task_id_2 });
+    }
+
+    Ok(problems)
+}
+
+/// Fix every repairable problem. Returns how many were fixed.
+pub fn repair(db: &SyncManager, problems: &[Problem]) -> Result<usize> {
+    let mut fixed = 0;
+    for problem in problems {
+        match problem {
+            Problem::UnparsedTriagedTitle { .. } => continue,
+            Problem::MissingUniqueId { task_id } => {
+                db.set_unique_id(task_id, &uuid::Uuid::new_v4().to_string())?;
+            }
+            Problem::DuplicateUniqueId { task_ids, .. } => {
+                for task_id in task_ids.iter().skip(1) {
+                    db.set_unique_id(task_id, &uuid::Uuid::new_v4().to_string())?;
+                }
+            }
+            Problem::OrphanedMapping { map_key, task_id, .. } => {
+                let mut map = db.get_id_map(map_key)?;
+                map.remove(task_id);
+                db.set_state(map_key, &serde_json::to_string(&map)?)?;
+            }
+            Problem::OrphanedDismissedPair { task_id_1, task_id_2 } => db.undismiss_pair(task_id_1, task_id_2)?,
+            Problem::OrphanedQueuedPair { task_id_1, task_id_2 } => db.remove_queued_pair(task_id_1, task_id_2)?,
+        }
+        fixed += 1;
+    }
+    Ok(fixed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::CachedTask;
+    use std::path::Path;
+
+    #[test]
+    fn test_check_and_repair() {
+        let db = SyncManager::open(Path::new(":memory:")).unwrap();
+        let mut good = CachedTask::parse_from_emoji_string("[2026-01-21][P1][WORK] Fine", "good", "list");
+        good.priority = Some("P1".into());
+        db.upsert_task_locally(&good).unwrap();
+        let mut broken = CachedTask::parse_from_emoji_string("Lost its prefix", "broken", "list");
+        broken.priority = Some("P0".into());
+        db.upsert_task_locally(&broken).unwrap();
+
+        db.set_state("clickup_id_map", r#"{"good": "cu1", "gone": "cu2"}"#).unwrap();
+        db.set_state("mstodo_id_map", r#"{"gone": "list1 todo1"}"#).unwrap();
+        db.dismiss_pair("good", "gone").unwrap();
+        db.queue_merge_pair("broken", "good").unwrap();
+
+        let problems = check(&db).unwrap();
+        assert_eq!(problems, vec![
+            Problem::UnparsedTriagedTitle { task_id: "broken".into(), title: "Lost its prefix".into() },
+            Problem::OrphanedMapping { map_key: "clickup_id_map", provider: "ClickUp", task_id: "gone".into() },
+            Problem::OrphanedMapping { map_key: "mstodo_id_map", provider: "Microsoft To Do", task_id: "gone".into() },
+            Problem::OrphanedDismissedPair { task_id_1: "gone".into(), task_id_2: "good".into() },
+        ]);
+
+        assert_eq!(repair(&db, &problems).unwrap(), 3);
+        let remaining = check(&db).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert!(!remaining[0].is_repairable());
+        assert_eq!(db.get_id_map("clickup_id_map").unwrap().len(), 1);
+        assert!(db.get_id_map("mstodo_id_map").unwrap().is_empty());
+    }
+}
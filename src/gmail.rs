@@ -0,0 +1,189 @@
+use anyhow::{Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+use crate::logging::LoggedCommand;
+use crate::{create_task_in_google, output, push_dirty_tasks, update_task_locally, App, Config, Task};
+
+// Gmail as a task source: after a sync, threads carrying `label` become tasks titled
+// with the subject, the thread's link in the notes. The label is then swapped for
+// `processed_label` (or just removed), and thread IDs are remembered in sync_state so
+// a label change that fails doesn't create the task twice.
+
+/// sync_state key: Gmail thread ID → the task made from it
+const THREAD_MAP: &str = "gmail_thread_id_map";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GmailSource {
+    pub enabled: bool,
+    /// Threads with this label become tasks
+    pub label: String,
+    /// Label added once a thread has its task; None just removes `label`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub processed_label: Option<String>,
+    /// Google Tasks list for the new tasks
+    pub list_id: String,
+    /// At most this many threads per sync
+    pub max: usize,
+}
+
+impl Default for GmailSource {
+    fn default() -> Self {
+        GmailSource {
+            enabled: false,
+            label: "to-task".into(),
+            processed_label: Some("to-task/done".into()),
+            list_id: "@default".into(),
+            max: 25,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Email {
+    pub thread_id: String,
+    pub subject: String,
+    pub from: Option<String>,
+}
+
+/// Threads from `gog gmail search --json`; accepts a `threads` or `messages` array
+pub fn parse_emails(json: &serde_json::Value) -> Vec<Email> {
+    let items = json["threads"].as_array().or_else(|| json["messages"].as_array());
+    items.into_iter().flatten()
+        .filter_map(|item| {
+            let thread_id = item["threadId"].as_str().or_else(|| item["id"].as_str())?;
+            let subject = item["subject"].as_str().map(str::trim).filter(|s| !s.is_empty()).unwrap_or("(no subject)");
+            Some(Email {
+                thread_id: thread_id.to_string(),
+                subject: subject.to_string(),
+                from: item["from"].as_str().map(|s| s.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// The subject without "Re:"/"Fwd:" prefixes
+pub fn task_title(subject: &str) -> String {
+    let mut title = subject.trim();
+    while let Some((prefix, rest)) = title.split_once(':') {
+        if !["re", "fw", "fwd", "aw"].contains(&prefix.trim().to_lowercase().as_str()) {
+            break;
+        }
+        title = rest.trim();
+    }
+    if title.is_empty() { subject.trim().to_string() } else { title.to_string() }
+}
+
+pub fn thread_url(account: &str, thread_id: &str) -> String {
+    format!("https://mail.google.com/mail/?authuser={}#all/{}", account, thread_id)
+}
+
+/// The "Email: <url>" line written into an imported task's notes. Google Tasks links
+/// are read-only through the API, so this is where the thread lives.
+pub fn email_link(notes: &str) -> Option<&str> {
+    notes.lines()
+        .find_map(|line| line.strip_prefix("Email: "))
+        .map(str::trim)
+        .filter(|url| url.contains("mail.google.com"))
+}
+
+fn search(config: &Config, source: &GmailSource) -> Result<Vec<Email>> {
+    let output = Command::new("gog")
+        .args([
+            "gmail", "search", &format!("label:{}", source.label),
+            "--max", &source.max.to_string(),
+            "--account", &config.google_account,
+            "--json",
+        ])
+        .logged_output()
+        .context("Could not search Gmail. Make sure 'gog' CLI is installed.")?;
+    if !output.status.success() {
+        anyhow::bail!("Gmail search failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(parse_emails(&serde_json::from_slice(&output.stdout).unwrap_or(serde_json::Value::Null)))
+}
+
+/// Swap the thread's label for the processed one
+fn mark_processed(config: &Config, source: &GmailSource, thread_id: &str) -> bool {
+    let mut cmd = Command::new("gog");
+    cmd.args(["gmail", "thread", "modify", thread_id, "--remove", &source.label, "--account", &config.google_account]);
+    if let Some(processed) = &source.processed_label {
+        cmd.args(["--add", processed]);
+    }
+    cmd.logged_output().is_ok_and(|o| o.status.success())
+}
+
+/// Create tasks for labeled threads that don't have one yet; returns how many
+pub fn import_labeled(app: &App) -> Result<usize> {
+    let config = &app.config;
+    let source = &config.gmail;
+    if !source.enabled || crate::sync::is_offline() {
+        return Ok(0);
+    }
+
+    let emails = match search(config, source) {
+        Ok(emails) => emails,
+        Err(e) => {
+            tracing::warn!(error = %e, "gmail: search failed");
+            return Ok(0);
+        }
+    };
+
+    let mut imported = app.db.get_id_map(THREAD_MAP)?;
+    let mut created = 0;
+    for email in emails {
+        if !imported.contains_key(&email.thread_id) {
+            let mut task = Task::parse_with_config(&task_title(&email.subject), "Inbox", Some(config));
+            let mut notes = vec![format!("Email: {}", thread_url(&config.google_account, &email.thread_id))];
+            notes.extend(email.from.as_ref().map(|from| format!("From: {}", from)));
+            task.notes = Some(notes.join("\n"));
+
+            let id = create_task_in_google(app, &source.list_id, &task.title, None)?;
+            task.id = Some(id.clone());
+            task.list_id = Some(source.list_id.clone());
+            update_task_locally(app, &task)?;
+            imported.insert(email.thread_id.clone(), id);
+            app.db.set_state(THREAD_MAP, &serde_json::to_string(&imported)?)?;
+            created += 1;
+        }
+        if !mark_processed(config, source, &email.thread_id) {
+            tracing::warn!(thread_id = %email.thread_id, "gmail: couldn't relabel thread");
+        }
+    }
+
+    if created > 0 {
+        push_dirty_tasks(app)?;
+        output::note(format!("📧 Added {} task(s) from Gmail", created).cyan());
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_emails() {
+        let json = serde_json::json!({"threads": [
+            {"id": "18c1", "subject": "Re: Fwd: Budget numbers", "from": "Sam <sam@example.com>"},
+            {"id": "18c2", "subject": "  "},
+            {"subject": "no id"},
+        ]});
+        let emails = parse_emails(&json);
+        assert_eq!(emails.len(), 2);
+        assert_eq!(emails[0].from.as_deref(), Some("Sam <sam@example.com>"));
+        assert_eq!(emails[1].subject, "(no subject)");
+        assert_eq!(task_title(&emails[0].subject), "Budget numbers");
+        assert_eq!(task_title("Meeting: Tuesday"), "Meeting: Tuesday");
+        assert_eq!(task_title("Re:"), "Re:");
+
+        let messages = serde_json::json!({"messages": [{"id": "m1", "threadId": "t1", "subject": "Hi"}]});
+        assert_eq!(parse_emails(&messages)[0].thread_id, "t1");
+
+        let notes = format!("Email: {}\nFrom: Sam", thread_url("me@example.com", "18c1"));
+        assert_eq!(email_link(&notes), Some("https://mail.google.com/mail/?authuser=me@example.com#all/18c1"));
+        assert_eq!(email_link("Email: https://example.com"), None);
+    }
+}
@@ -0,0 +1,169 @@
+use anyhow::Result;
+use chrono::{Datelike, Local, NaiveDate};
+use colored::*;
+use std::collections::HashMap;
+
+use crate::sync::{Goal, TaskChange};
+use crate::{find_tasks_by_id_prefix, get_tasks_from_cache, App, GoalAction, Task};
+
+// Quarterly goals live only in the cache; a task rolls up to at most one. A goal's
+// activity is the change log of its linked tasks (created, retitled, rescheduled,
+// completed), so a goal with no recent changes is one nothing is moving.
+
+/// Goals without a linked-task change in this many days are flagged as stale
+pub const STALE_DAYS: i64 = 14;
+
+/// "2026-Q2" for a date in April-June 2026
+pub fn quarter_of(date: NaiveDate) -> String {
+    format!("{}-Q{}", date.year(), (date.month() - 1) / 3 + 1)
+}
+
+/// Linked tasks and latest change for one goal
+pub struct GoalActivity {
+    pub goal: Goal,
+    pub open: usize,
+    pub done: usize,
+    /// RFC3339 time of the latest change to a linked task
+    pub last_change: Option<String>,
+}
+
+pub fn activity(goals: Vec<Goal>, links: &HashMap<String, i64>, tasks: &[Task], history: &[TaskChange]) -> Vec<GoalActivity> {
+    goals.into_iter()
+        .map(|goal| {
+            let linked = |id: &str| links.get(id) == Some(&goal.id);
+            let (done, open): (Vec<&Task>, Vec<&Task>) = tasks.iter()
+                .filter(|t| t.id.as_deref().is_some_and(linked))
+                .partition(|t| t.is_done());
+            let last_change = history.iter()
+                .filter(|c| linked(&c.task_id))
+                .map(|c| c.at.clone())
+                .max();
+            GoalActivity { goal, open: open.len(), done: done.len(), last_change }
+        })
+        .collect()
+}
+
+/// Goals with no linked-task change since `since` (RFC3339 time or date)
+pub fn stale(app: &App, tasks: &[Task], since: &str) -> Result<Vec<GoalActivity>> {
+    let history = app.db.get_history_since("")?;
+    Ok(activity(app.db.get_goals()?, &app.db.get_goal_links()?, tasks, &history).into_iter()
+        .filter(|a| a.last_change.as_deref().is_none_or(|at| at < since))
+        .collect())
+}
+
+/// Goal title per linked task ID, for grouping
+pub fn titles_by_task(app: &App) -> Result<HashMap<String, String>> {
+    let titles: HashMap<i64, String> = app.db.get_goals()?.into_iter().map(|g| (g.id, g.title)).collect();
+    Ok(app.db.get_goal_links()?.into_iter()
+        .filter_map(|(task_id, goal_id)| Some((task_id, titles.get(&goal_id)?.clone())))
+        .collect())
+}
+
+/// When a goal was last moved, relative to today
+fn describe_last_change(last_change: Option<&str>) -> String {
+    let day = last_change
+        .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+        .map(|at| at.with_timezone(&Local).date_naive());
+    match day.map(|d| (Local::now().date_naive() - d).num_days()) {
+        None => "no task activity".to_string(),
+        Some(0) => "last activity today".to_string(),
+        Some(1) => "last activity yesterday".to_string(),
+        Some(n) => format!("last activity {} days ago", n),
+    }
+}
+
+pub fn cmd_goal(app: &App, action: GoalAction) -> Result<()> {
+    match action {
+        GoalAction::Add { title, quarter } => {
+            let title = title.join(" ");
+            if title.trim().is_empty() {
+                anyhow::bail!("Give the goal a title");
+            }
+            let quarter = quarter.unwrap_or_else(|| quarter_of(Local::now().date_naive()));
+            let id = app.db.add_goal(title.trim(), &quarter)?;
+            println!("{}", format!("🎯 Added goal #{} for {}: {}", id, quarter, title.trim()).green());
+        }
+        GoalAction::Link { task, goal } => {
+            let tasks = get_tasks_from_cache(app)?;
+            let matching = find_tasks_by_id_prefix(&tasks, &task);
+            let task = match matching.len() {
+                0 => anyhow::bail!("No task found with ID starting with '{}'", task),
+                1 => matching[0],
+                n => anyhow::bail!("{} tasks match '{}'. Please be more specific", n, task),
+            };
+            let Some(goal) = app.db.get_goals()?.into_iter().find(|g| g.id == goal) else {
+                anyhow::bail!("No goal #{} (see `goal list`)", goal);
+            };
+            app.db.link_goal(task.id.as_deref().unwrap_or_default(), goal.id)?;
+            println!("{}", format!("🔗 {} → #{} {}", task.title, goal.id, goal.title).green());
+        }
+        GoalAction::List { quarter } => {
+            let tasks = get_tasks_from_cache(app)?;
+            let history = app.db.get_history_since("")?;
+            let since = (chrono::Utc::now() - chrono::Duration::days(STALE_DAYS)).to_rfc3339();
+            let goals: Vec<Goal> = app.db.get_goals()?.into_iter()
+                .filter(|g| quarter.as_deref().is_none_or(|q| g.quarter.eq_ignore_ascii_case(q)))
+                .collect();
+            if goals.is_empty() {
+                println!("{}", "No goals yet. Add one with `goal add <title>`.".dimmed());
+                return Ok(());
+            }
+
+            println!("{}", "🎯 Goals\n".cyan().bold());
+            for a in activity(goals, &app.db.get_goal_links()?, &tasks, &history) {
+                let stale = a.last_change.as_deref().is_none_or(|at| at < since.as_str());
+                let moved = describe_last_change(a.last_change.as_deref());
+                println!("  {} {} {}  {}",
+                    format!("#{}", a.goal.id).dimmed(),
+                    a.goal.title.bold(),
+                    format!("({})", a.goal.quarter).dimmed(),
+                    format!("{} open · {} done · ", a.open, a.done).dimmed().to_string()
+                        + &if stale { format!("⚠ {}", moved).yellow().to_string() } else { moved.dimmed().to_string() });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quarter_of() {
+        assert_eq!(quarter_of(NaiveDate::from_ymd_opt(2026, 3, 31).unwrap()), "2026-Q1");
+        assert_eq!(quarter_of(NaiveDate::from_ymd_opt(2026, 4, 1).unwrap()), "2026-Q2");
+        assert_eq!(quarter_of(NaiveDate::from_ymd_opt(2026, 12, 1).unwrap()), "2026-Q4");
+    }
+
+    #[test]
+    fn test_goal_activity() {
+        let db = crate::sync::SyncManager::open(std::path::Path::new(":memory:")).unwrap();
+        let launch = db.add_goal("Launch v2", "2026-Q2").unwrap();
+        let fitness = db.add_goal("Run a half marathon", "2026-Q2").unwrap();
+        db.link_goal("a", fitness).unwrap();
+        db.link_goal("a", launch).unwrap();
+        db.link_goal("b", launch).unwrap();
+
+        let task = |id: &str, title: &str, completed: bool| {
+            let mut task = Task::parse_with_config(title, "Tasks", None);
+            task.id = Some(id.to_string());
+            task.completed = completed;
+            task
+        };
+        let tasks = vec![
+            task("a", "[2026-04-02][P1][WORK] Write launch post", false),
+            task("b", "[2026-04-01][P1][WORK] Fix signup bug", true),
+        ];
+        let history = vec![TaskChange {
+            task_id: "b".into(), field: "status".into(), old_value: Some("needsAction".into()),
+            new_value: Some("completed".into()), source: "cli".into(), at: "2026-04-01T10:00:00+00:00".into(),
+        }];
+
+        let goals = activity(db.get_goals().unwrap(), &db.get_goal_links().unwrap(), &tasks, &history);
+        assert_eq!((goals[0].open, goals[0].done), (1, 1));
+        assert_eq!(goals[0].last_change.as_deref(), Some("2026-04-01T10:00:00+00:00"));
+        // Relinking moved "a" off the fitness goal
+        assert_eq!((goals[1].open, goals[1].done, goals[1].last_change.is_none()), (0, 0, true));
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use colored::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::similarity::normalize_title;
+use crate::{get_tasks_from_cache, local_date, App, Task};
+
+// A habit is a recurring task tagged #habit. Google Tasks makes a new task for each
+// occurrence, so occurrences are grouped by their normalized title, and a day counts
+// as done when an occurrence was completed that day (from the change log, or its
+// due date when the log doesn't have it).
+
+pub const HABIT_TAG: &str = "habit";
+
+/// Days shown in the heat strip
+const STRIP_DAYS: usize = 28;
+
+pub struct Habit {
+    pub name: String,
+    pub done_days: BTreeSet<NaiveDate>,
+    /// Today's occurrence, if it's still open
+    pub open_today: Option<Task>,
+}
+
+impl Habit {
+    /// Consecutive done days ending today, or yesterday while today is still open
+    pub fn streak(&self, today: NaiveDate) -> usize {
+        let mut day = if self.done_days.contains(&today) { today } else { today - chrono::Duration::days(1) };
+        let mut streak = 0;
+        while self.done_days.contains(&day) {
+            streak += 1;
+            day -= chrono::Duration::days(1);
+        }
+        streak
+    }
+
+    pub fn best_streak(&self) -> usize {
+        let mut best = 0;
+        let mut run = 0;
+        let mut previous: Option<NaiveDate> = None;
+        for day in &self.done_days {
+            run = if previous.is_some_and(|p| *day - p == chrono::Duration::days(1)) { run + 1 } else { 1 };
+            best = best.max(run);
+            previous = Some(*day);
+        }
+        best
+    }
+
+    /// One cell per day for the last STRIP_DAYS days, oldest first: ■ done, □ missed
+    pub fn heat_strip(&self, today: NaiveDate) -> String {
+        (0..STRIP_DAYS).rev()
+            .map(|ago| {
+                let day = today - chrono::Duration::days(ago as i64);
+                if self.done_days.contains(&day) {
+                    "■".green().to_string()
+                } else {
+                    "□".dimmed().to_string()
+                }
+            })
+            .collect()
+    }
+}
+
+pub fn is_habit(task: &Task) -> bool {
+    task.tags.iter().any(|t| t.eq_ignore_ascii_case(HABIT_TAG))
+}
+
+/// Group #habit tasks into habits. `completed_on` maps task IDs to the day they were completed.
+pub fn collect(tasks: &[Task], completed_on: &HashMap<String, NaiveDate>, today: NaiveDate) -> Vec<Habit> {
+    let today_str = today.format("%Y-%m-%d").to_string();
+    let mut habits: BTreeMap<String, Habit> = BTreeMap::new();
+    for task in tasks.iter().filter(|t| is_habit(t)) {
+        let habit = habits.entry(normalize_title(&task.title)).or_insert_with(|| Habit {
+            name: task.title.clone(),
+            done_days: BTreeSet::new(),
+            open_today: None,
+        });
+        if task.is_done() {
+            let day = task.id.as_ref()
+                .and_then(|id| completed_on.get(id).copied())
+                .or_else(|| NaiveDate::parse_from_str(&task.date, "%Y-%m-%d").ok());
+            habit.done_days.extend(day);
+        } else if task.date == today_str {
+            habit.open_today = Some(task.clone());
+        }
+    }
+    habits.into_values().collect()
+}
+
+/// Habits from the cache, with completion days from the change log
+pub fn load(app: &App, tasks: &[Task]) -> Result<Vec<Habit>> {
+    let mut completed_on = HashMap::new();
+    for change in app.db.get_history_since("")?.into_iter()
+        .filter(|c| c.field == "status" && c.new_value.as_deref() == Some("completed"))
+    {
+        if let Some(day) = local_date(&change.at) {
+            completed_on.insert(change.task_id, day);
+        }
+    }
+    Ok(collect(tasks, &completed_on, Local::now().date_naive()))
+}
+
+/// The habit a task is an occurrence of
+pub fn habit_for<'a>(habits: &'a [Habit], task: &Task) -> Option<&'a Habit> {
+    let key = normalize_title(&task.title);
+    habits.iter().find(|h| normalize_title(&h.name) == key)
+}
+
+/// `stats habits`: streaks and the last four weeks for every habit
+pub fn print_stats(app: &App) -> Result<()> {
+    let tasks = get_tasks_from_cache(app)?;
+    let habits = load(app, &tasks)?;
+    if habits.is_empty() {
+        println!("{}", format!("No habits yet. Tag a recurring task #{} to track its streak.", HABIT_TAG).dimmed());
+        return Ok(());
+    }
+
+    let today = Local::now().date_naive();
+    println!("{}", format!("🔗 Habits (last {} days)\n", STRIP_DAYS).cyan().bold());
+    for habit in &habits {
+        let streak = habit.streak(today);
+        let streak_str = format!("{:>3}d streak", streak);
+        println!("  {} {}  {}  {}",
+            habit.heat_strip(today),
+            if streak > 0 { streak_str.green() } else { streak_str.dimmed() },
+            format!("best {}d", habit.best_streak()).dimmed(),
+            habit.name);
+    }
+    Ok(())
+}
+
+/// "Don't break the chain" lines for habits still open today
+pub fn nudges(app: &App, tasks: &[Task]) -> Result<Vec<String>> {
+    if !tasks.iter().any(|t| is_habit(t) && !t.is_done()) {
+        return Ok(Vec::new());
+    }
+    let today = Local::now().date_naive();
+    Ok(load(app, tasks)?.iter()
+        .filter(|h| h.open_today.is_some())
+        .map(|h| match h.streak(today) {
+            0 => format!("🔗 Start a chain: {}", h.name),
+            n => format!("🔗 Don't break the chain: {} ({}-day streak)", h.name, n),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_habit_streaks() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let occurrence = |id: &str, date: &str, completed: bool| {
+            let mut task = Task::parse_with_config(&format!("[{}][P2][LIFE]{{15m}} Meditate #habit", date), "Tasks", None);
+            task.id = Some(id.to_string());
+            task.completed = completed;
+            task
+        };
+        let tasks = vec![
+            occurrence("a", "2026-03-04", true),
+            occurrence("b", "2026-03-05", true),
+            occurrence("c", "2026-03-06", true),
+            // Done late: the log says it was finished on the 8th
+            occurrence("d", "2026-03-07", true),
+            occurrence("e", "2026-03-09", true),
+            occurrence("f", "2026-03-10", false),
+            Task::parse_with_config("[2026-03-10][P2][LIFE] Meditate", "Tasks", None),
+        ];
+        let completed_on = HashMap::from([("d".to_string(), NaiveDate::from_ymd_opt(2026, 3, 8).unwrap())]);
+        let habits = collect(&tasks, &completed_on, today);
+
+        assert_eq!(habits.len(), 1);
+        let habit = &habits[0];
+        assert_eq!(habit.open_today.as_ref().and_then(|t| t.id.as_deref()), Some("f"));
+        // Today is still open, so the streak runs through yesterday: 8th and 9th
+        assert_eq!(habit.streak(today), 2);
+        assert_eq!(habit.best_streak(), 3);
+        assert!(habit_for(&habits, &tasks[0]).is_some());
+        assert!(habit_for(&habits, &Task::parse_with_config("Stretch #habit", "Tasks", None)).is_none());
+    }
+}
@@ -0,0 +1,315 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::parse_date_from_text;
+
+// Readers for other apps' export files. Each turns its format into ImportedTask;
+// `import --source` then formats, de-duplicates and creates them like `add` does.
+//   todoist  the per-project CSV from Todoist's "Export as a template"
+//   clickup  the CSV from ClickUp's "Export view"
+//   keep     Google Takeout's Keep folder (or a single note's .json)
+
+pub const SOURCES: [&str; 3] = ["todoist", "clickup", "keep"];
+
+/// Todoist @labels, which become #tags
+static LABEL_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:^|\s)@(\w+)").unwrap());
+
+/// A task read from another app's export
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportedTask {
+    pub title: String,
+    pub priority: Option<String>,
+    /// The source's project/list name; only used if it matches a configured project
+    pub project: Option<String>,
+    pub due: Option<String>,
+    pub time: Option<String>,
+    pub tags: Vec<String>,
+    pub notes: Option<String>,
+}
+
+/// Read `path` as an export from `source`
+pub fn read(source: &str, path: &Path) -> Result<Vec<ImportedTask>> {
+    match source {
+        "todoist" => {
+            let project = path.file_stem().map(|s| s.to_string_lossy().into_owned());
+            Ok(todoist(&read_text(path)?, project))
+        }
+        "clickup" => Ok(clickup(&read_text(path)?)),
+        "keep" => keep(path),
+        _ => anyhow::bail!("Unknown source '{}' (expected one of {})", source, SOURCES.join(", ")),
+    }
+}
+
+fn read_text(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+fn todoist(text: &str, project: Option<String>) -> Vec<ImportedTask> {
+    records(text).into_iter()
+        .filter(|r| field(r, "type").is_some_and(|t| t.eq_ignore_ascii_case("task")))
+        .filter_map(|r| {
+            let content = field(&r, "content")?;
+            let tags = LABEL_REGEX.captures_iter(content).map(|c| c[1].to_string()).collect();
+            let title = LABEL_REGEX.replace_all(content, "").trim().to_string();
+            // Todoist's 1 is its most urgent; 4 is "no priority"
+            let priority = match field(&r, "priority") {
+                Some("1") => Some("P0"),
+                Some("2") => Some("P1"),
+                Some("3") => Some("P2"),
+                _ => None,
+            };
+            let minutes = field(&r, "duration").and_then(|d| d.parse::<i64>().ok()).map(|d| {
+                if field(&r, "duration_unit") == Some("day") { d * 8 * 60 } else { d }
+            });
+            Some(ImportedTask {
+                title,
+                priority: priority.map(String::from),
+                project: project.clone(),
+                due: field(&r, "date").and_then(parse_due),
+                time: minutes.and_then(estimate),
+                tags,
+                notes: field(&r, "description").map(String::from),
+            })
+        })
+        .collect()
+}
+
+fn clickup(text: &str) -> Vec<ImportedTask> {
+    records(text).into_iter()
+        .filter(|r| !field(r, "status").is_some_and(|s| ["closed", "complete", "done"].contains(&s.to_lowercase().as_str())))
+        .filter_map(|r| {
+            let title = field(&r, "task name")?.to_string();
+            let priority = field(&r, "priority").and_then(|p| match p.to_lowercase().as_str() {
+                "1" | "urgent" => Some("P0"),
+                "2" | "high" => Some("P1"),
+                "3" | "normal" => Some("P2"),
+                "4" | "low" => Some("P3"),
+                _ => None,
+            });
+            // Dates and estimates come as milliseconds, with a "… Text" twin for humans
+            let due = field(&r, "due date").and_then(parse_due)
+                .or_else(|| field(&r, "due date text").and_then(parse_due));
+            let minutes = field(&r, "time estimated").and_then(|t| t.parse::<i64>().ok()).map(|ms| ms / 60_000);
+            let tags = field(&r, "tags")
+                .map(|t| t.trim_matches(['[', ']']).split(',').map(|t| t.trim().replace(' ', "-")).filter(|t| !t.is_empty()).collect())
+                .unwrap_or_default();
+            Some(ImportedTask {
+                title,
+                priority: priority.map(String::from),
+                project: field(&r, "list name").map(String::from),
+                due,
+                time: minutes.and_then(estimate),
+                tags,
+                notes: field(&r, "task content").map(String::from),
+            })
+        })
+        .collect()
+}
+
+fn keep(path: &Path) -> Result<Vec<ImportedTask>> {
+    let mut files = Vec::new();
+    if path.is_dir() {
+        for entry in std::fs::read_dir(path)? {
+            let entry = entry?.path();
+            if entry.extension().is_some_and(|e| e == "json") {
+                files.push(entry);
+            }
+        }
+        files.sort();
+    } else {
+        files.push(path.to_path_buf());
+    }
+
+    let mut tasks = Vec::new();
+    for file in files {
+        let note: serde_json::Value = serde_json::from_str(&read_text(&file)?)
+            .with_context(|| format!("{} is not a Keep note", file.display()))?;
+        tasks.extend(keep_note(&note));
+    }
+    Ok(tasks)
+}
+
+/// A checklist becomes one task per unchecked item; any other note becomes one task
+fn keep_note(note: &serde_json::Value) -> Vec<ImportedTask> {
+    if note["isTrashed"].as_bool() == Some(true) || note["isArchived"].as_bool() == Some(true) {
+        return Vec::new();
+    }
+    let title = note["title"].as_str().map(str::trim).filter(|t| !t.is_empty());
+    let tags: Vec<String> = note["labels"].as_array()
+        .map(|labels| labels.iter().filter_map(|l| l["name"].as_str()).map(|n| n.replace(' ', "-")).collect())
+        .unwrap_or_default();
+
+    if let Some(items) = note["listContent"].as_array() {
+        return items.iter()
+            .filter(|item| item["isChecked"].as_bool() != Some(true))
+            .filter_map(|item| item["text"].as_str().map(str::trim).filter(|t| !t.is_empty()))
+            .map(|text| ImportedTask {
+                title: text.to_string(),
+                tags: tags.clone(),
+                notes: title.map(|t| format!("From Keep list \"{}\"", t)),
+                ..Default::default()
+            })
+            .collect();
+    }
+
+    let text = note["textContent"].as_str().unwrap_or("").trim();
+    let (title, notes) = match title {
+        Some(title) => (title.to_string(), Some(text.to_string()).filter(|t| !t.is_empty())),
+        None => {
+            let mut lines = text.splitn(2, '\n');
+            let first = lines.next().unwrap_or("").trim().to_string();
+            (first, lines.next().map(|rest| rest.trim().to_string()).filter(|r| !r.is_empty()))
+        }
+    };
+    if title.is_empty() {
+        return Vec::new();
+    }
+    vec![ImportedTask { title, tags, notes, ..Default::default() }]
+}
+
+/// A due date as YYYY-MM-DD: ISO dates, epoch milliseconds, or phrases like "tomorrow"
+fn parse_due(value: &str) -> Option<String> {
+    let value = value.trim();
+    if let Some(date) = value.get(..10).and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()) {
+        return Some(date.format("%Y-%m-%d").to_string());
+    }
+    if let Ok(ms) = value.parse::<i64>() {
+        return DateTime::from_timestamp_millis(ms).map(|dt| dt.format("%Y-%m-%d").to_string());
+    }
+    parse_date_from_text(value)
+}
+
+/// Minutes as a title estimate ("30m", "2h", "1h30m")
+pub fn estimate(minutes: i64) -> Option<String> {
+    match minutes {
+        m if m <= 0 => None,
+        m if m < 60 => Some(format!("{}m", m)),
+        m if m % 60 == 0 => Some(format!("{}h", m / 60)),
+        m => Some(format!("{}h{}m", m / 60, m % 60)),
+    }
+}
+
+/// A non-empty field by (lowercase) column name
+fn field<'a>(record: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    record.get(name).map(|v| v.trim()).filter(|v| !v.is_empty())
+}
+
+/// CSV rows keyed by lowercased header
+fn records(text: &str) -> Vec<HashMap<String, String>> {
+    let mut rows = parse_csv(text).into_iter();
+    let Some(header) = rows.next() else {
+        return Vec::new();
+    };
+    let header: Vec<String> = header.iter().map(|h| h.trim().to_lowercase()).collect();
+    rows.map(|row| header.iter().cloned().zip(row).collect()).collect()
+}
+
+/// Rows of an RFC 4180 CSV file (quoted fields may hold commas, quotes and newlines)
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.is_empty() => in_quotes = true,
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|r| r.iter().any(|f| !f.is_empty()));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_handles_quotes_and_newlines() {
+        let rows = parse_csv("a,b,c\r\n\"x, y\",\"say \"\"hi\"\"\",\"two\nlines\"\n\n");
+        assert_eq!(rows, vec![
+            vec!["a", "b", "c"],
+            vec!["x, y", "say \"hi\"", "two\nlines"],
+        ]);
+    }
+
+    #[test]
+    fn test_todoist_tasks() {
+        let csv = "TYPE,CONTENT,DESCRIPTION,PRIORITY,INDENT,AUTHOR,RESPONSIBLE,DATE,DATE_LANG,TIMEZONE,DURATION,DURATION_UNIT\n\
+                   section,Errands,,,,,,,,,,\n\
+                   task,Buy milk @home,,1,1,,,2025-08-04,en,,30,minute\n\
+                   task,Someday idea,Long note,4,1,,,,en,,,\n";
+        let tasks = todoist(csv, Some("Life".into()));
+        assert_eq!(tasks, vec![
+            ImportedTask {
+                title: "Buy milk".into(),
+                priority: Some("P0".into()),
+                project: Some("Life".into()),
+                due: Some("2025-08-04".into()),
+                time: Some("30m".into()),
+                tags: vec!["home".into()],
+                notes: None,
+            },
+            ImportedTask {
+                title: "Someday idea".into(),
+                project: Some("Life".into()),
+                notes: Some("Long note".into()),
+                ..Default::default()
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_clickup_skips_closed_and_converts_millis() {
+        let csv = "Task ID,Task Name,Task Content,Status,Due Date,Priority,Tags,List Name,Time Estimated\n\
+                   1,Ship report,,to do,1754265600000,high,\"[q3, client work]\",Work,5400000\n\
+                   2,Old thing,,closed,,,,Work,\n";
+        let tasks = clickup(csv);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].priority.as_deref(), Some("P1"));
+        assert_eq!(tasks[0].due.as_deref(), Some("2025-08-04"));
+        assert_eq!(tasks[0].time.as_deref(), Some("1h30m"));
+        assert_eq!(tasks[0].tags, vec!["q3", "client-work"]);
+    }
+
+    #[test]
+    fn test_keep_checklist_and_note() {
+        let list = serde_json::json!({
+            "title": "Groceries",
+            "listContent": [{"text": "Eggs", "isChecked": false}, {"text": "Bread", "isChecked": true}],
+            "labels": [{"name": "shopping"}],
+        });
+        let tasks = keep_note(&list);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Eggs");
+        assert_eq!(tasks[0].tags, vec!["shopping"]);
+
+        let note = serde_json::json!({"title": "", "textContent": "Call plumber\nabout the leak"});
+        let tasks = keep_note(&note);
+        assert_eq!(tasks[0].title, "Call plumber");
+        assert_eq!(tasks[0].notes.as_deref(), Some("about the leak"));
+
+        assert!(keep_note(&serde_json::json!({"title": "x", "isTrashed": true})).is_empty());
+    }
+}
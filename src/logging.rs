@@ -0,0 +1,195 @@
+use std::future::Future;
+use std::io::{self, IsTerminal};
+use std::process::{Command, Output};
+use std::time::Instant;
+use tracing::level_filters::LevelFilter;
+use tracing::{debug, info, warn};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt;
+use tracing_subscriber::prelude::*;
+
+// Everything at debug and above goes to a daily log in <data dir>/logs, so a push
+// that failed overnight leaves a trail. The console only gets events with -v/-vv,
+// on stderr, so command output and the MCP stdio protocol stay clean.
+
+/// Daily log files kept before the oldest is deleted
+const LOG_FILES_KEPT: usize = 14;
+
+/// Command-line flags whose value is task content, which stays out of the log (the cache
+/// may be encrypted, and a plaintext log would undo that)
+const CONTENT_FLAGS: [&str; 2] = ["--title", "--notes"];
+
+/// Start logging. Without a writable data dir we still log to the console.
+pub fn init(verbosity: u8) {
+    let console_level = match verbosity {
+        0 => LevelFilter::OFF,
+        1 => LevelFilter::INFO,
+        _ => LevelFilter::DEBUG,
+    };
+    let console = fmt::layer()
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal() && !crate::output::is_quiet())
+        .with_target(false)
+        .without_time()
+        .with_filter(console_level);
+
+    let file = crate::paths::data_dir().ok()
+        .map(|dir| dir.join("logs"))
+        .filter(|logs| std::fs::create_dir_all(logs).is_ok())
+        .and_then(|logs| {
+            RollingFileAppender::builder()
+                .rotation(Rotation::DAILY)
+                .filename_prefix("thegarden")
+                .filename_suffix("log")
+                .max_log_files(LOG_FILES_KEPT)
+                .build(logs)
+                .ok()
+        })
+        .map(|appender| fmt::layer().with_writer(appender).with_ansi(false).with_filter(LevelFilter::DEBUG));
+
+    let _ = tracing_subscriber::registry().with(console).with(file).try_init();
+}
+
+/// `Command::output` that logs the invocation, how it exited and how long it took
+pub trait LoggedCommand {
+    fn logged_output(&mut self) -> io::Result<Output>;
+}
+
+impl LoggedCommand for Command {
+    fn logged_output(&mut self) -> io::Result<Output> {
+        let program = self.get_program().to_string_lossy().into_owned();
+        let args: Vec<String> = self.get_args().map(|a| a.to_string_lossy().into_owned()).collect();
+        let start = Instant::now();
+        let result = self.output();
+        log_command(program, args, start, &result);
+        result
+    }
+}
+
+/// `logged_output` for tokio's Command, for running several at once
+pub trait LoggedAsyncCommand {
+    fn logged_output(&mut self) -> impl Future<Output = io::Result<Output>>;
+}
+
+impl LoggedAsyncCommand for tokio::process::Command {
+    async fn logged_output(&mut self) -> io::Result<Output> {
+        let program = self.as_std().get_program().to_string_lossy().into_owned();
+        let args: Vec<String> = self.as_std().get_args().map(|a| a.to_string_lossy().into_owned()).collect();
+        let start = Instant::now();
+        let result = self.output().await;
+        log_command(program, args, start, &result);
+        result
+    }
+}
+
+/// `args` with the values of CONTENT_FLAGS replaced by "…"
+fn redact_args(args: Vec<String>) -> Vec<String> {
+    let mut redact_next = false;
+    args.into_iter()
+        .map(|arg| {
+            let redact = std::mem::replace(&mut redact_next, CONTENT_FLAGS.contains(&arg.as_str()));
+            match arg.split_once('=') {
+                _ if redact => "…".to_string(),
+                Some((flag, _)) if CONTENT_FLAGS.contains(&flag) => format!("{}=…", flag),
+                _ => arg,
+            }
+        })
+        .collect()
+}
+
+fn log_command(program: String, args: Vec<String>, start: Instant, result: &io::Result<Output>) {
+    let args = redact_args(args);
+    let elapsed_ms = start.elapsed().as_millis() as u64;
+    match result {
+        Ok(o) if o.status.success() => debug!(program, ?args, elapsed_ms, "command ok"),
+        Ok(o) => warn!(
+            program, ?args, elapsed_ms, status = %o.status,
+            stderr = %String::from_utf8_lossy(&o.stderr).trim(),
+            "command failed"
+        ),
+        Err(e) => warn!(program, ?args, error = %e, "command could not start"),
+    }
+}
+
+/// `RequestBuilder::send` that logs the method, URL (without query) and response status
+pub trait LoggedRequest {
+    fn send_logged(self) -> reqwest::Result<reqwest::blocking::Response>;
+    /// `send_logged` for URLs whose path is a secret (Discord, Zapier and n8n webhooks):
+    /// only the host is logged, and errors leave the URL out
+    fn send_redacted(self) -> reqwest::Result<reqwest::blocking::Response>;
+}
+
+impl LoggedRequest for reqwest::blocking::RequestBuilder {
+    fn send_logged(self) -> reqwest::Result<reqwest::blocking::Response> {
+        send_blocking(self, false)
+    }
+
+    fn send_redacted(self) -> reqwest::Result<reqwest::blocking::Response> {
+        send_blocking(self, true)
+    }
+}
+
+fn send_blocking(builder: reqwest::blocking::RequestBuilder, redact: bool) -> reqwest::Result<reqwest::blocking::Response> {
+    let strip = |e: reqwest::Error| if redact { e.without_url() } else { e };
+    let (client, request) = builder.build_split();
+    let request = request.map_err(strip)?;
+    let method = request.method().clone();
+    let url = if redact {
+        redact_url(request.url().as_str())
+    } else {
+        format!("{}{}", request.url().host_str().unwrap_or_default(), request.url().path())
+    };
+    let start = Instant::now();
+    let result = client.execute(request).map_err(strip);
+    log_http(&method, &url, start, result.as_ref().map(|r| r.status()));
+    result
+}
+
+/// A URL with its path and query hidden, for logs and messages
+pub fn redact_url(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(url) => format!("{}/…", url.host_str().unwrap_or_default()),
+        Err(_) => "(invalid URL)".to_string(),
+    }
+}
+
+/// `send_logged` for async requests
+pub trait LoggedAsyncRequest {
+    fn send_logged(self) -> impl Future<Output = reqwest::Result<reqwest::Response>>;
+}
+
+impl LoggedAsyncRequest for reqwest::RequestBuilder {
+    async fn send_logged(self) -> reqwest::Result<reqwest::Response> {
+        let (client, request) = self.build_split();
+        let request = request?;
+        let method = request.method().clone();
+        let url = format!("{}{}", request.url().host_str().unwrap_or_default(), request.url().path());
+        let start = Instant::now();
+        let result = client.execute(request).await;
+        log_http(&method, &url, start, result.as_ref().map(|r| r.status()));
+        result
+    }
+}
+
+fn log_http(method: &reqwest::Method, url: &str, start: Instant, status: Result<reqwest::StatusCode, &reqwest::Error>) {
+    let elapsed_ms = start.elapsed().as_millis() as u64;
+    match status {
+        Ok(status) if status.is_success() => info!(%method, url, status = status.as_u16(), elapsed_ms, "http"),
+        Ok(status) => warn!(%method, url, status = status.as_u16(), elapsed_ms, "http error"),
+        Err(e) => warn!(%method, url, error = %e, "http request failed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_content_stays_out_of_command_logs() {
+        let args = ["update", "l", "t", "--title", "Salary review", "--notes=Ask for 10%", "--status", "completed"];
+        assert_eq!(
+            redact_args(args.iter().map(|a| a.to_string()).collect()),
+            ["update", "l", "t", "--title", "…", "--notes=…", "--status", "completed"]
+        );
+    }
+}
@@ -1,41 +1,106 @@
 mod sync;
+mod completions;
 mod airtable;
 mod airtable_sync;
 mod clickup;
 mod clickup_sync;
+mod mcp;
+mod similarity;
+mod paths;
+mod output;
+mod logging;
+mod migrations;
+mod fsck;
+mod backup;
+mod export;
+mod importers;
+mod webhooks;
+mod serve;
+mod provider;
+mod report;
+mod burndown;
+mod habits;
+mod goals;
+mod reminders;
+mod template;
+mod delegation;
+mod agenda;
+mod reconcile;
+mod meeting_prep;
+mod gmail;
+mod obsidian;
+mod org;
+mod mstodo;
+mod asana;
+mod notify;
+mod mqtt;
+mod privacy;
+mod encryption;
+mod check;
 
 use anyhow::{Context, Result};
 use chrono::{Datelike, Local, Utc, TimeZone};
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::engine::ArgValueCandidates;
 use colored::*;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent},
-    terminal::{disable_raw_mode, enable_raw_mode},
+    cursor::MoveTo,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
 };
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::io::{stdout, Write};
+use std::io::{stdout, IsTerminal, Write};
 use std::path::PathBuf;
 use std::process::Command;
-use sync::{SyncManager, CachedTask};
+use sync::{SyncManager, CachedTask, CalendarBlock, TaskChange};
 use airtable::AirtableClient;
-use airtable_sync::AirtableSync;
+use airtable_sync::{AirtableSync, SelectOptions};
 use clickup::ClickUpClient;
 use clickup_sync::ClickUpSync;
+use logging::{LoggedCommand, LoggedRequest};
+use provider::TaskProvider;
 
 // Pre-compiled regex for parsing task titles
 // Format: [date][priority][project][status][@context]{time} title
 // Status and context are optional
 static TASK_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"\[([^\]]+)\]\[([^\]]+)\]\[([^\]]+)\](?:\[([^\]]+)\])?(?:\[(@[^\]]+)\])?(?:\{([^}]+)\})?\s*(.+)").unwrap()
+    Regex::new(r"\[([^\]]+)\]\[([^\]]+)\]\[([^\]]+)\](?:\[([^\]@][^\]]*)\])?(?:\[(@[^\]]+)\])?(?:\{([^}]+)\})?\s*(.+)").unwrap()
 });
 
+static HASHTAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"#(\w+)").unwrap());
+
+/// A person in a title: "+bob" (a word that starts with a letter, so "+1" and "C++" don't count)
+static PERSON_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:^|\s)\+([A-Za-z][\w-]*)").unwrap());
+
 #[derive(Parser)]
 #[command(name = "thegarden")]
 #[command(about = "Task management CLI for ADHD-friendly triage", long_about = None)]
 struct Cli {
+    /// Skip the automatic sync before this command and use the cache as-is
+    #[arg(long, global = true)]
+    no_sync: bool,
+    /// Don't touch Google at all: read from the cache and queue changes for the next sync
+    #[arg(long, global = true)]
+    offline: bool,
+    /// Config file to use instead of config.json in the data directory
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Directory for the cache and default config (overrides $THEGARDEN_HOME and the XDG directories)
+    #[arg(long, global = true, value_name = "DIR")]
+    data_dir: Option<PathBuf>,
+    /// No colors or sync chatter; focus/next report through the exit code
+    #[arg(short, long, global = true)]
+    quiet: bool,
+    /// Like --quiet, and list/focus/next print one tab-separated line per task
+    #[arg(long, global = true)]
+    porcelain: bool,
+    /// Log sync activity to stderr (-v), including every gog call and HTTP request (-vv)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
     #[command(subcommand)]
     command: Commands,
 }
@@ -62,11 +127,68 @@ enum Commands {
         /// Only do context pass
         #[arg(long)]
         context: bool,
+        /// Only do person pass (who the task is with, as +name)
+        #[arg(long)]
+        person: bool,
+        /// Fully triage each task (every pass back-to-back) before moving to the next
+        #[arg(long)]
+        single_pass: bool,
+        #[command(flatten)]
+        scope: TriageScope,
     },
     /// Show only critical tasks for today (P0 + overdue/due today P1)
-    Focus,
+    Focus {
+        /// Keep the screen updated with the top task and a countdown against its estimate
+        #[arg(short, long)]
+        watch: bool,
+        /// Also show tasks whose start date hasn't arrived yet
+        #[arg(long)]
+        include_future: bool,
+        /// Also show completed P0/P1 tasks (pick one to reopen it)
+        #[arg(long, conflicts_with = "watch")]
+        include_done: bool,
+        /// Ignore the current context (see `context set` and context_rules)
+        #[arg(long)]
+        all_contexts: bool,
+        /// Show due dates as YYYY-MM-DD instead of "in 3d"
+        #[arg(long, conflicts_with = "watch")]
+        absolute: bool,
+    },
     /// Show this week's plan (meetings + tasks)
-    Plan,
+    Plan {
+        /// Plan next week instead of this one
+        #[arg(long, conflicts_with_all = ["from", "to", "days"])]
+        next_week: bool,
+        /// First day to plan (YYYY-MM-DD); without --to, plans 7 days
+        #[arg(long, conflicts_with = "days")]
+        from: Option<chrono::NaiveDate>,
+        /// Last day to plan (YYYY-MM-DD); without --from, starts today
+        #[arg(long, conflicts_with = "days")]
+        to: Option<chrono::NaiveDate>,
+        /// Plan this many days starting today
+        #[arg(long)]
+        days: Option<i64>,
+    },
+    /// Pick the one task to do right now
+    Next {
+        /// Only tasks that fit in this many minutes
+        #[arg(short, long)]
+        minutes: Option<i64>,
+        /// Where you are (e.g. @home); tasks for other contexts are skipped
+        #[arg(short, long, add = ArgValueCandidates::new(completions::contexts))]
+        context: Option<String>,
+        /// Ignore the current context (see `context set` and context_rules)
+        #[arg(long, conflicts_with = "context")]
+        all_contexts: bool,
+    },
+    /// List open tasks whose estimate fits in the minutes you have
+    Fit {
+        /// Minutes available
+        minutes: i64,
+        /// Ignore the current context (see `context set` and context_rules)
+        #[arg(long)]
+        all_contexts: bool,
+    },
     /// Schedule tasks into calendar blocks
     Schedule {
         /// Schedule for the whole week instead of just today
@@ -75,7 +197,12 @@ enum Commands {
         /// Automatically create calendar events without prompting
         #[arg(short, long)]
         auto: bool,
+        /// Show the calendar events that would be created, moved or removed without touching the calendar
+        #[arg(long)]
+        dry_run: bool,
     },
+    /// Ask how passed calendar blocks went: done, partial or reschedule, logging the time spent
+    Reconcile,
     /// List today's tasks
     List {
         /// Show all tasks (not just today)
@@ -87,21 +214,8 @@ enum Commands {
         /// Reverse sort order
         #[arg(short, long)]
         reverse: bool,
-        /// Filter by status (e.g., progress, review, blocked)
-        #[arg(long)]
-        status: Option<String>,
-        /// Filter by context (e.g., @work, @home)
-        #[arg(short = 'c', long)]
-        context: Option<String>,
-        /// Filter by priority (e.g., P0, P1, or "P0,P1" for multiple)
-        #[arg(short = 'p', long)]
-        priority: Option<String>,
-        /// Filter by project
-        #[arg(short = 'j', long)]
-        project: Option<String>,
-        /// Filter by tag
-        #[arg(short = 't', long)]
-        tag: Option<String>,
+        #[command(flatten)]
+        filter: TaskFilter,
         /// Show tasks from last N days
         #[arg(short = 'd', long)]
         days: Option<i64>,
@@ -111,26 +225,80 @@ enum Commands {
         /// Group tasks by date
         #[arg(short = 'g', long)]
         grouped: bool,
+        /// Select tasks with arrows/j-k and space, then act on them with single keys
+        #[arg(short = 'i', long)]
+        interactive: bool,
+        /// Also show tasks whose start date hasn't arrived yet
+        #[arg(long)]
+        include_future: bool,
+        /// Also show completed tasks
+        #[arg(long)]
+        include_done: bool,
+        /// Ignore the current context (see `context set` and context_rules)
+        #[arg(long)]
+        all_contexts: bool,
+        /// Render each line with a named template from `formats` (built in: compact, detailed)
+        #[arg(long, conflicts_with = "interactive")]
+        template: Option<String>,
+        /// Show dates as YYYY-MM-DD instead of "in 3d"
+        #[arg(long)]
+        absolute: bool,
     },
     /// Add a new task
     Add {
         /// Task title
-        title: String,
+        #[arg(required_unless_present_any = ["stdin", "file", "url"])]
+        title: Option<String>,
         /// Priority (P0, P1, P2, P3)
         #[arg(short, long)]
         priority: Option<String>,
         /// Project (WORKDAY, LIFE, SILVERMINE)
-        #[arg(short = 'j', long)]
+        #[arg(short = 'j', long, add = ArgValueCandidates::new(completions::projects))]
         project: Option<String>,
+        /// Read one task per line from stdin
+        #[arg(long, conflicts_with_all = ["title", "file"])]
+        stdin: bool,
+        /// Read one task per line from a file
+        #[arg(short, long, conflicts_with = "title")]
+        file: Option<PathBuf>,
+        /// Hide the task until this day (YYYY-MM-DD, friday, in 2 weeks, ...)
+        #[arg(long, conflicts_with_all = ["stdin", "file"])]
+        start: Option<String>,
+        /// Save a link to read later: tagged #read, titled from the page unless a title is given
+        #[arg(long, conflicts_with_all = ["stdin", "file"])]
+        url: Option<String>,
     },
     /// Bump incomplete tasks to tomorrow
     Bump {
+        /// Only these tasks (full or partial IDs); default is all of today's
+        #[arg(add = ArgValueCandidates::new(completions::task_ids))]
+        ids: Vec<String>,
         /// Number of days to bump (default: 1)
         #[arg(short, long, default_value = "1")]
         days: i64,
-        /// Bump to next Monday
+        /// Bump to the start of next week (week_starts_on, Monday by default)
         #[arg(short, long)]
         week: bool,
+        /// Ask y/n for each candidate
+        #[arg(short, long)]
+        interactive: bool,
+        #[command(flatten)]
+        filter: TaskFilter,
+        /// List the tasks that would move without moving them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Capture a thought into the local inbox instantly (pushed on the next sync)
+    In {
+        /// What to capture
+        #[arg(required = true, trailing_var_arg = true)]
+        text: Vec<String>,
+    },
+    /// Split a big task into smaller ones that inherit its priority and project
+    Split {
+        /// Task ID (or partial ID)
+        #[arg(add = ArgValueCandidates::new(completions::task_ids))]
+        id: String,
     },
     /// Find and merge duplicate tasks
     Merge {
@@ -140,9 +308,16 @@ enum Commands {
         /// Reset dismissed pairs (show all potential duplicates again)
         #[arg(long)]
         reset: bool,
+        /// Similarity method
+        #[arg(short = 'm', long, value_enum, default_value_t = similarity::Method::Token)]
+        method: similarity::Method,
+        /// List the pairs that would be offered without merging or dismissing anything
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Sync with Google Tasks (auto-runs on every command)
     Sync {
+        /// Re-fetch every task, and re-push unchanged ones to Airtable/ClickUp
         #[arg(short, long)]
         force: bool,
         /// Push tasks to Airtable for team visibility
@@ -151,32 +326,129 @@ enum Commands {
         /// Push tasks to ClickUp for team visibility
         #[arg(short, long)]
         clickup: bool,
+        /// Push tasks to Microsoft To Do
+        #[arg(short, long)]
+        mstodo: bool,
+        /// Push tasks to Asana, completing ones finished there
+        #[arg(long)]
+        asana: bool,
+        /// Auto-merge exact duplicates among newly synced tasks (queue near-matches for `merge`)
+        #[arg(short, long)]
+        dedupe: bool,
+        /// Show what would be pushed to Google (and Airtable/ClickUp/To Do/Asana) without pulling or pushing
+        #[arg(long)]
+        dry_run: bool,
+        #[command(subcommand)]
+        action: Option<SyncAction>,
     },
     /// Show details for a specific task
     Show {
         /// Task ID (or partial ID)
+        #[arg(add = ArgValueCandidates::new(completions::task_ids))]
+        id: String,
+        /// Print the task and its related records as JSON
+        #[arg(long)]
+        json: bool,
+        /// Show dates as YYYY-MM-DD instead of "in 3d"
+        #[arg(long)]
+        absolute: bool,
+    },
+    /// Show what changed on a task (or on every task with --since), and where the change came from
+    History {
+        /// Task ID (or partial ID)
+        #[arg(required_unless_present = "since", add = ArgValueCandidates::new(completions::task_ids))]
+        id: Option<String>,
+        /// Only changes since: 2d, 1w, or a YYYY-MM-DD date
+        #[arg(long)]
+        since: Option<String>,
+    },
+    /// Open a task's email/doc/drive link in the browser
+    Open {
+        /// Task ID (or partial ID)
+        #[arg(add = ArgValueCandidates::new(completions::task_ids))]
+        id: String,
+    },
+    /// Get a desktop notification about a task at a set time (fired by `notify`)
+    Remind {
+        /// Task ID (or partial ID)
+        #[arg(add = ArgValueCandidates::new(completions::task_ids))]
+        id: String,
+        /// When: "tomorrow 9am", "friday 14:30", "2026-03-05 8pm", "in 20 minutes"
+        #[arg(required_unless_present = "clear")]
+        when: Vec<String>,
+        /// Drop the task's pending reminders instead
+        #[arg(long)]
+        clear: bool,
+    },
+    /// Write today's focus list and finished tasks into the Obsidian daily note
+    Obsidian {
+        /// Vault folder (default: obsidian.vault in config)
+        #[arg(long)]
+        vault: Option<PathBuf>,
+        /// First complete tasks whose boxes were ticked in the note
+        #[arg(long)]
+        read_back: bool,
+    },
+    /// Fire due reminders as desktop notifications
+    Notify {
+        /// Keep running and check every 30 seconds
+        #[arg(long)]
+        watch: bool,
+    },
+    /// Add or edit a task's notes (opens $EDITOR unless -m is given)
+    Note {
+        /// Task ID (or partial ID)
+        #[arg(add = ArgValueCandidates::new(completions::task_ids))]
         id: String,
+        /// Set the notes to this text instead of opening an editor ("" clears them)
+        #[arg(short, long)]
+        message: Option<String>,
     },
-    /// Search tasks by title
+    /// Search tasks by title and notes
     Search {
-        /// Search query (substring match)
+        /// Search query (case-insensitive substring, or a regex with --regex)
         query: String,
+        /// Fields to search: title, notes, tags, description (the cache's property summary), links, all
+        #[arg(long = "in", value_delimiter = ',', value_parser = SEARCH_FIELDS, default_value = "title,notes")]
+        fields: Vec<String>,
+        /// Treat the query as a regular expression
+        #[arg(long)]
+        regex: bool,
         /// Filter by project
-        #[arg(short = 'j', long)]
+        #[arg(short = 'j', long, add = ArgValueCandidates::new(completions::projects))]
         project: Option<String>,
         /// Filter by status
         #[arg(long)]
         status: Option<String>,
         /// Filter by context
-        #[arg(short = 'c', long)]
+        #[arg(short = 'c', long, add = ArgValueCandidates::new(completions::contexts))]
         context: Option<String>,
         /// Filter by priority
         #[arg(short = 'p', long)]
         priority: Option<String>,
     },
+    /// Open tasks for a person or project, plus anything blocked or in review, for a meeting
+    Agenda {
+        /// A person (bob or +bob) or a project
+        who: String,
+        /// Print a Markdown checklist to paste into meeting notes
+        #[arg(long)]
+        markdown: bool,
+    },
+    /// Run a saved view from config (`views`), or list them
+    View {
+        #[arg(add = ArgValueCandidates::new(completions::views))]
+        name: Option<String>,
+    },
+    /// Today's focus, load and overdue count, posted to the notifiers that want it (for cron)
+    Digest {
+        /// Print the digest without posting it
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Summarize tasks with flexible slicing and grouping
     Summary {
-        /// Group by: date, priority, project, status, context, created (default: date)
+        /// Group by: date, priority, project, status, context, person, created, goal (default: date)
         #[arg(short, long, default_value = "date")]
         group: String,
         /// Show tasks created in the last N days
@@ -195,14 +467,251 @@ enum Commands {
         #[arg(short, long)]
         detailed: bool,
     },
+    /// Apply one change to every task matching the filters
+    Batch {
+        #[command(flatten)]
+        filter: TaskFilter,
+        /// Set a field, e.g. status=blocked (priority, project, status, context, time, date)
+        #[arg(long = "set", value_name = "KEY=VALUE")]
+        set: Vec<String>,
+        /// Move matching tasks N days from today
+        #[arg(long)]
+        bump: Option<i64>,
+        /// Mark matching tasks done
+        #[arg(long)]
+        complete: bool,
+        /// Delete matching tasks
+        #[arg(long)]
+        delete: bool,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+        /// Show the changes without applying them
+        #[arg(long, conflicts_with = "yes")]
+        dry_run: bool,
+    },
+    /// Set the context you're in; list, focus and next then hide other contexts' tasks
+    Context {
+        #[command(subcommand)]
+        action: ContextAction,
+    },
+    /// Manage #tags across tasks
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+    /// Quarterly goals that tasks roll up to
+    Goal {
+        #[command(subcommand)]
+        action: GoalAction,
+    },
+    /// Reports from your task history
+    Stats {
+        #[command(subcommand)]
+        action: StatsAction,
+    },
+    /// Run an MCP (Model Context Protocol) server over stdio for LLM assistants
+    Mcp,
+    /// Stay running and apply changes as they happen elsewhere
+    Serve {
+        /// Register a ClickUp webhook and apply its task updates/deletions to the cache
+        #[arg(long)]
+        webhooks: bool,
+        /// Local port to listen on
+        #[arg(long, default_value_t = serve::DEFAULT_PORT)]
+        port: u16,
+        /// Public URL ClickUp should call (forwarded to --port); remembered after the first run
+        #[arg(long)]
+        url: Option<String>,
+    },
+    /// Print a shell completion script (completes task IDs, projects and contexts)
+    ///
+    /// e.g. `source <(thegarden completions bash)` in ~/.bashrc
+    Completions {
+        #[arg(value_parser = completions::SHELLS)]
+        shell: String,
+    },
+    /// Dump every task with all its fields, for spreadsheets, pandas or a later `import`
+    Export {
+        #[arg(long, value_parser = export::FORMATS, default_value = "json")]
+        format: String,
+        /// Write here instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        filter: TaskFilter,
+    },
+    /// Weekly review for a status update: done by project, time spent, carry-over, new and bumped tasks
+    Report {
+        /// Report on a whole week (this one unless --ago)
+        #[arg(long)]
+        week: bool,
+        /// Weeks back from this one (1 = last week)
+        #[arg(long, default_value_t = 0, requires = "week")]
+        ago: i64,
+        #[arg(long, value_parser = report::FORMATS, default_value = "markdown")]
+        format: String,
+        /// Write here instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Chart open tasks and estimated hours over time, from the snapshot taken at each sync
+    Burndown {
+        /// Only this project
+        #[arg(short = 'j', long, add = ArgValueCandidates::new(completions::projects))]
+        project: Option<String>,
+        /// How many weeks back to chart
+        #[arg(long, default_value_t = 8)]
+        weeks: i64,
+    },
+    /// Load a JSON export (updates tasks still in the cache, recreates missing open ones),
+    /// or with --source, tasks exported from Todoist, ClickUp or Google Keep
+    Import {
+        /// Export file (for Keep, the Takeout folder works too)
+        file: PathBuf,
+        #[arg(long, value_parser = ["json", "org"], default_value = "json", conflicts_with = "source")]
+        format: String,
+        /// App the file came from
+        #[arg(long, value_parser = importers::SOURCES)]
+        source: Option<String>,
+        /// Show what would be added (and what looks like a duplicate) without creating anything
+        #[arg(long, requires = "source")]
+        dry_run: bool,
+    },
+    /// Save the cache and config to a timestamped .tar.gz (API keys blanked unless asked)
+    Backup {
+        /// Archive file, or directory to put it in (default: backups/ in the data dir)
+        path: Option<PathBuf>,
+        /// Keep API keys and tokens in the archived config
+        #[arg(long)]
+        include_secrets: bool,
+    },
+    /// Replace the cache and config with a backup's (the current ones are backed up first)
+    Restore {
+        archive: PathBuf,
+    },
+    /// Check the local cache for inconsistencies
+    Fsck {
+        /// Fix what can be fixed without changing any task's content
+        #[arg(long)]
+        repair: bool,
+    },
+    /// Apply pending cache schema migrations (they also run automatically on startup)
+    Migrate {
+        /// List every migration and whether it has run, without applying any
+        #[arg(long)]
+        status: bool,
+    },
+    /// A view from config run by name (`thegarden waiting`)
+    #[command(external_subcommand)]
+    External(Vec<String>),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+impl Commands {
+    fn is_dry_run(&self) -> bool {
+        match self {
+            Commands::Sync { dry_run, .. } | Commands::Bump { dry_run, .. } | Commands::Merge { dry_run, .. }
+            | Commands::Schedule { dry_run, .. } | Commands::Batch { dry_run, .. }
+            | Commands::Import { dry_run, .. } | Commands::Digest { dry_run, .. } => *dry_run,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum SyncAction {
+    /// Check that Google, Airtable and ClickUp accept the configured credentials, without syncing
+    Check,
+}
+
+#[derive(Subcommand)]
+enum ContextAction {
+    /// Make this the current context (e.g. @home)
+    Set {
+        #[arg(add = ArgValueCandidates::new(completions::contexts))]
+        context: String,
+    },
+    /// Go back to showing every context
+    Clear,
+    /// Print the current context
+    Show,
+}
+
+#[derive(Subcommand)]
+enum GoalAction {
+    /// Add a goal (for this quarter unless --quarter)
+    Add {
+        title: Vec<String>,
+        /// e.g. 2026-Q3
+        #[arg(long)]
+        quarter: Option<String>,
+    },
+    /// Goals with their open/done tasks and last activity
+    List {
+        /// Only this quarter, e.g. 2026-Q3
+        #[arg(long)]
+        quarter: Option<String>,
+    },
+    /// Roll a task up to a goal (replaces any goal it had)
+    Link {
+        /// Task ID (or partial ID)
+        #[arg(add = ArgValueCandidates::new(completions::task_ids))]
+        task: String,
+        /// Goal number from `goal list`
+        goal: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum StatsAction {
+    /// Estimated vs tracked time on finished tasks, per project and per tag
+    Estimates,
+    /// Streaks and the last four weeks for recurring tasks tagged #habit
+    Habits,
+}
+
+#[derive(Subcommand)]
+enum TagAction {
+    /// Add a tag to a task
+    Add {
+        /// Task ID (or partial ID)
+        #[arg(add = ArgValueCandidates::new(completions::task_ids))]
+        id: String,
+        /// Tag (with or without #)
+        tag: String,
+    },
+    /// Remove a tag from a task
+    Rm {
+        /// Task ID (or partial ID)
+        #[arg(add = ArgValueCandidates::new(completions::task_ids))]
+        id: String,
+        /// Tag (with or without #)
+        tag: String,
+    },
+    /// List tags on open tasks with counts
+    List,
+    /// Rename a tag on every task that has it
+    Rename {
+        old: String,
+        new: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 struct Priority {
     name: String,
     key: char,
     alt_key: Option<char>,
     description: String,
+    /// Shown next to tasks in plan and schedule (by position when unset: 🔴 🟡 🟢 🔵, then ⚪)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    emoji: Option<String>,
+    /// Color name for focus headings and labels (by position when unset: red, yellow, green, blue)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+    /// Choosing this priority completes the task (e.g. MoSCoW's "WONT"); done priorities sort last
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    done: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -213,13 +722,25 @@ struct TimeOption {
     alt_key: Option<char>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 struct StatusOption {
     name: String,
     key: char,
     #[serde(default)]
     alt_key: Option<char>,
     description: String,
+    /// Statuses a task can move to from this one (any when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    next: Vec<String>,
+    /// Moving a task here completes it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    done: bool,
+    /// The status's name in ClickUp, if not the built-in mapping
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    clickup: Option<String>,
+    /// The status's name in Airtable, if not this name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    airtable: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -228,11 +749,78 @@ struct TaskTypeDefaults {
     time: String,
 }
 
+/// A project and the defaults its tasks pick up. Written in config either as a plain
+/// description string or as an object with these fields.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct Project {
+    #[serde(default)]
+    description: String,
+    /// Context triage fills in when a task in this project has none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    context: Option<String>,
+    /// Priority triage fills in when a task in this project has none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<String>,
+    /// When `schedule` may book this project's tasks: "HH:MM-HH:MM", "before HH:MM"
+    /// or "after HH:MM", inside working hours
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hours: Option<String>,
+    /// ClickUp list this project's tasks are pushed to, overriding list_mappings/list_id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    clickup_list: Option<String>,
+    /// Microsoft To Do list (by name) this project's tasks are pushed to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    todo_list: Option<String>,
+    /// External syncs that get this project's tasks: airtable, clickup, mstodo, asana
+    /// (all of them when unset, none when empty)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sync_to: Option<Vec<String>>,
+    /// Event color for this project's task blocks, a Google color name or ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+}
+
+impl From<&str> for Project {
+    fn from(description: &str) -> Self {
+        Project { description: description.to_string(), ..Default::default() }
+    }
+}
+
+/// Accept both `"WORK": "Work tasks"` and `"WORK": {"description": ..., "priority": ...}`
+fn deserialize_projects<'de, D>(deserializer: D) -> std::result::Result<std::collections::HashMap<String, Project>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Entry {
+        Description(String),
+        Detailed(Project),
+    }
+    let entries = std::collections::HashMap::<String, Entry>::deserialize(deserializer)?;
+    Ok(entries.into_iter().map(|(name, entry)| {
+        let project = match entry {
+            Entry::Description(description) => Project::from(description.as_str()),
+            Entry::Detailed(project) => project,
+        };
+        (name, project)
+    }).collect())
+}
+
+/// What every command works against: the loaded config and one connection to the cache
+struct App {
+    config: Config,
+    db: SyncManager,
+    /// Google Tasks, or a stand-in for it
+    google: Box<dyn TaskProvider>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct Config {
     format: String,
     date_format: String,
-    projects: std::collections::HashMap<String, String>,
+    #[serde(deserialize_with = "deserialize_projects")]
+    projects: std::collections::HashMap<String, Project>,
     google_account: String,
     #[serde(default = "default_priorities")]
     priorities: Vec<Priority>,
@@ -242,208 +830,1108 @@ struct Config {
     statuses: Vec<StatusOption>,
     #[serde(default)]
     contexts: Vec<String>,
+    /// Current context by weekday and time of day, used when `context set` hasn't picked one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    context_rules: Vec<ContextRule>,
     #[serde(default)]
     task_types: std::collections::HashMap<String, TaskTypeDefaults>,
     #[serde(default = "default_sync_throttle")]
     sync_throttle_minutes: i64,
+    /// Requests in flight per provider while `sync` pushes changes
+    #[serde(default = "default_sync_concurrency")]
+    sync_concurrency: usize,
+    /// Keep config and cache in ~/.thegarden instead of moving them to the XDG directories
+    #[serde(default)]
+    legacy_paths: bool,
+    /// Always run as if --offline was given (an explicit `sync` still goes online)
+    #[serde(default)]
+    offline: bool,
+    /// Encrypt task titles, notes, links and stored tokens in cache.db with a key kept in the OS keychain
+    #[serde(default)]
+    encrypt_cache: bool,
+    /// Home time zone (IANA name, e.g. "America/Denver"): "today", overdue and calendar
+    /// blocks follow it instead of wherever the machine is set to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timezone: Option<String>,
+    /// First day of the week ("monday", "sunday", ...) for plan, schedule, bump, report and burndown
+    #[serde(default = "default_week_start")]
+    week_starts_on: String,
+    #[serde(default, skip_serializing_if = "DateNames::is_empty")]
+    date_names: DateNames,
+    #[serde(default, skip_serializing_if = "output::Theme::is_default")]
+    theme: output::Theme,
+    /// Named templates for `list --template <name>`; `focus` also drives the focus view
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    formats: std::collections::HashMap<String, String>,
+    /// Saved searches, run with `view <name>` or as `thegarden <name>`
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    views: std::collections::HashMap<String, View>,
+    /// Order of triage passes (priority, project, time, status, context)
+    #[serde(default = "default_triage_passes")]
+    triage_passes: Vec<String>,
+    /// Run the dedupe pass after every sync (same as `sync --dedupe`)
+    #[serde(default)]
+    auto_dedupe: bool,
+    /// Calendar event titles to ignore when finding free time (e.g. "Lunch hold", "Focus time")
+    #[serde(default)]
+    calendar_ignore: Vec<String>,
+    /// Calendar that receives task blocks from `schedule` (default: the account's primary calendar)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    block_calendar_id: Option<String>,
+    /// Event color per project for task blocks: a Google color name ("tomato") or ID ("11")
+    #[serde(default)]
+    project_colors: std::collections::HashMap<String, String>,
+    #[serde(default)]
+    work_hours: WorkHours,
+    #[serde(default)]
+    schedule_weights: ScheduleWeights,
+    #[serde(default)]
+    wip_limits: WipLimits,
+    #[serde(default)]
+    escalation: EscalationPolicy,
+    /// Roll overdue tasks forward to today after each sync
+    #[serde(default)]
+    auto_bump_overdue: bool,
+    /// Ask "any follow-up?" after completing a task in next, focus or triage
+    #[serde(default)]
+    follow_up_prompt: bool,
+    /// Create a #FollowUp task when an email task is delegated
+    #[serde(default)]
+    delegation_follow_up: delegation::FollowUpPolicy,
+    /// Create prep tasks ahead of calendar events that match a rule
+    #[serde(default)]
+    meeting_prep: meeting_prep::MeetingPrep,
+    /// Turn labeled Gmail threads into tasks on sync
+    #[serde(default)]
+    gmail: gmail::GmailSource,
+    #[serde(default)]
+    obsidian: obsidian::ObsidianConfig,
+    /// Tags that pin a task's date so auto-bump leaves it alone
+    #[serde(default = "default_pinned_tags")]
+    pinned_tags: Vec<String>,
+    #[serde(default)]
+    backup: BackupPolicy,
+    /// POSTed JSON for task events found during sync (Zapier, n8n, home automation)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    webhooks: Vec<webhooks::Webhook>,
+    /// Discord webhooks and Matrix rooms for the digest, new top-priority tasks and overload
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    notifiers: Vec<notify::Notifier>,
+    /// Publish garden health (overdue, P0 open, focus minutes) to an MQTT broker on sync
+    #[serde(default)]
+    mqtt: mqtt::MqttConfig,
+    /// What Airtable and ClickUp pushes leave out
+    #[serde(default)]
+    privacy: privacy::PrivacyConfig,
     #[serde(default)]
     airtable: Option<AirtableConfig>,
     #[serde(default)]
     clickup: Option<ClickUpConfig>,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct AirtableConfig {
     #[serde(default)]
-    enabled: bool,
-    api_key: String,
-    base_id: String,
-    table_name: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct ClickUpConfig {
+    microsoft_todo: mstodo::MsTodoConfig,
     #[serde(default)]
-    enabled: bool,
-    api_token: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    list_id: Option<String>,  // Default list for new tasks
-    #[serde(skip_serializing_if = "Option::is_none")]
-    list_mappings: Option<std::collections::HashMap<String, String>>,  // Google list ID -> ClickUp list ID
+    asana: asana::AsanaConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    embeddings: Option<similarity::EmbeddingConfig>,
 }
 
-fn default_sync_throttle() -> i64 {
-    10
+/// Weekday and month names in dates shown by plan, schedule, list and summary;
+/// chrono's English names when empty
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct DateNames {
+    /// Monday first
+    #[serde(default)]
+    weekdays: Vec<String>,
+    /// January first
+    #[serde(default)]
+    months: Vec<String>,
 }
 
-fn default_priorities() -> Vec<Priority> {
-    vec![
-        Priority { name: "P0".into(), key: '0', alt_key: Some('j'), description: "Urgent + Important".into() },
-        Priority { name: "P1".into(), key: '1', alt_key: Some('k'), description: "Important, not urgent".into() },
-        Priority { name: "P2".into(), key: '2', alt_key: Some('l'), description: "Urgent, not important".into() },
-        Priority { name: "P3".into(), key: '3', alt_key: Some(';'), description: "Not important, not urgent".into() },
-        Priority { name: "P5".into(), key: 'd', alt_key: Some('D'), description: "Delegate".into() },
-    ]
+impl DateNames {
+    fn is_empty(&self) -> bool {
+        self.weekdays.is_empty() && self.months.is_empty()
+    }
 }
 
-fn default_time_options() -> Vec<TimeOption> {
-    vec![
-        TimeOption { label: "15m".into(), key: '0', alt_key: Some('j') },
-        TimeOption { label: "30m".into(), key: '1', alt_key: Some('k') },
-        TimeOption { label: "45m".into(), key: '2', alt_key: Some('l') },
-        TimeOption { label: "1h".into(), key: '3', alt_key: Some(';') },
-        TimeOption { label: "3h".into(), key: '4', alt_key: Some('i') },
-        TimeOption { label: "8h".into(), key: '5', alt_key: Some('o') },
-    ]
+fn default_week_start() -> String {
+    "monday".to_string()
 }
 
-fn default_statuses() -> Vec<StatusOption> {
-    vec![
-        StatusOption { name: "todo".into(), key: 't', alt_key: Some('j'), description: "Not started".into() },
-        StatusOption { name: "progress".into(), key: 'p', alt_key: Some('k'), description: "In progress".into() },
-        StatusOption { name: "review".into(), key: 'r', alt_key: Some('l'), description: "In review".into() },
-        StatusOption { name: "blocked".into(), key: 'b', alt_key: Some(';'), description: "Blocked".into() },
-    ]
+/// First day of the week containing `day`
+fn start_of_week(day: chrono::NaiveDate, first: chrono::Weekday) -> chrono::NaiveDate {
+    day.week(first).first_day()
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct Task {
-    id: Option<String>,
-    list_id: Option<String>,
-    title: String,
-    date: String,
-    priority: Option<String>,
-    project: Option<String>,
-    status: Option<String>,
-    context: Option<String>,
-    time: Option<String>,
-    list: String,
-    attachment_type: Option<String>,
-    #[serde(default)]
-    tags: Vec<String>,
-}
+/// Emoji and color for the first priorities in the list when they don't set their own
+const PRIORITY_EMOJI: [&str; 4] = ["🔴", "🟡", "🟢", "🔵"];
+const PRIORITY_COLORS: [Color; 4] = [Color::Red, Color::Yellow, Color::Green, Color::Blue];
+/// Markers in place of PRIORITY_EMOJI with `theme.ascii`
+const PRIORITY_ASCII: [&str; 4] = ["(!)", "(+)", "(-)", "(.)"];
+
+/// Priority that marked a task done before completion was a status; still honored
+const DONE_PRIORITY: &str = "DONE";
+
+// Priorities rank in the order `priorities` lists them, so any scheme works
+// (P0-P5, A/B/C, Eisenhower quadrants, MoSCoW). The first two are the critical
+// ones focus shows and schedule books time for.
+impl Config {
+    /// Whether tasks in `project` go to the external sync `destination` ("clickup", ...).
+    /// Tasks without a configured project go everywhere.
+    fn syncs_project(&self, project: Option<&str>, destination: &str) -> bool {
+        project
+            .and_then(|p| self.projects.iter().find(|(name, _)| name.eq_ignore_ascii_case(p)))
+            .and_then(|(_, project)| project.sync_to.as_ref())
+            .is_none_or(|to| to.iter().any(|d| d.eq_ignore_ascii_case(destination)))
+    }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct TaskLink {
-    #[serde(rename = "type")]
-    link_type: String,
-    link: String,
-    description: Option<String>,
-}
+    /// Projects whose tasks stay out of the external sync `destination`
+    fn excluded_projects(&self, destination: &str) -> Vec<String> {
+        self.projects.keys()
+            .filter(|name| !self.syncs_project(Some(name), destination))
+            .cloned()
+            .collect()
+    }
 
-/// Parse natural language date from text (e.g., "due Monday", "in 3 days", "due 1/25")
-fn parse_date_from_text(text: &str) -> Option<String> {
-    let text_lower = text.to_lowercase();
-    let today = Local::now();
+    /// Priorities that rank tasks, highest first (done priorities left out)
+    fn ranked_priorities(&self) -> impl Iterator<Item = &Priority> {
+        self.priorities.iter().filter(|p| !p.done)
+    }
 
-    // Check for "in X days"
-    if let Some(caps) = Regex::new(r"in (\d+) days?").unwrap().captures(&text_lower) {
-        if let Ok(days) = caps.get(1).unwrap().as_str().parse::<i64>() {
-            let target = today + chrono::Duration::days(days);
-            return Some(target.format("%Y-%m-%d").to_string());
+    /// Sort key for a priority: its place in the list, then unset or unknown
+    /// priorities, then done ones
+    fn priority_rank(&self, priority: Option<&str>) -> usize {
+        match priority {
+            Some(p) if self.is_done_priority(p) => usize::MAX,
+            Some(p) => self.ranked_priorities().position(|c| c.name == p).unwrap_or(self.priorities.len()),
+            None => self.priorities.len(),
         }
     }
 
-    // Check for "tomorrow"
-    if text_lower.contains("tomorrow") {
-        let target = today + chrono::Duration::days(1);
-        return Some(target.format("%Y-%m-%d").to_string());
+    /// The highest priority (P0 by default)
+    fn top_priority(&self) -> Option<&str> {
+        self.ranked_priorities().next().map(|p| p.name.as_str())
     }
 
-    // Check for day of week (e.g., "due Monday", "next Tuesday")
-    let weekdays = [
-        ("monday", 0), ("mon", 0),
-        ("tuesday", 1), ("tue", 1),
-        ("wednesday", 2), ("wed", 2),
-        ("thursday", 3), ("thu", 3),
-        ("friday", 4), ("fri", 4),
-        ("saturday", 5), ("sat", 5),
-        ("sunday", 6), ("sun", 6),
-    ];
+    /// One of the two highest priorities (P0 and P1 by default)
+    fn is_critical(&self, priority: Option<&str>) -> bool {
+        priority.is_some_and(|p| self.ranked_priorities().take(2).any(|c| c.name == p))
+    }
 
-    for (day_name, target_weekday) in &weekdays {
-        if text_lower.contains(day_name) {
-            let current_weekday = today.weekday().num_days_from_monday();
-            let days_ahead = if *target_weekday >= current_weekday {
-                (*target_weekday - current_weekday) as i64
-            } else {
-                (7 - current_weekday + *target_weekday) as i64
-            };
-            
-            // If it's the same day, assume next week
-            let days_to_add = if days_ahead == 0 { 7 } else { days_ahead };
-            
-            let target = today + chrono::Duration::days(days_to_add);
-            return Some(target.format("%Y-%m-%d").to_string());
-        }
+    /// Whether this priority means the task is complete
+    fn is_done_priority(&self, priority: &str) -> bool {
+        priority == DONE_PRIORITY || self.priorities.iter().any(|p| p.done && p.name == priority)
     }
 
-    // Check for M/D or M/DD format (e.g., "1/25", "12/5")
-    if let Some(caps) = Regex::new(r"(\d{1,2})/(\d{1,2})").unwrap().captures(&text_lower) {
-        if let (Ok(month), Ok(day)) = (
-            caps.get(1).unwrap().as_str().parse::<u32>(),
-            caps.get(2).unwrap().as_str().parse::<u32>(),
-        ) {
-            let year = today.year();
-            if let Some(target) = chrono::NaiveDate::from_ymd_opt(year, month, day) {
-                // If the date is in the past this year, assume next year
-                if target < today.naive_local().date() {
-                    if let Some(next_year_target) = chrono::NaiveDate::from_ymd_opt(year + 1, month, day) {
-                        return Some(next_year_target.format("%Y-%m-%d").to_string());
-                    }
-                } else {
-                    return Some(target.format("%Y-%m-%d").to_string());
-                }
+    /// What summaries call completed tasks
+    fn done_priority(&self) -> &str {
+        self.priorities.iter().find(|p| p.done).map_or(DONE_PRIORITY, |p| p.name.as_str())
+    }
+
+    fn priority_emoji(&self, priority: Option<&str>) -> String {
+        if self.theme.ascii {
+            if priority.is_some_and(|p| self.is_done_priority(p)) {
+                return "(x)".to_string();
             }
+            return priority.and_then(|p| self.ranked_priorities().position(|c| c.name == p))
+                .and_then(|rank| PRIORITY_ASCII.get(rank))
+                .unwrap_or(&"( )")
+                .to_string();
+        }
+        let configured = priority.and_then(|p| self.priorities.iter().find(|c| c.name == p));
+        if let Some(emoji) = configured.and_then(|c| c.emoji.clone()) {
+            return emoji;
         }
+        if priority.is_some_and(|p| self.is_done_priority(p)) {
+            return "✅".to_string();
+        }
+        priority.and_then(|p| self.ranked_priorities().position(|c| c.name == p))
+            .and_then(|rank| PRIORITY_EMOJI.get(rank))
+            .unwrap_or(&"⚪")
+            .to_string()
     }
 
-    None
-}
+    /// Statuses a task in `current` can move to, in workflow order. A status without
+    /// a `next` list, or one the config doesn't know (like needsTriage), can go anywhere.
+    fn next_statuses(&self, current: Option<&str>) -> Vec<&StatusOption> {
+        let allowed = current
+            .and_then(|c| self.statuses.iter().find(|s| s.name == c))
+            .map(|s| &s.next)
+            .filter(|next| !next.is_empty());
+        self.statuses.iter()
+            .filter(|s| Some(s.name.as_str()) != current)
+            .filter(|s| allowed.is_none_or(|next| next.contains(&s.name)))
+            .collect()
+    }
 
-impl Task {
-    /// Get a short ID for display (first 8 chars)
-    fn short_id(&self) -> String {
-        self.id.as_ref()
-            .map(|id| id.chars().take(8).collect())
-            .unwrap_or_else(|| "--------".to_string())
+    /// Whether the workflow lets a task move between these statuses. Statuses the
+    /// config doesn't list are free-form and always allowed.
+    fn allows_transition(&self, from: Option<&str>, to: &str) -> bool {
+        from == Some(to)
+            || !self.statuses.iter().any(|s| s.name == to)
+            || self.next_statuses(from).iter().any(|s| s.name == to)
     }
 
-    fn format(&self, config: &Config) -> String {
-        let mut formatted = config.format.clone();
-        formatted = formatted.replace("{date}", &self.date);
-        formatted = formatted.replace("{priority}", self.priority.as_deref().unwrap_or("--"));
-        formatted = formatted.replace("{project}", self.project.as_deref().unwrap_or("---"));
+    /// A task's project entry, matched case-insensitively
+    fn project(&self, name: Option<&str>) -> Option<&Project> {
+        let name = name?;
+        self.projects.iter().find(|(p, _)| p.eq_ignore_ascii_case(name)).map(|(_, project)| project)
+    }
 
-        // Status is optional in format
-        let status_str = self.status.as_ref().map(|s| format!("[{}]", s)).unwrap_or_default();
-        formatted = formatted.replace("{status}", &status_str);
+    /// Scheduling preference for a task: a work_hours preference (context, tags,
+    /// priority) wins, then its project's hours
+    fn task_preference(&self, task: &Task) -> Option<&String> {
+        self.work_hours.task_preference(task)
+            .or_else(|| self.project(task.project.as_deref()).and_then(|p| p.hours.as_ref()))
+    }
 
-        // Context is optional in format
-        let context_str = self.context.as_ref().map(|c| format!("[{}]", c)).unwrap_or_default();
-        formatted = formatted.replace("{context}", &context_str);
+    /// Window a task may be scheduled in on a date: its preference, otherwise working hours
+    fn task_window(&self, task: &Task, date: &chrono::NaiveDate) -> Option<(chrono::NaiveTime, chrono::NaiveTime)> {
+        let work = self.work_hours.window_for(date)?;
+        Some(self.task_preference(task)
+            .and_then(|pref| resolve_preference_window(pref, work))
+            .unwrap_or(work))
+    }
 
-        formatted = formatted.replace("{time}", self.time.as_deref().unwrap_or(""));
-        
-        // Add hashtags to title if present
-        let title_with_tags = if self.tags.is_empty() {
-            self.title.clone()
+    /// First context rule covering this moment
+    fn context_rule_at(&self, at: chrono::NaiveDateTime) -> Option<&ContextRule> {
+        self.context_rules.iter().find(|rule| rule.applies_at(at))
+    }
+
+    fn week_start(&self) -> chrono::Weekday {
+        self.week_starts_on.trim().parse().unwrap_or(chrono::Weekday::Mon)
+    }
+
+    /// The `formats` entry called `name` (or the built-in one), checked for unknown placeholders
+    fn template(&self, name: &str) -> Result<&str> {
+        let template = match self.formats.get(name) {
+            Some(template) => template.as_str(),
+            None => match template::BUILTIN.iter().find(|(builtin, _)| *builtin == name) {
+                Some((_, template)) => template,
+                None => {
+                    let mut names: Vec<&str> = self.formats.keys().map(String::as_str)
+                        .chain(template::BUILTIN.iter().map(|(builtin, _)| *builtin))
+                        .collect();
+                    names.sort();
+                    names.dedup();
+                    anyhow::bail!("No format template '{}' (available: {})", name, names.join(", "));
+                }
+            },
+        };
+        template::validate(template)?;
+        Ok(template)
+    }
+
+    /// `date.format(fmt)` with the configured names for %A/%a (weekday) and %B/%b (month);
+    /// short names are the first three letters
+    fn format_date(&self, date: chrono::NaiveDate, fmt: &str) -> String {
+        let names = &self.date_names;
+        let short = |name: &str| name.chars().take(3).collect::<String>();
+        let mut fmt = fmt.to_string();
+        if let (7, Some(name)) = (names.weekdays.len(), names.weekdays.get(date.weekday().num_days_from_monday() as usize)) {
+            fmt = fmt.replace("%A", name).replace("%a", &short(name));
+        }
+        if let (12, Some(name)) = (names.months.len(), names.months.get(date.month0() as usize)) {
+            fmt = fmt.replace("%B", name).replace("%b", &short(name));
+        }
+        date.format(&fmt).to_string()
+    }
+
+    /// Whether moving to this status completes the task
+    fn is_done_status(&self, status: Option<&str>) -> bool {
+        status.is_some_and(|status| self.statuses.iter().any(|s| s.done && s.name == status))
+    }
+
+    fn priority_color(&self, priority: Option<&str>) -> Color {
+        if let Some(color) = priority.and_then(|p| output::Theme::color_for(&self.theme.priorities, p)) {
+            return color;
+        }
+        let configured = priority.and_then(|p| self.priorities.iter().find(|c| c.name == p));
+        if let Some(color) = configured.and_then(|c| c.color.as_deref()).and_then(|c| c.parse().ok()) {
+            return color;
+        }
+        if priority.is_some_and(|p| self.is_done_priority(p)) {
+            return Color::Green;
+        }
+        priority.and_then(|p| self.ranked_priorities().position(|c| c.name == p))
+            .and_then(|rank| PRIORITY_COLORS.get(rank).copied())
+            .unwrap_or(Color::White)
+    }
+
+    fn status_color(&self, status: &str) -> Color {
+        output::Theme::color_for(&self.theme.statuses, status).unwrap_or(Color::Cyan)
+    }
+}
+
+/// Working hours and spacing rules used when finding free time for `schedule`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct WorkHours {
+    /// Default working window, "HH:MM-HH:MM"
+    #[serde(default = "default_work_window")]
+    hours: String,
+    /// Per-weekday overrides ("mon".."sun"): a window or "off"
+    #[serde(default)]
+    days: std::collections::HashMap<String, String>,
+    /// Protected lunch window, e.g. "12:00-13:00"
+    #[serde(default)]
+    lunch: Option<String>,
+    /// Minutes kept free before and after meetings
+    #[serde(default)]
+    buffer_minutes: i64,
+    /// Longest single task block; longer tasks are split into several blocks
+    #[serde(default)]
+    max_block_minutes: Option<i64>,
+    /// Time-of-day preferences keyed by "@context", "#tag" or priority ("P0"):
+    /// "HH:MM-HH:MM" (may reach outside working hours), "before HH:MM" or "after HH:MM"
+    #[serde(default)]
+    preferences: std::collections::BTreeMap<String, String>,
+    /// Stretch task blocks by your tracked/estimated factor from `stats estimates`
+    #[serde(default)]
+    inflate_estimates: bool,
+}
+
+fn default_pinned_tags() -> Vec<String> {
+    vec!["harddeadline".to_string()]
+}
+
+fn default_work_window() -> String {
+    "08:00-18:00".to_string()
+}
+
+impl Default for WorkHours {
+    fn default() -> Self {
+        WorkHours {
+            hours: default_work_window(),
+            days: std::collections::HashMap::new(),
+            lunch: None,
+            buffer_minutes: 0,
+            max_block_minutes: None,
+            preferences: std::collections::BTreeMap::new(),
+            inflate_estimates: false,
+        }
+    }
+}
+
+/// How `schedule` orders tasks before fitting them into free time; each part adds
+/// weight × its measure to a task's score, and higher scores are placed first
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct ScheduleWeights {
+    /// Per priority level above the lowest
+    #[serde(default = "default_priority_weight")]
+    priority: i64,
+    /// Per day closer than a week to the due date (overdue days keep counting, up to two weeks)
+    #[serde(default = "default_deadline_weight")]
+    deadline: i64,
+    /// Per task waiting on this one (a parent waits on its subtasks)
+    #[serde(default = "default_blocking_weight")]
+    blocking: i64,
+    /// Once for tasks limited to a preference window, so others don't take their only slots
+    #[serde(default = "default_window_weight")]
+    window: i64,
+    /// Per percent of the remaining free time the estimate needs
+    #[serde(default = "default_fit_weight")]
+    fit: i64,
+}
+
+fn default_priority_weight() -> i64 { 1000 }
+fn default_deadline_weight() -> i64 { 50 }
+fn default_blocking_weight() -> i64 { 200 }
+fn default_window_weight() -> i64 { 150 }
+fn default_fit_weight() -> i64 { 1 }
+
+impl Default for ScheduleWeights {
+    fn default() -> Self {
+        ScheduleWeights {
+            priority: default_priority_weight(),
+            deadline: default_deadline_weight(),
+            blocking: default_blocking_weight(),
+            window: default_window_weight(),
+            fit: default_fit_weight(),
+        }
+    }
+}
+
+/// Parse "HH:MM-HH:MM" into start and end times
+fn parse_time_window(window: &str) -> Option<(chrono::NaiveTime, chrono::NaiveTime)> {
+    let (start, end) = window.split_once('-')?;
+    let start = chrono::NaiveTime::parse_from_str(start.trim(), "%H:%M").ok()?;
+    let end = chrono::NaiveTime::parse_from_str(end.trim(), "%H:%M").ok()?;
+    (start < end).then_some((start, end))
+}
+
+impl WorkHours {
+    /// Working window for a date, or None on days off
+    fn window_for(&self, date: &chrono::NaiveDate) -> Option<(chrono::NaiveTime, chrono::NaiveTime)> {
+        let day_key = date.format("%a").to_string().to_lowercase();
+        let window = self.days.get(&day_key).unwrap_or(&self.hours);
+        if window.trim().eq_ignore_ascii_case("off") {
+            return None;
+        }
+        parse_time_window(window).or_else(|| {
+            eprintln!("{}", format!("⚠ Invalid work_hours window '{}', using {}", window, default_work_window()).yellow());
+            parse_time_window(&default_work_window())
+        })
+    }
+
+    /// Span to look for free time on a date: working hours stretched to cover any
+    /// preference windows outside them, or None on days off
+    fn free_time_window(&self, date: &chrono::NaiveDate) -> Option<(chrono::NaiveTime, chrono::NaiveTime)> {
+        let work = self.window_for(date)?;
+        Some(self.preferences.values()
+            .filter_map(|pref| resolve_preference_window(pref, work))
+            .fold(work, |(start, end), (s, e)| (start.min(s), end.max(e))))
+    }
+
+    /// First matching preference for a task: context, then tags, then priority
+    fn task_preference(&self, task: &Task) -> Option<&String> {
+        let keys = task.context.iter().cloned()
+            .chain(task.tags.iter().map(|t| format!("#{}", t)))
+            .chain(task.priority.iter().cloned());
+        for key in keys {
+            let found = self.preferences.iter().find(|(k, _)| k.eq_ignore_ascii_case(&key));
+            if let Some((_, pref)) = found {
+                return Some(pref);
+            }
+        }
+        None
+    }
+}
+
+/// Turn a preference ("HH:MM-HH:MM", "before HH:MM", "after HH:MM") into a window,
+/// using working hours for the open end
+fn resolve_preference_window(
+    pref: &str,
+    work: (chrono::NaiveTime, chrono::NaiveTime),
+) -> Option<(chrono::NaiveTime, chrono::NaiveTime)> {
+    let parse = |t: &str| chrono::NaiveTime::parse_from_str(t.trim(), "%H:%M").ok();
+    let pref = pref.trim().to_lowercase();
+    let window = if let Some(t) = pref.strip_prefix("before ") {
+        (work.0, parse(t)?)
+    } else if let Some(t) = pref.strip_prefix("after ") {
+        (parse(t)?, work.1)
+    } else {
+        parse_time_window(&pref)?
+    };
+    (window.0 < window.1).then_some(window)
+}
+
+/// "Between 09:00 and 17:00 on weekdays I'm @work"
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct ContextRule {
+    context: String,
+    /// "HH:MM-HH:MM"
+    hours: String,
+    /// "mon".."sun", "weekdays" or "weekends"; every day when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    days: Vec<String>,
+}
+
+impl ContextRule {
+    fn applies_at(&self, at: chrono::NaiveDateTime) -> bool {
+        let weekday = at.weekday();
+        let day_key = at.format("%a").to_string().to_lowercase();
+        let on_day = self.days.is_empty() || self.days.iter().any(|d| match d.to_lowercase().as_str() {
+            "weekdays" => weekday.num_days_from_monday() < 5,
+            "weekends" => weekday.num_days_from_monday() >= 5,
+            d => d.get(..3) == Some(day_key.as_str()),
+        });
+        on_day && parse_time_window(&self.hours).is_some_and(|(start, end)| at.time() >= start && at.time() < end)
+    }
+
+    /// "weekdays 09:00-17:00"
+    fn describe(&self) -> String {
+        let days = if self.days.is_empty() { "every day".to_string() } else { self.days.join(",") };
+        format!("{} {}", days, self.hours)
+    }
+
+    /// The context with its "@", however it was written in the config
+    fn context(&self) -> String {
+        format!("@{}", self.context.trim().trim_start_matches('@'))
+    }
+}
+
+/// Work-in-progress caps; going over warns in `list`/`summary` and asks before triage assigns it
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct WipLimits {
+    /// Max open tasks per project in a status, e.g. {"progress": 3}
+    #[serde(default)]
+    status_per_project: std::collections::HashMap<String, usize>,
+    /// Max open tasks at a priority across all projects, e.g. {"P0": 5}
+    #[serde(default)]
+    priority_total: std::collections::HashMap<String, usize>,
+}
+
+impl WipLimits {
+    fn status_limit(&self, status: Option<&str>) -> Option<(&String, usize)> {
+        let status = status?;
+        self.status_per_project.iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(status))
+            .map(|(name, max)| (name, *max))
+    }
+
+    fn priority_limit(&self, priority: Option<&str>) -> Option<(&String, usize)> {
+        let priority = priority?;
+        self.priority_total.iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(priority))
+            .map(|(name, max)| (name, *max))
+    }
+
+    /// Limits the current task list is already over
+    fn warnings(&self, tasks: &[Task]) -> Vec<String> {
+        let open: Vec<&Task> = tasks.iter().filter(|t| !t.is_done()).collect();
+        let mut warnings = Vec::new();
+
+        for (status, max) in &self.status_per_project {
+            let mut per_project: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+            for task in open.iter().filter(|t| t.status.as_deref().map(|s| s.eq_ignore_ascii_case(status)).unwrap_or(false)) {
+                *per_project.entry(task.project.as_deref().unwrap_or("---")).or_default() += 1;
+            }
+            for (project, count) in per_project.into_iter().filter(|(_, count)| count > max) {
+                warnings.push(format!("{} has {} tasks in {} (limit {})", project, count, status, max));
+            }
+        }
+        for (priority, max) in &self.priority_total {
+            let count = open.iter().filter(|t| t.priority.as_deref().map(|p| p.eq_ignore_ascii_case(priority)).unwrap_or(false)).count();
+            if count > *max {
+                warnings.push(format!("{} open {} tasks (limit {})", count, priority, max));
+            }
+        }
+        warnings.sort();
+        warnings
+    }
+
+    /// Why changing `before` into `after` would go over a limit, if it would
+    fn breach(&self, tasks: &[Task], before: &Task, after: &Task) -> Option<String> {
+        let others = || tasks.iter().filter(|t| t.id != after.id && !t.is_done());
+        let same = |a: Option<&str>, b: Option<&str>| match (a, b) {
+            (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+            (a, b) => a == b,
+        };
+
+        if !same(before.status.as_deref(), after.status.as_deref()) {
+            if let Some((status, max)) = self.status_limit(after.status.as_deref()) {
+                let count = others()
+                    .filter(|t| same(t.status.as_deref(), Some(status)) && t.project == after.project)
+                    .count() + 1;
+                if count > max {
+                    return Some(format!("{} would have {} tasks in {} (limit {})",
+                        after.project.as_deref().unwrap_or("---"), count, status, max));
+                }
+            }
+        }
+        if !same(before.priority.as_deref(), after.priority.as_deref()) {
+            if let Some((priority, max)) = self.priority_limit(after.priority.as_deref()) {
+                let count = others().filter(|t| same(t.priority.as_deref(), Some(priority))).count() + 1;
+                if count > max {
+                    return Some(format!("That makes {} open {} tasks (limit {})", count, priority, max));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Automatic backups of the cache and config, taken by the first command each day
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct BackupPolicy {
+    #[serde(default)]
+    daily: bool,
+    /// How many automatic backups to keep; older ones are deleted
+    #[serde(default = "default_backup_keep")]
+    keep: usize,
+    /// Keep API keys and tokens in the archived config.json
+    #[serde(default)]
+    include_secrets: bool,
+    /// Where automatic backups go (default: backups/ in the data dir)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dir: Option<PathBuf>,
+}
+
+fn default_backup_keep() -> usize {
+    7
+}
+
+impl Default for BackupPolicy {
+    fn default() -> Self {
+        BackupPolicy {
+            daily: false,
+            keep: default_backup_keep(),
+            include_secrets: false,
+            dir: None,
+        }
+    }
+}
+
+/// Aging rules applied after each sync; every change is logged for `summary`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct EscalationPolicy {
+    /// e.g. [{"from": "P2", "to": "P1", "older_than_days": 14}]
+    #[serde(default)]
+    rules: Vec<EscalationRule>,
+    /// Flag (without changing) tasks overdue by at least this many days
+    #[serde(default)]
+    flag_overdue_days: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct EscalationRule {
+    from: String,
+    to: String,
+    older_than_days: i64,
+}
+
+impl EscalationPolicy {
+    /// The first rule that applies to a task of this age, if any
+    fn rule_for(&self, task: &Task, age_days: i64) -> Option<&EscalationRule> {
+        let priority = task.priority.as_deref()?;
+        self.rules.iter().find(|r| r.from.eq_ignore_ascii_case(priority) && age_days >= r.older_than_days)
+    }
+}
+
+/// Apply the escalation policy to the cache: bump aged priorities, flag long-overdue tasks,
+/// log both, and push any priority changes
+fn apply_escalations(app: &App) -> Result<()> {
+    let config = &app.config;
+    let policy = &config.escalation;
+    if policy.rules.is_empty() && policy.flag_overdue_days.is_none() {
+        return Ok(());
+    }
+
+    let sync_manager = &app.db;
+    let created: std::collections::HashMap<String, String> = sync_manager.get_all_cached_tasks()?
+        .into_iter()
+        .filter_map(|c| c.created.map(|created| (c.id, created)))
+        .collect();
+    let today = Local::now().date_naive();
+
+    let mut escalated = Vec::new();
+    let mut flagged = 0;
+    for task in get_tasks_from_cache(app)? {
+        let Some(id) = task.id.clone() else { continue };
+        if task.is_done() {
+            continue;
+        }
+
+        let age_days = created.get(&id)
+            .and_then(|c| chrono::DateTime::parse_from_rfc3339(c).ok())
+            .map(|c| (today - c.with_timezone(&Local).date_naive()).num_days());
+        if let Some(rule) = age_days.and_then(|age| policy.rule_for(&task, age)) {
+            let reason = format!("{} older than {} days", rule.from, rule.older_than_days);
+            sync_manager.log_priority_drift(&id, &task.title, task.priority.as_deref(), Some(&rule.to), &reason)?;
+            let mut updated = task.clone();
+            updated.priority = Some(rule.to.to_uppercase());
+            escalated.push(updated);
+        }
+
+        if let (Some(days), Ok(date)) = (policy.flag_overdue_days, chrono::NaiveDate::parse_from_str(&task.date, "%Y-%m-%d")) {
+            if (today - date).num_days() >= days {
+                let reason = format!("overdue {}+ days, due {}", days, task.date);
+                if sync_manager.log_priority_drift(&id, &task.title, task.priority.as_deref(), None, &reason)? {
+                    flagged += 1;
+                }
+            }
+        }
+    }
+
+    if !escalated.is_empty() {
+        save_tasks_locally(sync_manager, config, &escalated)?;
+        push_dirty_tasks(app)?;
+        output::note(format!("⬆️  Escalated {} aging tasks (see `summary`)", escalated.len()).yellow());
+    }
+    if flagged > 0 {
+        output::note(format!("🚩 Flagged {} long-overdue tasks (see `summary`)", flagged).yellow());
+    }
+    Ok(())
+}
+
+/// Overdue, unfinished tasks that auto-bump may move (no pinned tag)
+fn is_rollover_candidate(config: &Config, task: &Task, today_str: &str) -> bool {
+    !task.date.is_empty()
+        && task.date.as_str() < today_str
+        && !task.is_done()
+        && !task.tags.iter().any(|tag| config.pinned_tags.iter().any(|p| p.trim_start_matches('#').eq_ignore_ascii_case(tag)))
+}
+
+/// Roll overdue tasks forward to today (like `bump`, but automatic)
+fn auto_bump_overdue(app: &App) -> Result<()> {
+    let config = &app.config;
+    let today_str = Local::now().format("%Y-%m-%d").to_string();
+    let rolled: Vec<Task> = get_tasks_from_cache(app)?
+        .into_iter()
+        .filter(|t| is_rollover_candidate(config, t, &today_str))
+        .map(|mut t| {
+            t.date = today_str.clone();
+            t
+        })
+        .collect();
+    if rolled.is_empty() {
+        return Ok(());
+    }
+
+    save_tasks_locally(&app.db, config, &rolled)?;
+    push_dirty_tasks(app)?;
+    output::note(format!("📅 Rolled over {} overdue tasks to today", rolled.len()).cyan());
+    Ok(())
+}
+
+/// Housekeeping that runs after every sync
+fn after_sync(app: &App, created_ids: &[String], dedupe: bool) -> Result<()> {
+    let config = &app.config;
+    sync::set_change_source("auto");
+    if dedupe || config.auto_dedupe {
+        dedupe_synced_tasks(app, created_ids)?;
+    }
+    apply_escalations(app)?;
+    if config.auto_bump_overdue {
+        auto_bump_overdue(app)?;
+    }
+    gmail::import_labeled(app)?;
+    delegation::create_follow_ups(app)?;
+    meeting_prep::create_prep_tasks(app)?;
+    webhooks::fire(app)?;
+    notify::after_sync(app)?;
+    mqtt::publish(app)
+}
+
+/// Print WIP limit warnings, if any
+fn print_wip_warnings(warnings: &[String]) {
+    if warnings.is_empty() || output::is_quiet() {
+        return;
+    }
+    println!("\n{}", "🚧 Over WIP limits:".yellow().bold());
+    for warning in warnings {
+        println!("  {}", warning.yellow());
+    }
+}
+
+/// Bumps at which a task is called out in summary as chronically postponed
+const CHRONIC_BUMP_THRESHOLD: i64 = 5;
+
+/// Open tasks bumped at least `threshold` times, most-bumped first
+fn chronic_bumps<'a>(tasks: &'a [Task], counts: &std::collections::HashMap<String, i64>, threshold: i64) -> Vec<(&'a Task, i64)> {
+    let mut chronic: Vec<(&Task, i64)> = tasks.iter()
+        .filter(|t| !t.is_done())
+        .filter_map(|t| {
+            let count = *counts.get(t.id.as_deref()?)?;
+            (count >= threshold).then_some((t, count))
+        })
+        .collect();
+    chronic.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+    chronic
+}
+
+/// Green "✓ " in front of a completed task, empty otherwise
+fn done_marker(task: &Task) -> String {
+    let check = if output::is_ascii() { "x" } else { "✓" };
+    if task.is_done() { format!("{} ", check.green()) } else { String::new() }
+}
+
+/// Dimmed "↻n" marker for a task that has been bumped, empty otherwise
+fn bump_marker(counts: &std::collections::HashMap<String, i64>, task: &Task) -> String {
+    match task.id.as_deref().and_then(|id| counts.get(id)) {
+        Some(n) => format!(" ↻{}", n).dimmed().to_string(),
+        None => String::new(),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct AirtableConfig {
+    #[serde(default)]
+    enabled: bool,
+    api_key: String,
+    base_id: String,
+    table_name: String,
+    /// Let Airtable add select options a task needs (the token's user must be a base creator)
+    #[serde(default = "default_create_options")]
+    create_options: bool,
+    /// Select field → local value → Airtable option, e.g. {"Priority": {"P0": "Urgent"}}
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    option_map: std::collections::HashMap<String, std::collections::HashMap<String, String>>,
+    /// "+person" or project → collaborator email (or text, for a text Assignee field)
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    assignees: std::collections::HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_assignee: Option<String>,
+}
+
+fn default_create_options() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct ClickUpConfig {
+    #[serde(default)]
+    enabled: bool,
+    api_token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    list_id: Option<String>,  // Default list for new tasks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    list_mappings: Option<std::collections::HashMap<String, String>>,  // Google list ID -> ClickUp list ID
+    /// Prefix the Google Tasks title of each task created in ClickUp with 🔃
+    #[serde(default)]
+    mark_synced: bool,
+    /// "+person" or project → ClickUp member ID
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    assignees: std::collections::HashMap<String, u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_assignee: Option<u64>,
+}
+
+fn default_triage_passes() -> Vec<String> {
+    ["priority", "project", "time", "status", "context"].iter().map(|s| s.to_string()).collect()
+}
+
+fn default_sync_throttle() -> i64 {
+    10
+}
+
+fn default_sync_concurrency() -> usize {
+    4
+}
+
+fn default_priorities() -> Vec<Priority> {
+    vec![
+        Priority { name: "P0".into(), key: '0', alt_key: Some('j'), description: "Urgent + Important".into(), ..Default::default() },
+        Priority { name: "P1".into(), key: '1', alt_key: Some('k'), description: "Important, not urgent".into(), ..Default::default() },
+        Priority { name: "P2".into(), key: '2', alt_key: Some('l'), description: "Urgent, not important".into(), ..Default::default() },
+        Priority { name: "P3".into(), key: '3', alt_key: Some(';'), description: "Not important, not urgent".into(), ..Default::default() },
+        Priority { name: "P5".into(), key: 'd', alt_key: Some('D'), description: "Delegate".into(), ..Default::default() },
+    ]
+}
+
+fn default_time_options() -> Vec<TimeOption> {
+    vec![
+        TimeOption { label: "15m".into(), key: '0', alt_key: Some('j') },
+        TimeOption { label: "30m".into(), key: '1', alt_key: Some('k') },
+        TimeOption { label: "45m".into(), key: '2', alt_key: Some('l') },
+        TimeOption { label: "1h".into(), key: '3', alt_key: Some(';') },
+        TimeOption { label: "3h".into(), key: '4', alt_key: Some('i') },
+        TimeOption { label: "8h".into(), key: '5', alt_key: Some('o') },
+    ]
+}
+
+fn default_statuses() -> Vec<StatusOption> {
+    vec![
+        StatusOption { name: "todo".into(), key: 't', alt_key: Some('j'), description: "Not started".into(), ..Default::default() },
+        StatusOption { name: "progress".into(), key: 'p', alt_key: Some('k'), description: "In progress".into(), ..Default::default() },
+        StatusOption { name: "review".into(), key: 'r', alt_key: Some('l'), description: "In review".into(), ..Default::default() },
+        StatusOption { name: "blocked".into(), key: 'b', alt_key: Some(';'), description: "Blocked".into(), ..Default::default() },
+    ]
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Task {
+    id: Option<String>,
+    list_id: Option<String>,
+    title: String,
+    date: String,
+    priority: Option<String>,
+    project: Option<String>,
+    status: Option<String>,
+    context: Option<String>,
+    time: Option<String>,
+    list: String,
+    attachment_type: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Day the task was created (from Google), kept separate from its due `date`
+    #[serde(default)]
+    created: Option<String>,
+    /// Hidden from list/focus/triage until this day (YYYY-MM-DD)
+    #[serde(default)]
+    start: Option<String>,
+    /// Free-form notes (Google Tasks `notes`)
+    #[serde(default)]
+    notes: Option<String>,
+    /// Completed in Google Tasks (or marked done here, waiting to be pushed)
+    #[serde(default)]
+    completed: bool,
+}
+
+// Natural-language date phrases, tried in this order by find_date_in_text
+static IN_N_DAYS_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bin (\d+|an?|one|two|three|four|five|six) (days?|weeks?)\b").unwrap()
+});
+static NEXT_WEEK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bnext week\b").unwrap());
+static TOMORROW_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\btomorrow\b").unwrap());
+static WEEKDAY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:next )?(monday|mon|tuesday|tues|tue|wednesday|wed|thursday|thurs|thu|friday|fri|saturday|sat|sunday|sun)\b").unwrap()
+});
+static MONTH_DAY_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(\d{1,2})/(\d{1,2})\b").unwrap());
+
+/// Parse natural language date from text (e.g., "due Monday", "in 3 days", "due 1/25")
+fn parse_date_from_text(text: &str) -> Option<String> {
+    find_date_in_text(text).map(|(date, _)| date)
+}
+
+/// Like parse_date_from_text, but also returns the byte range of the matched phrase
+fn find_date_in_text(text: &str) -> Option<(String, std::ops::Range<usize>)> {
+    let today = Local::now();
+    let ymd = |days: i64| (today + chrono::Duration::days(days)).format("%Y-%m-%d").to_string();
+
+    // Check for "in X days" / "in two weeks"
+    if let Some(caps) = IN_N_DAYS_REGEX.captures(text) {
+        let n = match caps[1].to_lowercase().as_str() {
+            "a" | "an" | "one" => Some(1),
+            "two" => Some(2),
+            "three" => Some(3),
+            "four" => Some(4),
+            "five" => Some(5),
+            "six" => Some(6),
+            digits => digits.parse::<i64>().ok(),
+        };
+        if let Some(n) = n {
+            let days = if caps[2].to_lowercase().starts_with("week") { n * 7 } else { n };
+            return Some((ymd(days), caps.get(0).unwrap().range()));
+        }
+    }
+
+    // Check for "next week" (the coming Monday)
+    if let Some(m) = NEXT_WEEK_REGEX.find(text) {
+        let days_until_monday = 7 - today.weekday().num_days_from_monday() as i64;
+        return Some((ymd(days_until_monday), m.range()));
+    }
+
+    // Check for "tomorrow"
+    if let Some(m) = TOMORROW_REGEX.find(text) {
+        return Some((ymd(1), m.range()));
+    }
+
+    // Check for day of week (e.g., "due Monday", "next Tuesday")
+    if let Some(caps) = WEEKDAY_REGEX.captures(text) {
+        let target_weekday = match &caps[1].to_lowercase()[..3] {
+            "mon" => 0,
+            "tue" => 1,
+            "wed" => 2,
+            "thu" => 3,
+            "fri" => 4,
+            "sat" => 5,
+            _ => 6,
+        };
+        let current_weekday = today.weekday().num_days_from_monday();
+        let days_ahead = if target_weekday >= current_weekday {
+            (target_weekday - current_weekday) as i64
+        } else {
+            (7 - current_weekday + target_weekday) as i64
+        };
+
+        // If it's the same day, assume next week
+        let days_to_add = if days_ahead == 0 { 7 } else { days_ahead };
+        return Some((ymd(days_to_add), caps.get(0).unwrap().range()));
+    }
+
+    // Check for M/D or M/DD format (e.g., "1/25", "12/5")
+    if let Some(caps) = MONTH_DAY_REGEX.captures(text) {
+        if let (Ok(month), Ok(day)) = (caps[1].parse::<u32>(), caps[2].parse::<u32>()) {
+            let year = today.year();
+            if let Some(target) = chrono::NaiveDate::from_ymd_opt(year, month, day) {
+                // If the date is in the past this year, assume next year
+                let target = if target < today.naive_local().date() {
+                    chrono::NaiveDate::from_ymd_opt(year + 1, month, day)
+                } else {
+                    Some(target)
+                };
+                if let Some(target) = target {
+                    return Some((target.format("%Y-%m-%d").to_string(), caps.get(0).unwrap().range()));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+static DUE_WORD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\b(?:due|by|on)\s+$").unwrap());
+static QUICK_TIME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(?:\d+h\d+m|\d+(?:\.\d+)?[hm])$").unwrap()
+});
+
+/// Parse a quick-add line: `p1 @work /SILVERMINE 45m follow up with legal tomorrow #FollowUp`.
+/// Priority, context, project, time and date tokens are stripped from the title; hashtags stay.
+fn parse_quick_add(text: &str, config: &Config) -> Task {
+    let mut rest = text.to_string();
+
+    // Date phrase first (it can span words), along with a leading "due"/"by"/"on"
+    let mut date = None;
+    if let Some((parsed, range)) = find_date_in_text(&rest) {
+        let start = DUE_WORD_REGEX.find(&rest[..range.start])
+            .map(|m| m.start())
+            .unwrap_or(range.start);
+        rest = format!("{} {}", &rest[..start], &rest[range.end..]);
+        date = Some(parsed);
+    }
+
+    let mut priority = None;
+    let mut project = None;
+    let mut context = None;
+    let mut time = None;
+    let mut words = Vec::new();
+
+    for word in rest.split_whitespace() {
+        let upper = word.to_uppercase();
+        if priority.is_none() && config.priorities.iter().any(|p| p.name == upper) && upper.starts_with('P') {
+            priority = Some(upper);
+        } else if context.is_none() && word.len() > 1 && word.starts_with('@') {
+            context = Some(word.to_lowercase());
+        } else if project.is_none() && word.len() > 1 && word.starts_with('/')
+            && word[1..].chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+            project = Some(word[1..].to_uppercase());
+        } else if time.is_none() && QUICK_TIME_REGEX.is_match(word) {
+            time = Some(word.to_lowercase());
         } else {
-            let tags_str = self.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ");
-            format!("{} {}", self.title, tags_str.dimmed().to_string())
+            words.push(word);
+        }
+    }
+
+    let title = words.join(" ");
+    let mut task = Task::parse_with_config(&title, "My Tasks", Some(config));
+    task.title = title;
+    task.date = date.unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string());
+    // Inline tokens win over #TaskType defaults
+    task.priority = priority.or(task.priority);
+    task.time = time.or(task.time);
+    task.project = project;
+    task.context = context;
+    task
+}
+
+impl Task {
+    /// Who the task is with: the first +name in the title
+    fn person(&self) -> Option<&str> {
+        PERSON_REGEX.captures(&self.title).map(|caps| caps.get(1).unwrap().as_str())
+    }
+
+    /// Replace the title's +name (or add one at the end)
+    fn set_person(&mut self, name: &str) {
+        let name: String = name.chars().filter(|c| !c.is_whitespace()).collect();
+        let title = match PERSON_REGEX.find(&self.title) {
+            Some(found) => {
+                let lead = if found.as_str().starts_with('+') { "" } else { " " };
+                format!("{}{}+{}{}", &self.title[..found.start()], lead, name, &self.title[found.end()..])
+            }
+            None => format!("{} +{}", self.title.trim_end(), name),
         };
-        formatted = formatted.replace("{title}", &title_with_tags);
-        formatted
+        self.title = title;
+    }
+
+    /// Completed, or still carrying a [DONE] priority from before completion was a status
+    fn is_done(&self) -> bool {
+        self.completed || self.priority.as_deref() == Some(DONE_PRIORITY)
+    }
+
+    /// Get a short ID for display (first 8 chars)
+    fn short_id(&self) -> String {
+        self.id.as_ref()
+            .map(|id| id.chars().take(8).collect())
+            .unwrap_or_else(|| "--------".to_string())
     }
 
-    fn parse(title: &str, list: &str) -> Task {
-        Task::parse_with_config(title, list, None)
+    fn format(&self, config: &Config) -> String {
+        template::render(&config.format, self, Local::now().date_naive())
     }
 
     fn parse_with_config(title: &str, list: &str, config: Option<&Config>) -> Task {
         // Extract hashtags from title
-        let hashtag_regex = Regex::new(r"#(\w+)").unwrap();
-        let tags: Vec<String> = hashtag_regex
+        let tags: Vec<String> = HASHTAG_REGEX
             .captures_iter(title)
             .map(|cap| cap.get(1).unwrap().as_str().to_string())
             .collect();
@@ -466,12 +1954,19 @@ impl Task {
                 list: list.to_string(),
                 attachment_type: None,
                 tags,
+                created: None,
+                start: None,
+                notes: None,
+                completed: false,
             };
             
             // Override date if we found one in the title text
             if let Some(date) = parsed_date {
                 task.date = date;
             }
+            // A done priority in the title means the task is complete
+            let priority = task.priority.as_deref().unwrap_or_default();
+            task.completed = config.map_or(priority == DONE_PRIORITY, |c| c.is_done_priority(priority));
             
             task
         } else {
@@ -503,48 +1998,24 @@ impl Task {
                 list: list.to_string(),
                 attachment_type: None,
                 tags,
+                created: None,
+                start: None,
+                notes: None,
+                completed: false,
             }
         }
     }
-    
-    fn needs_triage(&self, force: bool, priority_only: bool, project_only: bool, time_only: bool, status_only: bool, context_only: bool) -> bool {
-        if force {
-            return true;
-        }
-
-        // If specific pass requested, check only that property
-        if priority_only {
-            return self.priority.is_none();
-        }
-        if project_only {
-            return self.project.is_none();
-        }
-        if time_only {
-            return self.time.is_none();
-        }
-        if status_only {
-            return self.status.is_none();
-        }
-        if context_only {
-            return self.context.is_none();
-        }
-
-        // Otherwise, needs triage if ANY of the core properties is missing
-        // (status and context are optional extras, not required for basic triage)
-        self.priority.is_none() || self.project.is_none() || self.time.is_none()
-    }
 }
 
 fn get_config_path() -> Result<PathBuf> {
-    let home = dirs::home_dir().context("Could not find home directory")?;
-    Ok(home.join(".thegarden").join("config.json"))
+    paths::config_path()
 }
 
 fn create_default_config() -> Config {
     let mut projects = std::collections::HashMap::new();
-    projects.insert("WORK".into(), "Work tasks".into());
-    projects.insert("LIFE".into(), "Personal life tasks".into());
-    projects.insert("SIDE".into(), "Side projects".into());
+    projects.insert("WORK".into(), Project::from("Work tasks"));
+    projects.insert("LIFE".into(), Project::from("Personal life tasks"));
+    projects.insert("SIDE".into(), Project::from("Side projects"));
 
     let mut task_types = std::collections::HashMap::new();
     task_types.insert("FollowUp".into(), TaskTypeDefaults {
@@ -569,10 +2040,45 @@ fn create_default_config() -> Config {
         time_options: default_time_options(),
         statuses: default_statuses(),
         contexts: vec!["@work".into(), "@home".into(), "@phone".into(), "@errands".into()],
+        context_rules: Vec::new(),
         task_types,
         sync_throttle_minutes: 10,
+        sync_concurrency: 4,
+        legacy_paths: false,
+        offline: false,
+        encrypt_cache: false,
+        timezone: None,
+        week_starts_on: default_week_start(),
+        date_names: DateNames::default(),
+        theme: output::Theme::default(),
+        formats: std::collections::HashMap::new(),
+        views: std::collections::HashMap::new(),
+        triage_passes: default_triage_passes(),
+        calendar_ignore: Vec::new(),
+        block_calendar_id: None,
+        project_colors: std::collections::HashMap::new(),
+        work_hours: WorkHours::default(),
+        schedule_weights: ScheduleWeights::default(),
+        wip_limits: WipLimits::default(),
+        escalation: EscalationPolicy::default(),
+        auto_bump_overdue: false,
+        follow_up_prompt: false,
+        delegation_follow_up: delegation::FollowUpPolicy::default(),
+        meeting_prep: meeting_prep::MeetingPrep::default(),
+        gmail: gmail::GmailSource::default(),
+        obsidian: obsidian::ObsidianConfig::default(),
+        pinned_tags: default_pinned_tags(),
+        auto_dedupe: false,
+        backup: BackupPolicy::default(),
+        webhooks: Vec::new(),
+        notifiers: Vec::new(),
+        mqtt: mqtt::MqttConfig::default(),
+        privacy: privacy::PrivacyConfig::default(),
         airtable: None,
         clickup: None,
+        microsoft_todo: mstodo::MsTodoConfig::default(),
+        asana: asana::AsanaConfig::default(),
+        embeddings: None,
     }
 }
 
@@ -586,8 +2092,8 @@ fn load_config() -> Result<Config> {
         fs::create_dir_all(config_dir)?;
         let contents = serde_json::to_string_pretty(&config)?;
         fs::write(&config_path, &contents)?;
-        println!("{}", format!("Created default config at {}", config_path.display()).yellow());
-        println!("{}", "Please edit it to set your google_account and customize settings.\n".yellow());
+        eprintln!("{}", format!("Created default config at {}", config_path.display()).yellow());
+        eprintln!("{}", "Please edit it to set your google_account and customize settings.\n".yellow());
         return Ok(config);
     }
 
@@ -598,28 +2104,56 @@ fn load_config() -> Result<Config> {
     Ok(config)
 }
 
+/// Make `Local` the configured home time zone. Timestamps are stored in UTC and every
+/// date and time shown or compared goes through `Local`, so this is the one switch.
+fn apply_home_timezone(config: &Config) {
+    let Some(tz) = config.timezone.as_deref().map(str::trim).filter(|tz| !tz.is_empty()) else {
+        return;
+    };
+    let tzdir = std::env::var("TZDIR").unwrap_or_else(|_| "/usr/share/zoneinfo".to_string());
+    if cfg!(windows) || !std::path::Path::new(&tzdir).join(tz).is_file() {
+        eprintln!("{}", format!("⚠ Unknown timezone '{}', using the system time zone", tz).yellow());
+        return;
+    }
+    // Before any other thread starts
+    std::env::set_var("TZ", tz);
+}
+
+/// Local calendar day of a stored RFC3339 timestamp
+fn local_date(at: &str) -> Option<chrono::NaiveDate> {
+    chrono::DateTime::parse_from_rfc3339(at).ok().map(|at| at.with_timezone(&Local).date_naive())
+}
+
 fn read_single_key() -> Result<char> {
+    loop {
+        match read_key()? {
+            KeyCode::Char(c) => return Ok(c),
+            KeyCode::Enter => return Ok('\n'),
+            KeyCode::Esc => return Ok('\x1b'),
+            _ => continue,
+        }
+    }
+}
+
+/// Read one raw key press, including arrows and other non-character keys
+fn read_key() -> Result<KeyCode> {
     enable_raw_mode()?;
     let key = loop {
-        if let Event::Key(KeyEvent { code, .. }) = event::read()? {
-            match code {
-                KeyCode::Char(c) => break c,
-                KeyCode::Enter => break '\n',
-                KeyCode::Esc => break '\x1b',
-                _ => continue,
-            }
+        if let Event::Key(KeyEvent { code, kind: KeyEventKind::Press, .. }) = event::read()? {
+            break code;
         }
     };
     disable_raw_mode()?;
     Ok(key)
 }
 
-fn should_sync(config: &Config, force: bool) -> Result<bool> {
+fn should_sync(app: &App, force: bool) -> Result<bool> {
+    let config = &app.config;
     if force {
         return Ok(true);
     }
     
-    let sync_manager = SyncManager::new()?;
+    let sync_manager = &app.db;
     let last_query = sync_manager.get_state("last_query")?;
     
     if let Some(last) = last_query {
@@ -629,7 +2163,7 @@ fn should_sync(config: &Config, force: bool) -> Result<bool> {
             let elapsed_minutes = elapsed_duration.num_minutes();
             
             if elapsed_minutes < config.sync_throttle_minutes {
-                println!("{}", format!("⚡ Using cache (synced {} min ago)", elapsed_minutes).dimmed());
+                output::note(format!("⚡ Using cache (synced {} min ago)", elapsed_minutes).dimmed());
                 return Ok(false);
             }
         }
@@ -638,15 +2172,19 @@ fn should_sync(config: &Config, force: bool) -> Result<bool> {
     Ok(true)
 }
 
-fn update_last_query() -> Result<()> {
-    let sync_manager = SyncManager::new()?;
+fn update_last_query(app: &App) -> Result<()> {
+    let sync_manager = &app.db;
     sync_manager.set_state("last_query", &Utc::now().to_rfc3339())?;
     Ok(())
 }
 
-fn sync_with_google(account: &str, force: bool) -> Result<()> {
-    let sync_manager = SyncManager::new()?;
+/// Pull changes from Google Tasks into the cache. Returns the IDs of tasks it didn't have before.
+fn sync_with_google(app: &App, force: bool) -> Result<Vec<String>> {
+    flush_inbox(app)?;
+
+    let sync_manager = &app.db;
     let last_sync = sync_manager.get_last_sync()?;
+    tracing::info!(force, since = last_sync.as_deref().unwrap_or("never"), "sync started");
 
     // If force or no last sync, do full sync
     let updated_min = if force {
@@ -656,101 +2194,158 @@ fn sync_with_google(account: &str, force: bool) -> Result<()> {
     };
 
     if force {
-        println!("{}", "🔄 Force syncing all tasks...".cyan());
+        output::note("🔄 Force syncing all tasks...".cyan());
     } else if let Some(ref last) = last_sync {
-        println!("{}", format!("🔄 Syncing (updated since {})...", &last[..19]).dimmed());
+        output::note(format!("🔄 Syncing (updated since {})...", &last[..19]).dimmed());
     } else {
-        println!("{}", "🔄 First sync - fetching all tasks...".cyan());
+        output::note("🔄 First sync - fetching all tasks...".cyan());
     }
 
-    // Fetch lists
-    let lists_output = Command::new("gog")
-        .args(&["tasks", "lists", "list", "--account", account, "--json"])
-        .output()
-        .context("Failed to run gog command")?;
+    let list_ids = app.google.list_ids().context("Failed to fetch task lists")?;
+    let mut remote: Vec<(CachedTask, Option<String>)> = Vec::new();
 
-    if !lists_output.status.success() {
-        anyhow::bail!("gog command failed");
-    }
+    for list_id in &list_ids {
+        // Only tasks updated since the last sync, unless forced
+        let tasks = match app.google.tasks(list_id, updated_min.as_deref()) {
+            Ok(tasks) => tasks,
+            Err(e) => {
+                tracing::warn!(list_id, error = %e, "failed to get tasks");
+                continue;
+            }
+        };
+        for task in &tasks {
+            let task_id = task["id"].as_str().unwrap_or("");
+            let title = task["title"].as_str().unwrap_or("");
+            let status = task["status"].as_str().unwrap_or("needsAction");
+            let updated = task["updated"].as_str().unwrap_or("");
+            let created = task["created"].as_str().map(|s| s.to_string());
+            let links = task["links"].as_array().and_then(|l| serde_json::to_string(l).ok());
+            let notes = task["notes"].as_str().map(|s| s.to_string());
+            let due_date = due_date_from_title(title)
+                .or_else(|| task["due"].as_str().and_then(|d| d.get(..10)).map(|d| d.to_string()));
+
+            let cached = CachedTask {
+                id: task_id.to_string(),
+                unique_id: uuid::Uuid::new_v4().to_string(),
+                list_id: list_id.to_string(),
+                title: title.to_string(),
+                status: status.to_string(),
+                updated: updated.to_string(),
+                created,
+                links,
+                dirty: false, // From remote, not dirty
+                priority: None,
+                project: None,
+                context: None,
+                duration: None,
+                due_date,
+                start_date: None,
+                scheduled_date: None,
+                tags: None,
+                user_description: notes,
+                taskgarden_description: String::new(), // Will be regenerated
+            };
 
-    let lists_json: serde_json::Value = serde_json::from_slice(&lists_output.stdout)?;
-    let mut synced_count = 0;
+            remote.push((cached, task["parent"].as_str().map(|p| p.to_string())));
+        }
+    }
 
-    if let Some(tasklists) = lists_json["tasklists"].as_array() {
-        for list in tasklists {
-            let list_id = list["id"].as_str().unwrap_or("");
-            
-            // Build args with optional updatedMin filter
-            let mut args = vec!["tasks", "list", list_id, "--account", account, "--json"];
-            
-            // Add updatedMin filter if we have a last sync time
-            if let Some(ref min_time) = updated_min {
-                args.push("--updated-min");
-                args.push(min_time);
-            }
-
-            let tasks_output = Command::new("gog")
-                .args(&args)
-                .output()
-                .context("Failed to get tasks")?;
-
-            if tasks_output.status.success() {
-                let tasks_json: serde_json::Value = serde_json::from_slice(&tasks_output.stdout)?;
-                if let Some(tasks) = tasks_json["tasks"].as_array() {
-                    for task in tasks {
-                        let task_id = task["id"].as_str().unwrap_or("");
-                        let title = task["title"].as_str().unwrap_or("");
-                        let status = task["status"].as_str().unwrap_or("needsAction");
-                        let updated = task["updated"].as_str().unwrap_or("");
-                        let created = task["created"].as_str().map(|s| s.to_string());
-                        let links = task["links"].as_array().map(|l| serde_json::to_string(l).ok()).flatten();
-
-                        let cached = CachedTask {
-                            id: task_id.to_string(),
-                            unique_id: uuid::Uuid::new_v4().to_string(),
-                            list_id: list_id.to_string(),
-                            title: title.to_string(),
-                            status: status.to_string(),
-                            updated: updated.to_string(),
-                            created,
-                            links,
-                            dirty: false, // From remote, not dirty
-                            priority: None,
-                            project: None,
-                            context: None,
-                            duration: None,
-                            due_date: None,
-                            start_date: None,
-                            scheduled_date: None,
-                            tags: None,
-                            user_description: None,
-                            taskgarden_description: String::new(), // Will be regenerated
-                        };
-
-                        // Use upsert_task_from_remote to skip dirty tasks
-                        if sync_manager.upsert_task_from_remote(&cached)? {
-                            synced_count += 1;
-                        }
-                    }
-                }
-            }
+    let mut created = Vec::new();
+    for (task, _) in &remote {
+        if sync_manager.get_task_by_id(&task.id)?.is_none() {
+            created.push(task.id.clone());
         }
     }
+    // Write everything in one transaction; dirty local tasks are skipped
+    let synced_ids = sync_manager.upsert_tasks_batch(&remote)?;
 
     // Update last sync timestamp
     let now = Utc::now().to_rfc3339();
     sync_manager.set_last_sync(&now)?;
+    burndown::record(app, &now)?;
 
-    if synced_count == 0 {
-        println!("{}", "✓ No changes".dimmed());
+    tracing::info!(changed = synced_ids.len(), "sync finished");
+    if synced_ids.is_empty() {
+        output::note("✓ No changes".dimmed());
     } else {
-        println!("{}", format!("✓ Synced {} tasks", synced_count).green());
+        output::note(format!("✓ Synced {} tasks", synced_ids.len()).green());
     }
     
+    Ok(created)
+}
+
+/// Auto-merge tasks this sync created whose full title exactly matches another open
+/// task, and queue near-duplicates (same name, other date/priority/project) for `merge`
+fn dedupe_synced_tasks(app: &App, created_ids: &[String]) -> Result<()> {
+    if created_ids.is_empty() {
+        return Ok(());
+    }
+
+    let sync_manager = &app.db;
+    let tasks = get_tasks_from_cache(app)?;
+    let new_ids: std::collections::HashSet<&str> = created_ids.iter().map(|s| s.as_str()).collect();
+    let mut removed_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut merged_count = 0;
+    let mut queued_count = 0;
+
+    let is_open = |t: &Task| t.id.is_some() && !t.is_done();
+
+    for new_task in tasks.iter().filter(|t| is_open(t)) {
+        let new_id = new_task.id.as_deref().unwrap_or_default();
+        if !new_ids.contains(new_id) || removed_ids.contains(new_id) {
+            continue;
+        }
+
+        let normalized = similarity::normalize_title(&new_task.title);
+        if normalized.is_empty() {
+            continue;
+        }
+
+        for existing in tasks.iter().filter(|t| is_open(t)) {
+            let existing_id = existing.id.as_deref().unwrap_or_default();
+            if existing_id == new_id || removed_ids.contains(existing_id) {
+                continue;
+            }
+            if sync_manager.is_pair_dismissed(new_id, existing_id)? {
+                continue;
+            }
+
+            if existing.format(&app.config) == new_task.format(&app.config) {
+                // Exact duplicate: fold the new task into the existing one
+                if let Err(e) = delete_task_everywhere(app, new_task) {
+                    output::note(format!("  ⚠ Couldn't remove duplicate {}: {:#}", new_task.title, e).yellow());
+                    sync_manager.queue_merge_pair(new_id, existing_id)?;
+                    queued_count += 1;
+                    break;
+                }
+                let merged = merge_task_fields(&app.config, existing, new_task, existing.title.clone());
+                update_task_locally(app, &merged)?;
+                removed_ids.insert(new_id.to_string());
+                output::note(format!("  🔁 Merged duplicate: {}", new_task.title).dimmed());
+                merged_count += 1;
+                break;
+            } else if similarity::normalize_title(&existing.title) == normalized
+                || similarity::token_similarity(&new_task.title, &existing.title) >= 0.8
+            {
+                sync_manager.queue_merge_pair(new_id, existing_id)?;
+                queued_count += 1;
+            }
+        }
+    }
+
+    if merged_count > 0 {
+        push_dirty_tasks_to_google(app)?;
+        output::note(format!("✓ Auto-merged {} duplicate tasks", merged_count).green());
+    }
+    if queued_count > 0 {
+        output::note(format!("🔍 {} possible duplicates queued - run `merge` to review", queued_count).yellow());
+    }
+
     Ok(())
 }
 
-fn sync_to_airtable(config: &Config) -> Result<()> {
+async fn sync_to_airtable(app: &App, dry_run: bool, force: bool) -> Result<()> {
+    let config = &app.config;
     // Check if Airtable is enabled
     let airtable_config = match &config.airtable {
         Some(cfg) if cfg.enabled => cfg,
@@ -764,28 +2359,50 @@ fn sync_to_airtable(config: &Config) -> Result<()> {
         }
     };
 
-    println!("{}", "📤 Pushing tasks to Airtable...".cyan());
+    println!("{}", if dry_run { "🔎 Airtable (dry run)" } else { "📤 Pushing tasks to Airtable..." }.cyan());
 
     // Create Airtable client
     let client = AirtableClient::new(airtable::AirtableConfig {
         api_key: airtable_config.api_key.clone(),
         base_id: airtable_config.base_id.clone(),
         table_name: airtable_config.table_name.clone(),
+        typecast: airtable_config.create_options,
     })?;
 
+    // Check select values against the table's options, so a missing one doesn't fail each record
+    let (select_options, assignee_type) = match client.fetch_fields().await {
+        Ok(fields) => {
+            let assignee_type = fields.iter().find(|f| f.name == "Assignee").map(|f| f.field_type.clone());
+            (Some(SelectOptions::new(fields, airtable_config.option_map.clone(), airtable_config.create_options)), assignee_type)
+        }
+        Err(e) => {
+            println!("{}", format!("⚠️  Couldn't read the Airtable schema, sending select values as they are: {:#}", e).yellow());
+            (None, None)
+        }
+    };
+
     // Create sync manager
-    let sync_manager = SyncManager::new()?;
-    let airtable_sync = AirtableSync::new(sync_manager, client);
+    let sync_manager = &app.db;
+    let mut airtable_sync = AirtableSync::new(sync_manager, client, &app.config.statuses);
+    if let Some(select_options) = select_options {
+        airtable_sync = airtable_sync.with_select_options(select_options);
+    }
+    airtable_sync = airtable_sync
+        .with_excluded_projects(app.config.excluded_projects("airtable"))
+        .with_privacy(app.config.privacy.clone())
+        .with_assignees(airtable_config.assignees.clone(), airtable_config.default_assignee.clone(), assignee_type);
 
     // Push to Airtable
-    let stats = airtable_sync.push_to_airtable()?;
+    let stats = airtable_sync.push_to_airtable(dry_run, force, app.config.sync_concurrency).await?;
 
-    if stats.created > 0 || stats.updated > 0 {
+    if dry_run {
+        println!("{}", format!("Would create {}, update {} ({} unchanged)", stats.created, stats.updated, stats.unchanged).cyan());
+    } else if stats.created > 0 || stats.updated > 0 {
         println!(
             "{}",
             format!(
-                "✓ Created: {}, Updated: {}, Errors: {}",
-                stats.created, stats.updated, stats.errors
+                "✓ Created: {}, Updated: {}, Unchanged: {}, Errors: {}",
+                stats.created, stats.updated, stats.unchanged, stats.errors
             )
             .green()
         );
@@ -799,11 +2416,18 @@ fn sync_to_airtable(config: &Config) -> Result<()> {
             format!("⚠️  {} tasks failed to sync", stats.errors).yellow()
         );
     }
+    for (field, value) in &stats.unmapped {
+        println!(
+            "{}",
+            format!("⚠️  Airtable has no {} option \"{}\"; pushed without it (add it, or map it in airtable.option_map)", field, value).yellow()
+        );
+    }
 
     Ok(())
 }
 
-fn sync_to_clickup(config: &Config) -> Result<()> {
+async fn sync_to_clickup(app: &App, dry_run: bool, force: bool) -> Result<()> {
+    let config = &app.config;
     // Check if ClickUp is enabled
     let clickup_config = match &config.clickup {
         Some(cfg) if cfg.enabled => cfg,
@@ -817,7 +2441,7 @@ fn sync_to_clickup(config: &Config) -> Result<()> {
         }
     };
 
-    println!("{}", "📤 Pushing tasks to ClickUp...".cyan());
+    println!("{}", if dry_run { "🔎 ClickUp (dry run)" } else { "📤 Pushing tasks to ClickUp..." }.cyan());
 
     // Get list mappings (or use single list_id for all tasks)
     let list_mappings = if let Some(ref mappings) = clickup_config.list_mappings {
@@ -825,7 +2449,7 @@ fn sync_to_clickup(config: &Config) -> Result<()> {
     } else if let Some(ref list_id) = clickup_config.list_id {
         // Single list mode: all tasks go to one ClickUp list
         // We'll create a universal mapping by getting all Google list IDs from cache
-        let sync_manager = SyncManager::new()?;
+        let sync_manager = &app.db;
         let all_tasks = sync_manager.get_all_cached_tasks()?;
         let mut map = std::collections::HashMap::new();
         
@@ -848,18 +2472,30 @@ fn sync_to_clickup(config: &Config) -> Result<()> {
     })?;
 
     // Create sync manager
-    let sync_manager = SyncManager::new()?;
-    let clickup_sync = ClickUpSync::new(sync_manager, client, list_mappings);
+    let sync_manager = &app.db;
+    let project_lists = app.config.projects.iter()
+        .filter_map(|(name, project)| Some((name.clone(), project.clickup_list.clone()?)))
+        .collect();
+    let mut clickup_sync = ClickUpSync::new(sync_manager, client, list_mappings, project_lists, &app.config);
+    if clickup_config.mark_synced {
+        clickup_sync = clickup_sync.with_synced_marker(app.config.google_account.clone());
+    }
+    clickup_sync = clickup_sync
+        .with_excluded_projects(app.config.excluded_projects("clickup"))
+        .with_privacy(app.config.privacy.clone())
+        .with_assignees(clickup_config.assignees.clone(), clickup_config.default_assignee);
 
     // Push to ClickUp
-    let stats = clickup_sync.push_to_clickup()?;
+    let stats = clickup_sync.push_to_clickup(dry_run, force, app.config.sync_concurrency).await?;
 
-    if stats.created > 0 || stats.updated > 0 {
+    if dry_run {
+        println!("{}", format!("Would create {}, update {} ({} unchanged)", stats.created, stats.updated, stats.unchanged).cyan());
+    } else if stats.created > 0 || stats.updated > 0 {
         println!(
             "{}",
             format!(
-                "✓ Created: {}, Updated: {}, Errors: {}",
-                stats.created, stats.updated, stats.errors
+                "✓ Created: {}, Updated: {}, Unchanged: {}, Errors: {}",
+                stats.created, stats.updated, stats.unchanged, stats.errors
             )
             .green()
         );
@@ -877,9 +2513,66 @@ fn sync_to_clickup(config: &Config) -> Result<()> {
     Ok(())
 }
 
-fn get_tasks_from_cache() -> Result<Vec<Task>> {
-    let config = load_config()?;
-    let sync_manager = SyncManager::new()?;
+/// The due date a title carries explicitly (a date phrase or [YYYY-MM-DD] prefix), if any
+fn due_date_from_title(title: &str) -> Option<String> {
+    parse_date_from_text(title).or_else(|| {
+        TASK_REGEX.captures(title)
+            .map(|caps| caps[1].to_string())
+            .filter(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").is_ok())
+    })
+}
+
+/// Whether a task's start date is still in the future
+fn starts_later(task: &Task, today: &str) -> bool {
+    task.start.as_deref().is_some_and(|start| start > today)
+}
+
+/// Whether a task belongs in a context: it's tagged with it, or has no context at all
+fn in_context(task: &Task, context: &str) -> bool {
+    let wanted = context.trim_start_matches('@');
+    task.context.as_deref().is_none_or(|c| c.trim_start_matches('@').eq_ignore_ascii_case(wanted))
+}
+
+/// The context set with `context set`, else the context rule for right now, unless
+/// the command asked for all contexts
+fn current_context(app: &App, all_contexts: bool) -> Result<Option<String>> {
+    if all_contexts {
+        return Ok(None);
+    }
+    match app.db.get_state("current_context")? {
+        Some(context) => Ok(Some(context)),
+        None => Ok(active_context_rule(app)?.map(ContextRule::context)),
+    }
+}
+
+/// The context rule in effect: one covers right now and `context set` hasn't overridden it
+fn active_context_rule(app: &App) -> Result<Option<&ContextRule>> {
+    if app.db.get_state("current_context")?.is_some() {
+        return Ok(None);
+    }
+    Ok(app.config.context_rule_at(Local::now().naive_local()))
+}
+
+/// "📍 @work (rule: weekdays 09:00-17:00)" when a rule picked the context
+fn context_indicator(app: &App, context: &str) -> Result<String> {
+    Ok(match active_context_rule(app)? {
+        Some(rule) => format!("📍 {} (rule: {})", context, rule.describe()),
+        None => format!("📍 {}", context),
+    })
+}
+
+/// Parse a start date given as YYYY-MM-DD or a phrase like "friday" / "in 2 weeks"
+fn parse_start_date(text: &str) -> Result<String> {
+    let text = text.trim();
+    if chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d").is_ok() {
+        return Ok(text.to_string());
+    }
+    parse_date_from_text(text).with_context(|| format!("Couldn't understand start date '{}'", text))
+}
+
+fn get_tasks_from_cache(app: &App) -> Result<Vec<Task>> {
+    let config = &app.config;
+    let sync_manager = &app.db;
     let cached_tasks = sync_manager.get_all_cached_tasks()?;
 
     let mut tasks = Vec::new();
@@ -887,18 +2580,26 @@ fn get_tasks_from_cache() -> Result<Vec<Task>> {
         // Map list_id to list title (simplified for now)
         let list_title = "Tasks"; // TODO: Cache list names too
 
-        let mut task = Task::parse_with_config(&cached.title, list_title, Some(&config));
+        let mut task = Task::parse_with_config(&cached.title, list_title, Some(config));
 
         // Add ID and list_id from cache
         task.id = Some(cached.id);
         task.list_id = Some(cached.list_id);
 
-        // Use Google's creation date as the task date (override parsed date)
-        if let Some(ref created) = cached.created {
-            // Parse RFC3339 timestamp and extract just the date part
-            if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(created) {
-                task.date = dt.format("%Y-%m-%d").to_string();
-            }
+        // Keep Google's creation date separate (RFC3339 → the local day)
+        task.created = cached.created.as_deref()
+            .and_then(local_date)
+            .map(|day| day.format("%Y-%m-%d").to_string());
+
+        task.start = cached.start_date.clone();
+        task.notes = cached.user_description.clone();
+        task.completed |= cached.status == "completed";
+
+        // The due date wins; undated tasks stay on the day they were created
+        if let Some(due) = cached.due_date.clone().or_else(|| due_date_from_title(&cached.title)) {
+            task.date = due;
+        } else if let Some(ref created) = task.created {
+            task.date = created.clone();
         }
 
         // Detect attachment type from cached links
@@ -918,6 +2619,9 @@ fn get_tasks_from_cache() -> Result<Vec<Task>> {
                 }
             }
         }
+        if task.attachment_type.is_none() && task.notes.as_deref().and_then(gmail::email_link).is_some() {
+            task.attachment_type = Some("email".to_string());
+        }
 
         tasks.push(task);
     }
@@ -925,76 +2629,48 @@ fn get_tasks_from_cache() -> Result<Vec<Task>> {
     Ok(tasks)
 }
 
-fn update_task_in_google(account: &str, task: &Task) -> Result<()> {
-    if task.id.is_none() || task.list_id.is_none() {
-        anyhow::bail!("Task missing ID or list_id");
-    }
+/// Update task in local cache only (marks as dirty for later push)
+fn update_task_locally(app: &App, task: &Task) -> Result<()> {
+    let sync_manager = &app.db;
+    let cached = local_cache_row(sync_manager, &app.config, task)?;
+    sync_manager.upsert_task_locally(&cached)?;
 
-    let formatted_title = task.format(&load_config()?);
-    let task_id = task.id.as_ref().unwrap();
-    let list_id = task.list_id.as_ref().unwrap();
+    Ok(())
+}
 
-    // Update via gog CLI
-    let output = Command::new("gog")
-        .args(&[
-            "tasks", "update",
-            list_id,
-            task_id,
-            "--title", &formatted_title,
-            "--account", account,
-        ])
-        .output()
-        .context("Failed to update task in Google Tasks")?;
+/// Count a manual bump against each task so chronic procrastination shows up in summary
+fn record_bumps(sync_manager: &SyncManager, tasks: &[Task]) -> Result<()> {
+    let ids: Vec<&str> = tasks.iter().filter_map(|t| t.id.as_deref()).collect();
+    sync_manager.increment_bump_counts(&ids)
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to update task: {}", stderr);
+/// Move tasks to `target_date` in the local cache in one go, counting the bump against each
+fn bump_tasks(app: &App, tasks: &mut [Task], target_date: &str) -> Result<()> {
+    for task in tasks.iter_mut() {
+        task.date = target_date.to_string();
     }
+    save_tasks_locally(&app.db, &app.config, tasks)?;
+    record_bumps(&app.db, tasks)
+}
 
-    // Update cache - preserve existing links and created date from cache
-    let sync_manager = SyncManager::new()?;
-    let existing = sync_manager.get_task_by_id(task_id)?;
-    let existing_links = existing.as_ref().and_then(|t| t.links.clone());
-    let existing_created = existing.as_ref().and_then(|t| t.created.clone());
-
-    let cached = CachedTask {
-        id: task_id.clone(),
-        unique_id: existing.as_ref().map(|t| t.unique_id.clone()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
-        list_id: list_id.clone(),
-        title: formatted_title,
-        status: "needsAction".to_string(),
-        updated: Utc::now().to_rfc3339(),
-        created: existing_created,
-        links: existing_links,
-        dirty: false, // Just pushed to Google, so not dirty
-        priority: existing.as_ref().and_then(|t| t.priority.clone()),
-        project: existing.as_ref().and_then(|t| t.project.clone()),
-        context: existing.as_ref().and_then(|t| t.context.clone()),
-        duration: existing.as_ref().and_then(|t| t.duration.clone()),
-        due_date: existing.as_ref().and_then(|t| t.due_date.clone()),
-        start_date: existing.as_ref().and_then(|t| t.start_date.clone()),
-        scheduled_date: existing.as_ref().and_then(|t| t.scheduled_date.clone()),
-        tags: existing.as_ref().and_then(|t| t.tags.clone()),
-        user_description: existing.as_ref().and_then(|t| t.user_description.clone()),
-        taskgarden_description: String::new(), // Will be regenerated
-    };
-    sync_manager.upsert_task(&cached)?;
-
-    Ok(())
+/// Update several tasks in the local cache in one transaction (all marked dirty)
+fn save_tasks_locally(sync_manager: &SyncManager, config: &Config, tasks: &[Task]) -> Result<()> {
+    let rows = tasks.iter()
+        .map(|t| local_cache_row(sync_manager, config, t))
+        .collect::<Result<Vec<_>>>()?;
+    sync_manager.upsert_tasks_locally(&rows)
 }
 
-/// Update task in local cache only (marks as dirty for later push)
-fn update_task_locally(task: &Task) -> Result<()> {
+/// Build the dirty cache row for a locally edited task, preserving cached metadata
+fn local_cache_row(sync_manager: &SyncManager, config: &Config, task: &Task) -> Result<CachedTask> {
     if task.id.is_none() || task.list_id.is_none() {
         anyhow::bail!("Task missing ID or list_id");
     }
 
-    let formatted_title = task.format(&load_config()?);
+    let formatted_title = task.format(config);
     let task_id = task.id.as_ref().unwrap();
     let list_id = task.list_id.as_ref().unwrap();
 
-    let sync_manager = SyncManager::new()?;
-
     // Preserve existing properties from cache
     let existing = sync_manager.get_task_by_id(task_id)?;
     let existing_links = existing.as_ref().and_then(|t| t.links.clone());
@@ -1005,7 +2681,7 @@ fn update_task_locally(task: &Task) -> Result<()> {
         unique_id: existing.as_ref().map(|t| t.unique_id.clone()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
         list_id: list_id.clone(),
         title: formatted_title,
-        status: "needsAction".to_string(),
+        status: if task.is_done() { "completed" } else { "needsAction" }.to_string(),
         updated: Utc::now().to_rfc3339(),
         created: existing_created,
         links: existing_links,
@@ -1014,65 +2690,116 @@ fn update_task_locally(task: &Task) -> Result<()> {
         project: existing.as_ref().and_then(|t| t.project.clone()),
         context: existing.as_ref().and_then(|t| t.context.clone()),
         duration: existing.as_ref().and_then(|t| t.duration.clone()),
-        due_date: existing.as_ref().and_then(|t| t.due_date.clone()),
-        start_date: existing.as_ref().and_then(|t| t.start_date.clone()),
+        due_date: Some(task.date.clone()),
+        start_date: task.start.clone(),
         scheduled_date: existing.as_ref().and_then(|t| t.scheduled_date.clone()),
         tags: existing.as_ref().and_then(|t| t.tags.clone()),
-        user_description: existing.as_ref().and_then(|t| t.user_description.clone()),
+        user_description: task.notes.clone(),
         taskgarden_description: String::new(), // Will be regenerated
     };
-    sync_manager.upsert_task_locally(&cached)?;
+
+    Ok(cached)
+}
+
+/// What the next sync would send to Google: captured inbox items and locally edited tasks
+fn preview_google_push(app: &App) -> Result<()> {
+    let sync_manager = &app.db;
+    let inbox = sync_manager.get_unpushed_inbox()?;
+    let dirty = sync_manager.get_dirty_tasks()?;
+
+    println!("{}", "🔎 Google Tasks (dry run, nothing pulled)".cyan());
+    if inbox.is_empty() && dirty.is_empty() {
+        println!("{}", "  Nothing to push".dimmed());
+    }
+    for (_, title) in &inbox {
+        println!("  {} create   {}", "+".green(), title);
+    }
+    for task in &dirty {
+        let verb = if task.status == "completed" { "complete" } else { "update  " };
+        println!("  {} {} {} {}", "~".cyan(), verb, task.id.chars().take(8).collect::<String>().dimmed(), task.title);
+    }
+    Ok(())
+}
+
+/// Run a future to completion on a single-threaded runtime, so it can borrow the App.
+/// Never call this from inside another future.
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to start the tokio runtime")
+        .block_on(future)
+}
+
+/// Push all dirty tasks to Google
+fn push_dirty_tasks_to_google(app: &App) -> Result<()> {
+    block_on(push_dirty_tasks_to_google_async(app))
+}
+
+async fn push_dirty_tasks_to_google_async(app: &App) -> Result<()> {
+    if sync::is_offline() {
+        let queued = app.db.get_dirty_tasks()?.len();
+        if queued > 0 {
+            output::note(format!("📴 Offline: {} changes queued for the next sync", queued).dimmed());
+        }
+        return Ok(());
+    }
+
+    let (success_count, fail_count) = push_dirty_tasks_async(app).await?;
+
+    if success_count > 0 {
+        output::note(format!("✓ Pushed {} tasks to Google", success_count).green());
+    }
+    if fail_count > 0 {
+        println!("{}", format!("⚠ {} tasks failed to push (will retry next time)", fail_count).yellow());
+    }
 
     Ok(())
 }
 
-/// Push all dirty tasks to Google
-fn push_dirty_tasks_to_google(account: &str) -> Result<()> {
-    let sync_manager = SyncManager::new()?;
+/// Push all dirty tasks to Google without printing a summary.
+/// Returns (pushed, failed) counts; per-task failures go to stderr.
+/// Offline, nothing is pushed and the tasks stay dirty.
+fn push_dirty_tasks(app: &App) -> Result<(usize, usize)> {
+    block_on(push_dirty_tasks_async(app))
+}
+
+/// `push_dirty_tasks`, `sync_concurrency` tasks at a time
+async fn push_dirty_tasks_async(app: &App) -> Result<(usize, usize)> {
+    if sync::is_offline() {
+        return Ok((0, 0));
+    }
+
+    let sync_manager = &app.db;
     let dirty_tasks = sync_manager.get_dirty_tasks()?;
 
     if dirty_tasks.is_empty() {
-        return Ok(());
+        return Ok((0, 0));
     }
 
     let mut success_count = 0;
     let mut fail_count = 0;
 
-    for task in &dirty_tasks {
-        // Check if task is marked DONE - need to complete it
-        let is_done = task.title.contains("[DONE]");
-
-        // Build args
-        let mut args = vec![
-            "tasks".to_string(), "update".to_string(),
-            task.list_id.clone(),
-            task.id.clone(),
-            "--title".to_string(), task.title.clone(),
-            "--account".to_string(), account.to_string(),
-        ];
-
-        // If marked DONE, also set status to completed
-        if is_done {
-            args.push("--status".to_string());
-            args.push("completed".to_string());
-        }
-
-        // Update via gog CLI
-        let output = Command::new("gog")
-            .args(&args)
-            .output();
+    // Completion travels as Google's status, not in the title.
+    // Notes only go out once they exist locally, so a stale cache can't wipe them.
+    let updates: Vec<provider::Update> = dirty_tasks.iter()
+        .map(|task| provider::Update {
+            list_id: task.list_id.clone(),
+            task_id: task.id.clone(),
+            title: task.title.clone(),
+            notes: task.user_description.clone(),
+            completed: task.status == "completed",
+        })
+        .collect();
+    let results = app.google.update_all(&updates, app.config.sync_concurrency).await;
 
-        match output {
-            Ok(o) if o.status.success() => {
+    for (task, pushed) in dirty_tasks.iter().zip(results) {
+        match pushed {
+            Ok(()) => {
                 // Mark as clean after successful push
                 sync_manager.mark_task_clean(&task.id)?;
                 success_count += 1;
             }
-            Ok(o) => {
-                let stderr = String::from_utf8_lossy(&o.stderr);
-                eprintln!("{}", format!("  ⚠ Failed to push task {}: {}", &task.id[..8], stderr).yellow());
-                fail_count += 1;
-            }
             Err(e) => {
                 eprintln!("{}", format!("  ⚠ Failed to push task {}: {}", &task.id[..8], e).yellow());
                 fail_count += 1;
@@ -1080,743 +2807,768 @@ fn push_dirty_tasks_to_google(account: &str) -> Result<()> {
         }
     }
 
-    if success_count > 0 {
-        println!("{}", format!("✓ Pushed {} tasks to Google", success_count).green());
-    }
-    if fail_count > 0 {
-        println!("{}", format!("⚠ {} tasks failed to push (will retry next time)", fail_count).yellow());
-    }
-
-    Ok(())
+    tracing::info!(pushed = success_count, failed = fail_count, "pushed dirty tasks");
+    Ok((success_count, fail_count))
 }
 
-fn get_tasks_from_google(account: &str) -> Result<Vec<Task>> {
-    let output = Command::new("gog")
-        .args(&["tasks", "lists", "list", "--account", account, "--json"])
-        .output()
-        .context("Failed to run gog command")?;
-
-    if !output.status.success() {
-        anyhow::bail!("gog command failed");
-    }
-
-    let lists_json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
-    let mut all_tasks = Vec::new();
-
-    if let Some(tasklists) = lists_json["tasklists"].as_array() {
-        for list in tasklists {
-            let list_id = list["id"].as_str().unwrap_or("");
-            let list_title = list["title"].as_str().unwrap_or("");
-
-            let tasks_output = Command::new("gog")
-                .args(&["tasks", "list", list_id, "--account", account, "--json"])
-                .output()
-                .context("Failed to get tasks")?;
-
-            if tasks_output.status.success() {
-                let tasks_json: serde_json::Value = serde_json::from_slice(&tasks_output.stdout)?;
-                if let Some(tasks) = tasks_json["tasks"].as_array() {
-                    for task in tasks {
-                        if let Some(title) = task["title"].as_str() {
-                            let mut parsed_task = Task::parse(title, list_title);
-                            
-                            // Check for attachments/links
-                            if let Some(links) = task["links"].as_array() {
-                                if let Some(first_link) = links.first() {
-                                    let link_type = first_link["type"].as_str().unwrap_or("");
-                                    let link_url = first_link["link"].as_str().unwrap_or("");
-                                    
-                                    if link_type == "email" || link_url.contains("mail.google.com") {
-                                        parsed_task.attachment_type = Some("email".to_string());
-                                    } else if link_url.contains("docs.google.com") {
-                                        parsed_task.attachment_type = Some("doc".to_string());
-                                    } else if link_url.contains("drive.google.com") {
-                                        parsed_task.attachment_type = Some("drive".to_string());
-                                    }
-                                }
-                            }
-                            
-                            all_tasks.push(parsed_task);
-                        }
-                    }
-                }
-            }
-        }
+/// Create a task in Google Tasks and cache it locally. Returns the new task's ID.
+fn create_task_in_google(app: &App, list_id: &str, title: &str, parent: Option<&str>) -> Result<String> {
+    if sync::is_offline() {
+        anyhow::bail!("Offline: can't create tasks in Google Tasks (`in` captures them for later)");
     }
+    let task_json = app.google.create(list_id, title, parent).context("Failed to create task")?;
+    let task_id = task_json["id"].as_str()
+        .context("Google did not return a task ID")?
+        .to_string();
+
+    let now = Utc::now().to_rfc3339();
+    let cached = CachedTask {
+        id: task_id.clone(),
+        unique_id: uuid::Uuid::new_v4().to_string(),
+        list_id: list_id.to_string(),
+        title: title.to_string(),
+        status: "needsAction".to_string(),
+        updated: now.clone(),
+        created: Some(task_json["created"].as_str().map(|s| s.to_string()).unwrap_or(now)),
+        links: None,
+        dirty: false,
+        priority: None,
+        project: None,
+        context: None,
+        duration: None,
+        due_date: due_date_from_title(title),
+        start_date: None,
+        scheduled_date: None,
+        tags: None,
+        user_description: None,
+        taskgarden_description: String::new(), // Will be regenerated
+    };
+    let sync_manager = &app.db;
+    sync_manager.upsert_task(&cached)?;
+    sync_manager.set_task_parent(&task_id, parent)?;
 
-    Ok(all_tasks)
+    Ok(task_id)
 }
 
-fn cmd_triage(config: &Config, force: bool, priority_only: bool, project_only: bool, time_only: bool, status_only: bool, context_only: bool) -> Result<()> {
-    println!("{}", "🌱 The Garden - Interactive Triage\n".green().bold());
+/// One property-setting pass of `triage`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TriagePass {
+    Priority,
+    Project,
+    Time,
+    Status,
+    Context,
+    Person,
+}
 
-    if force {
-        println!("{}", "🔄 Force mode - re-triaging all tasks\n".yellow());
-    }
-    if priority_only {
-        println!("{}", "📊 Priority-only mode\n".cyan());
-    }
-    if project_only {
-        println!("{}", "🏷️  Project-only mode\n".cyan());
-    }
-    if time_only {
-        println!("{}", "⏱️  Time-only mode\n".cyan());
-    }
-    if status_only {
-        println!("{}", "📋 Status-only mode\n".cyan());
-    }
-    if context_only {
-        println!("{}", "📍 Context-only mode\n".cyan());
+impl TriagePass {
+    const NAMES: &'static str = "priority, project, time, status, context, person";
+
+    fn parse(name: &str) -> Result<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "priority" | "p" => Ok(TriagePass::Priority),
+            "project" | "j" => Ok(TriagePass::Project),
+            "time" | "t" => Ok(TriagePass::Time),
+            "status" | "s" => Ok(TriagePass::Status),
+            "context" | "c" => Ok(TriagePass::Context),
+            "person" | "w" => Ok(TriagePass::Person),
+            other => anyhow::bail!("Unknown triage pass '{}' (use {})", other, Self::NAMES),
+        }
     }
 
-    let tasks = get_tasks_from_cache()?;
-    let mut to_triage: Vec<Task> = tasks.into_iter()
-        .filter(|t| t.needs_triage(force, priority_only, project_only, time_only, status_only, context_only))
-        .collect();
-
-    if to_triage.is_empty() {
-        println!("{}", "✅ All tasks are triaged!".green());
-        return Ok(());
+    fn label(&self) -> &'static str {
+        match self {
+            TriagePass::Priority => "Priorities",
+            TriagePass::Project => "Projects",
+            TriagePass::Time => "Time Estimates",
+            TriagePass::Status => "Status",
+            TriagePass::Context => "Context",
+            TriagePass::Person => "Person",
+        }
     }
 
-    let total = to_triage.len();
-    println!("Found {} tasks needing triage\n", total.to_string().yellow().bold());
-
-    // ═══ PASS 1: PRIORITIES ═══
-    let should_do_priority = !project_only && !time_only && !status_only && !context_only;
-    
-    if should_do_priority {
-        println!("\n{}", "═══ Pass 1: Priorities ═══\n".cyan().bold());
-        
-        // Undo history: stores (index, old_task)
-        let mut undo_history: Vec<(usize, Task)> = Vec::new();
-        let mut i = 0;
-
-        while i < to_triage.len() {
-            // Skip tasks that already have priority (unless force mode)
-            if !force && to_triage[i].priority.is_some() {
-                i += 1;
-                continue;
-            }
-
-            // Display current task
-            {
-                let task = &to_triage[i];
-
-                println!("\n{}", format!("Task {}/{}", i + 1, total).cyan());
-                println!("{}", format!("ID: {}", task.short_id()).dimmed());
-
-                // Display title with attachment indicator
-                let display_title = if let Some(ref att_type) = task.attachment_type {
-                    format!("{} ({})", task.title, att_type).yellow()
-                } else {
-                    task.title.yellow()
-                };
-                println!("{}", display_title);
-                println!("{}", format!("(from: {})", task.list).dimmed());
-
-                // Show options
-                println!("\n{}", "Priority:".dimmed());
-                for p in &config.priorities {
-                    let key_str = if let Some(alt) = p.alt_key {
-                        format!("{}/{}", p.key, alt)
-                    } else {
-                        p.key.to_string()
-                    };
-                    println!("  {} {} - {}", format!("{}.", key_str).cyan(), p.name, p.description);
-                }
-                println!("  {} Done (mark complete)", "x.".cyan());
-                println!("  {} Skip", "s.".dimmed());
-                if !undo_history.is_empty() {
-                    println!("  {} Undo", "u.".yellow());
-                }
-                println!("  {} Quit (save progress)", "q.".red());
-            }
+    /// Priority, project and time make a task "triaged"; status, context and person are optional extras
+    fn is_core(&self) -> bool {
+        matches!(self, TriagePass::Priority | TriagePass::Project | TriagePass::Time)
+    }
 
-            print!("\n> ");
-            stdout().flush()?;
+    /// Whether this pass has anything to ask about `task`.
+    /// `only` means this pass was requested on its own, which re-asks the optional extras.
+    fn needs(&self, task: &Task, force: bool, only: bool) -> bool {
+        if force {
+            return true;
+        }
+        match self {
+            TriagePass::Priority => task.priority.as_ref().map(|p| p == "--").unwrap_or(true),
+            TriagePass::Project => task.project.as_ref().map(|p| p == "---").unwrap_or(true),
+            TriagePass::Time => task.time.is_none(),
+            TriagePass::Status => only || task.status.is_none(),
+            TriagePass::Context => only || task.context.is_none(),
+            TriagePass::Person => only || task.person().is_none(),
+        }
+    }
 
-            let choice = read_single_key()?;
-            println!("{}", choice); // Echo the keystroke
+    /// Status and context passes only run when the config defines options for them
+    fn has_options(&self, config: &Config) -> bool {
+        match self {
+            TriagePass::Status => !config.statuses.is_empty(),
+            TriagePass::Context => !config.contexts.is_empty(),
+            _ => true,
+        }
+    }
+}
 
-            let mut go_back = false;
+/// What the user chose for one task in one pass
+enum TriageChoice {
+    Set(Box<Task>),
+    /// New date set; the task sits out the remaining passes
+    Defer(Box<Task>),
+    /// Replaced by smaller tasks; the original sits out the remaining passes
+    Split(Box<Task>),
+    Skip,
+    Undo,
+    Quit,
+    Invalid,
+}
 
-            // Check if choice matches any priority key
-            let matched_priority = config.priorities.iter().find(|p| {
-                let c_lower = choice.to_ascii_lowercase();
-                p.key.to_ascii_lowercase() == c_lower ||
-                p.alt_key.map(|a| a.to_ascii_lowercase() == c_lower).unwrap_or(false)
-            });
+/// Alternate home-row keys for numbered project/context menus
+const TRIAGE_ALT_KEYS: [char; 8] = ['j', 'k', 'l', ';', 'a', 'f', 'g', 'h'];
 
-            if let Some(priority) = matched_priority {
-                let old_task = to_triage[i].clone();
-                to_triage[i].priority = Some(priority.name.clone());
-                println!("{}", format!("  ✓ {}", priority.name).green());
-                undo_history.push((i, old_task));
+fn print_triage_task(task: &Task, n: usize, total: usize) {
+    println!("\n{}", format!("Task {}/{}", n, total).cyan());
+    println!("{}", format!("ID: {}", task.short_id()).dimmed());
 
-                print!("{}", "  💾 Saving...".dimmed());
-                if let Err(e) = update_task_locally(&to_triage[i]) {
-                    println!(" {}", format!("❌ Failed: {}", e).red());
-                } else {
-                    println!(" {}", "✓".green());
-                }
-            } else if choice == 'x' || choice == 'X' {
-                let old_task = to_triage[i].clone();
-                to_triage[i].priority = Some("DONE".to_string());
-                println!("{}", "  ✓ Marked as Done".green());
-                undo_history.push((i, old_task));
-
-                print!("{}", "  💾 Saving...".dimmed());
-                if let Err(e) = update_task_locally(&to_triage[i]) {
-                    println!(" {}", format!("❌ Failed: {}", e).red());
-                } else {
-                    println!(" {}", "✓".green());
-                }
-            } else if choice == 's' || choice == 'S' || choice == ' ' {
-                println!("{}", "  → Skipped".dimmed());
-            } else if choice == 'u' || choice == 'U' {
-                if let Some((prev_idx, prev_task)) = undo_history.pop() {
-                    println!("{}", "  ↶ Undoing...".yellow());
+    // Display title with attachment indicator
+    let display_title = if let Some(ref att_type) = task.attachment_type {
+        format!("{} ({})", task.title, att_type).yellow()
+    } else {
+        task.title.yellow()
+    };
+    println!("{}", display_title);
 
-                    // Restore previous task
-                    to_triage[prev_idx] = prev_task.clone();
+    if task.priority.is_none() && task.project.is_none() && task.time.is_none() {
+        println!("{}", format!("(from: {})", task.list).dimmed());
+    } else {
+        println!("{}", format!("[{}][{}]{}{}",
+            task.priority.as_deref().unwrap_or("--"),
+            task.project.as_deref().unwrap_or("---"),
+            task.status.as_ref().map(|s| format!("[{}]", s)).unwrap_or_default(),
+            task.time.as_ref().map(|t| format!("{{{}}}", t)).unwrap_or_default()
+        ).green());
+    }
+}
 
-                    // Save restored state locally
-                    print!("{}", "  💾 Restoring...".dimmed());
-                    if let Err(e) = update_task_locally(&prev_task) {
-                        println!(" {}", format!("❌ Failed: {}", e).red());
-                    } else {
-                        println!(" {}", "✓".green());
-                    }
+/// Show the options for one pass, read a key, and apply it to a copy of the task
+fn prompt_triage_pass(app: &App, pass: TriagePass, task: &Task, can_undo: bool) -> Result<TriageChoice> {
+    let config = &app.config;
+    let project_keys: Vec<String> = config.projects.keys().cloned().collect();
+    let people = if pass == TriagePass::Person { known_people(&get_tasks_from_cache(app)?) } else { Vec::new() };
+    // Enter takes a suggestion: the project's default priority or context, or an
+    // estimate from similar finished tasks
+    let project_name = task.project.as_deref().unwrap_or_default();
+    let suggestion = match pass {
+        TriagePass::Priority => config.project(task.project.as_deref())
+            .and_then(|p| p.priority.clone())
+            .map(|p| (p, format!("{} default", project_name))),
+        TriagePass::Context => config.project(task.project.as_deref())
+            .and_then(|p| p.context.clone())
+            .map(|c| (c, format!("{} default", project_name))),
+        TriagePass::Time => suggest_estimate(config, task, &get_tasks_from_cache(app)?)
+            .map(|(label, n)| (label, format!("median of {} similar done task{}", n, if n == 1 { "" } else { "s" }))),
+        _ => None,
+    };
+    let numbered = |items: &[String]| {
+        for (idx, item) in items.iter().enumerate() {
+            let alt = TRIAGE_ALT_KEYS.get(idx).map(|c| format!("/{}", c)).unwrap_or_default();
+            println!("  {} {}", format!("{}{}.", idx, alt).cyan(), item);
+        }
+    };
+    let key_label = |key: char, alt: Option<char>| match alt {
+        Some(alt) => format!("{}/{}.", key, alt),
+        None => format!("{}.", key),
+    };
 
-                    // Go back to that task
-                    i = prev_idx;
-                    go_back = true;
-                } else {
-                    println!("{}", "  ⚠ Nothing to undo".yellow());
-                    continue; // Stay on current task
-                }
-            } else if choice == 'q' || choice == 'Q' || choice == '\x1b' {
-                println!("\n{}", "Saving and exiting...".yellow());
-                // Push dirty tasks before exiting
-                println!("{}", "Pushing changes to Google...".dimmed());
-                push_dirty_tasks_to_google(&config.google_account)?;
-                return Ok(());
-            } else {
-                println!("{}", "  ⚠ Invalid input, skipping".yellow());
+    match pass {
+        TriagePass::Priority => {
+            println!("\n{}", "Priority:".dimmed());
+            for p in &config.priorities {
+                println!("  {} {} - {}", key_label(p.key, p.alt_key).cyan(), p.name, p.description);
             }
-
-            if !go_back {
-                i += 1;
+            println!("  {} Done (mark complete)", "x.".cyan());
+            println!("  {} Defer (not now - ask when)", "w.".cyan());
+            println!("  {} Start later (hide until a date)", ">.".cyan());
+            println!("  {} Split into smaller tasks", "!.".cyan());
+        }
+        TriagePass::Project => {
+            println!("\n{}", "Project:".dimmed());
+            numbered(&project_keys);
+        }
+        TriagePass::Time => {
+            println!("\n{}", "Time estimate:".dimmed());
+            for t in &config.time_options {
+                println!("  {} {}", key_label(t.key, t.alt_key).cyan(), t.label);
+            }
+        }
+        TriagePass::Status => {
+            println!("\n{}", "Status:".dimmed());
+            for s in config.next_statuses(task.status.as_deref()) {
+                println!("  {} {} - {}", key_label(s.key, s.alt_key).cyan(), s.name, s.description);
             }
         }
+        TriagePass::Context => {
+            println!("\n{}", "Context:".dimmed());
+            numbered(&config.contexts);
+        }
+        TriagePass::Person => {
+            println!("\n{}", "Person:".dimmed());
+            numbered(&people);
+            println!("  {} Someone else (type a name)", "+.".cyan());
+        }
     }
+    if let Some((ref value, ref why)) = suggestion {
+        println!("  {} {} ({})", "↵.".cyan(), value, why);
+    }
+    println!("  {} Skip", "s.".dimmed());
+    if can_undo {
+        println!("  {} Undo", "u.".yellow());
+    }
+    println!("  {} Quit (save progress)", "q.".red());
 
-    // Filter for pass 2: only tasks with priorities (exclude DONE and skipped)
-    let mut for_project_pass: Vec<Task> = to_triage.into_iter()
-        .filter(|t| t.priority.is_some() && t.priority.as_ref().unwrap() != "DONE")
-        .collect();
-
-    let should_do_project = !priority_only && !time_only && !status_only && !context_only;
-    
-    if should_do_project && !for_project_pass.is_empty() {
-        let project_count = for_project_pass.len();
-        
-        // ═══ PASS 2: PROJECTS ═══
-        println!("\n{}", "═══ Pass 2: Projects ═══\n".cyan().bold());
-        let project_keys: Vec<String> = config.projects.keys().cloned().collect();
-        
-        let mut undo_history: Vec<(usize, Task)> = Vec::new();
-        let mut i = 0;
-
-        while i < for_project_pass.len() {
-            // Skip tasks that already have a real project (not "---")
-            if !force {
-                if let Some(ref proj) = for_project_pass[i].project {
-                    if proj != "---" {
-                        i += 1;
-                        continue;
-                    }
-                }
-            }
+    print!("\n> ");
+    stdout().flush()?;
 
-            // Display current task
-            {
-                let task = &for_project_pass[i];
+    let choice = read_single_key()?;
+    println!("{}", choice); // Echo the keystroke
 
-                println!("\n{}", format!("Task {}/{}", i + 1, project_count).cyan());
-                println!("{}", format!("ID: {}", task.short_id()).dimmed());
+    match choice {
+        'u' | 'U' => return Ok(TriageChoice::Undo),
+        's' | 'S' | ' ' => return Ok(TriageChoice::Skip),
+        'q' | 'Q' | '\x1b' => return Ok(TriageChoice::Quit),
+        _ => {}
+    }
 
-                // Display title with attachment indicator
-                let display_title = if let Some(ref att_type) = task.attachment_type {
-                    format!("{} ({})", task.title, att_type).yellow()
-                } else {
-                    task.title.yellow()
-                };
-                println!("{}", display_title);
-                println!("{}", format!("[{}]", task.priority.as_ref().unwrap()).green());
-
-                // Show options
-                let alt_keys = ['j', 'k', 'l', ';', 'a', 'f', 'g', 'h'];
-                println!("\n{}", "Project:".dimmed());
-                for (idx, proj) in project_keys.iter().enumerate() {
-                    let alt = alt_keys.get(idx).map(|c| format!("/{}", c)).unwrap_or_default();
-                    println!("  {} {}", format!("{}{}.", idx, alt).cyan(), proj);
-                }
-                println!("  {} Skip (no project)", "s.".dimmed());
-                if !undo_history.is_empty() {
-                    println!("  {} Undo", "u.".yellow());
-                }
-                println!("  {} Quit (save progress)", "q.".red());
+    if let (Some((value, _)), '\n') = (suggestion, choice) {
+        let mut updated = task.clone();
+        match pass {
+            TriagePass::Priority => {
+                updated.completed |= config.is_done_priority(&value);
+                updated.priority = Some(value.clone());
             }
+            TriagePass::Time => updated.time = Some(value.clone()),
+            _ => updated.context = Some(value.clone()),
+        }
+        println!("{}", format!("  ✓ {}", value).green());
+        return Ok(TriageChoice::Set(Box::new(updated)));
+    }
 
-            print!("\n> ");
-            stdout().flush()?;
-
-            let choice = read_single_key()?;
-            println!("{}", choice);
-
-            let mut go_back = false;
-
-            // Map alt keys to indices
-            let alt_keys = ['j', 'k', 'l', ';', 'a', 'f', 'g', 'h'];
-            let alt_index = alt_keys.iter().position(|&c| c == choice.to_ascii_lowercase());
-
-            if choice == 'u' || choice == 'U' {
-                if let Some((prev_idx, prev_task)) = undo_history.pop() {
-                    println!("{}", "  ↶ Undoing...".yellow());
-
-                    for_project_pass[prev_idx] = prev_task.clone();
-
-                    print!("{}", "  💾 Restoring...".dimmed());
-                    if let Err(e) = update_task_locally(&prev_task) {
-                        println!(" {}", format!("❌ Failed: {}", e).red());
-                    } else {
-                        println!(" {}", "✓".green());
-                    }
+    let c_lower = choice.to_ascii_lowercase();
+    let key_matches = |key: char, alt: Option<char>| {
+        key.to_ascii_lowercase() == c_lower || alt.map(|a| a.to_ascii_lowercase() == c_lower).unwrap_or(false)
+    };
+    // Digit or home-row alt key for numbered menus
+    let menu_index = if choice.is_ascii_digit() {
+        choice.to_digit(10).map(|d| d as usize)
+    } else {
+        TRIAGE_ALT_KEYS.iter().position(|&c| c == c_lower)
+    };
 
-                    i = prev_idx;
-                    go_back = true;
-                } else {
-                    println!("{}", "  ⚠ Nothing to undo".yellow());
-                    continue;
-                }
-            } else if choice == 's' || choice == 'S' || choice == ' ' {
-                println!("{}", "  → No project".dimmed());
-            } else if choice == 'q' || choice == 'Q' || choice == '\x1b' {
-                println!("\n{}", "Saving and exiting...".yellow());
-                // Push dirty tasks before exiting
-                println!("{}", "Pushing changes to Google...".dimmed());
-                push_dirty_tasks_to_google(&config.google_account)?;
-                return Ok(());
+    let mut updated = task.clone();
+    let label = match pass {
+        TriagePass::Priority => {
+            if let Some(p) = config.priorities.iter().find(|p| key_matches(p.key, p.alt_key)) {
+                updated.priority = Some(p.name.clone());
+                updated.completed |= p.done;
+                p.name.clone()
+            } else if c_lower == 'x' {
+                updated.completed = true;
+                "Marked as Done".to_string()
+            } else if c_lower == 'w' {
+                return prompt_defer(task);
+            } else if c_lower == '>' {
+                return prompt_start(task);
+            } else if c_lower == '!' {
+                return Ok(match split_task(app, task)? {
+                    Some(updated) => TriageChoice::Split(Box::new(updated)),
+                    None => TriageChoice::Skip,
+                });
             } else {
-                // Check for digit or alt key
-                let num: Option<usize> = if choice.is_ascii_digit() {
-                    choice.to_string().parse().ok()
-                } else {
-                    alt_index
-                };
-
-                if let Some(idx) = num {
-                    if idx < project_keys.len() {
-                        let old_task = for_project_pass[i].clone();
-                        let proj = &project_keys[idx];
-
-                        for_project_pass[i].project = Some(proj.clone());
-                        println!("{}", format!("  ✓ {}", proj).green());
-
-                        undo_history.push((i, old_task));
-
-                        print!("{}", "  💾 Saving...".dimmed());
-                        if let Err(e) = update_task_locally(&for_project_pass[i]) {
-                            println!(" {}", format!("❌ Failed: {}", e).red());
-                        } else {
-                            println!(" {}", "✓".green());
-                        }
-                    } else {
-                        println!("{}", "  ⚠ Invalid number, skipping".yellow());
-                    }
+                return Ok(TriageChoice::Invalid);
+            }
+        }
+        TriagePass::Project => match menu_index.and_then(|i| project_keys.get(i)) {
+            Some(proj) => {
+                updated.project = Some(proj.clone());
+                let filled = apply_project_defaults(config, &mut updated);
+                if filled.is_empty() {
+                    proj.clone()
                 } else {
-                    println!("{}", "  ⚠ Invalid input, skipping".yellow());
+                    format!("{} ({})", proj, filled.join(", "))
                 }
             }
-
-            if !go_back {
-                i += 1;
+            None => return Ok(TriageChoice::Invalid),
+        },
+        TriagePass::Time => match config.time_options.iter().find(|t| key_matches(t.key, t.alt_key)) {
+            Some(t) => {
+                updated.time = Some(t.label.clone());
+                t.label.clone()
             }
-        }
-    }
-
-    let should_do_time = !priority_only && !project_only && !status_only && !context_only;
-    
-    if should_do_time && !for_project_pass.is_empty() {
-        let time_count = for_project_pass.len();
-        
-        // ═══ PASS 3: TIME ESTIMATES ═══
-        println!("\n{}", "═══ Pass 3: Time Estimates ═══\n".cyan().bold());
-
-        let mut undo_history: Vec<(usize, Task)> = Vec::new();
-        let mut i = 0;
-
-        while i < for_project_pass.len() {
-            // Skip tasks that already have a time estimate
-            if !force && for_project_pass[i].time.is_some() {
-                i += 1;
-                continue;
+            None => return Ok(TriageChoice::Invalid),
+        },
+        TriagePass::Status => match config.next_statuses(task.status.as_deref()).into_iter().find(|s| key_matches(s.key, s.alt_key)) {
+            Some(s) => {
+                updated.status = Some(s.name.clone());
+                updated.completed |= s.done;
+                s.name.clone()
             }
-
-            // Display current task
-            {
-                let task = &for_project_pass[i];
-
-                println!("\n{}", format!("Task {}/{}", i + 1, time_count).cyan());
-                println!("{}", format!("ID: {}", task.short_id()).dimmed());
-
-                // Display title with attachment indicator
-                let display_title = if let Some(ref att_type) = task.attachment_type {
-                    format!("{} ({})", task.title, att_type).yellow()
-                } else {
-                    task.title.yellow()
-                };
-                println!("{}", display_title);
-                println!("{}", format!("[{}][{}]",
-                    task.priority.as_ref().unwrap(),
-                    task.project.as_ref().unwrap_or(&"---".to_string())
-                ).green());
-
-                // Show options
-                println!("\n{}", "Time estimate:".dimmed());
-                for time_opt in &config.time_options {
-                    let key_str = if let Some(alt) = time_opt.alt_key {
-                        format!("{}/{}", time_opt.key, alt)
-                    } else {
-                        time_opt.key.to_string()
-                    };
-                    println!("  {} {}", format!("{}.", key_str).cyan(), time_opt.label);
-                }
-                println!("  {} Skip", "s.".dimmed());
-                if !undo_history.is_empty() {
-                    println!("  {} Undo", "u.".yellow());
-                }
-                println!("  {} Quit (save progress)", "q.".red());
+            None => return Ok(TriageChoice::Invalid),
+        },
+        TriagePass::Context => match menu_index.and_then(|i| config.contexts.get(i)) {
+            Some(ctx) => {
+                updated.context = Some(ctx.clone());
+                ctx.clone()
             }
+            None => return Ok(TriageChoice::Invalid),
+        },
+        TriagePass::Person => {
+            let name = match menu_index.and_then(|i| people.get(i)) {
+                Some(name) => name.clone(),
+                None if choice == '+' => {
+                    print!("{} ", "  Name:".yellow());
+                    stdout().flush()?;
+                    let mut input = String::new();
+                    std::io::stdin().read_line(&mut input)?;
+                    match input.trim().trim_start_matches('+') {
+                        "" => return Ok(TriageChoice::Skip),
+                        name => name.to_string(),
+                    }
+                }
+                None => return Ok(TriageChoice::Invalid),
+            };
+            updated.set_person(&name);
+            format!("+{}", name)
+        }
+    };
 
-            print!("\n> ");
-            stdout().flush()?;
-
-            let choice = read_single_key()?;
-            println!("{}", choice);
+    println!("{}", format!("  ✓ {}", label).green());
+    Ok(TriageChoice::Set(Box::new(updated)))
+}
 
-            let mut go_back = false;
+/// Names used with +name on open tasks, most used first (for the triage person menu).
+/// Names differing only in case count as one, spelled as first seen.
+fn known_people(tasks: &[Task]) -> Vec<String> {
+    let mut counts: std::collections::HashMap<String, (String, usize)> = std::collections::HashMap::new();
+    for person in tasks.iter().filter(|t| !t.is_done()).filter_map(|t| t.person()) {
+        counts.entry(person.to_lowercase()).or_insert_with(|| (person.to_string(), 0)).1 += 1;
+    }
+    let mut people: Vec<(String, (String, usize))> = counts.into_iter().collect();
+    people.sort_by(|a, b| b.1.1.cmp(&a.1.1).then_with(|| a.0.cmp(&b.0)));
+    people.into_iter().take(TRIAGE_ALT_KEYS.len()).map(|(_, (name, _))| name).collect()
+}
 
-            if choice == 'u' || choice == 'U' {
-                if let Some((prev_idx, prev_task)) = undo_history.pop() {
-                    println!("{}", "  ↶ Undoing...".yellow());
+/// Suggest a time estimate from finished tasks like this one: the median estimate of
+/// those with similar titles, else of those sharing a tag in the same project, snapped
+/// to the nearest time option. Returns the label and how many tasks it came from.
+fn suggest_estimate(config: &Config, task: &Task, history: &[Task]) -> Option<(String, usize)> {
+    let done: Vec<&Task> = history.iter()
+        .filter(|t| t.completed && (t.id.is_none() || t.id != task.id))
+        .filter(|t| t.time.as_deref().is_some_and(|time| parse_time_to_minutes(time) > 0))
+        .collect();
+    let mut similar: Vec<&Task> = done.iter().copied()
+        .filter(|t| similarity::token_similarity(&t.title, &task.title) >= ESTIMATE_SIMILARITY)
+        .collect();
+    if similar.is_empty() && task.project.is_some() {
+        similar = done.into_iter()
+            .filter(|t| t.project == task.project && t.tags.iter().any(|tag| task.tags.contains(tag)))
+            .collect();
+    }
+    if similar.is_empty() {
+        return None;
+    }
 
-                    for_project_pass[prev_idx] = prev_task.clone();
+    let mut minutes: Vec<i32> = similar.iter().map(|t| parse_time_to_minutes(t.time.as_deref().unwrap_or_default())).collect();
+    minutes.sort_unstable();
+    let median = minutes[minutes.len() / 2];
+    let label = config.time_options.iter()
+        .min_by_key(|o| (parse_time_to_minutes(&o.label) - median).abs())
+        .map_or_else(|| format!("{}m", median), |o| o.label.clone());
+    Some((label, similar.len()))
+}
 
-                    print!("{}", "  💾 Restoring...".dimmed());
-                    if let Err(e) = update_task_locally(&prev_task) {
-                        println!(" {}", format!("❌ Failed: {}", e).red());
-                    } else {
-                        println!(" {}", "✓".green());
-                    }
+/// How alike two titles must be for one's estimate to inform the other's
+const ESTIMATE_SIMILARITY: f64 = 0.5;
 
-                    i = prev_idx;
-                    go_back = true;
-                } else {
-                    println!("{}", "  ⚠ Nothing to undo".yellow());
-                    continue;
-                }
-            } else if choice == 's' || choice == 'S' || choice == ' ' {
-                println!("{}", "  → No estimate".dimmed());
-            } else if choice == 'q' || choice == 'Q' || choice == '\x1b' {
-                println!("\n{}", "Saving and exiting...".yellow());
-                // Push dirty tasks before exiting
-                println!("{}", "Pushing changes to Google...".dimmed());
-                push_dirty_tasks_to_google(&config.google_account)?;
-                return Ok(());
-            } else {
-                // Check if choice matches any time option key or alt_key
-                let matched_time = config.time_options.iter().find(|t| {
-                    let c_lower = choice.to_ascii_lowercase();
-                    t.key.to_ascii_lowercase() == c_lower ||
-                    t.alt_key.map(|a| a.to_ascii_lowercase() == c_lower).unwrap_or(false)
-                });
+/// Fill a task's unset priority and context from its project's defaults.
+/// Returns the values it filled in.
+fn apply_project_defaults(config: &Config, task: &mut Task) -> Vec<String> {
+    let Some(project) = config.project(task.project.as_deref()) else {
+        return Vec::new();
+    };
+    let mut filled = Vec::new();
+    if let (None, Some(priority)) = (&task.priority, &project.priority) {
+        task.priority = Some(priority.clone());
+        task.completed |= config.is_done_priority(priority);
+        filled.push(priority.clone());
+    }
+    if let (None, Some(context)) = (&task.context, &project.context) {
+        task.context = Some(context.clone());
+        filled.push(context.clone());
+    }
+    filled
+}
 
-                if let Some(time_opt) = matched_time {
-                    let old_task = for_project_pass[i].clone();
-                    for_project_pass[i].time = Some(time_opt.label.clone());
-                    println!("{}", format!("  ✓ {}", time_opt.label).green());
+/// Ask when to revisit a task and move its date there
+fn prompt_defer(task: &Task) -> Result<TriageChoice> {
+    print!("{} ", "  When? (e.g. friday, in 2 weeks, 3/1):".yellow());
+    stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+
+    match parse_date_from_text(input.trim()) {
+        Some(date) => {
+            let mut updated = task.clone();
+            updated.date = date.clone();
+            println!("{}", format!("  ⏭  Deferred to {}", date).green());
+            Ok(TriageChoice::Defer(Box::new(updated)))
+        }
+        None => {
+            println!("{}", format!("  ⚠ Couldn't understand '{}'", input.trim()).yellow());
+            Ok(TriageChoice::Invalid)
+        }
+    }
+}
 
-                    undo_history.push((i, old_task));
+/// Ask when a task can start and hide it until then (its due date stays put)
+fn prompt_start(task: &Task) -> Result<TriageChoice> {
+    print!("{} ", "  Start when? (e.g. monday, in 2 weeks, 2026-03-01):".yellow());
+    stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+
+    match parse_start_date(&input) {
+        Ok(date) => {
+            let mut updated = task.clone();
+            updated.start = Some(date.clone());
+            println!("{}", format!("  🛫 Hidden until {}", date).green());
+            Ok(TriageChoice::Defer(Box::new(updated)))
+        }
+        Err(e) => {
+            println!("{}", format!("  ⚠ {}", e).yellow());
+            Ok(TriageChoice::Invalid)
+        }
+    }
+}
 
-                    print!("{}", "  💾 Saving...".dimmed());
-                    if let Err(e) = update_task_locally(&for_project_pass[i]) {
-                        println!(" {}", format!("❌ Failed: {}", e).red());
-                    } else {
-                        println!(" {}", "✓".green());
-                    }
-                } else {
-                    println!("{}", "  ⚠ Invalid input, skipping".yellow());
-                }
-            }
+/// Prompt for replacement titles and create them next to (or under) `task`.
+/// Returns the updated original, or None if nothing was entered.
+fn split_task(app: &App, task: &Task) -> Result<Option<Task>> {
+    let config = &app.config;
+    let list_id = task.list_id.clone().context("Task missing list_id")?;
 
-            if !go_back {
-                i += 1;
-            }
+    println!("{}", "  Split into (one title per line, empty line to finish):".yellow());
+    let mut titles = Vec::new();
+    loop {
+        print!("  {} ", "+".cyan());
+        stdout().flush()?;
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
         }
+        titles.push(line.trim().to_string());
+    }
+    if titles.is_empty() {
+        println!("{}", "  → Nothing to split".dimmed());
+        return Ok(None);
     }
 
-    // ═══ PASS 4: STATUS ═══
-    let should_do_status = status_only || (!priority_only && !project_only && !time_only && !context_only);
+    print!("{} ", "  Complete the original? (y = done, n = keep as parent)".yellow());
+    stdout().flush()?;
+    let complete = matches!(read_single_key()?, 'y' | 'Y');
+    println!();
 
-    if should_do_status && !for_project_pass.is_empty() && !config.statuses.is_empty() {
-        let status_count = for_project_pass.len();
+    // Keeping the original makes the new tasks its subtasks; completing it makes them siblings
+    let parent = if complete { None } else { task.id.as_deref() };
+    for title in &titles {
+        let child = Task {
+            id: None,
+            list_id: None,
+            title: title.clone(),
+            date: Local::now().format("%Y-%m-%d").to_string(),
+            priority: task.priority.clone().filter(|p| !app.config.is_done_priority(p)),
+            project: task.project.clone(),
+            status: None,
+            context: task.context.clone(),
+            time: None,
+            list: task.list.clone(),
+            attachment_type: None,
+            tags: HASHTAG_REGEX.captures_iter(title).map(|c| c[1].to_string()).collect(),
+            created: None,
+            start: None,
+            notes: None,
+            completed: false,
+        };
+        let formatted = if child.priority.is_some() || child.project.is_some() {
+            child.format(config)
+        } else {
+            title.clone()
+        };
+        let new_id = create_task_in_google(app, &list_id, &formatted, parent)?;
+        println!("  {} {}", "✓".green(), format!("{} {}", &new_id[..8.min(new_id.len())], formatted).dimmed());
+    }
 
-        println!("\n{}", "═══ Pass 4: Status ═══\n".cyan().bold());
+    let mut updated = task.clone();
+    if complete {
+        updated.completed = true;
+    } else if !updated.tags.iter().any(|t| t == "Parent") {
+        updated.tags.push("Parent".to_string());
+    }
+    println!("{}", format!("  ✂  Split into {} tasks", titles.len()).green());
+    Ok(Some(updated))
+}
 
-        let mut undo_history: Vec<(usize, Task)> = Vec::new();
-        let mut i = 0;
+fn cmd_split(app: &App, id: &str) -> Result<()> {
+    let config = &app.config;
+    let tasks = get_tasks_from_cache(app)?;
+    let matching = find_tasks_by_id_prefix(&tasks, id);
 
-        while i < for_project_pass.len() {
-            // Skip tasks that already have status (unless force or status_only mode)
-            if !force && !status_only && for_project_pass[i].status.is_some() {
-                i += 1;
-                continue;
+    let task = match matching.len() {
+        0 => anyhow::bail!("No task found with ID starting with '{}'", id),
+        1 => matching[0],
+        _ => {
+            println!("{}", format!("Multiple tasks match '{}'. Please be more specific:", id).yellow());
+            for task in matching {
+                println!("  {} {}", task.short_id().dimmed(), task.title);
             }
+            return Ok(());
+        }
+    };
 
-            // Display current task
-            {
-                let task = &for_project_pass[i];
+    println!("{}", "✂  Split Task\n".cyan().bold());
+    println!("{} {}\n", task.short_id().dimmed(), task.format(config).yellow());
 
-                println!("\n{}", format!("Task {}/{}", i + 1, status_count).cyan());
-                println!("{}", format!("ID: {}", task.short_id()).dimmed());
+    if let Some(updated) = split_task(app, task)? {
+        update_task_locally(app, &updated)?;
+        println!("\n{}", "Pushing changes to Google...".dimmed());
+        push_dirty_tasks_to_google(app)?;
+    }
 
-                let display_title = if let Some(ref att_type) = task.attachment_type {
-                    format!("{} ({})", task.title, att_type).yellow()
-                } else {
-                    task.title.yellow()
-                };
-                println!("{}", display_title);
-                println!("{}", format!("[{}][{}]{}",
-                    task.priority.as_ref().unwrap_or(&"--".to_string()),
-                    task.project.as_ref().unwrap_or(&"---".to_string()),
-                    task.time.as_ref().map(|t| format!("{{{}}}", t)).unwrap_or_default()
-                ).green());
-
-                // Show options
-                println!("\n{}", "Status:".dimmed());
-                for status_opt in &config.statuses {
-                    let key_str = if let Some(alt) = status_opt.alt_key {
-                        format!("{}/{}", status_opt.key, alt)
-                    } else {
-                        status_opt.key.to_string()
-                    };
-                    println!("  {} {} - {}", format!("{}.", key_str).cyan(), status_opt.name, status_opt.description);
-                }
-                println!("  {} Skip", "s.".dimmed());
-                if !undo_history.is_empty() {
-                    println!("  {} Undo", "u.".yellow());
-                }
-                println!("  {} Quit (save progress)", "q.".red());
-            }
+    Ok(())
+}
 
-            print!("\n> ");
-            stdout().flush()?;
+/// Save one triage decision locally, reporting success inline
+fn save_triage_step(app: &App, task: &Task, verb: &str) {
+    print!("{}", format!("  💾 {}...", verb).dimmed());
+    if let Err(e) = update_task_locally(app, task) {
+        println!(" {}", format!("❌ Failed: {}", e).red());
+    } else {
+        println!(" {}", "✓".green());
+    }
+}
 
-            let choice = read_single_key()?;
-            println!("{}", choice);
+#[allow(clippy::too_many_arguments)]
+fn cmd_triage(app: &App, force: bool, priority_only: bool, project_only: bool, time_only: bool, status_only: bool, context_only: bool, person_only: bool, single_pass: bool, scope: &TriageScope) -> Result<()> {
+    let config = &app.config;
+    println!("{}", output::plain("🌱 The Garden - Interactive Triage\n").green().bold());
 
-            let mut go_back = false;
+    // Naming one task means re-triaging it even if it's already done
+    let force = force || scope.id.is_some();
 
-            if choice == 'u' || choice == 'U' {
-                if let Some((prev_idx, prev_task)) = undo_history.pop() {
-                    println!("{}", "  ↶ Undoing...".yellow());
-                    for_project_pass[prev_idx] = prev_task.clone();
+    if force {
+        println!("{}", "🔄 Force mode - re-triaging all tasks\n".yellow());
+    }
 
-                    print!("{}", "  💾 Restoring...".dimmed());
-                    if let Err(e) = update_task_locally(&prev_task) {
-                        println!(" {}", format!("❌ Failed: {}", e).red());
-                    } else {
-                        println!(" {}", "✓".green());
-                    }
+    // A --<pass> flag runs just that pass; otherwise use the configured order
+    let only = [
+        (priority_only, TriagePass::Priority, "📊 Priority-only mode\n"),
+        (project_only, TriagePass::Project, "🏷️  Project-only mode\n"),
+        (time_only, TriagePass::Time, "⏱️  Time-only mode\n"),
+        (status_only, TriagePass::Status, "📋 Status-only mode\n"),
+        (context_only, TriagePass::Context, "📍 Context-only mode\n"),
+        (person_only, TriagePass::Person, "👤 Person-only mode\n"),
+    ].into_iter().find(|(on, _, _)| *on);
+
+    let passes: Vec<TriagePass> = match only {
+        Some((_, pass, banner)) => {
+            println!("{}", banner.cyan());
+            vec![pass]
+        }
+        None => config.triage_passes.iter()
+            .map(|name| TriagePass::parse(name))
+            .collect::<Result<Vec<_>>>()
+            .context("Invalid triage_passes in config.json")?,
+    };
+    let only = only.is_some();
+    let passes: Vec<TriagePass> = passes.into_iter().filter(|p| p.has_options(config)).collect();
 
-                    i = prev_idx;
-                    go_back = true;
-                } else {
-                    println!("{}", "  ⚠ Nothing to undo".yellow());
-                    continue;
-                }
-            } else if choice == 's' || choice == 'S' || choice == ' ' {
-                println!("{}", "  → No status".dimmed());
-            } else if choice == 'q' || choice == 'Q' || choice == '\x1b' {
-                println!("\n{}", "Saving and exiting...".yellow());
-                println!("{}", "Pushing changes to Google...".dimmed());
-                push_dirty_tasks_to_google(&config.google_account)?;
-                return Ok(());
-            } else {
-                // Check if choice matches any status key or alt_key
-                let matched_status = config.statuses.iter().find(|s| {
-                    let c_lower = choice.to_ascii_lowercase();
-                    s.key.to_ascii_lowercase() == c_lower ||
-                    s.alt_key.map(|a| a.to_ascii_lowercase() == c_lower).unwrap_or(false)
-                });
+    if single_pass {
+        println!("{}", "🎯 Single-pass mode - each task fully triaged before the next\n".cyan());
+    }
 
-                if let Some(status_opt) = matched_status {
-                    let old_task = for_project_pass[i].clone();
-                    for_project_pass[i].status = Some(status_opt.name.clone());
-                    println!("{}", format!("  ✓ {}", status_opt.name).green());
+    // Core passes decide what counts as untriaged; fall back to all passes if none are configured
+    let gating: Vec<TriagePass> = if passes.iter().any(|p| p.is_core()) {
+        passes.iter().copied().filter(|p| only || p.is_core()).collect()
+    } else {
+        passes.clone()
+    };
 
-                    undo_history.push((i, old_task));
+    // Anything captured offline needs a Google ID before it can be triaged
+    flush_inbox(app)?;
 
-                    print!("{}", "  💾 Saving...".dimmed());
-                    if let Err(e) = update_task_locally(&for_project_pass[i]) {
-                        println!(" {}", format!("❌ Failed: {}", e).red());
-                    } else {
-                        println!(" {}", "✓".green());
-                    }
-                } else {
-                    println!("{}", "  ⚠ Invalid input, skipping".yellow());
-                }
-            }
+    let mut tasks = get_tasks_from_cache(app)?;
+    // Everything, for WIP limit checks as tasks get updated
+    let mut board = tasks.clone();
+    if !scope.include_future {
+        let today = Local::now().format("%Y-%m-%d").to_string();
+        tasks.retain(|t| !starts_later(t, &today));
+    }
+    if !scope.is_empty() {
+        scope.retain(&mut tasks)?;
+        println!("{}", format!("🔎 Scoped to {} matching tasks\n", tasks.len()).cyan());
+    }
+    let mut to_triage: Vec<Task> = tasks.into_iter()
+        .filter(|t| gating.iter().any(|p| p.needs(t, force, only)))
+        .collect();
 
-            if !go_back {
-                i += 1;
-            }
+    // Inbox captures go first; ones that no longer need triage leave the inbox lane
+    let sync_manager = &app.db;
+    let inbox_ids = sync_manager.get_inbox_task_ids()?;
+    for id in &inbox_ids {
+        let still_waiting = to_triage.iter().any(|t| t.id.as_deref() == Some(id.as_str()));
+        if !still_waiting && scope.is_empty() && !only {
+            sync_manager.remove_inbox_task(id)?;
         }
     }
+    to_triage.sort_by_key(|t| {
+        t.id.as_ref()
+            .and_then(|id| inbox_ids.iter().position(|i| i == id))
+            .unwrap_or(usize::MAX)
+    });
+    let inbox_count = to_triage.iter()
+        .filter(|t| t.id.as_ref().map(|id| inbox_ids.contains(id)).unwrap_or(false))
+        .count();
 
-    // ═══ PASS 5: CONTEXT ═══
-    let should_do_context = context_only || (!priority_only && !project_only && !time_only && !status_only);
-
-    if should_do_context && !for_project_pass.is_empty() && !config.contexts.is_empty() {
-        let context_count = for_project_pass.len();
-
-        println!("\n{}", "═══ Pass 5: Context ═══\n".cyan().bold());
+    if to_triage.is_empty() {
+        println!("{}", "✅ All tasks are triaged!".green());
+        return Ok(());
+    }
 
-        let alt_keys = ['j', 'k', 'l', ';', 'a', 'f', 'g', 'h'];
-        let mut undo_history: Vec<(usize, Task)> = Vec::new();
-        let mut i = 0;
+    let total = to_triage.len();
+    println!("Found {} tasks needing triage\n", total.to_string().yellow().bold());
+    if inbox_count > 0 {
+        println!("{}", format!("📥 Starting with {} inbox captures\n", inbox_count).cyan());
+    }
 
-        while i < for_project_pass.len() {
-            // Skip tasks that already have context (unless force or context_only mode)
-            if !force && !context_only && for_project_pass[i].context.is_some() {
-                i += 1;
-                continue;
-            }
+    // Walk (task, pass) steps: pass-major normally, task-major in single-pass mode
+    let priority_pos = passes.iter().position(|p| *p == TriagePass::Priority);
+    let steps = total * passes.len();
+    let step_at = |k: usize| if single_pass {
+        (k / passes.len(), k % passes.len())
+    } else {
+        (k % total, k / total)
+    };
 
-            // Display current task
-            {
-                let task = &for_project_pass[i];
+    // Undo history: stores (step, old_task)
+    let mut undo_history: Vec<(usize, Task)> = Vec::new();
+    let mut deferred: std::collections::HashSet<usize> = std::collections::HashSet::new();
+    let mut current_pass = None;
+    let mut current_task = None;
+    let mut k = 0;
+
+    while k < steps {
+        let (ti, pi) = step_at(k);
+        let pass = passes[pi];
+        let task = &to_triage[ti];
+
+        // Completed tasks leave triage; so do tasks left unprioritized once the priority pass is behind them
+        let dropped = task.is_done()
+            || deferred.contains(&ti)
+            || priority_pos.map(|pos| pos < pi && TriagePass::Priority.needs(task, false, false)).unwrap_or(false);
+        if dropped || !pass.needs(task, force, only) {
+            k += 1;
+            continue;
+        }
 
-                println!("\n{}", format!("Task {}/{}", i + 1, context_count).cyan());
-                println!("{}", format!("ID: {}", task.short_id()).dimmed());
+        if !single_pass && current_pass != Some(pi) {
+            println!("\n{}", format!("═══ Pass {}: {} ═══\n", pi + 1, pass.label()).cyan().bold());
+            current_pass = Some(pi);
+        }
+        if !single_pass || current_task != Some(ti) {
+            print_triage_task(task, ti + 1, total);
+            current_task = Some(ti);
+        }
 
-                let display_title = if let Some(ref att_type) = task.attachment_type {
-                    format!("{} ({})", task.title, att_type).yellow()
-                } else {
-                    task.title.yellow()
-                };
-                println!("{}", display_title);
-                println!("{}", format!("[{}][{}]{}{}",
-                    task.priority.as_ref().unwrap_or(&"--".to_string()),
-                    task.project.as_ref().unwrap_or(&"---".to_string()),
-                    task.status.as_ref().map(|s| format!("[{}]", s)).unwrap_or_default(),
-                    task.time.as_ref().map(|t| format!("{{{}}}", t)).unwrap_or_default()
-                ).green());
-
-                // Show options
-                println!("\n{}", "Context:".dimmed());
-                for (idx, ctx) in config.contexts.iter().enumerate() {
-                    let alt = alt_keys.get(idx).map(|c| format!("/{}", c)).unwrap_or_default();
-                    println!("  {} {}", format!("{}{}.", idx, alt).cyan(), ctx);
+        match prompt_triage_pass(app, pass, task, !undo_history.is_empty())? {
+            TriageChoice::Set(updated) => {
+                if let Some(reason) = config.wip_limits.breach(&board, &to_triage[ti], &updated) {
+                    print!("{}", format!("  🚧 {}. Assign anyway? (y/n): ", reason).yellow());
+                    stdout().flush()?;
+                    let key = read_single_key()?;
+                    println!("{}", key);
+                    if key != 'y' && key != 'Y' {
+                        // Ask this pass again
+                        current_task = None;
+                        continue;
+                    }
                 }
-                println!("  {} Skip (no context)", "s.".dimmed());
-                if !undo_history.is_empty() {
-                    println!("  {} Undo", "u.".yellow());
+                if let Some(entry) = board.iter_mut().find(|t| t.id == updated.id) {
+                    *entry = (*updated).clone();
+                }
+                undo_history.push((k, to_triage[ti].clone()));
+                let finished = updated.completed && !to_triage[ti].completed;
+                to_triage[ti] = *updated;
+                save_triage_step(app, &to_triage[ti], "Saving");
+                if finished {
+                    prompt_follow_up(app, &to_triage[ti])?;
                 }
-                println!("  {} Quit (save progress)", "q.".red());
             }
-
-            print!("\n> ");
-            stdout().flush()?;
-
-            let choice = read_single_key()?;
-            println!("{}", choice);
-
-            let mut go_back = false;
-
-            // Map alt keys to indices
-            let alt_index = alt_keys.iter().position(|&c| c == choice.to_ascii_lowercase());
-
-            if choice == 'u' || choice == 'U' {
-                if let Some((prev_idx, prev_task)) = undo_history.pop() {
+            TriageChoice::Defer(updated) => {
+                undo_history.push((k, to_triage[ti].clone()));
+                to_triage[ti] = *updated;
+                deferred.insert(ti);
+                save_triage_step(app, &to_triage[ti], "Saving");
+            }
+            TriageChoice::Split(updated) => {
+                // New tasks already exist in Google, so a split can't be undone
+                to_triage[ti] = *updated;
+                deferred.insert(ti);
+                save_triage_step(app, &to_triage[ti], "Saving");
+            }
+            TriageChoice::Skip => println!("{}", "  → Skipped".dimmed()),
+            TriageChoice::Undo => {
+                if let Some((prev_k, prev_task)) = undo_history.pop() {
                     println!("{}", "  ↶ Undoing...".yellow());
-                    for_project_pass[prev_idx] = prev_task.clone();
-
-                    print!("{}", "  💾 Restoring...".dimmed());
-                    if let Err(e) = update_task_locally(&prev_task) {
-                        println!(" {}", format!("❌ Failed: {}", e).red());
-                    } else {
-                        println!(" {}", "✓".green());
+                    let (prev_ti, _) = step_at(prev_k);
+                    if let Some(entry) = board.iter_mut().find(|t| t.id == prev_task.id) {
+                        *entry = prev_task.clone();
                     }
+                    to_triage[prev_ti] = prev_task;
+                    deferred.remove(&prev_ti);
+                    save_triage_step(app, &to_triage[prev_ti], "Restoring");
 
-                    i = prev_idx;
-                    go_back = true;
+                    // Go back to that step and show the task again
+                    k = prev_k;
+                    current_task = None;
                 } else {
                     println!("{}", "  ⚠ Nothing to undo".yellow());
-                    continue;
                 }
-            } else if choice == 's' || choice == 'S' || choice == ' ' {
-                println!("{}", "  → No context".dimmed());
-            } else if choice == 'q' || choice == 'Q' || choice == '\x1b' {
+                continue;
+            }
+            TriageChoice::Quit => {
                 println!("\n{}", "Saving and exiting...".yellow());
+                // Push dirty tasks before exiting
                 println!("{}", "Pushing changes to Google...".dimmed());
-                push_dirty_tasks_to_google(&config.google_account)?;
+                push_dirty_tasks_to_google(app)?;
                 return Ok(());
-            } else {
-                // Check for digit or alt key
-                let num: Option<usize> = if choice.is_ascii_digit() {
-                    choice.to_string().parse().ok()
-                } else {
-                    alt_index
-                };
-
-                if let Some(idx) = num {
-                    if idx < config.contexts.len() {
-                        let old_task = for_project_pass[i].clone();
-                        let ctx = &config.contexts[idx];
-
-                        for_project_pass[i].context = Some(ctx.clone());
-                        println!("{}", format!("  ✓ {}", ctx).green());
-
-                        undo_history.push((i, old_task));
-
-                        print!("{}", "  💾 Saving...".dimmed());
-                        if let Err(e) = update_task_locally(&for_project_pass[i]) {
-                            println!(" {}", format!("❌ Failed: {}", e).red());
-                        } else {
-                            println!(" {}", "✓".green());
-                        }
-                    } else {
-                        println!("{}", "  ⚠ Invalid number, skipping".yellow());
-                    }
-                } else {
-                    println!("{}", "  ⚠ Invalid input, skipping".yellow());
-                }
-            }
-
-            if !go_back {
-                i += 1;
             }
+            TriageChoice::Invalid => println!("{}", "  ⚠ Invalid input, skipping".yellow()),
         }
+
+        k += 1;
     }
 
     // ═══ SUMMARY ═══
     // Push all dirty tasks to Google at the end
     println!("\n{}", "Pushing changes to Google...".dimmed());
-    push_dirty_tasks_to_google(&config.google_account)?;
+    push_dirty_tasks_to_google(app)?;
 
     println!("\n{}", "═══ Triage Complete! ═══\n".green().bold());
     println!("{}", "All changes have been saved to Google Tasks.".green());
@@ -1824,6 +3576,9 @@ fn cmd_triage(config: &Config, force: bool, priority_only: bool, project_only: b
     Ok(())
 }
 
+/// Calendar event as (start, end, summary)
+type CalendarEvent = (chrono::DateTime<Local>, chrono::DateTime<Local>, String);
+
 #[derive(Debug, Clone)]
 struct TimeBlock {
     start: chrono::DateTime<Local>,
@@ -1850,96 +3605,40 @@ struct ScheduleSuggestion {
     block: TimeBlock,
 }
 
-fn cmd_schedule(config: &Config, week: bool, auto: bool) -> Result<()> {
+fn cmd_schedule(app: &App, week: bool, auto: bool, dry_run: bool) -> Result<()> {
+    let config = &app.config;
     let today = Local::now();
     
     // Determine date range
     let (start_date, end_date, range_label) = if week {
-        let days_since_monday = today.weekday().num_days_from_monday();
-        let start_of_week = (today - chrono::Duration::days(days_since_monday as i64)).date_naive();
-        let end_of_week = start_of_week + chrono::Duration::days(6);
-        (start_of_week, end_of_week, "This Week".to_string())
+        let first_day = start_of_week(today.date_naive(), config.week_start());
+        (first_day, first_day + chrono::Duration::days(6), "This Week".to_string())
     } else {
         let today_date = today.date_naive();
-        (today_date, today_date, format!("Today ({})", today.format("%A, %b %d")))
+        (today_date, today_date, format!("Today ({})", config.format_date(today_date, "%A, %b %d")))
     };
     
     let start_str = start_date.format("%Y-%m-%d").to_string();
     let end_str = end_date.format("%Y-%m-%d").to_string();
     
-    println!("{}", format!("🗓️  Schedule Suggestions for {}\n", range_label).cyan().bold());
+    println!("{}", output::header(&format!("🗓️  Schedule Suggestions for {}\n", range_label)));
     
     // Fetch calendar events
-    let events_output = Command::new("gog")
-        .args(&[
-            "calendar", "events",
-            "--from", &start_str,
-            "--to", &end_str,
-            "--account", &config.google_account,
-            "--json",
-        ])
-        .output();
-    
-    if events_output.is_err() {
-        println!("{}", "⚠️  Could not fetch calendar events. Make sure 'gog' CLI is installed.".yellow());
-        return Ok(());
-    }
-    
-    let output = events_output?;
-    if !output.status.success() {
-        println!("{}", "⚠️  Failed to fetch calendar events.".yellow());
-        return Ok(());
-    }
-    
-    // Parse events
-    let mut events_by_day: std::collections::BTreeMap<String, Vec<(chrono::DateTime<Local>, chrono::DateTime<Local>, String)>> = std::collections::BTreeMap::new();
-    
-    if let Ok(events_json) = serde_json::from_slice::<serde_json::Value>(&output.stdout) {
-        if let Some(events) = events_json["events"].as_array() {
-            for event in events {
-                if let (Some(summary), Some(start_str), Some(end_str)) = (
-                    event["summary"].as_str(),
-                    event["start"].as_str(),
-                    event["end"].as_str(),
-                ) {
-                    if let (Ok(start_dt), Ok(end_dt)) = (
-                        chrono::DateTime::parse_from_rfc3339(start_str),
-                        chrono::DateTime::parse_from_rfc3339(end_str),
-                    ) {
-                        let start_local: chrono::DateTime<Local> = start_dt.into();
-                        let end_local: chrono::DateTime<Local> = end_dt.into();
-                        let date_key = start_local.format("%Y-%m-%d").to_string();
-                        
-                        events_by_day.entry(date_key).or_insert_with(Vec::new)
-                            .push((start_local, end_local, summary.to_string()));
-                    }
-                }
-            }
+    let events_by_day = match fetch_calendar_events(app, &start_str, &end_str) {
+        Ok(events) => events,
+        Err(e) => {
+            println!("{}", format!("⚠️  {}", e).yellow());
+            return Ok(());
         }
-    }
+    };
     
     // Get tasks that need scheduling (P0 and P1 with time estimates)
-    let tasks = get_tasks_from_cache()?;
+    let tasks = get_tasks_from_cache(app)?;
     let schedulable_tasks: Vec<Task> = tasks.into_iter()
-        .filter(|t| {
-            // Must have time estimate
-            if t.time.is_none() {
-                return false;
-            }
-            
-            // Must be P0 or P1
-            if let Some(ref priority) = t.priority {
-                if priority != "P0" && priority != "P1" {
-                    return false;
-                }
-            } else {
-                return false;
-            }
-            
-            // Must be in date range
-            t.date >= start_str && t.date <= end_str
-        })
+        .filter(|t| is_schedulable(config, t, &start_str, &end_str))
         .collect();
+    let schedulable_tasks = inflate_estimates(app, schedulable_tasks)?;
+    let parents = app.db.get_parent_ids()?;
     
     if schedulable_tasks.is_empty() {
         println!("{}", "✓ No tasks with time estimates to schedule!".green());
@@ -1948,15 +3647,16 @@ fn cmd_schedule(config: &Config, week: bool, auto: bool) -> Result<()> {
     
     // Process each day
     let mut all_suggestions: Vec<ScheduleSuggestion> = Vec::new();
+    let mut free_by_day: std::collections::BTreeMap<chrono::NaiveDate, Vec<TimeBlock>> = std::collections::BTreeMap::new();
     
     let mut current_date = start_date;
     while current_date <= end_date {
         let date_str = current_date.format("%Y-%m-%d").to_string();
-        let date_label = current_date.format("%A, %b %d").to_string();
+        let date_label = config.format_date(current_date, "%A, %b %d");
         
         // Skip past days
         if current_date < today.date_naive() {
-            current_date = current_date + chrono::Duration::days(1);
+            current_date += chrono::Duration::days(1);
             continue;
         }
         
@@ -1964,7 +3664,7 @@ fn cmd_schedule(config: &Config, week: bool, auto: bool) -> Result<()> {
         let day_events = events_by_day.get(&date_str).cloned().unwrap_or_default();
         
         // Calculate free blocks for this day
-        let free_blocks = calculate_free_blocks(&current_date, &day_events);
+        let free_blocks = calculate_free_blocks(&config.work_hours, &current_date, &day_events);
         
         // Get tasks for this day
         let day_tasks: Vec<&Task> = schedulable_tasks.iter()
@@ -1991,16 +3691,13 @@ fn cmd_schedule(config: &Config, week: bool, auto: bool) -> Result<()> {
             }
             
             // Schedule tasks into free blocks
-            let suggestions = schedule_tasks_into_blocks(day_tasks.clone(), &free_blocks);
+            let suggestions = schedule_tasks_into_blocks(config, day_tasks.clone(), &free_blocks, &parents);
+            free_by_day.insert(current_date, free_blocks);
             
             if !suggestions.is_empty() {
                 println!("\n{}", "  Suggested schedule:".dimmed());
                 for suggestion in &suggestions {
-                    let priority_emoji = match suggestion.task.priority.as_deref() {
-                        Some("P0") => "🔴",
-                        Some("P1") => "🟡",
-                        _ => "⚪",
-                    };
+                    let priority_emoji = config.priority_emoji(suggestion.task.priority.as_deref());
                     
                     let project_str = suggestion.task.project.as_ref()
                         .map(|p| format!("[{}]", p))
@@ -2048,46 +3745,355 @@ fn cmd_schedule(config: &Config, week: bool, auto: bool) -> Result<()> {
             println!();
         }
         
-        current_date = current_date + chrono::Duration::days(1);
+        current_date += chrono::Duration::days(1);
     }
     
     // Offer to create calendar events
-    if !all_suggestions.is_empty() && !auto {
-        print!("\n{}", "Block these on your calendar? (y/n): ".bold());
+    if dry_run {
+        create_calendar_blocks(app, &all_suggestions, true)?;
+    } else if !all_suggestions.is_empty() && !auto {
+        print!("\n{}", "Block these on your calendar? (y/n/e=edit first): ".bold());
         stdout().flush()?;
         
-        let choice = read_single_key()?;
+        let mut choice = read_single_key()?;
         println!("{}", choice);
         
+        if choice == 'e' || choice == 'E' {
+            all_suggestions = review_schedule_suggestions(all_suggestions, &free_by_day)?;
+            if all_suggestions.is_empty() {
+                println!("{}", "  Nothing left to block.".dimmed());
+                return Ok(());
+            }
+            print!("\n{}", format!("Block {} accepted items on your calendar? (y/n): ", all_suggestions.len()).bold());
+            stdout().flush()?;
+            choice = read_single_key()?;
+            println!("{}", choice);
+        }
+        
         if choice == 'y' || choice == 'Y' {
-            create_calendar_blocks(config, &all_suggestions)?;
+            create_calendar_blocks(app, &all_suggestions, false)?;
         } else {
             println!("{}", "  Skipped calendar blocking.".dimmed());
         }
-    } else if !all_suggestions.is_empty() && auto {
-        create_calendar_blocks(config, &all_suggestions)?;
+    } else if auto {
+        // Even with nothing to place, auto runs clear out blocks for finished tasks
+        create_calendar_blocks(app, &all_suggestions, false)?;
     }
     
     Ok(())
 }
 
-fn calculate_free_blocks(date: &chrono::NaiveDate, events: &[(chrono::DateTime<Local>, chrono::DateTime<Local>, String)]) -> Vec<TimeBlock> {
+/// Walk through suggestions one at a time: accept, reject, shift, or resize each.
+/// Returns the accepted suggestions.
+fn review_schedule_suggestions(
+    suggestions: Vec<ScheduleSuggestion>,
+    free_by_day: &std::collections::BTreeMap<chrono::NaiveDate, Vec<TimeBlock>>,
+) -> Result<Vec<ScheduleSuggestion>> {
+    println!("\n{}", "  a/Enter=accept  r=reject  +=+30m  h=+1h  n=next free block  t=change duration  q=accept rest".dimmed());
+    
+    let mut pending: std::collections::VecDeque<ScheduleSuggestion> = suggestions.into();
+    let mut accepted: Vec<ScheduleSuggestion> = Vec::new();
+    
+    while let Some(mut suggestion) = pending.pop_front() {
+        let day = suggestion.block.start.date_naive();
+        let free = free_by_day.get(&day).map(|v| v.as_slice()).unwrap_or(&[]);
+        
+        loop {
+            // Everything else already holding time that day
+            let taken: Vec<&TimeBlock> = accepted.iter().chain(pending.iter())
+                .map(|s| &s.block)
+                .filter(|b| b.start.date_naive() == day)
+                .collect();
+            let conflict = !slot_is_free(free, &taken, &suggestion.block);
+            
+            print!("\n  {} [{}] {}{}  ",
+                suggestion.block.format_time_range().cyan(),
+                suggestion.task.priority.as_deref().unwrap_or("--"),
+                suggestion.task.title,
+                if conflict { " ⚠️  overlaps busy time".yellow().to_string() } else { String::new() },
+            );
+            stdout().flush()?;
+            
+            let key = read_single_key()?;
+            println!("{}", key);
+            
+            let shift = |block: &TimeBlock, minutes: i64| TimeBlock {
+                start: block.start + chrono::Duration::minutes(minutes),
+                end: block.end + chrono::Duration::minutes(minutes),
+                duration_minutes: block.duration_minutes,
+            };
+            
+            match key {
+                'a' | 'A' | '\n' => {
+                    accepted.push(suggestion);
+                    break;
+                }
+                'r' | 'R' => {
+                    println!("{}", "    ✗ Rejected".dimmed());
+                    break;
+                }
+                '+' => suggestion.block = shift(&suggestion.block, 30),
+                'h' | 'H' => suggestion.block = shift(&suggestion.block, 60),
+                'n' | 'N' => match next_free_slot(free, &taken, &suggestion.block) {
+                    Some(block) => suggestion.block = block,
+                    None => println!("{}", "    ⚠ No later free block fits this".yellow()),
+                },
+                't' | 'T' => {
+                    print!("{} ", "    New duration (e.g. 45m, 2h):".yellow());
+                    stdout().flush()?;
+                    let mut input = String::new();
+                    std::io::stdin().read_line(&mut input)?;
+                    let minutes = parse_time_to_minutes(input.trim()) as i64;
+                    if minutes > 0 {
+                        suggestion.block.end = suggestion.block.start + chrono::Duration::minutes(minutes);
+                        suggestion.block.duration_minutes = minutes;
+                    } else {
+                        println!("{}", format!("    ⚠ Couldn't understand '{}'", input.trim()).yellow());
+                    }
+                }
+                'q' | 'Q' | '\x1b' => {
+                    accepted.push(suggestion);
+                    accepted.extend(pending.drain(..));
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+    
+    accepted.sort_by_key(|s| s.block.start);
+    Ok(accepted)
+}
+
+/// Free time left in `free` once the `taken` blocks are carved out, in order
+fn remaining_free_time(free: &[TimeBlock], taken: &[&TimeBlock]) -> Vec<TimeBlock> {
+    let mut gaps = Vec::new();
+    for block in free {
+        let mut cursor = block.start;
+        let mut inside: Vec<&&TimeBlock> = taken.iter()
+            .filter(|t| t.start < block.end && t.end > block.start)
+            .collect();
+        inside.sort_by_key(|t| t.start);
+        for t in inside {
+            if t.start > cursor {
+                gaps.push(TimeBlock { start: cursor, end: t.start, duration_minutes: (t.start - cursor).num_minutes() });
+            }
+            cursor = cursor.max(t.end);
+        }
+        if block.end > cursor {
+            gaps.push(TimeBlock { start: cursor, end: block.end, duration_minutes: (block.end - cursor).num_minutes() });
+        }
+    }
+    gaps
+}
+
+/// Whether `block` sits entirely inside free time not claimed by `taken`
+fn slot_is_free(free: &[TimeBlock], taken: &[&TimeBlock], block: &TimeBlock) -> bool {
+    remaining_free_time(free, taken).iter().any(|gap| gap.start <= block.start && gap.end >= block.end)
+}
+
+/// The same-length slot at the start of the next free gap after `block` starts
+fn next_free_slot(free: &[TimeBlock], taken: &[&TimeBlock], block: &TimeBlock) -> Option<TimeBlock> {
+    remaining_free_time(free, taken).into_iter()
+        .find(|gap| gap.start > block.start && gap.duration_minutes >= block.duration_minutes)
+        .map(|gap| TimeBlock {
+            start: gap.start,
+            end: gap.start + chrono::Duration::minutes(block.duration_minutes),
+            duration_minutes: block.duration_minutes,
+        })
+}
+
+/// Fetch calendar events between two dates (inclusive), grouped by local date
+fn fetch_calendar_events(app: &App, start_str: &str, end_str: &str) -> Result<std::collections::BTreeMap<String, Vec<CalendarEvent>>> {
+    let config = &app.config;
+    let events_json = fetch_calendar_json(config, start_str, end_str)?;
+    busy_events_by_day(app, &events_json)
+}
+
+/// Raw `gog calendar events` JSON between two dates (inclusive)
+fn fetch_calendar_json(config: &Config, start_str: &str, end_str: &str) -> Result<serde_json::Value> {
+    if sync::is_offline() {
+        anyhow::bail!("Offline: the calendar isn't cached, so this needs a connection");
+    }
+    let output = Command::new("gog")
+        .args([
+            "calendar", "events",
+            "--from", start_str,
+            "--to", end_str,
+            "--account", &config.google_account,
+            "--json",
+        ])
+        .logged_output()
+        .context("Could not fetch calendar events. Make sure 'gog' CLI is installed.")?;
+    
+    if !output.status.success() {
+        anyhow::bail!("Failed to fetch calendar events.");
+    }
+    
+    Ok(serde_json::from_slice(&output.stdout).unwrap_or(serde_json::Value::Null))
+}
+
+/// Events that block time, grouped by local date
+fn busy_events_by_day(app: &App, events_json: &serde_json::Value) -> Result<std::collections::BTreeMap<String, Vec<CalendarEvent>>> {
+    let config = &app.config;
+    let mut events_by_day: std::collections::BTreeMap<String, Vec<CalendarEvent>> = std::collections::BTreeMap::new();
+    
+    // Our own task blocks get rescheduled, so they don't count as busy time
+    let own_blocks: std::collections::HashSet<String> = app.db.get_calendar_blocks()?
+        .into_iter()
+        .map(|b| b.event_id)
+        .collect();
+    
+    if let Some(events) = events_json["events"].as_array() {
+        for event in events {
+            let summary = event["summary"].as_str().unwrap_or("(busy)");
+
+            // Events marked "free" and ignored titles don't block time
+            if event["transparency"].as_str() == Some("transparent")
+                || is_ignored_event(config, summary)
+                || event["id"].as_str().map(|id| own_blocks.contains(id)).unwrap_or(false)
+            {
+                continue;
+            }
+
+            if let Some((start_local, end_local, _)) = event_bounds(event) {
+                // Multi-day (and all-day) events block each day they cover
+                let mut day = start_local.date_naive();
+                loop {
+                    events_by_day.entry(day.format("%Y-%m-%d").to_string()).or_default()
+                        .push((start_local, end_local, summary.to_string()));
+                    day += chrono::Duration::days(1);
+                    let next_midnight = Local.from_local_datetime(&day.and_hms_opt(0, 0, 0).unwrap()).earliest();
+                    if next_midnight.map(|m| m >= end_local).unwrap_or(true) {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    
+    Ok(events_by_day)
+}
+
+/// Start/end of a calendar event in local time, plus whether it's all-day.
+/// Accepts RFC3339 or date-only strings, bare or as Google's {dateTime}/{date} objects.
+fn event_bounds(event: &serde_json::Value) -> Option<(chrono::DateTime<Local>, chrono::DateTime<Local>, bool)> {
+    fn parse(value: &serde_json::Value) -> Option<(chrono::DateTime<Local>, bool)> {
+        let raw = value.as_str()
+            .or_else(|| value["dateTime"].as_str())
+            .or_else(|| value["date"].as_str())?;
+        if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(raw) {
+            return Some((dt.into(), false));
+        }
+        let date = chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d").ok()?;
+        let midnight = Local.from_local_datetime(&date.and_hms_opt(0, 0, 0)?).earliest()?;
+        Some((midnight, true))
+    }
+
+    let (start, all_day) = parse(&event["start"])?;
+    let end = match parse(&event["end"]) {
+        Some((end, _)) => end,
+        // All-day events without an end last the day
+        None if all_day => start + chrono::Duration::days(1),
+        None => start,
+    };
+    Some((start, end, all_day))
+}
+
+/// Calendar titles from `calendar_ignore` (case-insensitive substrings) never block scheduling
+fn is_ignored_event(config: &Config, summary: &str) -> bool {
+    let summary_lower = summary.to_lowercase();
+    config.calendar_ignore.iter().any(|pattern| summary_lower.contains(&pattern.to_lowercase()))
+}
+
+/// Tasks eligible for auto-scheduling: P0/P1 with a time estimate, dated within the range
+fn is_schedulable(config: &Config, t: &Task, start_str: &str, end_str: &str) -> bool {
+    // Must have time estimate
+    if t.time.is_none() {
+        return false;
+    }
+    
+    // Must be critical (P0 or P1 by default)
+    if !config.is_critical(t.priority.as_deref()) {
+        return false;
+    }
+    
+    // Must be in date range
+    t.date.as_str() >= start_str && t.date.as_str() <= end_str
+}
+
+/// Meeting load, estimated task load, and free time for one day, in minutes
+#[derive(Debug, Default, Clone, PartialEq)]
+struct DayCapacity {
+    meeting_minutes: i64,
+    task_minutes: i64,
+    free_minutes: i64,
+}
+
+impl DayCapacity {
+    fn is_overcommitted(&self) -> bool {
+        self.task_minutes > self.free_minutes
+    }
+
+    fn add(&mut self, other: &DayCapacity) {
+        self.meeting_minutes += other.meeting_minutes;
+        self.task_minutes += other.task_minutes;
+        self.free_minutes += other.free_minutes;
+    }
+}
+
+/// Meetings count only within working hours; tasks count if estimated and not done
+fn day_capacity(hours: &WorkHours, date: &chrono::NaiveDate, events: &[CalendarEvent], tasks: &[&Task]) -> DayCapacity {
+    let to_local = |time: chrono::NaiveTime| Local.from_local_datetime(&date.and_time(time)).earliest();
+    let meeting_minutes = match hours.window_for(date) {
+        Some((start, end)) => match (to_local(start), to_local(end)) {
+            (Some(work_start), Some(work_end)) => events.iter()
+                .map(|(s, e, _)| (e.min(&work_end).signed_duration_since(*s.max(&work_start))).num_minutes().max(0))
+                .sum(),
+            _ => 0,
+        },
+        None => 0,
+    };
+    let task_minutes = tasks.iter()
+        .filter(|t| !t.is_done())
+        .filter_map(|t| t.time.as_deref())
+        .map(|time| parse_time_to_minutes(time) as i64)
+        .sum();
+    let free_minutes = calculate_free_blocks(hours, date, events).iter().map(|b| b.duration_minutes).sum();
+    DayCapacity { meeting_minutes, task_minutes, free_minutes }
+}
+
+fn calculate_free_blocks(hours: &WorkHours, date: &chrono::NaiveDate, events: &[CalendarEvent]) -> Vec<TimeBlock> {
     let mut free_blocks = Vec::new();
     
-    // Define working hours: 8 AM to 6 PM
-    let work_start = date.and_hms_opt(8, 0, 0).unwrap();
-    let work_end = date.and_hms_opt(18, 0, 0).unwrap();
+    // Working hours for this weekday, plus any preference windows (none on days off)
+    let Some((day_start, day_end)) = hours.free_time_window(date) else {
+        return free_blocks;
+    };
+    let to_local = |time: chrono::NaiveTime| Local.from_local_datetime(&date.and_time(time)).earliest();
+    let (Some(work_start_dt), Some(work_end_dt)) = (to_local(day_start), to_local(day_end)) else {
+        return free_blocks;
+    };
     
-    let work_start_dt = Local.from_local_datetime(&work_start).unwrap();
-    let work_end_dt = Local.from_local_datetime(&work_end).unwrap();
+    // Meetings block their own time plus the buffer on each side; lunch blocks exactly
+    let buffer = chrono::Duration::minutes(hours.buffer_minutes.max(0));
+    let mut busy: Vec<(chrono::DateTime<Local>, chrono::DateTime<Local>)> = events.iter()
+        .map(|(start, end, _)| (*start - buffer, *end + buffer))
+        .collect();
+    if let Some((lunch_start, lunch_end)) = hours.lunch.as_deref().and_then(parse_time_window) {
+        if let (Some(start), Some(end)) = (to_local(lunch_start), to_local(lunch_end)) {
+            busy.push((start, end));
+        }
+    }
     
-    // Sort events by start time
-    let mut sorted_events = events.to_vec();
-    sorted_events.sort_by_key(|e| e.0);
+    // Sort busy spans by start time
+    busy.sort_by_key(|e| e.0);
     
     let mut current_time = work_start_dt;
     
-    for (event_start, event_end, _) in sorted_events {
+    for (event_start, event_end) in busy {
+        let event_start = event_start.min(work_end_dt);
+
         // If there's a gap before this event
         if event_start > current_time {
             let gap_duration = event_start.signed_duration_since(current_time);
@@ -2123,61 +4129,150 @@ fn calculate_free_blocks(date: &chrono::NaiveDate, events: &[(chrono::DateTime<L
     free_blocks
 }
 
-fn schedule_tasks_into_blocks(tasks: Vec<&Task>, free_blocks: &[TimeBlock]) -> Vec<ScheduleSuggestion> {
-    let mut suggestions = Vec::new();
-    let mut remaining_blocks = free_blocks.to_vec();
-    
-    // Sort tasks by priority (P0 first) then by duration (longest first)
-    let mut sorted_tasks = tasks.to_vec();
-    sorted_tasks.sort_by(|a, b| {
-        let a_pri = a.priority.as_deref().unwrap_or("P9");
-        let b_pri = b.priority.as_deref().unwrap_or("P9");
-        
-        if a_pri != b_pri {
-            a_pri.cmp(b_pri)
-        } else {
-            // Longer tasks first
-            let a_mins = parse_time_to_minutes(a.time.as_ref().unwrap());
-            let b_mins = parse_time_to_minutes(b.time.as_ref().unwrap());
-            b_mins.cmp(&a_mins)
+/// Tasks in the set waiting on each task: its parent, the parent's parent, and so on
+fn blocked_counts(tasks: &[&Task], parents: &std::collections::HashMap<String, String>) -> std::collections::HashMap<String, i64> {
+    let ids: std::collections::HashSet<&str> = tasks.iter().filter_map(|t| t.id.as_deref()).collect();
+    let mut counts = std::collections::HashMap::new();
+    for id in &ids {
+        let mut waiting = 0;
+        let mut current = *id;
+        // Bounded, in case a corrupt cache has a parent cycle
+        for _ in 0..parents.len() {
+            let Some(parent) = parents.get(current) else { break };
+            if ids.contains(parent.as_str()) {
+                waiting += 1;
+            }
+            current = parent;
         }
-    });
+        counts.insert(id.to_string(), waiting);
+    }
+    counts
+}
+
+/// A task's place in the schedule order, higher first (see ScheduleWeights)
+fn schedule_score(config: &Config, task: &Task, day: chrono::NaiveDate, free_minutes: i64, blocking: i64) -> i64 {
+    let weights = &config.schedule_weights;
+    let levels = config.ranked_priorities().count();
+    let above_lowest = levels.saturating_sub(config.priority_rank(task.priority.as_deref()) + 1) as i64;
+    let closeness = chrono::NaiveDate::parse_from_str(&task.date, "%Y-%m-%d")
+        .map_or(0, |due| (7 - (due - day).num_days()).clamp(0, 14));
+    let window = config.task_preference(task).is_some() as i64;
+    let minutes = task.time.as_deref().map_or(0, parse_time_to_minutes) as i64;
+    let share = if free_minutes > 0 { (minutes * 100 / free_minutes).min(100) } else { 0 };
+
+    weights.priority * above_lowest
+        + weights.deadline * closeness
+        + weights.blocking * blocking
+        + weights.window * window
+        + weights.fit * share
+}
+
+/// Highest score first, except that a task always comes after the subtasks it's waiting on
+fn schedule_order<'a>(
+    config: &Config,
+    tasks: &[&'a Task],
+    free_blocks: &[TimeBlock],
+    parents: &std::collections::HashMap<String, String>,
+) -> Vec<&'a Task> {
+    let day = free_blocks.first().map_or_else(|| Local::now().date_naive(), |b| b.start.date_naive());
+    let free_minutes: i64 = free_blocks.iter().map(|b| b.duration_minutes).sum();
+    let blocking = blocked_counts(tasks, parents);
+    let mut scored: Vec<(i64, &Task)> = tasks.iter()
+        .map(|t| {
+            let waiting = t.id.as_deref().and_then(|id| blocking.get(id)).copied().unwrap_or(0);
+            (schedule_score(config, t, day, free_minutes, waiting), *t)
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+    // Take the best-scoring task with no unplaced subtasks left in the set
+    let mut ordered = Vec::with_capacity(scored.len());
+    while !scored.is_empty() {
+        let ready = scored.iter()
+            .position(|(_, t)| !scored.iter().any(|(_, s)| {
+                s.id.as_deref().and_then(|id| parents.get(id)).is_some_and(|p| t.id.as_deref() == Some(p.as_str()))
+            }))
+            .unwrap_or(0);
+        ordered.push(scored.remove(ready).1);
+    }
+    ordered
+}
+
+/// Fit tasks into free blocks in schedule_order; `parents` maps subtask IDs to their
+/// parent, which is then booked after them
+fn schedule_tasks_into_blocks(
+    config: &Config,
+    tasks: Vec<&Task>,
+    free_blocks: &[TimeBlock],
+    parents: &std::collections::HashMap<String, String>,
+) -> Vec<ScheduleSuggestion> {
+    let hours = &config.work_hours;
+    let mut suggestions: Vec<ScheduleSuggestion> = Vec::new();
+    let mut remaining_blocks = free_blocks.to_vec();
+    let sorted_tasks = schedule_order(config, &tasks, free_blocks, parents);
     
+    // Long tasks are split into blocks of at most max_block_minutes, with a break between them
+    let max_block = hours.max_block_minutes.filter(|m| *m >= 15);
+    let break_minutes = hours.buffer_minutes.max(15);
+
     // Greedy scheduling: try to fit each task into available blocks
     for task in sorted_tasks {
-        let task_minutes = parse_time_to_minutes(task.time.as_ref().unwrap());
-        
-        // Find first block that can fit this task
-        if let Some((block_idx, block)) = remaining_blocks.iter()
-            .enumerate()
-            .find(|(_, b)| b.duration_minutes >= task_minutes as i64)
-        {
-            // Create suggestion
+        let task_minutes = parse_time_to_minutes(task.time.as_ref().unwrap()) as i64;
+        let chunk_minutes = max_block.map(|m| m.min(task_minutes)).unwrap_or(task_minutes);
+
+        // Only commit if every chunk fits
+        let mut blocks = remaining_blocks.clone();
+        let mut placed = Vec::new();
+        let mut left = task_minutes;
+        // Not before its subtasks are done
+        let mut not_before = suggestions.iter()
+            .filter(|s| s.task.id.as_deref().and_then(|id| parents.get(id)).is_some_and(|p| task.id.as_deref() == Some(p.as_str())))
+            .map(|s| s.block.end)
+            .max();
+        while left > 0 {
+            let minutes = chunk_minutes.min(left);
+            let slot = blocks.iter().enumerate().find_map(|(idx, b)| {
+                // Clip the free block to the task's preferred window for that day
+                let date = b.start.date_naive();
+                let (win_start, win_end) = config.task_window(task, &date)?;
+                let to_local = |time: chrono::NaiveTime| Local.from_local_datetime(&date.and_time(time)).earliest();
+                let mut start = b.start.max(to_local(win_start)?);
+                if let Some(nb) = not_before {
+                    start = start.max(nb);
+                }
+                let end = b.end.min(to_local(win_end)?);
+                ((end - start).num_minutes() >= minutes).then_some((idx, start))
+            });
+            let Some((block_idx, start)) = slot else { break };
+
             let scheduled_block = TimeBlock {
-                start: block.start,
-                end: block.start + chrono::Duration::minutes(task_minutes as i64),
-                duration_minutes: task_minutes as i64,
+                start,
+                end: start + chrono::Duration::minutes(minutes),
+                duration_minutes: minutes,
             };
-            
-            suggestions.push(ScheduleSuggestion {
-                task: (*task).clone(),
-                block: scheduled_block.clone(),
-            });
-            
-            // Update remaining block
-            let new_block_start = scheduled_block.end;
-            let new_duration = (block.end - new_block_start).num_minutes();
-            
-            if new_duration >= 15 {
-                // Replace with smaller block
-                remaining_blocks[block_idx] = TimeBlock {
-                    start: new_block_start,
-                    end: block.end,
-                    duration_minutes: new_duration,
-                };
-            } else {
-                // Remove block entirely
-                remaining_blocks.remove(block_idx);
+
+            // Update remaining blocks: keep what's left on either side if it's 15+ minutes
+            let block = blocks.remove(block_idx);
+            for (start, end) in [(block.start, scheduled_block.start), (scheduled_block.end, block.end)] {
+                let duration = (end - start).num_minutes();
+                if duration >= 15 {
+                    blocks.push(TimeBlock { start, end, duration_minutes: duration });
+                }
+            }
+            blocks.sort_by_key(|b| b.start);
+
+            not_before = Some(scheduled_block.end + chrono::Duration::minutes(break_minutes));
+            placed.push(scheduled_block);
+            left -= minutes;
+        }
+
+        if left == 0 {
+            remaining_blocks = blocks;
+            for block in placed {
+                suggestions.push(ScheduleSuggestion {
+                    task: (*task).clone(),
+                    block,
+                });
             }
         }
     }
@@ -2185,122 +4280,293 @@ fn schedule_tasks_into_blocks(tasks: Vec<&Task>, free_blocks: &[TimeBlock]) -> V
     suggestions
 }
 
-fn create_calendar_blocks(config: &Config, suggestions: &[ScheduleSuggestion]) -> Result<()> {
-    println!("\n{}", "  Creating calendar events...".dimmed());
+fn calendar_block_title(suggestion: &ScheduleSuggestion) -> String {
+    format!("[{}] {}", 
+        suggestion.task.priority.as_deref().unwrap_or("--"),
+        suggestion.task.title
+    )
+}
+
+/// Google Calendar event color ID for a color name or ID
+fn event_color_id(color: &str) -> Option<String> {
+    const COLORS: [&str; 11] = [
+        "lavender", "sage", "grape", "flamingo", "banana", "tangerine",
+        "peacock", "graphite", "blueberry", "basil", "tomato",
+    ];
+    let color = color.trim().to_lowercase();
+    if let Ok(id) = color.parse::<usize>() {
+        return (1..=COLORS.len()).contains(&id).then(|| id.to_string());
+    }
+    COLORS.iter().position(|c| *c == color).map(|idx| (idx + 1).to_string())
+}
+
+/// gog arguments shared by block create/update: title, times, target calendar and project color
+fn calendar_block_args(config: &Config, suggestion: &ScheduleSuggestion) -> Vec<String> {
+    let mut args = vec![
+        "--title".to_string(), calendar_block_title(suggestion),
+        "--start".to_string(), suggestion.block.start.to_rfc3339(),
+        "--end".to_string(), suggestion.block.end.to_rfc3339(),
+        "--account".to_string(), config.google_account.clone(),
+    ];
+    if let Some(ref calendar_id) = config.block_calendar_id {
+        args.extend(["--calendar".to_string(), calendar_id.clone()]);
+    }
+    let color = config.project(suggestion.task.project.as_deref())
+        .and_then(|project| project.color.as_ref())
+        .or_else(|| suggestion.task.project.as_ref()
+            .and_then(|project| config.project_colors.iter().find(|(p, _)| p.eq_ignore_ascii_case(project)))
+            .map(|(_, color)| color))
+        .and_then(|color| event_color_id(color));
+    if let Some(color) = color {
+        args.extend(["--color".to_string(), color]);
+    }
+    args
+}
+
+/// Create a single calendar event for a suggestion via gog. Returns the new event ID on success.
+fn create_calendar_event(config: &Config, suggestion: &ScheduleSuggestion) -> Option<String> {
+    let output = Command::new("gog")
+        .args(["calendar", "events", "create"])
+        .args(calendar_block_args(config, suggestion))
+        .arg("--json")
+        .logged_output()
+        .ok()
+        .filter(|o| o.status.success())?;
     
-    let mut success_count = 0;
-    let mut fail_count = 0;
+    let response: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    response["id"].as_str()
+        .or_else(|| response["event"]["id"].as_str())
+        .map(|id| id.to_string())
+}
+
+/// Move an existing calendar event to a suggestion's slot via gog. Returns true on success.
+fn update_calendar_event(config: &Config, event_id: &str, suggestion: &ScheduleSuggestion) -> bool {
+    let output = Command::new("gog")
+        .args(["calendar", "events", "update", event_id])
+        .args(calendar_block_args(config, suggestion))
+        .logged_output();
     
+    matches!(output, Ok(o) if o.status.success())
+}
+
+/// Delete a calendar event via gog. Returns true on success.
+fn delete_calendar_event(config: &Config, event_id: &str) -> bool {
+    let mut cmd = Command::new("gog");
+    cmd.args(["calendar", "events", "delete", event_id, "--account", &config.google_account]);
+    if let Some(ref calendar_id) = config.block_calendar_id {
+        cmd.args(["--calendar", calendar_id]);
+    }
+    
+    matches!(cmd.logged_output(), Ok(o) if o.status.success())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BlockOutcome {
+    Created,
+    Moved,
+    Unchanged,
+    Failed,
+}
+
+/// Make TaskGarden's calendar blocks match `suggestions`: keep or move blocks we already
+/// created for each task, create only the missing ones, and delete leftovers plus blocks
+/// for tasks that were completed or rescheduled. Returns one outcome per suggestion and
+/// the number of blocks removed.
+fn book_calendar_blocks(app: &App, suggestions: &[ScheduleSuggestion], dry_run: bool) -> Result<(Vec<BlockOutcome>, usize)> {
+    let config = &app.config;
+    let sync_manager = &app.db;
+    let today = Local::now().date_naive();
+    let parse = |raw: &str| chrono::DateTime::parse_from_rfc3339(raw).ok().map(|dt| dt.with_timezone(&Local));
+
+    // Blocks on earlier days are history; leave them alone
+    let mut upcoming: Vec<CalendarBlock> = sync_manager.get_calendar_blocks()?
+        .into_iter()
+        .filter(|b| parse(&b.start).map(|start| start.date_naive() >= today).unwrap_or(true))
+        .collect();
+
+    let mut outcomes = Vec::with_capacity(suggestions.len());
     for suggestion in suggestions {
-        let title = format!("[{}] {}", 
-            suggestion.task.priority.as_deref().unwrap_or("--"),
-            suggestion.task.title
-        );
-        
-        let start_str = suggestion.block.start.to_rfc3339();
-        let end_str = suggestion.block.end.to_rfc3339();
-        
-        // Create calendar event via gog
-        let output = Command::new("gog")
-            .args(&[
-                "calendar", "events", "create",
-                "--title", &title,
-                "--start", &start_str,
-                "--end", &end_str,
-                "--account", &config.google_account,
-            ])
-            .output();
-        
-        match output {
-            Ok(o) if o.status.success() => {
-                success_count += 1;
-                println!("    {} {}", "✓".green(), title.dimmed());
+        let block = CalendarBlock {
+            event_id: String::new(),
+            task_id: suggestion.task.id.clone().unwrap_or_default(),
+            start: suggestion.block.start.with_timezone(&Utc).to_rfc3339(),
+            end: suggestion.block.end.with_timezone(&Utc).to_rfc3339(),
+        };
+
+        // Reuse this task's earliest remaining block, if any
+        let existing = upcoming.iter().position(|b| b.task_id == block.task_id);
+        let outcome = match existing.map(|idx| upcoming.remove(idx)) {
+            Some(old) if parse(&old.start) == Some(suggestion.block.start) && parse(&old.end) == Some(suggestion.block.end) => {
+                BlockOutcome::Unchanged
             }
-            _ => {
-                fail_count += 1;
-                println!("    {} {}", "✗".red(), title.dimmed());
+            Some(_) if dry_run => BlockOutcome::Moved,
+            Some(old) => {
+                if update_calendar_event(config, &old.event_id, suggestion) {
+                    sync_manager.save_calendar_block(&CalendarBlock { event_id: old.event_id, ..block })?;
+                    BlockOutcome::Moved
+                } else {
+                    BlockOutcome::Failed
+                }
+            }
+            None if dry_run => BlockOutcome::Created,
+            None => match create_calendar_event(config, suggestion) {
+                Some(event_id) => {
+                    sync_manager.save_calendar_block(&CalendarBlock { event_id, ..block })?;
+                    BlockOutcome::Created
+                }
+                None => BlockOutcome::Failed,
+            },
+        };
+        outcomes.push(outcome);
+    }
+
+    // Leftover blocks: extras for scheduled tasks, or tasks that are done, gone, or moved to another day
+    let scheduled: std::collections::HashSet<&str> = suggestions.iter().filter_map(|s| s.task.id.as_deref()).collect();
+    let tasks = if upcoming.is_empty() { Vec::new() } else { get_tasks_from_cache(app)? };
+    let mut removed = 0;
+    for block in upcoming {
+        let stale = if scheduled.contains(block.task_id.as_str()) {
+            true
+        } else {
+            match tasks.iter().find(|t| t.id.as_deref() == Some(block.task_id.as_str())) {
+                None => true,
+                Some(task) => {
+                    let block_date = parse(&block.start).map(|dt| dt.format("%Y-%m-%d").to_string());
+                    task.is_done() || block_date.as_deref() != Some(task.date.as_str())
+                }
             }
+        };
+        if stale && dry_run {
+            removed += 1;
+        } else if stale && delete_calendar_event(config, &block.event_id) {
+            sync_manager.remove_calendar_block(&block.event_id)?;
+            removed += 1;
+        }
+    }
+
+    Ok((outcomes, removed))
+}
+
+fn create_calendar_blocks(app: &App, suggestions: &[ScheduleSuggestion], dry_run: bool) -> Result<()> {
+    println!("\n{}", if dry_run { "  Calendar blocks (dry run):" } else { "  Updating calendar blocks..." }.dimmed());
+    
+    let (outcomes, removed) = book_calendar_blocks(app, suggestions, dry_run)?;
+    
+    for (suggestion, outcome) in suggestions.iter().zip(&outcomes) {
+        let title = calendar_block_title(suggestion);
+        match outcome {
+            BlockOutcome::Created => println!("    {} {}", if dry_run { "+" } else { "✓" }.green(), title.dimmed()),
+            BlockOutcome::Moved => println!("    {} {} {}", "↻".cyan(), title.dimmed(), "(moved)".dimmed()),
+            BlockOutcome::Unchanged => println!("    {} {} {}", "=".dimmed(), title.dimmed(), "(already booked)".dimmed()),
+            BlockOutcome::Failed => println!("    {} {}", "✗".red(), title.dimmed()),
         }
     }
     
+    let count = |wanted: BlockOutcome| outcomes.iter().filter(|o| **o == wanted).count();
     println!();
-    if success_count > 0 {
-        println!("{}", format!("  ✓ Created {} calendar events", success_count).green());
+    if dry_run {
+        println!("{}", format!("  Would create {}, move {}, remove {} calendar events", count(BlockOutcome::Created), count(BlockOutcome::Moved), removed).cyan());
+        return Ok(());
     }
-    if fail_count > 0 {
-        println!("{}", format!("  ⚠️  {} events failed to create", fail_count).yellow());
+    if count(BlockOutcome::Created) > 0 {
+        println!("{}", format!("  ✓ Created {} calendar events", count(BlockOutcome::Created)).green());
+    }
+    if count(BlockOutcome::Moved) > 0 {
+        println!("{}", format!("  ↻ Moved {} existing calendar events", count(BlockOutcome::Moved)).cyan());
+    }
+    if removed > 0 {
+        println!("{}", format!("  🗑  Removed {} stale calendar events", removed).dimmed());
+    }
+    if count(BlockOutcome::Failed) > 0 {
+        println!("{}", format!("  ⚠️  {} events failed to update", count(BlockOutcome::Failed)).yellow());
     }
     
     Ok(())
 }
 
-fn cmd_plan(config: &Config) -> Result<()> {
+/// Date range for `plan`: this week by default, next week, an explicit range, or N days from today
+fn plan_range(
+    config: &Config,
+    today: chrono::NaiveDate,
+    next_week: bool,
+    from: Option<chrono::NaiveDate>,
+    to: Option<chrono::NaiveDate>,
+    days: Option<i64>,
+) -> Result<(chrono::NaiveDate, chrono::NaiveDate, String)> {
+    let first_day = start_of_week(today, config.week_start());
+    let range_label = |start: chrono::NaiveDate, end: chrono::NaiveDate| {
+        format!("{} - {}", config.format_date(start, "%a %b %d"), config.format_date(end, "%a %b %d"))
+    };
+
+    let (start, end, label) = if next_week {
+        let start = first_day + chrono::Duration::days(7);
+        (start, start + chrono::Duration::days(6), "Next Week".to_string())
+    } else if let Some(n) = days {
+        if n < 1 {
+            anyhow::bail!("--days must be at least 1");
+        }
+        let end = today + chrono::Duration::days(n - 1);
+        (today, end, range_label(today, end))
+    } else if from.is_some() || to.is_some() {
+        let start = from.unwrap_or(today);
+        let end = to.unwrap_or(start + chrono::Duration::days(6));
+        (start, end, range_label(start, end))
+    } else {
+        (first_day, first_day + chrono::Duration::days(6), "This Week".to_string())
+    };
+
+    if end < start {
+        anyhow::bail!("--to ({}) is before --from ({})", end, start);
+    }
+    Ok((start, end, label))
+}
+
+fn cmd_plan(app: &App, start_of_week: chrono::NaiveDate, end_of_week: chrono::NaiveDate, label: &str) -> Result<()> {
+    let config = &app.config;
     use std::collections::BTreeMap;
     
     let today = Local::now();
     
-    // Get start of week (Monday)
-    let days_since_monday = today.weekday().num_days_from_monday();
-    let start_of_week = (today - chrono::Duration::days(days_since_monday as i64)).date_naive();
-    
-    // Get end of week (Sunday)
-    let end_of_week = start_of_week + chrono::Duration::days(6);
-    
     let start_str = start_of_week.format("%Y-%m-%d").to_string();
     let end_str = end_of_week.format("%Y-%m-%d").to_string();
     
-    println!("{}", "🌱 This Week Plan\n".cyan().bold());
+    println!("{}", output::header(&format!("🌱 {} Plan\n", label)));
     
     // Fetch calendar events
-    let events_output = Command::new("gog")
-        .args(&[
-            "calendar", "events",
-            "--from", &start_str,
-            "--to", &end_str,
-            "--account", &config.google_account,
-            "--json",
-        ])
-        .output();
+    let events_json = fetch_calendar_json(config, &start_str, &end_str).ok();
+    let busy_by_day = match events_json {
+        Some(ref json) => busy_events_by_day(app, json)?,
+        None => BTreeMap::new(),
+    };
     
     let mut events_by_day: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
     
-    if let Ok(output) = events_output {
-        if output.status.success() {
-            if let Ok(events_json) = serde_json::from_slice::<serde_json::Value>(&output.stdout) {
-                if let Some(events) = events_json["events"].as_array() {
-                    for event in events {
-                        if let (Some(summary), Some(start)) = (
-                            event["summary"].as_str(),
-                            event["start"].as_str(),
-                        ) {
-                            // Parse start time and extract date and time
-                            if let Ok(start_dt) = chrono::DateTime::parse_from_rfc3339(start) {
-                                let date_str = start_dt.format("%Y-%m-%d").to_string();
-                                let time_str = start_dt.format("%I:%M %p").to_string();
-                                
-                                // Try to get end time too
-                                let end_str = if let Some(end) = event["end"].as_str() {
-                                    if let Ok(end_dt) = chrono::DateTime::parse_from_rfc3339(end) {
-                                        format!("-{}", end_dt.format("%I:%M %p"))
-                                    } else {
-                                        String::new()
-                                    }
-                                } else {
-                                    String::new()
-                                };
-                                
-                                let event_str = format!("📅 {}{}: {}", time_str, end_str, summary);
-                                events_by_day.entry(date_str).or_insert_with(Vec::new).push((time_str, event_str));
-                            }
-                        }
-                    }
-                }
+    if let Some(events) = events_json.as_ref().and_then(|json| json["events"].as_array()) {
+        for event in events {
+            if let (Some(summary), Some((start_dt, end_dt, all_day))) = (
+                event["summary"].as_str(),
+                event_bounds(event),
+            ) {
+                let date_str = start_dt.format("%Y-%m-%d").to_string();
+                let event_str = if all_day {
+                    format!("📅 All day: {}", summary)
+                } else {
+                    let end_str = if end_dt > start_dt {
+                        format!("-{}", end_dt.format("%I:%M %p"))
+                    } else {
+                        String::new()
+                    };
+                    format!("📅 {}{}: {}", start_dt.format("%I:%M %p"), end_str, summary)
+                };
+                // All-day events sort first
+                let time_str = if all_day { String::new() } else { start_dt.format("%H:%M").to_string() };
+                events_by_day.entry(date_str).or_default().push((time_str, event_str));
             }
         }
     }
     
     // Get tasks for the week
-    let tasks = get_tasks_from_cache()?;
+    let tasks = get_tasks_from_cache(app)?;
     let week_tasks: Vec<&Task> = tasks.iter()
         .filter(|t| t.date >= start_str && t.date <= end_str)
         .collect();
@@ -2308,7 +4574,37 @@ fn cmd_plan(config: &Config) -> Result<()> {
     // Group tasks by date
     let mut tasks_by_day: BTreeMap<String, Vec<&Task>> = BTreeMap::new();
     for task in week_tasks {
-        tasks_by_day.entry(task.date.clone()).or_insert_with(Vec::new).push(task);
+        tasks_by_day.entry(task.date.clone()).or_default().push(task);
+    }
+    
+    // Overdue (dated before the range, or before today when planning ahead) or
+    // undated P0/P1 work needs a home first
+    let overdue_before = start_str.clone().min(today.format("%Y-%m-%d").to_string());
+    let mut overdue: Vec<&Task> = tasks.iter()
+        .filter(|t| !t.is_done())
+        .filter(|t| {
+            if t.date.is_empty() {
+                config.is_critical(t.priority.as_deref())
+            } else {
+                t.date < overdue_before
+            }
+        })
+        .collect();
+    let rank = |t: &Task| config.priority_rank(t.priority.as_deref());
+    overdue.sort_by(|a, b| rank(a).cmp(&rank(b)).then_with(|| a.date.cmp(&b.date)));
+    
+    if !overdue.is_empty() {
+        println!("{}", format!("⚠️  Overdue / unscheduled ({})", overdue.len()).red().bold());
+        for task in &overdue {
+            println!("  [{}]{}{} {} {}",
+                task.priority.as_deref().unwrap_or("--"),
+                task.project.as_ref().map(|p| format!("[{}]", p)).unwrap_or_else(|| "[---]".to_string()).yellow(),
+                task.time.as_ref().map(|t| format!("{{{}}}", t)).unwrap_or_default().cyan(),
+                task.title,
+                if task.date.is_empty() { "(no date)".to_string() } else { format!("({})", task.date) }.dimmed(),
+            );
+        }
+        println!();
     }
     
     // Combine and display
@@ -2320,10 +4616,13 @@ fn cmd_plan(config: &Config) -> Result<()> {
         all_dates.insert(date.clone());
     }
     
+    let mut week_capacity = DayCapacity::default();
+    let mut overcommitted_days = 0;
+    
     for date in all_dates {
         // Format date as "Monday, Jan 20"
         if let Ok(date_parsed) = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
-            let date_label = date_parsed.format("%A, %b %d").to_string();
+            let date_label = config.format_date(date_parsed, "%A, %b %d");
             
             // Highlight today
             if date == today.format("%Y-%m-%d").to_string() {
@@ -2340,92 +4639,674 @@ fn cmd_plan(config: &Config) -> Result<()> {
                     println!("  {}", event);
                 }
             }
-            
-            // Show tasks (sorted by priority)
-            if let Some(tasks) = tasks_by_day.get(&date) {
-                let mut sorted_tasks = tasks.to_vec();
-                sorted_tasks.sort_by(|a, b| {
-                    let a_pri = a.priority.as_deref().unwrap_or("P9");
-                    let b_pri = b.priority.as_deref().unwrap_or("P9");
-                    a_pri.cmp(b_pri)
-                });
-                
-                for task in sorted_tasks {
-                    let priority_emoji = match task.priority.as_deref() {
-                        Some("P0") => "🔴",
-                        Some("P1") => "🟡",
-                        Some("P2") => "🟢",
-                        Some("P3") => "🔵",
-                        _ => "⚪",
-                    };
-                    
-                    let time_str = task.time.as_ref().map(|t| format!("{{{}}}", t)).unwrap_or_default();
-                    let project_str = task.project.as_ref().map(|p| format!("[{}]", p)).unwrap_or_else(|| "[---]".to_string());
-                    let tags_str = if task.tags.is_empty() {
-                        String::new()
-                    } else {
-                        format!(" {}", task.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" "))
-                    };
-                    
-                    println!("  {} [{}]{}{}{}{}", 
-                        priority_emoji,
-                        task.priority.as_deref().unwrap_or("--"),
-                        project_str.yellow(),
-                        time_str.cyan(),
-                        tags_str.dimmed(),
-                        format!(" {}", task.title)
-                    );
+            
+            // Show tasks (sorted by priority)
+            if let Some(tasks) = tasks_by_day.get(&date) {
+                let mut sorted_tasks = tasks.to_vec();
+                sorted_tasks.sort_by_key(|t| config.priority_rank(t.priority.as_deref()));
+                
+                for task in sorted_tasks {
+                    let priority_emoji = config.priority_emoji(task.priority.as_deref());
+                    
+                    let time_str = task.time.as_ref().map(|t| format!("{{{}}}", t)).unwrap_or_default();
+                    let project_str = task.project.as_ref().map(|p| format!("[{}]", p)).unwrap_or_else(|| "[---]".to_string());
+                    let tags_str = if task.tags.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" {}", task.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" "))
+                    };
+                    
+                    println!("  {} [{}]{}{}{} {}", 
+                        priority_emoji,
+                        task.priority.as_deref().unwrap_or("--"),
+                        project_str.yellow(),
+                        time_str.cyan(),
+                        tags_str.dimmed(),
+                        task.title
+                    );
+                }
+            }
+            // Capacity: is this day actually feasible?
+            let day_events = busy_by_day.get(&date).map(|v| v.as_slice()).unwrap_or(&[]);
+            let day_tasks = tasks_by_day.get(&date).map(|v| v.as_slice()).unwrap_or(&[]);
+            let capacity = day_capacity(&config.work_hours, &date_parsed, day_events, day_tasks);
+            let summary = format!("  ⏱  Meetings {:.1}h · Tasks {:.1}h · Free {:.1}h",
+                capacity.meeting_minutes as f64 / 60.0,
+                capacity.task_minutes as f64 / 60.0,
+                capacity.free_minutes as f64 / 60.0,
+            );
+            if capacity.is_overcommitted() {
+                println!("{}", format!("{}  ⚠️  Overcommitted by {:.1}h", summary,
+                    (capacity.task_minutes - capacity.free_minutes) as f64 / 60.0).red());
+                overcommitted_days += 1;
+            } else {
+                println!("{}", summary.dimmed());
+            }
+            week_capacity.add(&capacity);
+            
+
+            println!(); // Blank line between days
+        }
+    }
+    
+    println!("{}", format!("Total: {:.1}h meetings, {:.1}h of tasks, {:.1}h free",
+        week_capacity.meeting_minutes as f64 / 60.0,
+        week_capacity.task_minutes as f64 / 60.0,
+        week_capacity.free_minutes as f64 / 60.0,
+    ).bold());
+    if overcommitted_days > 0 {
+        println!("{}", format!("⚠️  {} overcommitted day(s) - move or drop something", overcommitted_days).red());
+    }
+    
+    // Offer to spread overdue work over the rest of the week
+    if !overdue.is_empty() && std::io::stdin().is_terminal() {
+        print!("\n{}", format!("Distribute {} overdue tasks across these days? (d=distribute, any other key=skip): ", overdue.len()).bold());
+        stdout().flush()?;
+        let choice = read_single_key()?;
+        println!("{}", choice);
+        
+        if choice == 'd' || choice == 'D' {
+            let mut days = Vec::new();
+            let mut day = today.date_naive().max(start_of_week);
+            while day <= end_of_week {
+                if config.work_hours.window_for(&day).is_some() {
+                    let date = day.format("%Y-%m-%d").to_string();
+                    let day_events = busy_by_day.get(&date).map(|v| v.as_slice()).unwrap_or(&[]);
+                    let day_tasks = tasks_by_day.get(&date).map(|v| v.as_slice()).unwrap_or(&[]);
+                    let capacity = day_capacity(&config.work_hours, &day, day_events, day_tasks);
+                    days.push((day, capacity.free_minutes - capacity.task_minutes));
+                }
+                day += chrono::Duration::days(1);
+            }
+            
+            let assignments = distribute_by_capacity(&overdue, &days);
+            let mut moved: Vec<Task> = Vec::new();
+            for (task, date) in overdue.iter().zip(&assignments) {
+                let Some(date) = date else { continue };
+                let mut task = (*task).clone();
+                task.date = date.format("%Y-%m-%d").to_string();
+                println!("  {} {} → {}", task.short_id().dimmed(), task.title, config.format_date(*date, "%a %b %d").cyan());
+                moved.push(task);
+            }
+            
+            if moved.is_empty() {
+                println!("{}", "  No working days left in this range.".yellow());
+            } else {
+                save_tasks_locally(&app.db, config, &moved)?;
+                println!("\n{}", "Pushing changes to Google...".dimmed());
+                push_dirty_tasks_to_google(app)?;
+                println!("{}", format!("✓ Distributed {} tasks", moved.len()).green());
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+/// Assign each task (already in priority order) to the earliest day whose remaining
+/// capacity fits its estimate (30m if unestimated), else the roomiest day.
+/// `days` holds (date, free minutes left); returns one date per task (None if no days).
+fn distribute_by_capacity(tasks: &[&Task], days: &[(chrono::NaiveDate, i64)]) -> Vec<Option<chrono::NaiveDate>> {
+    let mut remaining: Vec<(chrono::NaiveDate, i64)> = days.to_vec();
+    tasks.iter()
+        .map(|task| {
+            let minutes = task.time.as_deref().map(|t| parse_time_to_minutes(t) as i64).filter(|m| *m > 0).unwrap_or(30);
+            let idx = remaining.iter().position(|(_, left)| *left >= minutes)
+                .or_else(|| (0..remaining.len()).max_by_key(|&i| (remaining[i].1, std::cmp::Reverse(i))))?;
+            remaining[idx].1 -= minutes;
+            Some(remaining[idx].0)
+        })
+        .collect()
+}
+
+/// Live focus view: the top critical task with a countdown against its estimate,
+/// refreshed from the cache every few seconds and synced on the usual throttle
+fn cmd_focus_watch(app: &App, include_future: bool, context: Option<&str>) -> Result<()> {
+    let config = &app.config;
+    let sync_manager = &app.db;
+    let today_str = Local::now().format("%Y-%m-%d").to_string();
+
+    // Focused time today survives restarts: stored as "YYYY-MM-DD|seconds"
+    let mut focused_today = sync_manager.get_state("focus_elapsed")?
+        .and_then(|v| v.split_once('|').and_then(|(day, secs)| (day == today_str).then(|| secs.parse::<i64>().ok()).flatten()))
+        .unwrap_or(0);
+
+    let sync_every = chrono::Duration::minutes(config.sync_throttle_minutes.max(1));
+    let mut last_sync = Local::now();
+    let mut sync_note = String::new();
+    let mut tasks = get_tasks_from_cache(app)?;
+    let mut last_reload = Local::now();
+    let mut current: Option<(String, chrono::DateTime<Local>)> = None;
+    let mut last_tick = Local::now();
+    // Time on the task at the top counts toward its tracked time when it changes or we quit
+    let log_focus = |current: &Option<(String, chrono::DateTime<Local>)>, now: chrono::DateTime<Local>| -> Result<()> {
+        match current {
+            Some((id, started)) if !id.is_empty() && now > *started => sync_manager.log_time(id, (now - *started).num_seconds(), "focus"),
+            _ => Ok(()),
+        }
+    };
+
+    loop {
+        let now = Local::now();
+
+        // Pick up completions and edits made elsewhere
+        if !sync::is_offline() && now - last_sync >= sync_every {
+            sync_note = match sync_with_google(app, false) {
+                Ok(_) => String::new(),
+                Err(e) => format!("⚠ sync failed: {}", e),
+            };
+            last_sync = now;
+            tasks = get_tasks_from_cache(app)?;
+            last_reload = now;
+        } else if now - last_reload >= chrono::Duration::seconds(15) {
+            tasks = get_tasks_from_cache(app)?;
+            last_reload = now;
+        }
+
+        let critical = focus_tasks(&app.config, &tasks, &today_str, include_future, false, context);
+        if critical.is_empty() {
+            log_focus(&current, now)?;
+            current = None;
+        } else {
+            focused_today += (now - last_tick).num_seconds();
+            let top_id = critical[0].id.clone().unwrap_or_default();
+            if current.as_ref().map(|(id, _)| id != &top_id).unwrap_or(true) {
+                log_focus(&current, now)?;
+                current = Some((top_id, now));
+            }
+        }
+        last_tick = now;
+
+        execute!(stdout(), Clear(ClearType::All), MoveTo(0, 0))?;
+        println!("{}  {}", output::header("🎯 Focus"), format!("{}  (q=quit, r=refresh)", now.format("%H:%M")).dimmed());
+        println!();
+
+        match (critical.first(), &current) {
+            (Some(top), Some((_, started))) => {
+                let project_str = top.project.as_ref().map(|p| format!("[{}]", p)).unwrap_or_default();
+                println!("  {} [{}]{} {}", "NOW:".green().bold(), top.priority.as_deref().unwrap_or("--"), project_str.yellow(), top.title.bold());
+
+                let elapsed = (now - *started).num_seconds();
+                match top.time.as_deref().map(|t| parse_time_to_minutes(t) as i64 * 60).filter(|s| *s > 0) {
+                    Some(estimate) if elapsed <= estimate => {
+                        println!("  ⏳ {} left of {}", format_clock(estimate - elapsed).cyan().bold(), top.time.as_deref().unwrap_or_default());
+                    }
+                    Some(estimate) => {
+                        println!("  ⏰ {} over {}", format_clock(elapsed - estimate).red().bold(), top.time.as_deref().unwrap_or_default());
+                    }
+                    None => println!("  ⏱  {} (no estimate)", format_clock(elapsed).cyan()),
+                }
+
+                if critical.len() > 1 {
+                    println!("\n{}", "  Up next:".dimmed());
+                    for (idx, task) in critical.iter().enumerate().skip(1).take(5) {
+                        let time_str = task.time.as_ref().map(|t| format!("{{{}}}", t)).unwrap_or_default();
+                        println!("    {}. [{}]{} {}", idx + 1, task.priority.as_deref().unwrap_or("--"), time_str.cyan(), task.title);
+                    }
+                }
+            }
+            _ => println!("{}", "  No critical tasks! You're clear! ✨".green().bold()),
+        }
+
+        println!();
+        println!("{}", format!("  Today: {} focused · {} critical left · synced {}m ago",
+            format_clock(focused_today), critical.len(), (now - last_sync).num_minutes()).dimmed());
+        if !sync_note.is_empty() {
+            println!("  {}", sync_note.yellow());
+        }
+        stdout().flush()?;
+
+        // Wait up to a second for a key
+        enable_raw_mode()?;
+        let key = if event::poll(std::time::Duration::from_secs(1))? {
+            match event::read()? {
+                Event::Key(KeyEvent { code, kind: KeyEventKind::Press, .. }) => Some(code),
+                _ => None,
+            }
+        } else {
+            None
+        };
+        disable_raw_mode()?;
+
+        match key {
+            Some(KeyCode::Char('q')) | Some(KeyCode::Esc) => {
+                log_focus(&current, Local::now())?;
+                break;
+            }
+            Some(KeyCode::Char('r')) => {
+                // Force a sync on the next tick
+                last_sync = now - sync_every;
+            }
+            _ => {}
+        }
+    }
+
+    sync_manager.set_state("focus_elapsed", &format!("{}|{}", today_str, focused_today))?;
+    Ok(())
+}
+
+/// Seconds as "H:MM:SS", or "MM:SS" under an hour
+fn format_clock(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (h, m, s) = (seconds / 3600, (seconds % 3600) / 60, seconds % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{:02}:{:02}", m, s)
+    }
+}
+
+/// Whether focus would show a top-priority task that's past its date
+fn has_overdue_p0(config: &Config, tasks: &[Task], include_future: bool, context: Option<&str>) -> bool {
+    let today_str = Local::now().format("%Y-%m-%d").to_string();
+    focus_tasks(config, tasks, &today_str, include_future, false, context).iter()
+        .any(|t| t.priority.as_deref() == config.top_priority() && t.date < today_str)
+}
+
+/// Critical tasks, top priority first: every P0, plus P1s that are overdue or due today
+/// (the first two configured priorities)
+fn focus_tasks<'a>(config: &Config, tasks: &'a [Task], today_str: &str, include_future: bool, include_done: bool, context: Option<&str>) -> Vec<&'a Task> {
+    let top = config.top_priority();
+    let p0 = tasks.iter().filter(|t| t.priority.is_some() && t.priority.as_deref() == top);
+    let p1 = tasks.iter().filter(|t| {
+        t.priority.as_deref() != top && config.is_critical(t.priority.as_deref()) && t.date.as_str() <= today_str
+    });
+    p0.chain(p1)
+        .filter(|t| include_done || !t.is_done())
+        .filter(|t| include_future || !starts_later(t, today_str))
+        .filter(|t| context.is_none_or(|c| in_context(t, c)))
+        .collect()
+}
+
+fn cmd_focus(app: &App, include_future: bool, include_done: bool, context: Option<&str>, absolute: bool) -> Result<()> {
+    let today_str = Local::now().format("%Y-%m-%d").to_string();
+    if let Some(template) = app.config.formats.get("focus") {
+        template::validate(template)?;
+    }
+    let interactive = std::io::stdin().is_terminal();
+
+    // Tasks completed from this view (as they were before), so they can be toggled back
+    let mut completed: Vec<Task> = Vec::new();
+
+    loop {
+        let tasks = get_tasks_from_cache(app)?;
+        let critical = focus_tasks(&app.config, &tasks, &today_str, include_future, include_done, context);
+
+        if output::is_porcelain() {
+            for task in critical {
+                println!("{}", output::porcelain_line(task));
+            }
+            return Ok(());
+        }
+
+        let nudges = habits::nudges(app, &tasks)?;
+        if critical.is_empty() && completed.is_empty() {
+            println!("{}", output::plain("🎯 Focus - No critical tasks! You're clear! ✨").green().bold());
+            for nudge in &nudges {
+                println!("{}", nudge.yellow());
+            }
+            return Ok(());
+        }
+
+        print_focus(&app.config, &critical, &completed, &today_str, absolute);
+        for nudge in &nudges {
+            println!("{}", nudge.yellow());
+        }
+
+        if !interactive {
+            return Ok(());
+        }
+
+        print!("{}", "1-9=toggle done  b=bump to tomorrow  s=snooze  q=quit: ".bold());
+        stdout().flush()?;
+        let key = read_single_key()?;
+        println!("{}", key);
+
+        let rows = critical.len() + completed.len();
+        let pick = |prompt: &str| -> Result<Option<usize>> {
+            let key = if prompt.is_empty() {
+                key
+            } else {
+                print!("{}", prompt.yellow());
+                stdout().flush()?;
+                let key = read_single_key()?;
+                println!("{}", key);
+                key
+            };
+            Ok(key.to_digit(10).map(|d| d as usize).filter(|d| (1..=rows).contains(d)).map(|d| d - 1))
+        };
+
+        match key {
+            'q' | 'Q' | '\x1b' => return Ok(()),
+            '1'..='9' => {
+                let Some(idx) = pick("")? else { continue };
+                if idx < critical.len() && critical[idx].is_done() {
+                    reopen_task(app, critical[idx])?;
+                } else if idx < critical.len() {
+                    let original = critical[idx].clone();
+                    complete_task(app, &original)?;
+                    completed.push(original);
+                } else {
+                    // Toggle back: restore the task as it was and reopen it in Google
+                    let original = completed.remove(idx - critical.len());
+                    reopen_task(app, &original)?;
+                }
+            }
+            'b' | 'B' => {
+                let Some(idx) = pick("  Bump which #? ")?.filter(|i| *i < critical.len()) else { continue };
+                let mut task = critical[idx].clone();
+                task.date = (Local::now() + chrono::Duration::days(1)).format("%Y-%m-%d").to_string();
+                update_task_locally(app, &task)?;
+                record_bumps(&app.db, std::slice::from_ref(&task))?;
+                push_dirty_tasks(app)?;
+                println!("{}", format!("  📅 Bumped to tomorrow: {}", task.title).green());
+            }
+            's' | 'S' => {
+                let Some(idx) = pick("  Snooze which #? ")?.filter(|i| *i < critical.len()) else { continue };
+                if let TriageChoice::Defer(task) = prompt_defer(critical[idx])? {
+                    update_task_locally(app, &task)?;
+                    push_dirty_tasks(app)?;
+                }
+            }
+            _ => {}
+        }
+        println!();
+    }
+}
+
+/// How good a pick `task` is right now, or None if it doesn't qualify: higher priority,
+/// overdue dates, a snug fit for the available minutes, and a matching context all score up
+fn next_task_score(config: &Config, task: &Task, today_str: &str, minutes: Option<i64>, context: Option<&str>) -> Option<i64> {
+    if task.is_done() || (!task.date.is_empty() && task.date.as_str() > today_str) {
+        return None;
+    }
+
+    // By rank: P0 1000, P1 600, P2 300, P3 150 with the default priorities
+    let mut score = [1000, 600, 300, 150].get(config.priority_rank(task.priority.as_deref())).copied().unwrap_or(50);
+
+    // Overdue work climbs (up to two weeks' worth), today's gets a nudge
+    if let (Ok(date), Ok(today)) = (
+        chrono::NaiveDate::parse_from_str(&task.date, "%Y-%m-%d"),
+        chrono::NaiveDate::parse_from_str(today_str, "%Y-%m-%d"),
+    ) {
+        let days_late = (today - date).num_days();
+        score += if days_late > 0 { days_late.min(14) * 20 } else { 100 };
+    }
+
+    let estimate = task.time.as_deref().map(|t| parse_time_to_minutes(t) as i64).filter(|m| *m > 0);
+    if let Some(available) = minutes {
+        match estimate {
+            Some(est) if est > available => return None,
+            // Prefer tasks that use the window well
+            Some(est) => score += est * 100 / available.max(1),
+            None => score -= 100,
+        }
+    }
+
+    if let Some(wanted) = context {
+        let wanted = wanted.trim_start_matches('@').to_lowercase();
+        match task.context.as_deref() {
+            Some(ctx) if ctx.trim_start_matches('@').to_lowercase() == wanted => score += 200,
+            Some(_) => return None,
+            None => {}
+        }
+    }
+
+    Some(score)
+}
+
+fn cmd_next(app: &App, minutes: Option<i64>, context: Option<&str>) -> Result<()> {
+    let today_str = Local::now().format("%Y-%m-%d").to_string();
+    let tasks = get_tasks_from_cache(app)?;
+    let mut skipped: std::collections::HashSet<String> = std::collections::HashSet::new();
+
+    loop {
+        let pick = tasks.iter()
+            .filter(|t| !skipped.contains(t.id.as_deref().unwrap_or_default()))
+            .filter_map(|t| next_task_score(&app.config, t, &today_str, minutes, context).map(|score| (score, t)))
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, t)| t);
+
+        let Some(task) = pick else {
+            if output::is_quiet() {
+                std::process::exit(output::EXIT_NOTHING);
+            }
+            let msg = if skipped.is_empty() { "Nothing fits right now. ✨" } else { "That's everything that fits. ✨" };
+            println!("{}", msg.green().bold());
+            return Ok(());
+        };
+
+        if output::is_porcelain() {
+            println!("{}", output::porcelain_line(task));
+            return Ok(());
+        }
+
+        let time_str = task.time.as_ref().map(|t| format!("{{{}}}", t)).unwrap_or_default();
+        let project_str = task.project.as_ref().map(|p| format!("[{}]", p)).unwrap_or_default();
+        println!("\n{}", "👉 Do this now:".cyan().bold());
+        println!("\n   [{}]{}{} {}\n", task.priority.as_deref().unwrap_or("--"), project_str.yellow(), time_str.cyan(), task.title.bold());
+
+        if !std::io::stdin().is_terminal() {
+            return Ok(());
+        }
+
+        print!("{}", "t=start timer  d=done  s=skip  q=quit: ".bold());
+        stdout().flush()?;
+        let key = read_single_key()?;
+        println!("{}", key);
+
+        match key {
+            't' | 'T' => {
+                if run_task_timer(app, task)? {
+                    complete_task(app, task)?;
                 }
+                return Ok(());
             }
-            
-            println!(); // Blank line between days
+            'd' | 'D' => {
+                complete_task(app, task)?;
+                return Ok(());
+            }
+            's' | 'S' => {
+                skipped.insert(task.id.clone().unwrap_or_default());
+            }
+            _ => return Ok(()),
         }
     }
-    
+}
+
+/// Open, started tasks with an estimate of at most `minutes`, by priority then due date
+/// (undated last)
+fn tasks_that_fit<'a>(config: &Config, tasks: &'a [Task], minutes: i64, today_str: &str) -> Vec<&'a Task> {
+    let mut fits: Vec<&Task> = tasks.iter()
+        .filter(|t| !t.is_done() && !starts_later(t, today_str))
+        .filter(|t| t.time.as_deref().map(parse_time_to_minutes).is_some_and(|est| est > 0 && est as i64 <= minutes))
+        .collect();
+    fits.sort_by_key(|t| (config.priority_rank(t.priority.as_deref()), t.date.is_empty(), t.date.clone()));
+    fits
+}
+
+fn cmd_fit(app: &App, minutes: i64, context: Option<&str>) -> Result<()> {
+    let config = &app.config;
+    let today_str = Local::now().format("%Y-%m-%d").to_string();
+    let tasks = get_tasks_from_cache(app)?;
+    let mut fits = tasks_that_fit(config, &tasks, minutes, &today_str);
+    if let Some(ctx) = context {
+        fits.retain(|t| in_context(t, ctx));
+    }
+
+    if output::is_porcelain() {
+        for task in fits {
+            println!("{}", output::porcelain_line(task));
+        }
+        return Ok(());
+    }
+    if fits.is_empty() {
+        println!("{}", format!("Nothing estimated at {}m or less.", minutes).yellow());
+        return Ok(());
+    }
+
+    println!("{}", output::header(&format!("⏱  Fits in {} ({} tasks)\n", format_time_from_minutes(minutes as i32), fits.len())));
+    for task in fits {
+        println!("{} {}", task.short_id().dimmed(), task.format(config));
+    }
     Ok(())
 }
 
-fn cmd_focus(_config: &Config) -> Result<()> {
-    let tasks = get_tasks_from_cache()?;
-    let today = Local::now();
-    let today_str = today.format("%Y-%m-%d").to_string();
+/// Mark a task done locally and push it
+fn complete_task(app: &App, task: &Task) -> Result<()> {
+    let mut done = task.clone();
+    done.completed = true;
+    update_task_locally(app, &done)?;
+    push_dirty_tasks(app)?;
+    println!("{}", format!("✓ Done: {}", task.title).green());
+    prompt_follow_up(app, task)
+}
 
-    // Filter for critical tasks:
-    // - P0 (any date)
-    // - P1 that are overdue or due today
-    let critical: Vec<&Task> = tasks.iter()
-        .filter(|t| {
-            if let Some(ref priority) = t.priority {
-                if priority == "P0" {
-                    return true;
-                }
-                if priority == "P1" {
-                    return t.date <= today_str;
+/// The next action after `done`, from what was typed: same list, and the project and
+/// context carry over unless the text sets its own
+fn follow_up_task(config: &Config, done: &Task, input: &str) -> Task {
+    let mut follow_up = Task::parse_with_config(input, &done.list, Some(config));
+    follow_up.project = follow_up.project.or_else(|| done.project.clone());
+    follow_up.context = follow_up.context.or_else(|| done.context.clone());
+    follow_up.notes = Some(format!("Follow-up to: {} ({})", done.title, done.short_id()));
+    follow_up
+}
+
+/// With `follow_up_prompt` on, ask for the next action after completing `done` and create it
+fn prompt_follow_up(app: &App, done: &Task) -> Result<()> {
+    if !app.config.follow_up_prompt || !std::io::stdin().is_terminal() || output::is_quiet() {
+        return Ok(());
+    }
+    print!("{} ", "  Any follow-up? (Enter to skip):".yellow());
+    stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    if input.trim().is_empty() {
+        return Ok(());
+    }
+
+    let Some(list_id) = done.list_id.as_deref() else {
+        return Ok(());
+    };
+    let mut follow_up = follow_up_task(&app.config, done, input.trim());
+    let id = match create_task_in_google(app, list_id, &follow_up.format(&app.config), None) {
+        Ok(id) => id,
+        Err(e) => {
+            eprintln!("{}", format!("  ⚠️  Couldn't add the follow-up: {:#}", e).yellow());
+            return Ok(());
+        }
+    };
+    follow_up.id = Some(id);
+    follow_up.list_id = Some(list_id.to_string());
+    update_task_locally(app, &follow_up)?;
+    push_dirty_tasks(app)?;
+    println!("{}", format!("  ↳ Follow-up: {} {}", follow_up.short_id(), follow_up.title).green());
+    Ok(())
+}
+
+/// Count up against the task's estimate until a key is pressed.
+/// Returns true if the task was finished ('d'), false if the timer was stopped.
+/// The time spent is tracked against the task either way.
+fn run_task_timer(app: &App, task: &Task) -> Result<bool> {
+    let started = Local::now();
+    let estimate = task.time.as_deref().map(|t| parse_time_to_minutes(t) as i64 * 60).filter(|s| *s > 0);
+    println!("{}", "  d=done  q=stop".dimmed());
+
+    enable_raw_mode()?;
+    let finished = loop {
+        let elapsed = (Local::now() - started).num_seconds();
+        let status = match estimate {
+            Some(est) if elapsed <= est => format!("⏳ {} left", format_clock(est - elapsed)).cyan().to_string(),
+            Some(est) => format!("⏰ {} over", format_clock(elapsed - est)).red().to_string(),
+            None => format!("⏱  {}", format_clock(elapsed)).cyan().to_string(),
+        };
+        print!("\r  {}   ", status);
+        stdout().flush()?;
+
+        if event::poll(std::time::Duration::from_secs(1))? {
+            if let Event::Key(KeyEvent { code, kind: KeyEventKind::Press, .. }) = event::read()? {
+                match code {
+                    KeyCode::Char('d') | KeyCode::Char('D') => break true,
+                    KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => break false,
+                    _ => {}
                 }
             }
-            false
-        })
-        .collect();
+        }
+    };
+    disable_raw_mode()?;
+    println!();
+
+    let spent = (Local::now() - started).num_seconds();
+    println!("{}", format!("  Spent {}", format_clock(spent)).dimmed());
+    if let Some(id) = task.id.as_deref() {
+        app.db.log_time(id, spent, "timer")?;
+    }
+    Ok(finished)
+}
+
+/// Mark a completed task open again, here and in Google
+fn reopen_task(app: &App, task: &Task) -> Result<()> {
+    let mut reopened = task.clone();
+    reopened.completed = false;
+    reopened.priority = reopened.priority.filter(|p| !app.config.is_done_priority(p));
+    update_task_locally(app, &reopened)?;
+    push_dirty_tasks(app)?;
+    reopen_task_in_google(app, &reopened)?;
+    println!("{}", format!("  ↺ Reopened: {}", task.title).yellow());
+    Ok(())
+}
 
-    if critical.is_empty() {
-        println!("{}", "🎯 Focus - No critical tasks! You're clear! ✨".green().bold());
+/// Mark a task as not completed in Google (the title push alone leaves it completed)
+fn reopen_task_in_google(app: &App, task: &Task) -> Result<()> {
+    let (Some(task_id), Some(list_id)) = (task.id.as_deref(), task.list_id.as_deref()) else {
+        anyhow::bail!("Task missing ID or list_id");
+    };
+    // Offline the completion was never pushed, so there's nothing to undo remotely
+    if sync::is_offline() {
         return Ok(());
     }
+    app.google.reopen(list_id, task_id).context("Failed to reopen task")
+}
 
-    // Separate P0 and P1 tasks
-    let p0_tasks: Vec<&&Task> = critical.iter().filter(|t| t.priority.as_deref() == Some("P0")).collect();
-    let p1_tasks: Vec<&&Task> = critical.iter().filter(|t| t.priority.as_deref() == Some("P1")).collect();
+/// Numbered focus list: P0s, then due P1s, then anything completed from this view
+fn print_focus(config: &Config, critical: &[&Task], completed: &[Task], today_str: &str, absolute: bool) {
+    // Separate the top priority from the second one
+    let top = config.top_priority();
+    let (p0_tasks, p1_tasks): (Vec<&&Task>, Vec<&&Task>) = critical.iter().partition(|t| t.priority.as_deref() == top);
+    let heading = |tasks: &[&&Task], suffix: &str| {
+        let priority = tasks.first().and_then(|t| t.priority.as_deref());
+        format!("{} {} ({}):", config.priority_emoji(priority), priority.unwrap_or_default(), suffix)
+            .color(config.priority_color(priority))
+            .bold()
+    };
+    // `formats.focus` replaces the "[PROJECT]{time} title" part of each line
+    let line = |task: &Task| match config.formats.get("focus") {
+        Some(template) => template::render(template, task, Local::now().date_naive()),
+        None => {
+            let time_str = task.time.as_ref().map(|t| format!("{{{}}}", t)).unwrap_or_default();
+            let project_str = task.project.as_ref().map(|p| format!("[{}]", p)).unwrap_or_default();
+            format!("{}{} {}", project_str.yellow(), time_str.cyan(), task.title)
+        }
+    };
+    // Overdue tasks get a warning; ones due later say when
+    let due = |task: &Task| {
+        let label = template::day_label(&task.date, Local::now().date_naive(), absolute);
+        match task.date.as_str() {
+            "" => String::new(),
+            date if date < today_str => format!(" ⚠️ due {}", label),
+            date if date > today_str => format!(" · due {}", label).dimmed().to_string(),
+            _ => String::new(),
+        }
+    };
 
-    println!("{}", "🎯 Focus - Critical Tasks\n".cyan().bold());
+    println!("{}", output::header("🎯 Focus - Critical Tasks\n"));
 
     let mut total_minutes = 0;
 
     if !p0_tasks.is_empty() {
-        println!("{}", "🔴 P0 (do first):".red().bold());
+        println!("{}", heading(&p0_tasks, "do first"));
         for (idx, task) in p0_tasks.iter().enumerate() {
-            let time_str = task.time.as_ref().map(|t| format!("{{{}}}", t)).unwrap_or_default();
-            let project_str = task.project.as_ref().map(|p| format!("[{}]", p)).unwrap_or_default();
-            println!("  {}. {}{} {}", idx + 1, project_str.yellow(), time_str.cyan(), task.title);
+            println!("  {}. {}{}{}", idx + 1, done_marker(task), line(task), due(task));
             
             // Add to total
             if let Some(ref time) = task.time {
@@ -2436,12 +5317,9 @@ fn cmd_focus(_config: &Config) -> Result<()> {
     }
 
     if !p1_tasks.is_empty() {
-        println!("{}", "🟡 P1 (due today):".yellow().bold());
+        println!("{}", heading(&p1_tasks, "due today"));
         for (idx, task) in p1_tasks.iter().enumerate() {
-            let time_str = task.time.as_ref().map(|t| format!("{{{}}}", t)).unwrap_or_default();
-            let project_str = task.project.as_ref().map(|p| format!("[{}]", p)).unwrap_or_default();
-            let overdue_mark = if task.date < today_str { " ⚠️" } else { "" };
-            println!("  {}. {}{} {}{}", p0_tasks.len() + idx + 1, project_str.yellow(), time_str.cyan(), task.title, overdue_mark);
+            println!("  {}. {}{}{}", p0_tasks.len() + idx + 1, done_marker(task), line(task), due(task));
             
             // Add to total
             if let Some(ref time) = task.time {
@@ -2451,6 +5329,14 @@ fn cmd_focus(_config: &Config) -> Result<()> {
         println!();
     }
 
+    if !completed.is_empty() {
+        println!("{}", "✅ Done (press the number again to undo):".green().bold());
+        for (idx, task) in completed.iter().enumerate() {
+            println!("  {}. {}", critical.len() + idx + 1, task.title.dimmed().strikethrough());
+        }
+        println!();
+    }
+
     // Show total planned time
     let hours = total_minutes / 60;
     let mins = total_minutes % 60;
@@ -2461,8 +5347,6 @@ fn cmd_focus(_config: &Config) -> Result<()> {
     } else if mins > 0 {
         println!("{}", format!("Total planned: {}m", mins).dimmed());
     }
-
-    Ok(())
 }
 
 fn parse_time_to_minutes(time_str: &str) -> i32 {
@@ -2475,10 +5359,266 @@ fn parse_time_to_minutes(time_str: &str) -> i32 {
     }
 }
 
-fn cmd_list(config: &Config, all: bool, sort: &str, reverse: bool, status_filter: Option<&str>, context_filter: Option<&str>, priority_filter: Option<&str>, project_filter: Option<&str>, tag_filter: Option<&str>, days: Option<i64>, limit: Option<usize>, grouped: bool) -> Result<()> {
-    let tasks = get_tasks_from_cache()?;
+/// Narrows `triage` to one task or a slice of the backlog
+/// (`--project` is already the project-pass flag, hence `--in-project`)
+#[derive(clap::Args, Debug, Clone, Default)]
+struct TriageScope {
+    /// Triage just this task (full or partial ID)
+    id: Option<String>,
+    /// Only tasks in this project
+    #[arg(short = 'j', long)]
+    in_project: Option<String>,
+    /// Only tasks with this tag
+    #[arg(short = 't', long)]
+    tag: Option<String>,
+    /// Only tasks from this list (name or list ID)
+    #[arg(long)]
+    list: Option<String>,
+    /// Only tasks created recently: 2d, 1w, or a YYYY-MM-DD date
+    #[arg(long)]
+    since: Option<String>,
+    /// Also triage tasks whose start date hasn't arrived yet
+    #[arg(long)]
+    include_future: bool,
+}
+
+impl TriageScope {
+    fn is_empty(&self) -> bool {
+        self.id.is_none() && self.in_project.is_none() && self.tag.is_none()
+            && self.list.is_none() && self.since.is_none()
+    }
+
+    /// Keep only tasks inside the scope
+    fn retain(&self, tasks: &mut Vec<Task>) -> Result<()> {
+        if let Some(ref id) = self.id {
+            let matching: Vec<Task> = find_tasks_by_id_prefix(tasks, id).into_iter().cloned().collect();
+            match matching.len() {
+                0 => anyhow::bail!("No task found with ID starting with '{}'", id),
+                1 => *tasks = matching,
+                n => anyhow::bail!("{} tasks match '{}'. Please be more specific", n, id),
+            }
+        }
+
+        if let Some(ref project) = self.in_project {
+            let project_lower = project.to_lowercase();
+            tasks.retain(|t| {
+                t.project.as_ref()
+                    .map(|p| p.to_lowercase().contains(&project_lower))
+                    .unwrap_or(false)
+            });
+        }
+
+        if let Some(ref tag) = self.tag {
+            let tag_lower = tag.trim_start_matches('#').to_lowercase();
+            tasks.retain(|t| t.tags.iter().any(|tg| tg.to_lowercase().contains(&tag_lower)));
+        }
+
+        if let Some(ref list) = self.list {
+            let list_lower = list.to_lowercase();
+            tasks.retain(|t| {
+                t.list.to_lowercase().contains(&list_lower)
+                    || t.list_id.as_ref().map(|l| l.to_lowercase() == list_lower).unwrap_or(false)
+            });
+        }
+
+        if let Some(ref since) = self.since {
+            let cutoff = parse_since(since)?;
+            tasks.retain(|t| t.created.as_deref().unwrap_or(&t.date) >= cutoff.as_str());
+        }
+
+        Ok(())
+    }
+}
+
+/// Turn "2d" / "1w" / "YYYY-MM-DD" into the earliest date to include
+fn parse_since(since: &str) -> Result<String> {
+    let since = since.trim().to_lowercase();
+    if chrono::NaiveDate::parse_from_str(&since, "%Y-%m-%d").is_ok() {
+        return Ok(since);
+    }
+
+    let (num, unit) = since.split_at(since.len().saturating_sub(1));
+    let n: i64 = num.parse()
+        .with_context(|| format!("Invalid --since '{}', expected e.g. 2d, 1w, or YYYY-MM-DD", since))?;
+    let days = match unit {
+        "d" => n,
+        "w" => n * 7,
+        _ => anyhow::bail!("Invalid --since '{}', expected e.g. 2d, 1w, or YYYY-MM-DD", since),
+    };
+    // "1d" means today and yesterday
+    Ok((Local::now() - chrono::Duration::days(days)).format("%Y-%m-%d").to_string())
+}
+
+/// Property filters shared by `list` and `batch` (and saved in `views`)
+#[derive(clap::Args, Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct TaskFilter {
+    /// Filter by status (e.g., progress, review, blocked)
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+    /// Filter by context (e.g., @work, @home)
+    #[arg(short = 'c', long, add = ArgValueCandidates::new(completions::contexts))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<String>,
+    /// Filter by priority (e.g., P0, P1, or "P0,P1" for multiple)
+    #[arg(short = 'p', long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<String>,
+    /// Filter by project
+    #[arg(short = 'j', long, add = ArgValueCandidates::new(completions::projects))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    /// Filter by tag
+    #[arg(short = 't', long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+    /// Only tasks estimated at this or less (e.g. 15m); unestimated tasks are left out
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_time: Option<String>,
+    /// Filter by person (+name in the title)
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    person: Option<String>,
+}
+
+impl TaskFilter {
+    fn is_empty(&self) -> bool {
+        self.status.is_none() && self.context.is_none() && self.priority.is_none()
+            && self.project.is_none() && self.tag.is_none() && self.max_time.is_none() && self.person.is_none()
+    }
+
+    /// Keep only tasks matching every filter that was given
+    fn retain(&self, filtered: &mut Vec<&Task>) {
+        // Apply status filter
+        if let Some(ref status) = self.status {
+            let status_lower = status.to_lowercase();
+            filtered.retain(|t| {
+                t.status.as_ref()
+                    .map(|s| s.to_lowercase().contains(&status_lower))
+                    .unwrap_or(false)
+            });
+        }
+
+        // Apply context filter
+        if let Some(ref context) = self.context {
+            let context_lower = context.to_lowercase();
+            filtered.retain(|t| {
+                t.context.as_ref()
+                    .map(|c| c.to_lowercase().contains(&context_lower))
+                    .unwrap_or(false)
+            });
+        }
+
+        // Apply priority filter (supports comma-separated values like "P0,P1")
+        if let Some(ref priority) = self.priority {
+            let priorities: Vec<String> = priority.split(',')
+                .map(|p| p.trim().to_uppercase())
+                .collect();
+            filtered.retain(|t| {
+                t.priority.as_ref()
+                    .map(|p| priorities.iter().any(|pf| p.to_uppercase().contains(pf)))
+                    .unwrap_or(false)
+            });
+        }
+
+        // Apply project filter
+        if let Some(ref project) = self.project {
+            let project_lower = project.to_lowercase();
+            filtered.retain(|t| {
+                t.project.as_ref()
+                    .map(|p| p.to_lowercase().contains(&project_lower))
+                    .unwrap_or(false)
+            });
+        }
+
+        // Apply tag filter
+        if let Some(ref tag) = self.tag {
+            let tag_lower = tag.trim_start_matches('#').to_lowercase();
+            filtered.retain(|t| {
+                t.tags.iter().any(|tag_item| tag_item.to_lowercase().contains(&tag_lower))
+            });
+        }
+
+        if let Some(ref max_time) = self.max_time {
+            let max_minutes = parse_time_to_minutes(max_time);
+            filtered.retain(|t| t.time.as_deref().is_some_and(|time| parse_time_to_minutes(time) <= max_minutes));
+        }
+
+        if let Some(ref person) = self.person {
+            let person = person.trim_start_matches('+');
+            filtered.retain(|t| t.person().is_some_and(|p| p.eq_ignore_ascii_case(person)));
+        }
+    }
+}
+
+/// A saved search from `views`: `thegarden view <name>`, or just `thegarden <name>`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct View {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(flatten)]
+    filter: TaskFilter,
+    /// Only today's tasks (default: every date)
+    today: bool,
+    /// Same keys as `list --sort` (default: priority)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sort: Option<String>,
+    reverse: bool,
+    /// A `formats` template for each line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    template: Option<String>,
+}
+
+/// Run a view through `list`; without a name, list the views
+fn cmd_view(app: &App, name: Option<&str>) -> Result<()> {
+    let views = &app.config.views;
+    let Some(name) = name else {
+        if views.is_empty() {
+            println!("{}", "No views yet. Add some under \"views\" in config.json.".dimmed());
+            return Ok(());
+        }
+        let mut names: Vec<&String> = views.keys().collect();
+        names.sort();
+        for name in names {
+            // A view named like a command only runs as `view <name>`
+            let shadowed = if Cli::command().find_subcommand(name).is_some() { " (use `view`)" } else { "" };
+            let description = views[name].description.as_deref().unwrap_or_default();
+            println!("{} {}{}", name.cyan(), description, shadowed.dimmed());
+        }
+        return Ok(());
+    };
+    let Some(view) = views.get(name) else {
+        let mut names: Vec<&str> = views.keys().map(String::as_str).collect();
+        names.sort();
+        anyhow::bail!("No view '{}' (views: {})", name, if names.is_empty() { "none".to_string() } else { names.join(", ") });
+    };
+
+    let context = if view.filter.context.is_some() { None } else { current_context(app, false)? };
+    let sort = view.sort.as_deref().unwrap_or("priority");
+    cmd_list(app, !view.today, sort, view.reverse, &view.filter, None, None, false, false, false, false,
+        context.as_deref(), view.template.as_deref(), false, Some(name))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn cmd_list(app: &App, all: bool, sort: &str, reverse: bool, filter: &TaskFilter, days: Option<i64>, limit: Option<usize>, grouped: bool, interactive: bool, include_future: bool, include_done: bool, context: Option<&str>, template: Option<&str>, absolute: bool, title: Option<&str>) -> Result<()> {
+    let config = &app.config;
+    let template = template.map(|name| config.template(name)).transpose()?;
+    let tasks = get_tasks_from_cache(app)?;
+    let bump_counts = app.db.get_bump_counts()?;
     let today = Local::now();
     let today_str = today.format("%Y-%m-%d").to_string();
+    let render = |template: &str, task: &Task| if absolute {
+        template::render(template, task, today.date_naive())
+    } else {
+        template::render_relative(template, task, today.date_naive())
+    };
+    let line = |task: &Task| match template {
+        Some(template) => format!("{}{}{}", done_marker(task), render(template, task), bump_marker(&bump_counts, task)),
+        None => format!("{} {}{}{}", task.short_id().dimmed(), done_marker(task), render(&config.format, task), bump_marker(&bump_counts, task)),
+    };
 
     let mut filtered: Vec<&Task> = if let Some(d) = days {
         // Filter by last N days
@@ -2490,64 +5630,21 @@ fn cmd_list(config: &Config, all: bool, sort: &str, reverse: bool, status_filter
         tasks.iter().filter(|t| t.date == today_str).collect()
     };
 
-    // Apply status filter
-    if let Some(status) = status_filter {
-        let status_lower = status.to_lowercase();
-        filtered.retain(|t| {
-            t.status.as_ref()
-                .map(|s| s.to_lowercase().contains(&status_lower))
-                .unwrap_or(false)
-        });
-    }
-
-    // Apply context filter
-    if let Some(context) = context_filter {
-        let context_lower = context.to_lowercase();
-        filtered.retain(|t| {
-            t.context.as_ref()
-                .map(|c| c.to_lowercase().contains(&context_lower))
-                .unwrap_or(false)
-        });
-    }
-
-    // Apply priority filter (supports comma-separated values like "P0,P1")
-    if let Some(priority) = priority_filter {
-        let priorities: Vec<String> = priority.split(',')
-            .map(|p| p.trim().to_uppercase())
-            .collect();
-        filtered.retain(|t| {
-            t.priority.as_ref()
-                .map(|p| priorities.iter().any(|pf| p.to_uppercase().contains(pf)))
-                .unwrap_or(false)
-        });
+    filter.retain(&mut filtered);
+    if !include_future {
+        filtered.retain(|t| !starts_later(t, &today_str));
     }
-
-    // Apply project filter
-    if let Some(project) = project_filter {
-        let project_lower = project.to_lowercase();
-        filtered.retain(|t| {
-            t.project.as_ref()
-                .map(|p| p.to_lowercase().contains(&project_lower))
-                .unwrap_or(false)
-        });
+    if !include_done {
+        filtered.retain(|t| !t.is_done());
     }
-
-    // Apply tag filter
-    if let Some(tag) = tag_filter {
-        let tag_lower = tag.to_lowercase();
-        filtered.retain(|t| {
-            t.tags.iter().any(|tag_item| tag_item.to_lowercase().contains(&tag_lower))
-        });
+    if let Some(ctx) = context {
+        filtered.retain(|t| in_context(t, ctx));
     }
 
     // Sort based on the sort parameter
     match sort {
         "priority" | "p" => {
-            filtered.sort_by(|a, b| {
-                let a_pri = a.priority.as_deref().unwrap_or("P9");
-                let b_pri = b.priority.as_deref().unwrap_or("P9");
-                a_pri.cmp(b_pri)
-            });
+            filtered.sort_by_key(|t| config.priority_rank(t.priority.as_deref()));
         }
         "date" | "d" => {
             filtered.sort_by(|a, b| a.date.cmp(&b.date));
@@ -2572,7 +5669,7 @@ fn cmd_list(config: &Config, all: bool, sort: &str, reverse: bool, status_filter
             });
         }
         "title" | "n" => {
-            filtered.sort_by(|a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase()));
+            filtered.sort_by_key(|a| a.title.to_lowercase());
         }
         "status" | "s" => {
             filtered.sort_by(|a, b| {
@@ -2590,11 +5687,7 @@ fn cmd_list(config: &Config, all: bool, sort: &str, reverse: bool, status_filter
         }
         _ => {
             // Default to priority
-            filtered.sort_by(|a, b| {
-                let a_pri = a.priority.as_deref().unwrap_or("P9");
-                let b_pri = b.priority.as_deref().unwrap_or("P9");
-                a_pri.cmp(b_pri)
-            });
+            filtered.sort_by_key(|t| config.priority_rank(t.priority.as_deref()));
         }
     }
 
@@ -2618,7 +5711,9 @@ fn cmd_list(config: &Config, all: bool, sort: &str, reverse: bool, status_filter
         filtered.truncate(n);
     }
 
-    let date_desc = if let Some(d) = days {
+    let date_desc = if let Some(title) = title {
+        title.to_string()
+    } else if let Some(d) = days {
         format!("Last {} days", d)
     } else if all {
         "All Tasks".to_string()
@@ -2626,12 +5721,27 @@ fn cmd_list(config: &Config, all: bool, sort: &str, reverse: bool, status_filter
         "Today's Tasks".to_string()
     };
 
-    println!("🌱 The Garden - {} ({} tasks, sorted {}{})\n",
+    if output::is_porcelain() {
+        for task in filtered {
+            println!("{}", output::porcelain_line(task));
+        }
+        return Ok(());
+    }
+
+    if interactive {
+        let selected: Vec<Task> = filtered.into_iter().cloned().collect();
+        return cmd_list_interactive(app, &date_desc, selected);
+    }
+
+    println!("{}", output::plain(&format!("🌱 The Garden - {} ({} tasks, sorted {}{})\n",
         date_desc,
         filtered.len(),
         sort_label,
         if reverse { ", reversed" } else { "" }
-    );
+    )));
+    if let Some(ctx) = context {
+        println!("{}\n", format!("{} · --all-contexts to see everything", context_indicator(app, ctx)?).dimmed());
+    }
 
     if grouped {
         // Group by date
@@ -2639,7 +5749,7 @@ fn cmd_list(config: &Config, all: bool, sort: &str, reverse: bool, status_filter
         let mut grouped_tasks: BTreeMap<String, Vec<&Task>> = BTreeMap::new();
         
         for task in &filtered {
-            grouped_tasks.entry(task.date.clone()).or_insert_with(Vec::new).push(task);
+            grouped_tasks.entry(task.date.clone()).or_default().push(task);
         }
 
         for (date, tasks) in grouped_tasks {
@@ -2648,7 +5758,7 @@ fn cmd_list(config: &Config, all: bool, sort: &str, reverse: bool, status_filter
                 "OVERDUE".to_string().red().bold().to_string()
             } else if date == today_str {
                 let weekday = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
-                    .map(|d| d.format("%A, %b %d").to_string())
+                    .map(|d| config.format_date(d, "%A, %b %d"))
                     .unwrap_or_else(|_| "TODAY".to_string());
                 format!("TODAY ({})", weekday).green().bold().to_string()
             } else {
@@ -2656,67 +5766,215 @@ fn cmd_list(config: &Config, all: bool, sort: &str, reverse: bool, status_filter
                 let task_date = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok();
                 let is_this_week = task_date.map(|d| {
                     let days_diff = (d - today.naive_local().date()).num_days();
-                    days_diff >= 0 && days_diff <= 7
+                    (0..=7).contains(&days_diff)
                 }).unwrap_or(false);
 
                 if is_this_week {
-                    task_date.map(|d| d.format("%A, %b %d").to_string())
+                    task_date.map(|d| config.format_date(d, "%A, %b %d"))
                         .unwrap_or_else(|| date.clone())
                 } else {
                     date.clone()
                 }
             };
 
-            println!("{}", format!("════ {} ════", date_label).cyan());
-            for task in tasks {
-                let short_id = task.short_id();
-                println!("{} {}", short_id.dimmed(), task.format(config));
+            println!("{}", output::header(&format!("════ {} ════", date_label)));
+            for task in tasks {
+                println!("{}", line(task));
+            }
+            println!();
+        }
+    } else {
+        // Regular list view
+        for task in filtered {
+            // Show task ID on the left for easy reference (unless a template places it)
+            println!("{}", line(task));
+        }
+    }
+
+    print_wip_warnings(&config.wip_limits.warnings(&tasks));
+
+    Ok(())
+}
+
+/// Multi-select view over a filtered list: move, toggle, then apply one action to the selection
+fn cmd_list_interactive(app: &App, heading: &str, mut tasks: Vec<Task>) -> Result<()> {
+    let config = &app.config;
+    if tasks.is_empty() {
+        println!("{}", "No tasks to show.".green());
+        return Ok(());
+    }
+
+    let sync_manager = &app.db;
+    let mut selected: Vec<bool> = vec![false; tasks.len()];
+    let mut cursor = 0;
+    let mut message = String::new();
+    let mut changed = 0;
+
+    loop {
+        execute!(stdout(), Clear(ClearType::All), MoveTo(0, 0))?;
+        let picked = selected.iter().filter(|s| **s).count();
+        println!("{}", output::plain(&format!("🌱 The Garden - {} ({} tasks, {} selected)\n", heading, tasks.len(), picked)));
+        for (i, task) in tasks.iter().enumerate() {
+            let pointer = if i == cursor { ">".cyan().bold() } else { " ".normal() };
+            let check = if selected[i] { "[x]".green() } else { "[ ]".dimmed() };
+            println!("{} {} {} {}", pointer, check, task.short_id().dimmed(), task.format(config));
+        }
+        println!("\n{}", "↑/↓ j/k move · space select · a all · x done · b bump · p priority · d delete · q quit".dimmed());
+        if !message.is_empty() {
+            println!("{}", message);
+        }
+
+        // Actions apply to the selection, or to the task under the cursor if nothing is selected
+        let targets: Vec<usize> = if picked > 0 {
+            (0..tasks.len()).filter(|i| selected[*i]).collect()
+        } else {
+            vec![cursor]
+        };
+
+        message.clear();
+        match read_key()? {
+            KeyCode::Up | KeyCode::Char('k') => cursor = cursor.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => cursor = (cursor + 1).min(tasks.len() - 1),
+            KeyCode::Char(' ') => selected[cursor] = !selected[cursor],
+            KeyCode::Char('a') => {
+                let all_on = selected.iter().all(|s| *s);
+                selected.iter_mut().for_each(|s| *s = !all_on);
             }
-            println!();
+            KeyCode::Char('x') => {
+                let updated: Vec<Task> = targets.iter().map(|i| {
+                    let mut t = tasks[*i].clone();
+                    t.completed = true;
+                    t
+                }).collect();
+                save_tasks_locally(sync_manager, config, &updated)?;
+                changed += updated.len();
+                message = format!("✓ Marked {} done", updated.len()).green().to_string();
+                remove_indices(&mut tasks, &mut selected, &targets);
+            }
+            KeyCode::Char('b') => {
+                print!("{} ", "Bump by how many days? (1-9, w = next week)".yellow());
+                stdout().flush()?;
+                let now = Local::now();
+                let days = match read_single_key()? {
+                    'w' | 'W' => {
+                        let next_week = start_of_week(now.date_naive(), app.config.week_start()) + chrono::Duration::days(7);
+                        Some((next_week - now.date_naive()).num_days())
+                    }
+                    c => c.to_digit(10).filter(|d| *d > 0).map(|d| d as i64),
+                };
+                if let Some(days) = days {
+                    let date = (now + chrono::Duration::days(days)).format("%Y-%m-%d").to_string();
+                    let updated: Vec<Task> = targets.iter().map(|i| {
+                        let mut t = tasks[*i].clone();
+                        t.date = date.clone();
+                        t
+                    }).collect();
+                    save_tasks_locally(sync_manager, config, &updated)?;
+                    record_bumps(sync_manager, &updated)?;
+                    changed += updated.len();
+                    message = format!("✓ Bumped {} to {}", updated.len(), date).green().to_string();
+                    for (i, t) in targets.iter().zip(updated) {
+                        tasks[*i] = t;
+                    }
+                }
+            }
+            KeyCode::Char('p') => {
+                let keys: Vec<String> = config.priorities.iter()
+                    .map(|p| format!("{}={}", p.key, p.name))
+                    .collect();
+                print!("{} ", format!("Priority? ({})", keys.join(" ")).yellow());
+                stdout().flush()?;
+                let choice = read_single_key()?.to_ascii_lowercase();
+                if let Some(priority) = config.priorities.iter().find(|p| p.key.to_ascii_lowercase() == choice) {
+                    let updated: Vec<Task> = targets.iter().map(|i| {
+                        let mut t = tasks[*i].clone();
+                        t.priority = Some(priority.name.clone());
+                        t
+                    }).collect();
+                    save_tasks_locally(sync_manager, config, &updated)?;
+                    changed += updated.len();
+                    message = format!("✓ Set {} to {}", updated.len(), priority.name).green().to_string();
+                    for (i, t) in targets.iter().zip(updated) {
+                        tasks[*i] = t;
+                    }
+                }
+            }
+            KeyCode::Char('d') => {
+                print!("{} ", format!("Delete {} task(s)? (y/n)", targets.len()).red().bold());
+                stdout().flush()?;
+                if matches!(read_single_key()?, 'y' | 'Y') {
+                    for i in &targets {
+                        delete_task_everywhere(app, &tasks[*i])?;
+                    }
+                    message = format!("🗑  Deleted {}", targets.len()).red().to_string();
+                    remove_indices(&mut tasks, &mut selected, &targets);
+                }
+            }
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            _ => {}
         }
-    } else {
-        // Regular list view
-        for task in filtered {
-            // Show task ID on the left for easy reference
-            let short_id = task.short_id();
-            println!("{} {}", short_id.dimmed(), task.format(config));
+
+        if tasks.is_empty() {
+            println!("\n{}", "🎉 Nothing left in this view.".green());
+            break;
         }
+        cursor = cursor.min(tasks.len() - 1);
+    }
+
+    if changed > 0 {
+        println!("\n{}", "Pushing changes to Google...".dimmed());
+        push_dirty_tasks_to_google(app)?;
     }
 
     Ok(())
 }
 
-fn cmd_add(config: &Config, title: String, priority: Option<String>, project: Option<String>) -> Result<()> {
-    // Extract hashtags from title
-    let hashtag_regex = Regex::new(r"#(\w+)").unwrap();
-    let mut tags: Vec<String> = hashtag_regex
-        .captures_iter(&title)
-        .map(|cap| cap.get(1).unwrap().as_str().to_string())
-        .collect();
+/// Drop tasks (and their selection flags) at the given indices
+fn remove_indices(tasks: &mut Vec<Task>, selected: &mut Vec<bool>, indices: &[usize]) {
+    let mut sorted = indices.to_vec();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+    for i in sorted {
+        tasks.remove(i);
+        selected.remove(i);
+    }
+}
 
-    let task = Task {
-        id: None,
-        list_id: None,
-        date: Local::now().format(&config.date_format).to_string(),
-        priority: priority.clone(),
-        project: project.clone(),
-        status: None,
-        context: None,
-        time: None,
-        title: title.clone(),
-        list: "My Tasks".to_string(), // Default list
-        attachment_type: None,
-        tags: tags.clone(),
-    };
+fn cmd_add(app: &App, title: String, priority: Option<String>, project: Option<String>, start: Option<&str>, notes: Option<String>) -> Result<()> {
+    let config = &app.config;
+    let start = start.map(parse_start_date).transpose()?;
 
-    let formatted_title = task.format(config);
+    // Inline tokens (p1 @work /PROJECT 45m tomorrow #Tag) set fields; flags override them
+    let mut task = parse_quick_add(&title, config);
+    if priority.is_some() {
+        task.priority = priority;
+    }
+    if project.is_some() {
+        task.project = project;
+    }
+    let priority = task.priority.clone();
+    let project = task.project.clone();
+    let title = task.title.clone();
+    let mut tags = task.tags.clone();
+
+    let formatted_title = quick_add_title(&task, config);
 
     println!("Adding task: {}", formatted_title.cyan());
 
+    // No ID to hang anything on until Google creates it, so queue it like `in`
+    if sync::is_offline() {
+        if start.is_some() || notes.is_some() {
+            anyhow::bail!("Offline: --start and --url need the task to exist in Google first");
+        }
+        app.db.add_inbox_item(&formatted_title)?;
+        println!("{}", "📴 Offline: queued, it will be created on the next sync".dimmed());
+        return Ok(());
+    }
+
     // Check if ClickUp is configured with a default list
     if let Some(ref clickup_config) = config.clickup {
         if clickup_config.enabled {
-            if let Some(ref default_list_id) = clickup_config.list_id {
+            let project_list = config.project(project.as_deref()).and_then(|p| p.clickup_list.as_ref());
+            if let Some(default_list_id) = project_list.or(clickup_config.list_id.as_ref()) {
                 println!("{}", "📤 Adding directly to ClickUp...".dimmed());
                 
                 // Create ClickUp client
@@ -2730,22 +5988,14 @@ fn cmd_add(config: &Config, title: String, priority: Option<String>, project: Op
                     tags.push(proj.clone());
                 }
                 
-                // Map priority to ClickUp priority
-                let clickup_priority = match priority.as_deref() {
-                    Some("P0") => Some(1u8),
-                    Some("P1") => Some(2u8),
-                    Some("P2") => Some(3u8),
-                    Some("P3") | Some("P5") => Some(4u8),
-                    _ => None,
-                };
-                
+
                 // Create task in ClickUp
                 let clickup_task = clickup::ClickUpTask {
                     id: None,
                     name: title.clone(),
-                    description: None,
-                    status: Some("to do".to_string()),
-                    priority: clickup_priority,
+                    description: notes.clone(),
+                    status: Some(ClickUpSync::status_to_clickup(&config.statuses, None, "needsAction")),
+                    priority: ClickUpSync::priority_to_clickup(config, priority.as_deref()),
                     due_date: None,
                     start_date: None,
                     time_estimate: None,
@@ -2754,61 +6004,541 @@ fn cmd_add(config: &Config, title: String, priority: Option<String>, project: Op
                     custom_fields: None,
                 };
                 
-                match client.create_task(default_list_id, &clickup_task) {
+                match block_on(client.create_task(default_list_id, &clickup_task)) {
                     Ok(response) => {
                         println!("{}", format!("✓ Created in ClickUp: {}", response.id).green());
                         return Ok(());
                     }
                     Err(e) => {
                         eprintln!("{}", format!("⚠️  Failed to create in ClickUp: {}", e).yellow());
-                        println!("{}", "Falling back to manual Google Tasks entry...".dimmed());
+                        println!("{}", "Falling back to Google Tasks...".dimmed());
                     }
                 }
             }
         }
     }
     
-    // Fallback: print instructions for Google Tasks
-    println!("{}", "(Add this to Google Tasks manually)".dimmed());
+    let task_id = create_task_in_google(app, "@default", &formatted_title, None)?;
+    println!("{}", format!("✓ Created in Google Tasks: {}", &task_id[..8.min(task_id.len())]).green());
+
+    if let Some(start) = start {
+        let sync_manager = &app.db;
+        if let Some(mut cached) = sync_manager.get_task_by_id(&task_id)? {
+            cached.start_date = Some(start.clone());
+            sync_manager.upsert_task(&cached)?;
+        }
+        println!("{}", format!("🛫 Hidden until {}", start).dimmed());
+    }
+
+    if let Some(notes) = notes {
+        if let Some(mut cached) = app.db.get_task_by_id(&task_id)? {
+            cached.user_description = Some(notes);
+            app.db.upsert_task_locally(&cached)?;
+            push_dirty_tasks(app)?;
+        }
+    }
 
     Ok(())
 }
 
-/// Calculate similarity between two strings using bigrams (Dice coefficient)
-fn string_similarity(s1: &str, s2: &str) -> f64 {
-    let s1_lower = s1.to_lowercase();
-    let s2_lower = s2.to_lowercase();
+/// Tag for links saved with `add --url`
+const READ_LATER_TAG: &str = "read";
+
+static PAGE_TITLE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap());
+
+/// The page's <title>, entities decoded and whitespace collapsed
+fn page_title(html: &str) -> Option<String> {
+    let raw = PAGE_TITLE_REGEX.captures(html)?.get(1)?.as_str();
+    let title = raw.split_whitespace().collect::<Vec<_>>().join(" ")
+        .replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"")
+        .replace("&#39;", "'").replace("&#x27;", "'").replace("&nbsp;", " ")
+        .replace("&amp;", "&");
+    Some(title.trim().to_string()).filter(|t| !t.is_empty())
+}
+
+/// Fetch `url` and read its title; None when the page can't be had or has no title
+fn fetch_page_title(url: &str) -> Option<String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .user_agent("thegarden")
+        .build()
+        .ok()?;
+    let response = client.get(url).send_logged().ok()?.error_for_status().ok()?;
+    page_title(&response.text().ok()?)
+}
 
-    if s1_lower == s2_lower {
-        return 1.0;
+/// `add --url`: a #read task for the link, with the URL in its notes so `open` finds it
+fn cmd_add_url(app: &App, url: &str, title: Option<String>, priority: Option<String>, project: Option<String>, start: Option<&str>) -> Result<()> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        anyhow::bail!("'{}' isn't a web link (http:// or https://)", url);
     }
+    let title = match title {
+        Some(title) => title,
+        None if sync::is_offline() => url.to_string(),
+        None => fetch_page_title(url).unwrap_or_else(|| {
+            println!("{}", "Couldn't read the page title; using the link".dimmed());
+            url.to_string()
+        }),
+    };
+    let tag = format!("#{}", READ_LATER_TAG);
+    let title = if title.split_whitespace().any(|w| w.eq_ignore_ascii_case(&tag)) { title } else { format!("{} {}", title, tag) };
+    cmd_add(app, title, priority, project, start, Some(url.to_string()))
+}
 
-    let bigrams1: std::collections::HashSet<(char, char)> = s1_lower
-        .chars()
-        .zip(s1_lower.chars().skip(1))
-        .collect();
-    let bigrams2: std::collections::HashSet<(char, char)> = s2_lower
-        .chars()
-        .zip(s2_lower.chars().skip(1))
+/// Title to store for a newly added task: the full format once any field is set,
+/// otherwise the plain title so triage still picks it up
+fn quick_add_title(task: &Task, config: &Config) -> String {
+    if task.priority.is_some() || task.project.is_some() || task.context.is_some() || task.time.is_some() {
+        task.format(config)
+    } else {
+        task.title.clone()
+    }
+}
+
+/// Replace the first `#old` hashtag in a title with `#new` (or drop it when `new` is None),
+/// removing any repeats. Matching is case-insensitive. Returns None if the tag isn't there.
+fn retag_title(title: &str, old: &str, new: Option<&str>) -> Option<String> {
+    let hashtag = |tag: &str| Regex::new(&format!(r"(?i)\s*#{}\b", regex::escape(tag))).unwrap();
+    let old_regex = hashtag(old);
+    if !old_regex.is_match(title) {
+        return None;
+    }
+    // Renaming onto a tag the title already has just drops the old one
+    let replacement = match new {
+        Some(new) if !hashtag(new).is_match(title) => format!(" #{}", new),
+        _ => String::new(),
+    };
+    let mut replaced = false;
+    let retitled = old_regex.replace_all(title, |_: &regex::Captures| {
+        if replaced {
+            String::new()
+        } else {
+            replaced = true;
+            replacement.clone()
+        }
+    });
+    Some(retitled.trim().to_string())
+}
+
+/// Set a task's title and re-extract its tags
+fn set_task_title(task: &mut Task, title: String) {
+    task.tags = HASHTAG_REGEX.captures_iter(&title).map(|c| c[1].to_string()).collect();
+    task.title = title;
+}
+
+fn cmd_tag(app: &App, action: TagAction) -> Result<()> {
+    let config = &app.config;
+    let tasks = get_tasks_from_cache(app)?;
+
+    let find_one = |id: &str| -> Result<Task> {
+        let matching = find_tasks_by_id_prefix(&tasks, id);
+        match matching.len() {
+            0 => anyhow::bail!("No task found with ID starting with '{}'", id),
+            1 => Ok(matching[0].clone()),
+            n => anyhow::bail!("{} tasks match '{}'. Please be more specific", n, id),
+        }
+    };
+
+    let updated: Vec<Task> = match action {
+        TagAction::List => {
+            let mut counts: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+            for task in tasks.iter().filter(|t| !t.is_done()) {
+                for tag in &task.tags {
+                    *counts.entry(tag.clone()).or_default() += 1;
+                }
+            }
+            if counts.is_empty() {
+                println!("{}", "No tags on open tasks.".dimmed());
+                return Ok(());
+            }
+            let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+            counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            println!("{}", format!("🏷️  Tags ({} on open tasks)\n", counts.len()).cyan().bold());
+            for (tag, count) in counts {
+                println!("  {:>4}  #{}", count.to_string().dimmed(), tag);
+            }
+            return Ok(());
+        }
+        TagAction::Add { id, tag } => {
+            let tag = tag.trim_start_matches('#');
+            let mut task = find_one(&id)?;
+            if task.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                println!("{}", format!("{} already has #{}", task.title, tag).dimmed());
+                return Ok(());
+            }
+            let title = format!("{} #{}", task.title, tag);
+            set_task_title(&mut task, title);
+            vec![task]
+        }
+        TagAction::Rm { id, tag } => {
+            let tag = tag.trim_start_matches('#');
+            let mut task = find_one(&id)?;
+            let Some(title) = retag_title(&task.title, tag, None) else {
+                println!("{}", format!("{} doesn't have #{}", task.title, tag).dimmed());
+                return Ok(());
+            };
+            set_task_title(&mut task, title);
+            vec![task]
+        }
+        TagAction::Rename { old, new } => {
+            let (old, new) = (old.trim_start_matches('#'), new.trim_start_matches('#'));
+            tasks.iter()
+                .filter_map(|t| {
+                    let title = retag_title(&t.title, old, Some(new))?;
+                    let mut task = t.clone();
+                    set_task_title(&mut task, title);
+                    Some(task)
+                })
+                .collect()
+        }
+    };
+
+    if updated.is_empty() {
+        println!("{}", "No tasks have that tag.".dimmed());
+        return Ok(());
+    }
+
+    let sync_manager = &app.db;
+    save_tasks_locally(sync_manager, config, &updated)?;
+    for task in &updated {
+        println!("  {} {}", task.short_id().dimmed(), task.format(config));
+    }
+    println!("{}", format!("✓ Updated {} tasks", updated.len()).green());
+
+    println!("\n{}", "Pushing changes to Google...".dimmed());
+    push_dirty_tasks_to_google(app)?;
+
+    Ok(())
+}
+
+fn cmd_stats(app: &App, action: StatsAction) -> Result<()> {
+    match action {
+        StatsAction::Estimates => cmd_stats_estimates(app),
+        StatsAction::Habits => habits::print_stats(app),
+    }
+}
+
+/// Finished tasks with both an estimate and tracked time: (task, estimated, tracked) in seconds
+fn estimate_samples<'a>(tasks: &'a [Task], tracked: &std::collections::HashMap<String, i64>) -> Vec<(&'a Task, i64, i64)> {
+    tasks.iter()
+        .filter(|t| t.completed)
+        .filter_map(|t| {
+            let estimate = parse_time_to_minutes(t.time.as_deref()?) as i64 * 60;
+            let spent = *tracked.get(t.id.as_deref()?)?;
+            (estimate > 0 && spent > 0).then_some((t, estimate, spent))
+        })
+        .collect()
+}
+
+/// How much longer tasks take than estimated: total tracked over total estimated
+fn estimate_factor(samples: &[(&Task, i64, i64)]) -> Option<f64> {
+    let estimated: i64 = samples.iter().map(|(_, e, _)| e).sum();
+    let tracked: i64 = samples.iter().map(|(_, _, s)| s).sum();
+    (estimated > 0).then(|| tracked as f64 / estimated as f64)
+}
+
+/// Fewest finished, tracked tasks before `inflate_estimates` trusts the factor
+const MIN_ESTIMATE_SAMPLES: usize = 5;
+
+/// With `inflate_estimates` on, stretch each task's estimate by the tracked/estimated
+/// factor so schedule books the time things really take
+fn inflate_estimates(app: &App, tasks: Vec<Task>) -> Result<Vec<Task>> {
+    if !app.config.work_hours.inflate_estimates {
+        return Ok(tasks);
+    }
+    let history = get_tasks_from_cache(app)?;
+    let samples = estimate_samples(&history, &app.db.get_tracked_seconds()?);
+    let Some(factor) = estimate_factor(&samples).filter(|_| samples.len() >= MIN_ESTIMATE_SAMPLES) else {
+        return Ok(tasks);
+    };
+    Ok(tasks.into_iter()
+        .map(|mut t| {
+            if let Some(minutes) = t.time.as_deref().map(parse_time_to_minutes).filter(|m| *m > 0) {
+                t.time = Some(format!("{}m", (minutes as f64 * factor).ceil() as i64));
+            }
+            t
+        })
+        .collect())
+}
+
+fn cmd_stats_estimates(app: &App) -> Result<()> {
+    let tasks = get_tasks_from_cache(app)?;
+    let samples = estimate_samples(&tasks, &app.db.get_tracked_seconds()?);
+    let Some(factor) = estimate_factor(&samples) else {
+        println!("{}", "No finished tasks with both an estimate and tracked time yet.".dimmed());
+        println!("{}", "Time is tracked by the timer in `next` and by `focus --watch`.".dimmed());
+        return Ok(());
+    };
+
+    println!("{}", format!("📏 Estimates vs tracked time ({} finished tasks)
+", samples.len()).cyan().bold());
+    let row = |label: &str, group: &[(&Task, i64, i64)]| {
+        let estimated: i64 = group.iter().map(|(_, e, _)| e).sum();
+        let tracked: i64 = group.iter().map(|(_, _, s)| s).sum();
+        let factor = estimate_factor(group).unwrap_or(1.0);
+        let factor_str = format!("×{:.1}", factor);
+        println!("  {:<16} {:>3} tasks  est {:>8}  tracked {:>8}  {}",
+            label, group.len(),
+            format_time_from_minutes((estimated / 60) as i32),
+            format_time_from_minutes((tracked / 60) as i32),
+            if factor > 1.2 { factor_str.red() } else if factor < 0.8 { factor_str.yellow() } else { factor_str.green() });
+    };
+
+    let mut by_project: std::collections::BTreeMap<String, Vec<(&Task, i64, i64)>> = std::collections::BTreeMap::new();
+    let mut by_tag: std::collections::BTreeMap<String, Vec<(&Task, i64, i64)>> = std::collections::BTreeMap::new();
+    for sample in &samples {
+        by_project.entry(sample.0.project.clone().unwrap_or_else(|| "(none)".to_string())).or_default().push(*sample);
+        for tag in &sample.0.tags {
+            by_tag.entry(format!("#{}", tag)).or_default().push(*sample);
+        }
+    }
+
+    println!("{}", "By project:".bold());
+    for (project, group) in &by_project {
+        row(project, group);
+    }
+    if !by_tag.is_empty() {
+        println!("
+{}", "By tag:".bold());
+        for (tag, group) in &by_tag {
+            row(tag, group);
+        }
+    }
+
+    println!("
+{}", format!("Your factor: ×{:.1} — multiply estimates by {:.1}", factor, factor).bold());
+    if app.config.work_hours.inflate_estimates {
+        if samples.len() >= MIN_ESTIMATE_SAMPLES {
+            println!("{}", "schedule stretches task blocks by this factor (work_hours.inflate_estimates)".dimmed());
+        } else {
+            println!("{}", format!("schedule starts stretching task blocks after {} tracked tasks", MIN_ESTIMATE_SAMPLES).dimmed());
+        }
+    } else {
+        println!("{}", "Set work_hours.inflate_estimates to have schedule book blocks this much longer".dimmed());
+    }
+    Ok(())
+}
+
+fn cmd_context(app: &App, action: ContextAction) -> Result<()> {
+    let sync_manager = &app.db;
+    match action {
+        ContextAction::Set { context } => {
+            let context = format!("@{}", context.trim().trim_start_matches('@'));
+            sync_manager.set_state("current_context", &context)?;
+            println!("{}", format!("📍 Context set to {} (list, focus and next hide other contexts)", context).green());
+        }
+        ContextAction::Clear => {
+            sync_manager.delete_state("current_context")?;
+            println!("{}", "📍 Context cleared - showing every context".green());
+        }
+        ContextAction::Show => match current_context(app, false)? {
+            Some(context) => println!("{}", context_indicator(app, &context)?),
+            None => println!("{}", "No current context".dimmed()),
+        },
+    }
+    Ok(())
+}
+
+fn cmd_in(app: &App, text: &[String]) -> Result<()> {
+    let title = text.join(" ");
+    let title = title.trim();
+    if title.is_empty() {
+        anyhow::bail!("Nothing to capture");
+    }
+    app.db.add_inbox_item(title)?;
+    println!("{} {}", "📥".green(), title);
+    Ok(())
+}
+
+/// Create captured inbox items in Google Tasks. Failures stay in the inbox for next time.
+fn flush_inbox(app: &App) -> Result<()> {
+    if sync::is_offline() {
+        return Ok(());
+    }
+    let sync_manager = &app.db;
+    let items = sync_manager.get_unpushed_inbox()?;
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    let mut pushed = 0;
+    for (row_id, title) in &items {
+        match create_task_in_google(app, "@default", title, None) {
+            Ok(task_id) => {
+                sync_manager.mark_inbox_pushed(*row_id, &task_id)?;
+                pushed += 1;
+            }
+            Err(e) => {
+                eprintln!("{}", format!("⚠ Inbox item '{}' not pushed: {}", title, e).yellow());
+            }
+        }
+    }
+    if pushed > 0 {
+        output::note(format!("📥 Pushed {} inbox items", pushed).dimmed());
+    }
+    Ok(())
+}
+
+/// Add one task per line (blank lines and list bullets are ignored) straight to Google Tasks
+fn cmd_add_bulk(app: &App, input: &str, priority: Option<String>, project: Option<String>) -> Result<()> {
+    let config = &app.config;
+    let bullet_regex = Regex::new(r"^(?:[-*•]\s+)?(?:\[[ xX]?\]\s+)?").unwrap();
+    let lines: Vec<&str> = input.lines()
+        .map(|l| l.trim())
+        .map(|l| bullet_regex.find(l).map(|m| &l[m.end()..]).unwrap_or(l).trim())
+        .filter(|l| !l.is_empty())
         .collect();
 
-    if bigrams1.is_empty() || bigrams2.is_empty() {
-        return 0.0;
+    if lines.is_empty() {
+        println!("{}", "No tasks to add.".yellow());
+        return Ok(());
+    }
+
+    println!("{}", format!("📥 Adding {} tasks...\n", lines.len()).cyan().bold());
+
+    let mut added = 0;
+    let mut failed = 0;
+    for line in lines {
+        // Inline tokens, hashtags and "due friday"-style dates come from the line itself
+        let mut task = parse_quick_add(line, config);
+        if priority.is_some() {
+            task.priority = priority.clone();
+        }
+        if project.is_some() {
+            task.project = project.clone();
+        }
+
+        let title = quick_add_title(&task, config);
+
+        if sync::is_offline() {
+            app.db.add_inbox_item(&title)?;
+            println!("  {} {} {}", "📥".green(), title, "(queued)".dimmed());
+            added += 1;
+            continue;
+        }
+
+        match create_task_in_google(app, "@default", &title, None) {
+            Ok(_) => {
+                println!("  {} {}", "✓".green(), title);
+                added += 1;
+            }
+            Err(e) => {
+                println!("  {} {} {}", "❌".red(), title, format!("({})", e).dimmed());
+                failed += 1;
+            }
+        }
+    }
+
+    println!("\n{}", format!("✓ Added {} tasks", added).green());
+    if failed > 0 {
+        println!("{}", format!("⚠ {} tasks failed to add", failed).yellow());
     }
 
-    let intersection = bigrams1.intersection(&bigrams2).count();
-    (2.0 * intersection as f64) / (bigrams1.len() + bigrams2.len()) as f64
+    Ok(())
 }
 
-/// Compare priorities - returns the "higher" (more urgent) priority
-fn higher_priority(p1: Option<&str>, p2: Option<&str>) -> Option<String> {
-    let priority_order = ["P0", "P1", "P2", "P3", "P5", "DONE"];
+/// How alike an imported title and an existing one must be to count as a duplicate
+const IMPORT_DUPLICATE_SIMILARITY: f64 = 0.8;
+
+/// Create tasks from another app's export, skipping ones that look like tasks we already have
+fn cmd_import_from(app: &App, source: &str, path: &std::path::Path, dry_run: bool) -> Result<()> {
+    let config = &app.config;
+    let imported = importers::read(source, path)?;
+    if imported.is_empty() {
+        println!("{}", format!("No tasks found in {}", path.display()).yellow());
+        return Ok(());
+    }
+
+    let verb = if dry_run { "Would import" } else { "Importing" };
+    println!("{}", format!("📥 {} {} tasks from {}\n", verb, imported.len(), source).cyan().bold());
+
+    let mut known: Vec<String> = get_tasks_from_cache(app)?.into_iter().map(|t| t.title).collect();
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    let (mut added, mut duplicates, mut failed) = (0, 0, 0);
+
+    for item in imported {
+        if let Some(existing) = known.iter().find(|k| {
+            similarity::normalize_title(k) == similarity::normalize_title(&item.title)
+                || similarity::token_similarity(k, &item.title) >= IMPORT_DUPLICATE_SIMILARITY
+        }) {
+            println!("  {} {} {}", "=".dimmed(), item.title, format!("(already have \"{}\")", existing).dimmed());
+            duplicates += 1;
+            continue;
+        }
+        known.push(item.title.clone());
+
+        // Tags ride along in the title, as everywhere else
+        let mut name = item.title.clone();
+        for tag in &item.tags {
+            if !name.contains(&format!("#{}", tag)) {
+                name = format!("{} #{}", name, tag);
+            }
+        }
+        let project = item.project.as_deref()
+            .map(|p| p.to_uppercase().replace(' ', ""))
+            .filter(|p| config.projects.contains_key(p));
+        let mut task = Task::parse_with_config(&name, "Tasks", Some(config));
+        task.date = item.due.clone().unwrap_or_else(|| today.clone());
+        task.priority = item.priority.clone().or(task.priority);
+        task.project = project.or(task.project);
+        task.time = item.time.clone().or(task.time);
+        let title = if item.due.is_some() { task.format(config) } else { quick_add_title(&task, config) };
+
+        if dry_run {
+            println!("  {} {}", "+".green(), title);
+            added += 1;
+            continue;
+        }
+        if sync::is_offline() {
+            app.db.add_inbox_item(&title)?;
+            println!("  {} {} {}", "📥".green(), title, "(queued)".dimmed());
+            added += 1;
+            continue;
+        }
+
+        let created = create_task_in_google(app, "@default", &title, None).and_then(|id| {
+            if item.notes.is_some() {
+                let mut task = Task::parse_with_config(&title, "Tasks", Some(config));
+                task.id = Some(id);
+                task.list_id = Some("@default".to_string());
+                task.date = item.due.clone().unwrap_or_else(|| today.clone());
+                task.notes = item.notes.clone();
+                update_task_locally(app, &task)?;
+            }
+            Ok(())
+        });
+        match created {
+            Ok(()) => {
+                println!("  {} {}", "✓".green(), title);
+                added += 1;
+            }
+            Err(e) => {
+                println!("  {} {} {}", "❌".red(), title, format!("({})", e).dimmed());
+                failed += 1;
+            }
+        }
+    }
+
+    if !dry_run && !sync::is_offline() && added > 0 {
+        push_dirty_tasks_to_google(app)?;
+    }
 
+    let verb = if dry_run { "Would add" } else { "Added" };
+    println!("\n{}", format!("✓ {} {} tasks, skipped {} duplicates", verb, added, duplicates).green());
+    if failed > 0 {
+        println!("{}", format!("⚠ {} tasks failed to add", failed).yellow());
+    }
+    if dry_run {
+        println!("{}", "Dry run: nothing was changed.".dimmed());
+    }
+    Ok(())
+}
+
+/// Compare priorities - returns the "higher" (more urgent) priority
+fn higher_priority(config: &Config, p1: Option<&str>, p2: Option<&str>) -> Option<String> {
     match (p1, p2) {
         (Some(a), Some(b)) => {
-            let idx1 = priority_order.iter().position(|&x| x == a).unwrap_or(99);
-            let idx2 = priority_order.iter().position(|&x| x == b).unwrap_or(99);
-            Some(if idx1 <= idx2 { a } else { b }.to_string())
+            Some(if config.priority_rank(Some(a)) <= config.priority_rank(Some(b)) { a } else { b }.to_string())
         }
         (Some(a), None) => Some(a.to_string()),
         (None, Some(b)) => Some(b.to_string()),
@@ -2847,65 +6577,293 @@ fn earlier_date(d1: &str, d2: &str) -> String {
     if d1 <= d2 { d1.to_string() } else { d2.to_string() }
 }
 
-fn cmd_bump(config: &Config, days: i64, week: bool) -> Result<()> {
-    let tasks = get_tasks_from_cache()?;
-    let today = Local::now().format("%Y-%m-%d").to_string();
-
-    // Calculate target date
-    let target_date = if week {
-        // Find next Monday
-        let now = Local::now();
-        let days_until_monday = (8 - now.weekday().num_days_from_monday()) % 7;
-        let days_to_add = if days_until_monday == 0 { 7 } else { days_until_monday };
-        (now + chrono::Duration::days(days_to_add as i64)).format("%Y-%m-%d").to_string()
-    } else {
-        (Local::now() + chrono::Duration::days(days)).format("%Y-%m-%d").to_string()
-    };
+#[allow(clippy::too_many_arguments)]
+fn cmd_bump(app: &App, ids: &[String], days: i64, week: bool, interactive: bool, filter: &TaskFilter, dry_run: bool) -> Result<()> {
+    let config = &app.config;
+    let tasks = get_tasks_from_cache(app)?;
+    let today = Local::now().format("%Y-%m-%d").to_string();
+
+    // Calculate target date
+    let target_date = if week {
+        let next_week = start_of_week(Local::now().date_naive(), config.week_start()) + chrono::Duration::days(7);
+        next_week.format("%Y-%m-%d").to_string()
+    } else {
+        (Local::now() + chrono::Duration::days(days)).format("%Y-%m-%d").to_string()
+    };
+
+    // Named tasks, or today's incomplete tasks narrowed by the filters
+    let mut candidates: Vec<&Task> = if ids.is_empty() {
+        tasks.iter()
+            .filter(|t| {
+                t.date == today && !t.is_done()
+            })
+            .collect()
+    } else {
+        let mut named = Vec::new();
+        for id in ids {
+            let matching = find_tasks_by_id_prefix(&tasks, id);
+            match matching.len() {
+                0 => anyhow::bail!("No task found with ID starting with '{}'", id),
+                1 => named.push(matching[0]),
+                _ => {
+                    println!("{}", format!("Multiple tasks match '{}'. Please be more specific:", id).yellow());
+                    for task in matching {
+                        println!("  {} {}", task.short_id().dimmed(), task.title);
+                    }
+                    return Ok(());
+                }
+            }
+        }
+        named
+    };
+    filter.retain(&mut candidates);
+
+    if candidates.is_empty() {
+        println!("{}", "No matching tasks to bump.".green());
+        return Ok(());
+    }
+
+    let mut to_bump: Vec<Task> = if interactive {
+        println!("{}", format!("Bump to {}? (y/n, a=all remaining, q=stop)\n", target_date).cyan().bold());
+        let mut chosen = Vec::new();
+        for (idx, task) in candidates.iter().enumerate() {
+            print!("  {} {} ", task.short_id().dimmed(), task.format(config));
+            stdout().flush()?;
+            let key = read_single_key()?;
+            println!("{}", key);
+            match key {
+                'y' | 'Y' => chosen.push((*task).clone()),
+                'a' | 'A' => {
+                    chosen.extend(candidates[idx..].iter().map(|t| (*t).clone()));
+                    break;
+                }
+                'q' | 'Q' | '\x1b' => break,
+                _ => {}
+            }
+        }
+        println!();
+        if chosen.is_empty() {
+            println!("{}", "Nothing bumped.".dimmed());
+            return Ok(());
+        }
+        chosen
+    } else {
+        candidates.into_iter().cloned().collect()
+    };
+
+    if dry_run {
+        println!("{}", output::header(&format!("📅 Would bump {} tasks to {}\n", to_bump.len(), target_date)));
+        for task in &to_bump {
+            println!("  {} {} {}", task.short_id().dimmed(), task.title, format!("({} → {})", task.date, target_date).dimmed());
+        }
+        println!("\n{}", "Dry run: nothing was changed.".dimmed());
+        return Ok(());
+    }
+
+    println!("{}", output::header(&format!("📅 Bumping {} tasks to {}\n", to_bump.len(), target_date)));
+
+    for task in &to_bump {
+        println!("  {} {}", task.short_id().dimmed(), task.title);
+    }
+    bump_tasks(app, &mut to_bump, &target_date)?;
+    let bumped_count = to_bump.len();
+
+    // Push changes
+    println!("\n{}", "Pushing changes to Google...".dimmed());
+    push_dirty_tasks_to_google(app)?;
+
+    println!("\n{}", format!("✓ Bumped {} tasks to {}", bumped_count, target_date).green());
+
+    Ok(())
+}
+
+/// Apply a single `key=value` assignment from `batch --set`
+fn apply_batch_set(task: &mut Task, assignment: &str) -> Result<()> {
+    let (key, value) = assignment.split_once('=')
+        .with_context(|| format!("Expected KEY=VALUE, got '{}'", assignment))?;
+    let value = value.trim();
+    let optional = if value.is_empty() { None } else { Some(value.to_string()) };
+
+    match key.trim().to_lowercase().as_str() {
+        "priority" | "p" => task.priority = optional.map(|v| v.to_uppercase()),
+        "project" | "j" => task.project = optional.map(|v| v.to_uppercase()),
+        "status" | "s" => task.status = optional,
+        "context" | "c" => {
+            task.context = optional.map(|v| if v.starts_with('@') { v } else { format!("@{}", v) })
+        }
+        "time" | "t" => task.time = optional,
+        "date" | "d" => {
+            chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .with_context(|| format!("Invalid date '{}', expected YYYY-MM-DD", value))?;
+            task.date = value.to_string();
+        }
+        "start" => task.start = optional.map(|v| parse_start_date(&v)).transpose()?,
+        other => anyhow::bail!("Unknown field '{}' (use priority, project, status, context, time, date, start)", other),
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn cmd_batch(app: &App, filter: &TaskFilter, set: &[String], bump: Option<i64>, complete: bool, delete: bool, yes: bool, dry_run: bool) -> Result<()> {
+    let config = &app.config;
+    if filter.is_empty() {
+        anyhow::bail!("batch needs at least one filter (--priority, --project, --status, --context, --tag)");
+    }
+    let actions = !set.is_empty() as usize + bump.is_some() as usize + complete as usize + delete as usize;
+    if actions == 0 {
+        anyhow::bail!("Nothing to do: pass --set, --bump, --complete, or --delete");
+    }
+    if delete && actions > 1 {
+        anyhow::bail!("--delete can't be combined with other changes");
+    }
 
-    // Find today's incomplete tasks (not marked DONE)
-    let to_bump: Vec<Task> = tasks.into_iter()
-        .filter(|t| {
-            t.date == today &&
-            t.priority.as_ref().map(|p| p != "DONE").unwrap_or(true)
-        })
+    let tasks = get_tasks_from_cache(app)?;
+    let mut matched: Vec<&Task> = tasks.iter()
+        .filter(|t| !t.is_done())
         .collect();
+    filter.retain(&mut matched);
 
-    if to_bump.is_empty() {
-        println!("{}", "No incomplete tasks from today to bump.".green());
+    if matched.is_empty() {
+        println!("{}", "No tasks match those filters.".green());
         return Ok(());
     }
 
-    println!("{}", format!("📅 Bumping {} tasks to {}\n", to_bump.len(), target_date).cyan().bold());
+    // Build every change up front so a bad --set aborts before anything is written
+    let bump_date = bump.map(|days| (Local::now() + chrono::Duration::days(days)).format("%Y-%m-%d").to_string());
+    let mut updated = Vec::new();
+    for original in &matched {
+        let mut task = (*original).clone();
+        for assignment in set {
+            apply_batch_set(&mut task, assignment)?;
+        }
+        // Status changes follow the configured workflow
+        if let Some(ref status) = task.status {
+            let from = original.status.as_deref();
+            if !config.allows_transition(from, status) {
+                let allowed: Vec<&str> = config.next_statuses(from).iter().map(|s| s.name.as_str()).collect();
+                anyhow::bail!("\"{}\" can't move from {} to {} (allowed: {})", task.title, from.unwrap_or("no status"), status, allowed.join(", "));
+            }
+            task.completed |= config.is_done_status(Some(status));
+        }
+        if let Some(ref date) = bump_date {
+            task.date = date.clone();
+        }
+        if complete {
+            task.completed = true;
+        }
+        updated.push(task);
+    }
+
+    let verb = if delete { "Delete" } else { "Update" };
+    println!("{}", format!("📦 {} {} tasks:\n", verb, matched.len()).cyan().bold());
+    for (before, after) in matched.iter().zip(&updated) {
+        if delete {
+            println!("  {} {}", before.short_id().dimmed(), before.title.red());
+        } else {
+            println!("  {} {}", before.short_id().dimmed(), before.title.dimmed());
+            println!("  {} {}", " ".repeat(before.short_id().len()), after.format(config));
+        }
+    }
 
-    let mut bumped_count = 0;
+    if dry_run {
+        println!("\n{}", "Dry run: nothing was changed.".dimmed());
+        return Ok(());
+    }
 
-    for mut task in to_bump {
-        println!("  {} {}", task.short_id().dimmed(), task.title);
+    if !yes {
+        print!("\n{} ", "Apply? (y/n)".yellow().bold());
+        stdout().flush()?;
+        let key = read_single_key()?;
+        println!();
+        if key != 'y' && key != 'Y' {
+            println!("{}", "Cancelled.".dimmed());
+            return Ok(());
+        }
+    }
 
-        task.date = target_date.clone();
+    let sync_manager = &app.db;
 
-        if let Err(e) = update_task_locally(&task) {
-            println!("    {}", format!("❌ Failed: {}", e).red());
-        } else {
-            bumped_count += 1;
+    if delete {
+        for task in &matched {
+            delete_task_everywhere(app, task)?;
         }
+        println!("{}", format!("✓ Deleted {} tasks", matched.len()).green());
+        return Ok(());
     }
 
-    // Push changes
-    println!("\n{}", "Pushing changes to Google...".dimmed());
-    push_dirty_tasks_to_google(&config.google_account)?;
+    // One transaction for the whole batch, then a single push
+    save_tasks_locally(sync_manager, config, &updated)?;
+    if bump.is_some() {
+        record_bumps(sync_manager, &updated)?;
+    }
+    println!("{}", format!("✓ Updated {} tasks", updated.len()).green());
 
-    println!("\n{}", format!("✓ Bumped {} tasks to {}", bumped_count, target_date).green());
+    println!("\n{}", "Pushing changes to Google...".dimmed());
+    push_dirty_tasks_to_google(app)?;
 
     Ok(())
 }
 
-fn cmd_search(config: &Config, query: &str, project: Option<&str>, status: Option<&str>, context: Option<&str>, priority: Option<&str>) -> Result<()> {
-    let tasks = get_tasks_from_cache()?;
+const SEARCH_FIELDS: [&str; 6] = ["title", "notes", "tags", "description", "links", "all"];
+
+/// The searchable text of one task, by field; description and links come from its cache row.
+/// Airtable and ClickUp descriptions are built from the notes and links, so those cover them.
+fn search_texts(task: &Task, cached: Option<&CachedTask>, fields: &[String]) -> Vec<(&'static str, String)> {
+    let wanted = |field: &str| fields.iter().any(|f| f == field || f == "all");
+    let mut texts = Vec::new();
+    if wanted("title") {
+        texts.push(("title", task.title.clone()));
+    }
+    if let Some(notes) = task.notes.clone().filter(|_| wanted("notes")) {
+        texts.push(("notes", notes));
+    }
+    if wanted("tags") && !task.tags.is_empty() {
+        texts.push(("tags", task.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ")));
+    }
+    if let Some(cached) = cached {
+        if wanted("description") {
+            texts.push(("description", cached.taskgarden_description.clone()));
+        }
+        if let Some(links) = cached.links.clone().filter(|_| wanted("links")) {
+            texts.push(("links", links));
+        }
+    }
+    texts
+}
+
+#[allow(clippy::too_many_arguments)]
+fn cmd_search(app: &App, query: &str, fields: &[String], regex: bool, project: Option<&str>, status: Option<&str>, context: Option<&str>, priority: Option<&str>) -> Result<()> {
+    let config = &app.config;
+    let tasks = get_tasks_from_cache(app)?;
     let query_lower = query.to_lowercase();
+    let pattern = if regex {
+        Some(regex::RegexBuilder::new(query).case_insensitive(true).build()
+            .with_context(|| format!("Invalid regex '{}'", query))?)
+    } else {
+        None
+    };
+    let is_match = |text: &str| match &pattern {
+        Some(pattern) => pattern.is_match(text),
+        None => text.to_lowercase().contains(&query_lower),
+    };
+    let cached: std::collections::HashMap<String, CachedTask> = app.db.get_all_cached_tasks()?
+        .into_iter()
+        .map(|c| (c.id.clone(), c))
+        .collect();
 
+    // Each hit remembers the fields it matched in, shown after anything but a title match
+    let mut matched_in: std::collections::HashMap<String, Vec<&str>> = std::collections::HashMap::new();
     let mut results: Vec<&Task> = tasks.iter()
-        .filter(|t| t.title.to_lowercase().contains(&query_lower))
+        .filter(|t| {
+            let id = t.id.clone().unwrap_or_default();
+            let hits: Vec<&str> = search_texts(t, cached.get(&id), fields).into_iter()
+                .filter(|(_, text)| is_match(text))
+                .map(|(field, _)| field)
+                .collect();
+            let found = !hits.is_empty();
+            matched_in.insert(id, hits);
+            found
+        })
         .collect();
 
     // Apply filters
@@ -2953,31 +6911,35 @@ fn cmd_search(config: &Config, query: &str, project: Option<&str>, status: Optio
     println!("{}", format!("🔍 Found {} tasks matching '{}'\n", results.len(), query).cyan().bold());
 
     // Sort by priority
-    results.sort_by(|a, b| {
-        let a_pri = a.priority.as_deref().unwrap_or("P9");
-        let b_pri = b.priority.as_deref().unwrap_or("P9");
-        a_pri.cmp(b_pri)
-    });
+    results.sort_by_key(|t| config.priority_rank(t.priority.as_deref()));
 
     for task in results {
-        println!("{} {}", task.short_id().dimmed(), task.format(config));
+        let hits = matched_in.get(task.id.as_deref().unwrap_or_default()).map(Vec::as_slice).unwrap_or_default();
+        let where_found = if hits.contains(&"title") { String::new() } else { format!(" (in {})", hits.join(", ")) };
+        println!("{} {}{}", task.short_id().dimmed(), task.format(config), where_found.dimmed());
     }
 
     Ok(())
 }
 
-fn cmd_show(config: &Config, id: &str) -> Result<()> {
-    let tasks = get_tasks_from_cache()?;
-
-    // Find task by ID (full or partial match)
+/// Find tasks whose ID starts with the given (case-insensitive) prefix
+fn find_tasks_by_id_prefix<'a>(tasks: &'a [Task], id: &str) -> Vec<&'a Task> {
     let id_lower = id.to_lowercase();
-    let matching_tasks: Vec<&Task> = tasks.iter()
+    tasks.iter()
         .filter(|t| {
             t.id.as_ref()
                 .map(|tid| tid.to_lowercase().starts_with(&id_lower))
                 .unwrap_or(false)
         })
-        .collect();
+        .collect()
+}
+
+fn cmd_show(app: &App, id: &str, json: bool, absolute: bool) -> Result<()> {
+    let config = &app.config;
+    let tasks = get_tasks_from_cache(app)?;
+
+    // Find task by ID (full or partial match)
+    let matching_tasks = find_tasks_by_id_prefix(&tasks, id);
 
     if matching_tasks.is_empty() {
         println!("{}", format!("No task found with ID starting with '{}'", id).red());
@@ -2993,20 +6955,132 @@ fn cmd_show(config: &Config, id: &str) -> Result<()> {
     }
 
     let task = matching_tasks[0];
+    let task_id = task.id.as_deref().unwrap_or_default();
+    let sync_manager = &app.db;
+
+    let bump_count = sync_manager.get_bump_counts()?.get(task_id).copied().unwrap_or(0);
+    let pending_reminders = reminders::pending_for(app, task_id)?;
+    let airtable_id = linked_record_id(sync_manager, "airtable_id_map", task_id)?;
+    let clickup_id = linked_record_id(sync_manager, "clickup_id_map", task_id)?;
+    let clickup_url = clickup_id.as_ref().map(|id| format!("https://app.clickup.com/t/{}", id));
+    let asana_id = linked_record_id(sync_manager, "asana_id_map", task_id)?;
+    let asana_url = asana_id.as_ref().map(|id| format!("https://app.asana.com/0/{}/{}", app.config.asana.project, id));
+    let subtasks: Vec<&Task> = sync_manager.get_subtask_ids(task_id)?
+        .iter()
+        .filter_map(|sub_id| tasks.iter().find(|t| t.id.as_deref() == Some(sub_id.as_str())))
+        .collect();
+
+    // History: creation in Google, recorded field changes, and escalation-policy flags, oldest first
+    let mut history: Vec<(String, String)> = Vec::new();
+    if let Some(ref created) = task.created {
+        history.push((created.clone(), "created in Google".to_string()));
+    }
+    for change in sync_manager.get_task_history(task_id)? {
+        history.push((format_change_time(&change.at), format!("{} ({})", describe_change(&change), change.source)));
+    }
+    // Escalations already show up as title changes; flags don't change anything
+    for entry in sync_manager.get_priority_drift("")?.into_iter().filter(|d| d.task_id == task_id && d.to_priority.is_none()) {
+        history.push((format_change_time(&entry.at), format!("🚩 flagged ({})", entry.reason)));
+    }
+    history.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if json {
+        let output = serde_json::json!({
+            "task": task,
+            "formatted": task.format(config),
+            "bump_count": bump_count,
+            "airtable_record_id": airtable_id,
+            "clickup_task_id": clickup_id,
+            "clickup_url": clickup_url,
+            "asana_task_id": asana_id,
+            "asana_url": asana_url,
+            "reminders": pending_reminders,
+            "subtasks": subtasks.iter().map(|t| serde_json::json!({ "id": t.id, "title": t.title })).collect::<Vec<_>>(),
+            "history": history.iter().map(|(at, change)| serde_json::json!({ "at": at, "change": change })).collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
 
-    println!("{}", "═══ Task Details ═══".cyan().bold());
+    println!("{}", output::header("═══ Task Details ═══"));
     println!();
     println!("{}: {}", "ID".dimmed(), task.id.as_deref().unwrap_or("N/A"));
     println!("{}: {}", "Title".dimmed(), task.title.yellow().bold());
-    println!("{}: {}", "Date".dimmed(), task.date);
-    println!("{}: {}", "Priority".dimmed(), task.priority.as_deref().unwrap_or("--"));
+    let today = Local::now().date_naive();
+    let date = template::day_label(&task.date, today, absolute);
+    if date == task.date {
+        println!("{}: {}", "Date".dimmed(), task.date);
+    } else {
+        println!("{}: {} {}", "Date".dimmed(), date, format!("({})", task.date).dimmed());
+    }
+    if let Some(ref created) = task.created {
+        println!("{}: {}", "Created".dimmed(), template::day_label(created, today, absolute));
+    }
+    println!("{}: {}", "Priority".dimmed(), task.priority.as_deref().unwrap_or("--").color(config.priority_color(task.priority.as_deref())));
+    if let Some(ref status) = task.status {
+        println!("{}: {}", "Status".dimmed(), status.color(config.status_color(status)));
+    }
     println!("{}: {}", "Project".dimmed(), task.project.as_deref().unwrap_or("---"));
+    if let Some(person) = task.person() {
+        println!("{}: +{}", "Person".dimmed(), person);
+    }
     println!("{}: {}", "Time".dimmed(), task.time.as_deref().unwrap_or("none"));
     println!("{}: {}", "List".dimmed(), task.list);
+    if !task.tags.is_empty() {
+        let tags: Vec<String> = task.tags.iter().map(|t| format!("#{}", t)).collect();
+        println!("{}: {}", "Tags".dimmed(), tags.join(" "));
+    }
+    if bump_count > 0 {
+        println!("{}: {}", "Bumped".dimmed(), format!("↻{}", bump_count).yellow());
+    }
+    if !pending_reminders.is_empty() {
+        println!("{}: {}", "Reminders".dimmed(), pending_reminders.join(", "));
+    }
+    if habits::is_habit(task) {
+        let habits = habits::load(app, &tasks)?;
+        if let Some(habit) = habits::habit_for(&habits, task) {
+            let today = Local::now().date_naive();
+            println!("{}: {} {}d streak, best {}d", "Habit".dimmed(), habit.heat_strip(today), habit.streak(today), habit.best_streak());
+        }
+    }
 
     if let Some(ref att_type) = task.attachment_type {
         println!("{}: {}", "Attachment".dimmed(), att_type);
     }
+    if let Some(ref id) = airtable_id {
+        println!("{}: {}", "Airtable".dimmed(), id);
+    }
+    if let (Some(id), Some(url)) = (&clickup_id, &clickup_url) {
+        println!("{}: {} {}", "ClickUp".dimmed(), id, url.dimmed());
+    }
+    if let (Some(id), Some(url)) = (&asana_id, &asana_url) {
+        println!("{}: {} {}", "Asana".dimmed(), id, url.dimmed());
+    }
+
+    if let Some(notes) = task.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+        println!();
+        println!("{}:", "Notes".dimmed());
+        for line in notes.lines() {
+            println!("  {}", line);
+        }
+    }
+
+    if !subtasks.is_empty() {
+        println!();
+        println!("{}:", "Subtasks".dimmed());
+        for sub in &subtasks {
+            let done = if sub.is_done() { "✓".green() } else { "○".normal() };
+            println!("  {} {} {}", done, sub.short_id().dimmed(), sub.title);
+        }
+    }
+
+    if !history.is_empty() {
+        println!();
+        println!("{}:", "History".dimmed());
+        for (at, change) in &history {
+            println!("  {} {}", at.dimmed(), change);
+        }
+    }
 
     // Show formatted version
     println!();
@@ -3015,20 +7089,367 @@ fn cmd_show(config: &Config, id: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_merge(config: &Config, threshold: f64, reset: bool) -> Result<()> {
+/// An RFC3339 timestamp as local "YYYY-MM-DD HH:MM" (anything else is returned as-is)
+fn format_change_time(at: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(at)
+        .map(|dt| dt.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| at.to_string())
+}
+
+/// Report cache problems, optionally repairing them; fails while any remain
+fn cmd_fsck(app: &App, repair: bool) -> Result<()> {
+    let problems = fsck::check(&app.db)?;
+    if problems.is_empty() {
+        println!("{}", "✓ Cache is consistent".green());
+        return Ok(());
+    }
+
+    for problem in &problems {
+        let marker = if problem.is_repairable() { "✗".red() } else { "!".yellow() };
+        println!("  {} {}", marker, problem);
+    }
+    println!();
+
+    let repairable = problems.iter().filter(|p| p.is_repairable()).count();
+    let remaining = if repair {
+        let fixed = fsck::repair(&app.db, &problems)?;
+        println!("{}", format!("🔧 Repaired {} problems", fixed).green());
+        problems.len() - fixed
+    } else {
+        if repairable > 0 {
+            println!("{}", format!("{} can be fixed with `thegarden fsck --repair`", repairable).dimmed());
+        }
+        problems.len()
+    };
+
+    if remaining > 0 {
+        anyhow::bail!("{} problems remain", remaining);
+    }
+    Ok(())
+}
+
+/// List the cache's schema migrations, or apply the pending ones
+fn cmd_migrate(status_only: bool) -> Result<()> {
+    let statuses = SyncManager::migration_status()?;
+    let pending = statuses.iter().filter(|s| s.applied_at.is_none()).count();
+
+    if status_only {
+        for s in &statuses {
+            match &s.applied_at {
+                Some(at) => println!("  {} {:03} {} {}", "✓".green(), s.version, s.name, format_change_time(at).dimmed()),
+                None => println!("  {} {:03} {} {}", "·".yellow(), s.version, s.name, "pending".yellow()),
+            }
+        }
+        if pending > 0 {
+            println!("\n{}", format!("{} pending; run `thegarden migrate` (or any command) to apply", pending).yellow());
+        } else {
+            println!("\n{}", format!("✓ Up to date at version {}", migrations::latest_version()).green());
+        }
+        return Ok(());
+    }
+
+    if pending == 0 {
+        println!("{}", format!("✓ Cache schema is up to date (version {})", migrations::latest_version()).green());
+        return Ok(());
+    }
+    SyncManager::new()?;
+    println!("{}", format!("✓ Applied {} migrations (now at version {})", pending, migrations::latest_version()).green());
+    Ok(())
+}
+
+/// One-line description of a recorded change, e.g. "due: 2026-03-05 → 2026-03-06"
+fn describe_change(change: &TaskChange) -> String {
+    let value = |v: &Option<String>| v.clone().filter(|v| !v.is_empty()).unwrap_or_else(|| "—".to_string());
+    match change.field.as_str() {
+        "created" => format!("created: {}", value(&change.new_value)),
+        "deleted" => "deleted".to_string(),
+        field => format!("{}: {} → {}", field, value(&change.old_value), value(&change.new_value)),
+    }
+}
+
+fn cmd_history(app: &App, id: Option<&str>, since: Option<&str>) -> Result<()> {
+    let tasks = get_tasks_from_cache(app)?;
+    let sync_manager = &app.db;
+    let cutoff = since.map(parse_since).transpose()?;
+
+    let (heading, mut changes) = match id {
+        Some(id) => {
+            let matching = find_tasks_by_id_prefix(&tasks, id);
+            let task = match matching.len() {
+                0 => anyhow::bail!("No task found with ID starting with '{}'", id),
+                1 => matching[0],
+                n => anyhow::bail!("{} tasks match '{}'. Please be more specific", n, id),
+            };
+            let changes = sync_manager.get_task_history(task.id.as_deref().unwrap_or_default())?;
+            (format!("📜 History of {}", task.title), changes)
+        }
+        None => {
+            let cutoff = cutoff.clone().unwrap_or_default();
+            (format!("📜 Changes since {}", cutoff), sync_manager.get_history_since(&cutoff)?)
+        }
+    };
+    if let Some(ref cutoff) = cutoff {
+        changes.retain(|c| c.at.as_str() >= cutoff.as_str());
+    }
+
+    println!("{}\n", heading.cyan().bold());
+    if changes.is_empty() {
+        println!("{}", "No recorded changes.".dimmed());
+        return Ok(());
+    }
+    for change in &changes {
+        let who = format!("[{}]", change.source);
+        if id.is_some() {
+            println!("  {} {} {}", format_change_time(&change.at).dimmed(), describe_change(change), who.dimmed());
+        } else {
+            let short_id: String = change.task_id.chars().take(8).collect();
+            println!("  {} {} {} {}", format_change_time(&change.at).dimmed(), short_id.dimmed(), describe_change(change), who.dimmed());
+        }
+    }
+
+    Ok(())
+}
+
+/// The Airtable/ClickUp record a task was pushed to, from the ID map kept in sync_state
+fn linked_record_id(sync_manager: &SyncManager, map_key: &str, task_id: &str) -> Result<Option<String>> {
+    let Some(json) = sync_manager.get_state(map_key)? else {
+        return Ok(None);
+    };
+    let map: std::collections::HashMap<String, String> = serde_json::from_str(&json).unwrap_or_default();
+    Ok(map.get(task_id).cloned())
+}
+
+/// (label, url) pairs from a cached Google Tasks `links` JSON array
+fn parse_task_links(links_json: &str) -> Vec<(String, String)> {
+    let links: Vec<serde_json::Value> = serde_json::from_str(links_json).unwrap_or_default();
+    links.iter()
+        .filter_map(|link| {
+            let url = link["link"].as_str().filter(|u| !u.is_empty())?;
+            let label = link["description"].as_str().filter(|d| !d.is_empty())
+                .or(link["type"].as_str())
+                .unwrap_or("link");
+            Some((label.to_string(), url.to_string()))
+        })
+        .collect()
+}
+
+/// Web links in a task's notes: "Email: <url>" lines are labeled "email", others "link"
+fn note_links(notes: &str) -> Vec<(String, String)> {
+    notes.split_whitespace()
+        .filter(|word| word.starts_with("http://") || word.starts_with("https://"))
+        .map(|url| {
+            let label = if gmail::email_link(notes) == Some(url) { "email" } else { "link" };
+            (label.to_string(), url.to_string())
+        })
+        .collect()
+}
+
+/// Hand a URL to the system's default browser
+fn open_in_browser(url: &str) -> Result<()> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else {
+        Command::new("xdg-open")
+    };
+    let status = cmd.arg(url).status().context("Failed to launch the browser")?;
+    if !status.success() {
+        anyhow::bail!("Browser launcher exited with {}", status);
+    }
+    Ok(())
+}
+
+fn cmd_open(app: &App, id: &str) -> Result<()> {
+    let tasks = get_tasks_from_cache(app)?;
+    let matching = find_tasks_by_id_prefix(&tasks, id);
+    let task = match matching.len() {
+        0 => anyhow::bail!("No task found with ID starting with '{}'", id),
+        1 => matching[0],
+        n => anyhow::bail!("{} tasks match '{}'. Please be more specific", n, id),
+    };
+
+    let cached = app.db.get_task_by_id(task.id.as_deref().unwrap_or_default())?;
+    let mut links = cached.and_then(|c| c.links).map(|l| parse_task_links(&l)).unwrap_or_default();
+    // Gmail imports and `add --url` keep their link in the notes
+    links.extend(note_links(task.notes.as_deref().unwrap_or_default()));
+
+    let (label, url) = match links.len() {
+        0 => {
+            println!("{}", format!("No links on {}", task.title).yellow());
+            return Ok(());
+        }
+        1 => &links[0],
+        _ => {
+            println!("{}", format!("🔗 Links on {}\n", task.title).cyan().bold());
+            for (i, (label, url)) in links.iter().enumerate().take(9) {
+                println!("  {} {} {}", format!("{}.", i + 1).cyan(), label, url.dimmed());
+            }
+            print!("\n{} ", "Open which? (1-9, q=cancel)".yellow());
+            stdout().flush()?;
+            let key = read_single_key()?;
+            println!();
+            match key.to_digit(10).and_then(|d| links.get((d as usize).wrapping_sub(1))) {
+                Some(link) => link,
+                None => return Ok(()),
+            }
+        }
+    };
+
+    println!("{}", format!("🌐 Opening {}: {}", label, url).green());
+    open_in_browser(url)
+}
+
+/// Write new notes for a task, from -m or by editing them in $EDITOR
+fn cmd_note(app: &App, id: &str, message: Option<String>) -> Result<()> {
+    let tasks = get_tasks_from_cache(app)?;
+    let matching = find_tasks_by_id_prefix(&tasks, id);
+    let mut task = match matching.len() {
+        0 => anyhow::bail!("No task found with ID starting with '{}'", id),
+        1 => matching[0].clone(),
+        n => anyhow::bail!("{} tasks match '{}'. Please be more specific", n, id),
+    };
+
+    let notes = match message {
+        Some(message) => message,
+        None => edit_in_editor(task.notes.as_deref().unwrap_or_default(), &task.short_id())?,
+    };
+    let notes = notes.trim_end().to_string();
+    if task.notes.as_deref().unwrap_or_default() == notes {
+        println!("{}", "Notes unchanged.".dimmed());
+        return Ok(());
+    }
+
+    // An empty string (rather than None) clears the notes in Google on the next push
+    let cleared = notes.is_empty();
+    task.notes = Some(notes);
+    update_task_locally(app, &task)?;
+    if cleared {
+        println!("{}", format!("🗒  Cleared notes on {}", task.title).green());
+    } else {
+        println!("{}", format!("🗒  Saved notes on {}", task.title).green());
+    }
+
+    println!("\n{}", "Pushing changes to Google...".dimmed());
+    push_dirty_tasks_to_google(app)?;
+
+    Ok(())
+}
+
+/// Open `text` in $EDITOR (vi if unset) and return what was saved
+fn edit_in_editor(text: &str, name: &str) -> Result<String> {
+    let path = std::env::temp_dir().join(format!("taskgarden-note-{}.md", name));
+    fs::write(&path, text)?;
+
+    let editor = std::env::var("VISUAL").or_else(|_| std::env::var("EDITOR")).unwrap_or_else(|_| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().context("$EDITOR is empty")?;
+    let status = Command::new(program)
+        .args(parts)
+        .arg(&path)
+        .status()
+        .with_context(|| format!("Failed to run editor '{}'", editor))?;
+    if !status.success() {
+        anyhow::bail!("Editor exited with {}; notes not saved", status);
+    }
+
+    let edited = fs::read_to_string(&path)?;
+    let _ = fs::remove_file(&path);
+    Ok(edited)
+}
+
+/// Combine two duplicate tasks into `keep`: most urgent priority, earliest date,
+/// longest estimate, and the union of tags. `keep`'s metadata wins otherwise.
+fn merge_task_fields(config: &Config, keep: &Task, other: &Task, title: String) -> Task {
+    let mut merged_tags = keep.tags.clone();
+    for tag in &other.tags {
+        if !merged_tags.contains(tag) {
+            merged_tags.push(tag.clone());
+        }
+    }
+
+    Task {
+        id: keep.id.clone(),
+        list_id: keep.list_id.clone(),
+        title,
+        date: earlier_date(&keep.date, &other.date),
+        priority: higher_priority(config, keep.priority.as_deref(), other.priority.as_deref()),
+        project: keep.project.clone().or(other.project.clone()),
+        status: keep.status.clone().or(other.status.clone()),
+        context: keep.context.clone().or(other.context.clone()),
+        time: longer_time(keep.time.as_deref(), other.time.as_deref()),
+        list: keep.list.clone(),
+        attachment_type: keep.attachment_type.clone().or(other.attachment_type.clone()),
+        tags: merged_tags,
+        created: keep.created.clone(),
+        start: keep.start.clone().or(other.start.clone()),
+        notes: merge_notes(keep.notes.as_deref(), other.notes.as_deref()),
+        completed: keep.completed,
+    }
+}
+
+/// Keep both tasks' notes when merging, without repeating identical ones
+fn merge_notes(keep: Option<&str>, other: Option<&str>) -> Option<String> {
+    match (keep.filter(|n| !n.is_empty()), other.filter(|n| !n.is_empty())) {
+        (Some(a), Some(b)) if a != b => Some(format!("{}\n\n{}", a, b)),
+        (a, b) => a.or(b).map(|n| n.to_string()),
+    }
+}
+
+/// Delete a task from Google Tasks, then from the local cache if that worked
+fn delete_task_everywhere(app: &App, task: &Task) -> Result<()> {
+    // Deletes aren't queued, and a local-only delete would come back on the next sync
+    if sync::is_offline() {
+        anyhow::bail!("Offline: deleting tasks needs a connection");
+    }
+    if let Some(ref id) = task.id {
+        if let Some(ref list_id) = task.list_id {
+            app.google.delete(list_id, id).context("Failed to delete the task from Google Tasks")?;
+        }
+        app.db.delete_task_by_id(id)?;
+    }
+    Ok(())
+}
+
+fn cmd_merge(app: &App, threshold: f64, reset: bool, method: similarity::Method, dry_run: bool) -> Result<()> {
+    let config = &app.config;
     // Convert percentage to decimal (e.g., 80 -> 0.8)
     let threshold = if threshold > 1.0 { threshold / 100.0 } else { threshold };
 
-    println!("{}", format!("🔍 Finding duplicates ({}% similar)...\n", (threshold * 100.0) as i32).cyan().bold());
+    println!("{}", format!("🔍 Finding duplicates ({}% similar, {} method)...\n", (threshold * 100.0) as i32, method).cyan().bold());
 
-    let sync_manager = SyncManager::new()?;
+    let sync_manager = &app.db;
 
-    if reset {
+    if reset && !dry_run {
         sync_manager.reset_dismissed_pairs()?;
         println!("{}", "✓ Reset all dismissed pairs\n".green());
     }
 
-    let tasks = get_tasks_from_cache()?;
+    let tasks = get_tasks_from_cache(app)?;
+
+    // Embedding vectors are fetched once up front, one per task
+    let embeddings = if method == similarity::Method::Embedding {
+        let embedding_config = config.embeddings.as_ref()
+            .context("merge --method embedding requires an 'embeddings' section in config.json")?;
+        let texts: Vec<String> = tasks.iter().map(|t| similarity::normalize_title(&t.title)).collect();
+        println!("{}", format!("🧠 Embedding {} tasks...", texts.len()).dimmed());
+        Some(similarity::embed_all(embedding_config, &texts)?)
+    } else {
+        None
+    };
+
+    let pair_similarity = |i: usize, j: usize| -> f64 {
+        match method {
+            similarity::Method::Token => similarity::token_similarity(&tasks[i].title, &tasks[j].title),
+            similarity::Method::Bigram => similarity::string_similarity(&tasks[i].title, &tasks[j].title),
+            similarity::Method::Embedding => {
+                let vectors = embeddings.as_ref().expect("embeddings fetched above");
+                similarity::cosine_similarity(&vectors[i], &vectors[j])
+            }
+        }
+    };
 
     // Find potential duplicate pairs
     let mut pairs: Vec<(usize, usize, f64)> = Vec::new();
@@ -3042,13 +7463,28 @@ fn cmd_merge(config: &Config, threshold: f64, reset: bool) -> Result<()> {
                 }
             }
 
-            let sim = string_similarity(&tasks[i].title, &tasks[j].title);
+            let sim = pair_similarity(i, j);
             if sim >= threshold {
                 pairs.push((i, j, sim));
             }
         }
     }
 
+    // Near-duplicates queued by `sync --dedupe` are always offered, regardless of threshold
+    for (id1, id2) in sync_manager.get_merge_queue()? {
+        let index_of = |id: &str| tasks.iter().position(|t| t.id.as_deref() == Some(id));
+        match (index_of(&id1), index_of(&id2)) {
+            (Some(i), Some(j)) if !sync_manager.is_pair_dismissed(&id1, &id2)? => {
+                let (i, j) = (i.min(j), i.max(j));
+                if !pairs.iter().any(|(a, b, _)| *a == i && *b == j) {
+                    pairs.push((i, j, pair_similarity(i, j)));
+                }
+            }
+            _ if dry_run => {}
+            _ => sync_manager.remove_queued_pair(&id1, &id2)?,
+        }
+    }
+
     // Sort by similarity (highest first)
     pairs.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap());
 
@@ -3059,15 +7495,26 @@ fn cmd_merge(config: &Config, threshold: f64, reset: bool) -> Result<()> {
 
     println!("Found {} potential duplicate pairs\n", pairs.len().to_string().yellow().bold());
 
+    if dry_run {
+        for (i, j, sim) in &pairs {
+            println!("  {} {}", format!("{:>3}%", (sim * 100.0) as i32).cyan(), tasks[*i].title);
+            println!("       {}", tasks[*j].title);
+        }
+        println!("\n{}", "Dry run: nothing was merged or dismissed.".dimmed());
+        return Ok(());
+    }
+
     let mut merged_count = 0;
     let mut dismissed_count = 0;
+    let mut removed_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
 
     for (idx, (i, j, sim)) in pairs.iter().enumerate() {
         let task1 = &tasks[*i];
         let task2 = &tasks[*j];
 
         // Skip if either task was already merged (deleted)
-        if task1.id.is_none() || task2.id.is_none() {
+        let already_removed = |t: &Task| t.id.as_ref().map(|id| removed_ids.contains(id)).unwrap_or(true);
+        if already_removed(task1) || already_removed(task2) {
             continue;
         }
 
@@ -3106,127 +7553,32 @@ fn cmd_merge(config: &Config, threshold: f64, reset: bool) -> Result<()> {
 
         match choice {
             '1' | 'j' | 'J' => {
-                // Merge keeping task 1's title
-                // Combine tags from both tasks
-                let mut merged_tags = task1.tags.clone();
-                for tag in &task2.tags {
-                    if !merged_tags.contains(tag) {
-                        merged_tags.push(tag.clone());
-                    }
-                }
-                
-                let merged = Task {
-                    id: task1.id.clone(),
-                    list_id: task1.list_id.clone(),
-                    title: task1.title.clone(),
-                    date: earlier_date(&task1.date, &task2.date),
-                    priority: higher_priority(task1.priority.as_deref(), task2.priority.as_deref()),
-                    project: task1.project.clone().or(task2.project.clone()),
-                    status: task1.status.clone().or(task2.status.clone()),
-                    context: task1.context.clone().or(task2.context.clone()),
-                    time: longer_time(task1.time.as_deref(), task2.time.as_deref()),
-                    list: task1.list.clone(),
-                    attachment_type: task1.attachment_type.clone().or(task2.attachment_type.clone()),
-                    tags: merged_tags,
-                };
-
-                // Update task 1 with merged data
-                update_task_locally(&merged)?;
-
-                // Delete task 2 from cache and mark for deletion in Google
-                if let Some(ref id2) = task2.id {
-                    if let Some(ref list_id) = task2.list_id {
-                        // Delete from Google
-                        let _ = Command::new("gog")
-                            .args(&["tasks", "delete", list_id, id2, "--account", &config.google_account])
-                            .output();
-                    }
-                    sync_manager.delete_task_by_id(id2)?;
-                }
+                // Merge keeping task 1's title, delete task 2
+                let merged = merge_task_fields(&app.config, task1, task2, task1.title.clone());
+                update_task_locally(app, &merged)?;
+                delete_task_everywhere(app, task2)?;
+                removed_ids.extend(task2.id.clone());
 
                 println!("{}", "  ✓ Merged (kept task 1)".green());
                 merged_count += 1;
             }
             '2' | 'k' | 'K' => {
-                // Merge keeping task 2's title
-                // Combine tags from both tasks
-                let mut merged_tags = task2.tags.clone();
-                for tag in &task1.tags {
-                    if !merged_tags.contains(tag) {
-                        merged_tags.push(tag.clone());
-                    }
-                }
-                
-                let merged = Task {
-                    id: task2.id.clone(),
-                    list_id: task2.list_id.clone(),
-                    title: task2.title.clone(),
-                    date: earlier_date(&task1.date, &task2.date),
-                    priority: higher_priority(task1.priority.as_deref(), task2.priority.as_deref()),
-                    project: task2.project.clone().or(task1.project.clone()),
-                    status: task2.status.clone().or(task1.status.clone()),
-                    context: task2.context.clone().or(task1.context.clone()),
-                    time: longer_time(task1.time.as_deref(), task2.time.as_deref()),
-                    list: task2.list.clone(),
-                    attachment_type: task2.attachment_type.clone().or(task1.attachment_type.clone()),
-                    tags: merged_tags,
-                };
-
-                // Update task 2 with merged data
-                update_task_locally(&merged)?;
-
-                // Delete task 1 from cache and Google
-                if let Some(ref id1) = task1.id {
-                    if let Some(ref list_id) = task1.list_id {
-                        let _ = Command::new("gog")
-                            .args(&["tasks", "delete", list_id, id1, "--account", &config.google_account])
-                            .output();
-                    }
-                    sync_manager.delete_task_by_id(id1)?;
-                }
-
-                println!("{}", "  ✓ Merged (kept task 2)".green());
-                merged_count += 1;
-            }
-            '3' | 'l' | 'L' => {
-                // Merge joining both titles
-                let joined_title = format!("{} / {}", task1.title, task2.title);
-                
-                // Combine tags from both tasks
-                let mut merged_tags = task1.tags.clone();
-                for tag in &task2.tags {
-                    if !merged_tags.contains(tag) {
-                        merged_tags.push(tag.clone());
-                    }
-                }
-                
-                let merged = Task {
-                    id: task1.id.clone(),
-                    list_id: task1.list_id.clone(),
-                    title: joined_title.clone(),
-                    date: earlier_date(&task1.date, &task2.date),
-                    priority: higher_priority(task1.priority.as_deref(), task2.priority.as_deref()),
-                    project: task1.project.clone().or(task2.project.clone()),
-                    status: task1.status.clone().or(task2.status.clone()),
-                    context: task1.context.clone().or(task2.context.clone()),
-                    time: longer_time(task1.time.as_deref(), task2.time.as_deref()),
-                    list: task1.list.clone(),
-                    attachment_type: task1.attachment_type.clone().or(task2.attachment_type.clone()),
-                    tags: merged_tags,
-                };
-
-                // Update task 1 with merged data
-                update_task_locally(&merged)?;
+                // Merge keeping task 2's title, delete task 1
+                let merged = merge_task_fields(&app.config, task2, task1, task2.title.clone());
+                update_task_locally(app, &merged)?;
+                delete_task_everywhere(app, task1)?;
+                removed_ids.extend(task1.id.clone());
 
-                // Delete task 2 from cache and Google
-                if let Some(ref id2) = task2.id {
-                    if let Some(ref list_id) = task2.list_id {
-                        let _ = Command::new("gog")
-                            .args(&["tasks", "delete", list_id, id2, "--account", &config.google_account])
-                            .output();
-                    }
-                    sync_manager.delete_task_by_id(id2)?;
-                }
+                println!("{}", "  ✓ Merged (kept task 2)".green());
+                merged_count += 1;
+            }
+            '3' | 'l' | 'L' => {
+                // Merge joining both titles into task 1, delete task 2
+                let joined_title = format!("{} / {}", task1.title, task2.title);
+                let merged = merge_task_fields(&app.config, task1, task2, joined_title.clone());
+                update_task_locally(app, &merged)?;
+                delete_task_everywhere(app, task2)?;
+                removed_ids.extend(task2.id.clone());
 
                 println!("{}", format!("  ✓ Merged: {}", joined_title).green());
                 merged_count += 1;
@@ -3235,6 +7587,7 @@ fn cmd_merge(config: &Config, threshold: f64, reset: bool) -> Result<()> {
                 // Mark as not duplicates
                 if let (Some(id1), Some(id2)) = (&task1.id, &task2.id) {
                     sync_manager.dismiss_pair(id1, id2)?;
+                    sync_manager.remove_queued_pair(id1, id2)?;
                 }
                 println!("{}", "  ✓ Marked as not duplicates".yellow());
                 dismissed_count += 1;
@@ -3255,7 +7608,7 @@ fn cmd_merge(config: &Config, threshold: f64, reset: bool) -> Result<()> {
     // Push any changes
     if merged_count > 0 {
         println!("\n{}", "Pushing changes to Google...".dimmed());
-        push_dirty_tasks_to_google(&config.google_account)?;
+        push_dirty_tasks_to_google(app)?;
     }
 
     println!("\n{}", "═══ Merge Complete! ═══".green().bold());
@@ -3264,25 +7617,40 @@ fn cmd_merge(config: &Config, threshold: f64, reset: bool) -> Result<()> {
     Ok(())
 }
 
-fn cmd_summary(_config: &Config, group_by: &str, created_days: Option<i64>, due_days: Option<i64>, include_done: bool, sort_by: &str, detailed: bool) -> Result<()> {
+fn cmd_summary(app: &App, group_by: &str, created_days: Option<i64>, due_days: Option<i64>, include_done: bool, sort_by: &str, detailed: bool) -> Result<()> {
+    let config = &app.config;
     use std::collections::HashMap;
     
-    println!("{}", format!("📊 Task Summary - Grouped by {}\n", group_by).cyan().bold());
+    println!("{}", output::header(&format!("📊 Task Summary - Grouped by {}\n", group_by)));
     
-    let tasks = get_tasks_from_cache()?;
+    let tasks = get_tasks_from_cache(app)?;
     let today = Local::now().date_naive();
+    let wip_warnings = config.wip_limits.warnings(&tasks);
+    let bump_counts = app.db.get_bump_counts()?;
+    let chronic: Vec<(Task, i64)> = chronic_bumps(&tasks, &bump_counts, CHRONIC_BUMP_THRESHOLD)
+        .into_iter()
+        .map(|(t, n)| (t.clone(), n))
+        .collect();
+    let (goal_titles, stale_goals) = if group_by == "goal" {
+        let since = (Utc::now() - chrono::Duration::days(goals::STALE_DAYS)).to_rfc3339();
+        (goals::titles_by_task(app)?, goals::stale(app, &tasks, &since)?)
+    } else {
+        Default::default()
+    };
     
     // Filter tasks based on parameters
     let filtered_tasks: Vec<Task> = tasks.into_iter().filter(|task| {
         // Exclude done tasks unless requested
-        if !include_done && task.priority.as_ref().map(|p| p == "DONE").unwrap_or(false) {
+        if !include_done && task.is_done() {
             return false;
         }
         
         // Filter by creation date if specified
-        if let Some(_days) = created_days {
-            // For now, we'll skip this filter as we'd need to add created_date tracking
-            // This would require schema changes
+        if let Some(days) = created_days {
+            let cutoff = (today - chrono::Duration::days(days)).format("%Y-%m-%d").to_string();
+            if task.created.as_deref().is_none_or(|c| c < cutoff.as_str()) {
+                return false;
+            }
         }
         
         // Filter by due date if specified
@@ -3304,18 +7672,18 @@ fn cmd_summary(_config: &Config, group_by: &str, created_days: Option<i64>, due_
     for task in filtered_tasks {
         let group_key = match group_by {
             "date" | "due" => task.date.clone(),
+            "priority" if task.is_done() => config.done_priority().to_string(),
             "priority" => task.priority.clone().unwrap_or_else(|| "--".to_string()),
             "project" => task.project.clone().unwrap_or_else(|| "No Project".to_string()),
             "status" => task.status.clone().unwrap_or_else(|| "todo".to_string()),
             "context" => task.context.clone().unwrap_or_else(|| "No Context".to_string()),
-            "created" => {
-                // For now, use date as proxy (would need schema changes for real created date)
-                task.date.clone()
-            }
+            "person" => task.person().map(|p| format!("+{}", p)).unwrap_or_else(|| "No Person".to_string()),
+            "created" => task.created.clone().unwrap_or_else(|| "Unknown".to_string()),
+            "goal" => task.id.as_ref().and_then(|id| goal_titles.get(id)).cloned().unwrap_or_else(|| "No Goal".to_string()),
             _ => "Unknown".to_string(),
         };
         
-        groups.entry(group_key).or_insert_with(Vec::new).push(task);
+        groups.entry(group_key).or_default().push(task);
     }
     
     // Calculate time estimates and counts for each group
@@ -3336,20 +7704,18 @@ fn cmd_summary(_config: &Config, group_by: &str, created_days: Option<i64>, due_
     
     // Sort groups based on sort parameter
     match sort_by {
-        "count" => group_stats.sort_by(|a, b| b.count.cmp(&a.count)),
-        "time" => group_stats.sort_by(|a, b| b.total_minutes.cmp(&a.total_minutes)),
+        "count" => group_stats.sort_by_key(|g| std::cmp::Reverse(g.count)),
+        "time" => group_stats.sort_by_key(|g| std::cmp::Reverse(g.total_minutes)),
         _ => {
             // Sort by name, handling dates specially
             if group_by == "date" || group_by == "due" {
                 group_stats.sort_by(|a, b| a.name.cmp(&b.name));
             } else {
                 group_stats.sort_by(|a, b| {
-                    // Special sorting for priorities
+                    // Priorities in their configured order, unset after, done last
                     if group_by == "priority" {
-                        let priority_order = ["P0", "P1", "P2", "P3", "--", "DONE"];
-                        let a_idx = priority_order.iter().position(|&p| p == a.name).unwrap_or(999);
-                        let b_idx = priority_order.iter().position(|&p| p == b.name).unwrap_or(999);
-                        a_idx.cmp(&b_idx)
+                        let rank = |name: &str| config.priority_rank(Some(name).filter(|n| *n != "--"));
+                        rank(&a.name).cmp(&rank(&b.name)).then_with(|| a.name.cmp(&b.name))
                     } else {
                         a.name.cmp(&b.name)
                     }
@@ -3376,7 +7742,7 @@ fn cmd_summary(_config: &Config, group_by: &str, created_days: Option<i64>, due_
                     d if d < 0 => format!(" ({} days ago)", -d).red().to_string(),
                     d => format!(" (in {} days)", d).green().to_string(),
                 };
-                format!("{}{}", date.format("%a %b %d"), day_str)
+                format!("{}{}", config.format_date(date, "%a %b %d"), day_str)
             } else {
                 stats.name.clone()
             }
@@ -3390,8 +7756,10 @@ fn cmd_summary(_config: &Config, group_by: &str, created_days: Option<i64>, due_
             if let Some(tasks) = groups.get(&stats.name) {
                 for task in tasks {
                     let time_str = task.time.as_ref().map(|t| format!(" {{{}}}", t)).unwrap_or_default();
-                    let status_str = task.status.as_ref().map(|s| format!(" [{}]", s)).unwrap_or_default();
-                    println!("   • {}{}{}", task.title, time_str.dimmed(), status_str.cyan());
+                    let status_str = task.status.as_ref()
+                        .map(|s| format!(" [{}]", s).color(config.status_color(s)).to_string())
+                        .unwrap_or_default();
+                    println!("   • {}{}{}", task.title, time_str.dimmed(), status_str);
                 }
             }
             println!();
@@ -3408,10 +7776,16 @@ fn cmd_summary(_config: &Config, group_by: &str, created_days: Option<i64>, due_
     
     // Add insights based on grouping
     match group_by {
+        "goal" if !stale_goals.is_empty() => {
+            println!("\n⚠️  {} No task activity in {} days:", "Stale goals:".yellow(), goals::STALE_DAYS);
+            for stale in &stale_goals {
+                println!("   #{} {} ({})", stale.goal.id, stale.goal.title, stale.goal.quarter);
+            }
+        }
         "priority" => {
-            if let Some(p0_stats) = group_stats.iter().find(|s| s.name == "P0") {
+            if let Some(p0_stats) = group_stats.iter().find(|s| Some(s.name.as_str()) == config.top_priority()) {
                 if p0_stats.count > 5 {
-                    println!("\n⚠️  {} You have {} P0 tasks - consider re-prioritizing", "Warning:".red(), p0_stats.count);
+                    println!("\n⚠️  {} You have {} {} tasks - consider re-prioritizing", "Warning:".red(), p0_stats.count, p0_stats.name);
                 }
             }
         }
@@ -3444,6 +7818,32 @@ fn cmd_summary(_config: &Config, group_by: &str, created_days: Option<i64>, due_
         _ => {}
     }
     
+    print_wip_warnings(&wip_warnings);
+    
+    // Priority drift: what the escalation policy changed or flagged in the last two weeks
+    let since = (Utc::now() - chrono::Duration::days(14)).to_rfc3339();
+    let drift = app.db.get_priority_drift(&since)?;
+    if !drift.is_empty() {
+        println!("\n{}", "📈 Priority drift (last 14 days):".yellow().bold());
+        for entry in drift {
+            let change = match entry.to_priority {
+                Some(ref to) => format!("{} → {}", entry.from_priority.as_deref().unwrap_or("--"), to),
+                None => "🚩 flagged".to_string(),
+            };
+            let short_id: String = entry.task_id.chars().take(8).collect();
+            let day = local_date(&entry.at).map_or_else(|| entry.at.clone(), |d| d.format("%Y-%m-%d").to_string());
+            println!("  {} {} {} {} {}", day.dimmed(), short_id.dimmed(),
+                change.yellow(), entry.title, format!("({})", entry.reason).dimmed());
+        }
+    }
+
+    if !chronic.is_empty() {
+        println!("\n{}", format!("↻ Bumped {}+ times — delete, delegate, or break down?", CHRONIC_BUMP_THRESHOLD).yellow().bold());
+        for (task, count) in &chronic {
+            println!("  {} {} {}", task.short_id().dimmed(), format!("↻{}", count).yellow(), task.format(config));
+        }
+    }
+    
     Ok(())
 }
 
@@ -3461,60 +7861,1139 @@ fn format_time_from_minutes(minutes: i32) -> String {
 }
 
 fn main() -> Result<()> {
+    // Answer <TAB> requests from the script printed by `completions` before anything else runs
+    clap_complete::CompleteEnv::with_factory(Cli::command).var(completions::COMPLETE_VAR).complete();
+
     let cli = Cli::parse();
+    output::set_mode(if cli.porcelain {
+        output::Mode::Porcelain
+    } else if cli.quiet {
+        output::Mode::Quiet
+    } else {
+        output::Mode::Normal
+    });
+    if let Some(ref dir) = cli.data_dir {
+        paths::set_data_dir(dir.clone());
+    }
+    if let Some(ref path) = cli.config {
+        paths::set_config_path(path.clone());
+    }
+    paths::migrate_legacy()?;
+    logging::init(cli.verbose);
+    tracing::debug!(args = ?std::env::args().skip(1).collect::<Vec<_>>(), "start");
+
+    // Opening the cache applies migrations, so look at them first
+    if let Commands::Migrate { status } = cli.command {
+        return cmd_migrate(status);
+    }
+    // Nothing may hold the cache open while it's replaced
+    if let Commands::Restore { ref archive } = cli.command {
+        let safety = backup::restore(archive)?;
+        println!("{}", format!("✓ Restored {}", archive.display()).green());
+        println!("{}", format!("  The previous cache and config were saved to {}", safety.display()).dimmed());
+        return Ok(());
+    }
     let config = load_config()?;
+    apply_home_timezone(&config);
+    output::set_theme(&config.theme);
+    let google = provider::from_config(&config)?;
+    let mut db = SyncManager::new()?;
+    if db.set_encrypted(config.encrypt_cache)? {
+        let done = if config.encrypt_cache { "🔒 Encrypted the local cache" } else { "🔓 Decrypted the local cache" };
+        output::note(done.green());
+    }
+    let app = &App { config, db, google };
+    let config = &app.config;
+
+    // Anything clap didn't recognize has to be a view
+    if let Commands::External(ref args) = cli.command {
+        if !config.views.contains_key(&args[0]) {
+            anyhow::bail!("Unrecognized subcommand '{}' (see --help, or `view` for saved views)", args[0]);
+        }
+        if args.len() > 1 {
+            anyhow::bail!("Views take no arguments; use `list` with filters for a one-off");
+        }
+    }
+
+    // An explicit sync overrides the config default, but not the flag
+    let offline = cli.offline || (config.offline && !matches!(cli.command, Commands::Sync { .. }));
+    sync::set_offline(offline);
+
+    if config.backup.daily && !matches!(cli.command, Commands::Backup { .. }) {
+        match backup::run_daily(&app.db, &config.backup) {
+            // stdout belongs to the protocol under mcp
+            Ok(Some(archive)) if !matches!(cli.command, Commands::Mcp) => {
+                output::note(format!("💾 Daily backup: {}", archive.display()).dimmed())
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("{}", format!("⚠️  Daily backup failed: {:#}", e).yellow()),
+        }
+    }
 
     // Auto-sync before most commands (unless it's an explicit sync command)
     match &cli.command {
-        Commands::Sync { force, airtable, clickup } => {
+        Commands::Sync { dry_run: false, .. } if offline => {
+            anyhow::bail!("Can't sync with --offline");
+        }
+        Commands::Sync { action: Some(SyncAction::Check), .. } => {}
+        Commands::Sync { force, airtable, clickup, mstodo, asana, dedupe, dry_run, .. } => {
             // Always sync when explicitly called
-            sync_with_google(&config.google_account, *force)?;
-            update_last_query()?;
-            
-            // Push to Airtable if requested
-            if *airtable {
-                sync_to_airtable(&config)?;
+            if *dry_run {
+                preview_google_push(app)?;
+            } else {
+                let created_ids = sync_with_google(app, *force)?;
+                update_last_query(app)?;
+                after_sync(app, &created_ids, *dedupe)?;
+                reconcile::after_sync(app)?;
+                // Titles carrying the ClickUp marker when it's off, or garbled, get cleaned up
+                let keep_marker = config.clickup.as_ref().is_some_and(|c| c.mark_synced);
+                let cleaned = clickup_sync::clean_synced_markers(&app.db, keep_marker)?;
+                if cleaned > 0 {
+                    output::note(format!("🧹 Cleaned the 🔃 marker from {} task titles", cleaned).dimmed());
+                }
             }
-            
-            // Push to ClickUp if requested
-            if *clickup {
-                sync_to_clickup(&config)?;
+
+            // Queued changes go to Google first: the ClickUp push retitles the same tasks
+            // there, and running both at once would leave the title to whichever lands last
+            if !*dry_run {
+                push_dirty_tasks_to_google(app)?;
+            }
+            // Airtable and ClickUp (if requested) then push concurrently, so the slower one
+            // sets the pace rather than the sum of them
+            let (airtable, clickup) = block_on(async {
+                tokio::join!(
+                    async { if *airtable { sync_to_airtable(app, *dry_run, *force).await } else { Ok(()) } },
+                    async { if *clickup { sync_to_clickup(app, *dry_run, *force).await } else { Ok(()) } },
+                )
+            });
+            airtable?;
+            clickup?;
+
+            // Push to Microsoft To Do if requested
+            if *mstodo {
+                mstodo::sync_to_mstodo(app, *dry_run)?;
+            }
+
+            // Push to Asana if requested
+            if *asana {
+                asana::sync_to_asana(app, *dry_run)?;
             }
         }
-        Commands::Triage { .. } | Commands::Focus | Commands::Plan | Commands::Schedule { .. } | Commands::List { .. } | Commands::Merge { .. } | Commands::Show { .. } | Commands::Search { .. } | Commands::Bump { .. } | Commands::Summary { .. } => {
+        // The pre-command sync pushes queued changes, which a dry run mustn't do
+        command if command.is_dry_run() => {}
+        Commands::Triage { .. } | Commands::Focus { .. } | Commands::Next { .. } | Commands::Fit { .. } | Commands::Plan { .. } | Commands::Schedule { .. } | Commands::Reconcile | Commands::List { .. } | Commands::Merge { .. } | Commands::Show { .. } | Commands::Search { .. } | Commands::Bump { .. } | Commands::Summary { .. } | Commands::Digest { .. } | Commands::Batch { .. } | Commands::Split { .. } | Commands::Tag { .. } | Commands::Goal { .. } | Commands::Stats { .. } | Commands::Report { .. } | Commands::Burndown { .. } | Commands::Note { .. } | Commands::Remind { .. } | Commands::Open { .. } | Commands::History { .. } | Commands::Import { .. } | Commands::Agenda { .. } | Commands::Obsidian { .. } | Commands::View { .. } | Commands::External(_) => {
             // Smart sync (check throttle)
-            if should_sync(&config, false)? {
-                sync_with_google(&config.google_account, false)?;
+            if !offline && !cli.no_sync && should_sync(app, false)? {
+                let created_ids = sync_with_google(app, false)?;
+                after_sync(app, &created_ids, false)?;
             }
             // Always update last_query timestamp (even if we didn't sync)
-            update_last_query()?;
+            update_last_query(app)?;
         }
         _ => {}
     }
 
+    // Attribute cache writes from here on to the command that made them
+    sync::set_change_source(match cli.command {
+        Commands::Triage { .. } => "triage",
+        Commands::Merge { .. } => "merge",
+        Commands::Bump { .. } => "bump",
+        Commands::Mcp => "mcp",
+        _ => "cli",
+    });
+
     match cli.command {
-        Commands::Triage { force, priority, project, time, status, context } => cmd_triage(&config, force, priority, project, time, status, context)?,
-        Commands::Focus => cmd_focus(&config)?,
-        Commands::Plan => cmd_plan(&config)?,
-        Commands::Schedule { week, auto } => cmd_schedule(&config, week, auto)?,
-        Commands::List { all, sort, reverse, status, context, priority, project, tag, days, limit, grouped } => {
-            cmd_list(&config, all, &sort, reverse, status.as_deref(), context.as_deref(), priority.as_deref(), project.as_deref(), tag.as_deref(), days, limit, grouped)?
-        }
-        Commands::Add { title, priority, project } => cmd_add(&config, title, priority, project)?,
-        Commands::Merge { threshold, reset } => cmd_merge(&config, threshold, reset)?,
-        Commands::Show { id } => cmd_show(&config, &id)?,
-        Commands::Search { query, project, status, context, priority } => {
-            cmd_search(&config, &query, project.as_deref(), status.as_deref(), context.as_deref(), priority.as_deref())?
-        }
-        Commands::Bump { days, week } => cmd_bump(&config, days, week)?,
-        Commands::Sync { force: _, airtable: _, clickup: _ } => {
+        Commands::Triage { force, priority, project, time, status, context, person, single_pass, scope } => {
+            cmd_triage(app, force, priority, project, time, status, context, person, single_pass, &scope)?
+        }
+        Commands::Next { minutes, context, all_contexts } => {
+            let from_rule = context.is_none() && !all_contexts && active_context_rule(app)?.is_some();
+            let context = context.or(current_context(app, all_contexts)?);
+            if let Some(ctx) = context.as_deref().filter(|_| from_rule) {
+                output::note(format!("{} · --context or --all-contexts to override", context_indicator(app, ctx)?).dimmed());
+            }
+            cmd_next(app, minutes, context.as_deref())?
+        }
+        Commands::Fit { minutes, all_contexts } => {
+            let context = current_context(app, all_contexts)?;
+            if let Some(ctx) = context.as_deref() {
+                output::note(format!("{} · --all-contexts to override", context_indicator(app, ctx)?).dimmed());
+            }
+            cmd_fit(app, minutes, context.as_deref())?
+        }
+        Commands::Focus { watch, include_future, include_done, all_contexts, absolute } => {
+            let context = current_context(app, all_contexts)?;
+            let from_rule = !all_contexts && active_context_rule(app)?.is_some();
+            if let Some(ctx) = context.as_deref().filter(|_| from_rule && !watch) {
+                output::note(format!("{} · --all-contexts to override", context_indicator(app, ctx)?).dimmed());
+            }
+            if watch && !output::is_quiet() {
+                cmd_focus_watch(app, include_future, context.as_deref())?
+            } else {
+                cmd_focus(app, include_future, include_done, context.as_deref(), absolute)?
+            }
+            // Status bars and health checks key off the exit code
+            if output::is_quiet() && has_overdue_p0(&app.config, &get_tasks_from_cache(app)?, include_future, context.as_deref()) {
+                std::process::exit(output::EXIT_OVERDUE);
+            }
+        }
+        Commands::Plan { next_week, from, to, days } => {
+            let (start, end, label) = plan_range(&app.config, Local::now().date_naive(), next_week, from, to, days)?;
+            cmd_plan(app, start, end, &label)?
+        }
+        Commands::Schedule { week, auto, dry_run } => cmd_schedule(app, week, auto, dry_run)?,
+        Commands::Reconcile => reconcile::cmd_reconcile(app)?,
+        Commands::List { all, sort, reverse, filter, days, limit, grouped, interactive, include_future, include_done, all_contexts, template, absolute } => {
+            // An explicit --context filter takes over from the current context
+            let context = if filter.context.is_some() { None } else { current_context(app, all_contexts)? };
+            cmd_list(app, all, &sort, reverse, &filter, days, limit, grouped, interactive, include_future, include_done, context.as_deref(), template.as_deref(), absolute, None)?
+        }
+        Commands::Add { title, priority, project, stdin, file, start, url } => {
+            if let Some(url) = url {
+                cmd_add_url(app, &url, title, priority, project, start.as_deref())?
+            } else if stdin {
+                let mut input = String::new();
+                std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)?;
+                cmd_add_bulk(app, &input, priority, project)?
+            } else if let Some(path) = file {
+                let input = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                cmd_add_bulk(app, &input, priority, project)?
+            } else if let Some(title) = title {
+                cmd_add(app, title, priority, project, start.as_deref(), None)?
+            }
+        }
+        Commands::In { text } => cmd_in(app, &text)?,
+        Commands::Split { id } => cmd_split(app, &id)?,
+        Commands::Merge { threshold, reset, method, dry_run } => cmd_merge(app, threshold, reset, method, dry_run)?,
+        Commands::Show { id, json, absolute } => cmd_show(app, &id, json, absolute)?,
+        Commands::Note { id, message } => cmd_note(app, &id, message)?,
+        Commands::Open { id } => cmd_open(app, &id)?,
+        Commands::Remind { id, when, clear } => reminders::cmd_remind(app, &id, &when, clear)?,
+        Commands::Notify { watch } => reminders::cmd_notify(app, watch)?,
+        Commands::Obsidian { vault, read_back } => obsidian::cmd_obsidian(app, vault, read_back)?,
+        Commands::History { id, since } => cmd_history(app, id.as_deref(), since.as_deref())?,
+        Commands::Search { query, fields, regex, project, status, context, priority } => {
+            cmd_search(app, &query, &fields, regex, project.as_deref(), status.as_deref(), context.as_deref(), priority.as_deref())?
+        }
+        Commands::Agenda { who, markdown } => agenda::cmd_agenda(app, &who, markdown)?,
+        Commands::View { name } => cmd_view(app, name.as_deref())?,
+        Commands::External(args) => cmd_view(app, Some(&args[0]))?,
+        Commands::Bump { ids, days, week, interactive, filter, dry_run } => cmd_bump(app, &ids, days, week, interactive, &filter, dry_run)?,
+        Commands::Sync { action: Some(SyncAction::Check), .. } => check::cmd_sync_check(app)?,
+        Commands::Sync { dry_run, .. } => {
             // Already handled above
-            println!("{}", "✓ Sync complete!".green());
+            if dry_run {
+                println!("\n{}", "Dry run: nothing was changed.".dimmed());
+            } else {
+                println!("{}", "✓ Sync complete!".green());
+            }
         }
+        Commands::Digest { dry_run } => notify::cmd_digest(app, dry_run)?,
         Commands::Summary { group, created_days, due_days, include_done, sort, detailed } => {
-            cmd_summary(&config, &group, created_days, due_days, include_done, &sort, detailed)?
+            cmd_summary(app, &group, created_days, due_days, include_done, &sort, detailed)?
+        }
+        Commands::Batch { filter, set, bump, complete, delete, yes, dry_run } => {
+            cmd_batch(app, &filter, &set, bump, complete, delete, yes, dry_run)?
+        }
+        Commands::Context { action } => cmd_context(app, action)?,
+        Commands::Tag { action } => cmd_tag(app, action)?,
+        Commands::Goal { action } => goals::cmd_goal(app, action)?,
+        Commands::Stats { action } => cmd_stats(app, action)?,
+        Commands::Mcp => mcp::run(app)?,
+        Commands::Serve { webhooks, port, url } => {
+            if !webhooks {
+                anyhow::bail!("Nothing to serve: pass --webhooks");
+            }
+            serve::run(app, port, url.as_deref())?
+        }
+        Commands::Completions { shell } => completions::print_script(&shell)?,
+        Commands::Export { format, output, filter } => {
+            let tasks = export::collect(app, &filter)?;
+            let contents = match format.as_str() {
+                "csv" => export::to_csv(&tasks)?,
+                "org" => org::to_org(config, &tasks),
+                _ => export::to_json(&tasks)?,
+            };
+            match output {
+                Some(path) => {
+                    fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+                    eprintln!("{}", format!("✓ Exported {} tasks to {}", tasks.len(), path.display()).green());
+                }
+                None => print!("{}", contents),
+            }
+        }
+        Commands::Report { week, ago, format, output } => {
+            if !week {
+                anyhow::bail!("Nothing to report: pass --week");
+            }
+            let (start, end) = report::week_bounds(Local::now().date_naive(), ago, config.week_start());
+            let report = report::weekly(app, start, end)?;
+            let contents = match format.as_str() {
+                "html" => report.to_html(),
+                _ => report.to_markdown(),
+            };
+            match output {
+                Some(path) => {
+                    fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+                    eprintln!("{}", format!("✓ Wrote the report to {}", path.display()).green());
+                }
+                None => print!("{}", contents),
+            }
+        }
+        Commands::Burndown { project, weeks } => burndown::run(app, project.as_deref(), weeks)?,
+        Commands::Import { file, source: Some(source), dry_run, .. } => cmd_import_from(app, &source, &file, dry_run)?,
+        Commands::Import { file, format, .. } => {
+            let contents = fs::read_to_string(&file).with_context(|| format!("Failed to read {}", file.display()))?;
+            let stats = match format.as_str() {
+                "org" => org::import_org(app, &contents)?,
+                _ => export::import_json(app, &contents)?,
+            };
+            println!("\n{}", format!(
+                "✓ {} updated, {} recreated, {} unchanged, {} completed skipped",
+                stats.updated, stats.created, stats.unchanged, stats.skipped
+            ).green());
+            if stats.failed > 0 {
+                anyhow::bail!("{} tasks failed to import", stats.failed);
+            }
+        }
+        Commands::Backup { path, include_secrets } => {
+            let dest = match path {
+                Some(path) => path,
+                None => backup::default_dir()?,
+            };
+            let archive = backup::create(&app.db, &dest, include_secrets)?;
+            println!("{}", format!("💾 Backed up to {}", archive.display()).green());
+            if !include_secrets {
+                println!("{}", "  API keys were left out (--include-secrets keeps them)".dimmed());
+            }
         }
+        Commands::Fsck { repair } => cmd_fsck(app, repair)?,
+        Commands::Migrate { .. } | Commands::Restore { .. } => {} // Handled before the cache was opened
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_args_are_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_completions_accepts_supported_shells() {
+        for shell in completions::SHELLS {
+            assert!(Cli::try_parse_from(["thegarden", "completions", shell]).is_ok(), "{}", shell);
+        }
+        assert!(Cli::try_parse_from(["thegarden", "completions", "tcsh"]).is_err());
+    }
+
+    #[test]
+    fn test_merge_rejects_unknown_methods() {
+        let cli = Cli::try_parse_from(["thegarden", "merge", "-m", "e"]).unwrap();
+        assert!(matches!(cli.command, Commands::Merge { method: similarity::Method::Embedding, .. }));
+        assert!(Cli::try_parse_from(["thegarden", "merge", "--method", "embeddings"]).is_err());
+    }
+
+    #[test]
+    fn test_sync_flags_are_global() {
+        let cli = Cli::try_parse_from(["thegarden", "list", "--offline"]).unwrap();
+        assert!(cli.offline && !cli.no_sync);
+        let cli = Cli::try_parse_from(["thegarden", "--no-sync", "show", "abc"]).unwrap();
+        assert!(cli.no_sync && !cli.offline);
+    }
+
+    #[test]
+    fn test_bump_500_tasks_under_a_second() {
+        let app = App {
+            config: create_default_config(),
+            db: SyncManager::open(std::path::Path::new(":memory:")).unwrap(),
+            google: Box::new(provider::fake::FakeProvider::new()),
+        };
+        let mut tasks: Vec<Task> = (0..500).map(|i| {
+            let mut task = Task::parse_with_config(&format!("[2026-01-21][P2][WORK] Task {}", i), "Tasks", Some(&app.config));
+            task.id = Some(format!("task{:04}", i));
+            task.list_id = Some("list".into());
+            task
+        }).collect();
+
+        let start = std::time::Instant::now();
+        bump_tasks(&app, &mut tasks, "2026-01-22").unwrap();
+        let elapsed = start.elapsed();
+
+        assert!(elapsed < std::time::Duration::from_secs(1), "bumping 500 tasks took {:?}", elapsed);
+        assert_eq!(app.db.get_bump_counts().unwrap().len(), 500);
+        assert_eq!(app.db.get_task_by_id("task0042").unwrap().unwrap().due_date.as_deref(), Some("2026-01-22"));
+    }
+
+    #[test]
+    fn test_completion_is_a_status_not_a_title() {
+        let app = App {
+            config: create_default_config(),
+            db: SyncManager::open(std::path::Path::new(":memory:")).unwrap(),
+            google: Box::new(provider::fake::FakeProvider::new()),
+        };
+        let mut remote = CachedTask::parse_from_emoji_string("[2026-01-21][P0][WORK] Done on my phone", "remote", "list");
+        remote.status = "completed".into();
+        app.db.upsert_task(&remote).unwrap();
+        app.db.upsert_task(&CachedTask::parse_from_emoji_string("[2026-01-21][P0][WORK] Still open", "open", "list")).unwrap();
+
+        let tasks = get_tasks_from_cache(&app).unwrap();
+        let titles = |include_done| focus_tasks(&app.config, &tasks, "2026-01-21", false, include_done, None).iter().map(|t| t.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(false), vec!["Still open"]);
+        assert_eq!(titles(true).len(), 2);
+
+        let mut done = tasks.into_iter().find(|t| t.title == "Still open").unwrap();
+        done.completed = true;
+        update_task_locally(&app, &done).unwrap();
+        let pushed = app.db.get_dirty_tasks().unwrap();
+        assert_eq!(pushed[0].status, "completed");
+        assert_eq!(pushed[0].title, "[2026-01-21][P0][WORK] Still open");
+    }
+
+    #[test]
+    fn test_dry_run_skips_auto_sync() {
+        for args in [&["thegarden", "bump", "--dry-run"][..], &["thegarden", "sync", "--clickup", "--dry-run"], &["thegarden", "schedule", "--dry-run"]] {
+            assert!(Cli::try_parse_from(args).unwrap().command.is_dry_run(), "{:?}", args);
+        }
+        assert!(!Cli::try_parse_from(["thegarden", "bump"]).unwrap().command.is_dry_run());
+    }
+
+    #[test]
+    fn test_path_overrides_are_global() {
+        let cli = Cli::try_parse_from(["thegarden", "list", "--config", "alt.json", "--data-dir", "/tmp/garden"]).unwrap();
+        assert_eq!(cli.config, Some(PathBuf::from("alt.json")));
+        assert_eq!(cli.data_dir, Some(PathBuf::from("/tmp/garden")));
+    }
+
+    #[test]
+    fn test_format_round_trips() {
+        let config = create_default_config();
+        let title = "[2026-01-21][P0][WORK][@work]{2h} Fix editor bug #DeepWork";
+        let task = Task::parse_with_config(title, "Tasks", Some(&config));
+        assert_eq!(task.time.as_deref(), Some("2h"));
+        assert_eq!(task.format(&config), title);
+    }
+
+    #[test]
+    fn test_parse_quick_add() {
+        let config = create_default_config();
+        let tomorrow = (Local::now() + chrono::Duration::days(1)).format("%Y-%m-%d").to_string();
+        let task = parse_quick_add("p1 @work /SILVERMINE 45m follow up with legal due tomorrow #FollowUp", &config);
+        assert_eq!(task.title, "follow up with legal #FollowUp");
+        assert_eq!(task.priority.as_deref(), Some("P1"));
+        assert_eq!(task.context.as_deref(), Some("@work"));
+        assert_eq!(task.project.as_deref(), Some("SILVERMINE"));
+        assert_eq!(task.time.as_deref(), Some("45m"));
+        assert_eq!(task.date, tomorrow);
+        assert_eq!(task.tags, vec!["FollowUp"]);
+    }
+
+    #[test]
+    fn test_event_bounds_all_day() {
+        let event = serde_json::json!({ "summary": "OOO", "start": { "date": "2026-03-02" }, "end": { "date": "2026-03-03" } });
+        let (start, end, all_day) = event_bounds(&event).unwrap();
+        assert!(all_day);
+        assert_eq!(start.format("%Y-%m-%d %H:%M").to_string(), "2026-03-02 00:00");
+        assert_eq!(end.format("%Y-%m-%d %H:%M").to_string(), "2026-03-03 00:00");
+
+        let timed = serde_json::json!({ "start": "2026-03-02T10:00:00Z", "end": "2026-03-02T11:00:00Z" });
+        assert!(!event_bounds(&timed).unwrap().2);
+    }
+
+    #[test]
+    fn test_free_blocks_respect_work_hours() {
+        let hours = WorkHours {
+            hours: "09:00-17:00".into(),
+            days: [("sat".to_string(), "off".to_string())].into_iter().collect(),
+            lunch: Some("12:00-13:00".into()),
+            buffer_minutes: 15,
+            max_block_minutes: Some(90),
+            preferences: Default::default(),
+            inflate_estimates: false,
+        };
+        let monday = chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let at = |h, m| Local.from_local_datetime(&monday.and_hms_opt(h, m, 0).unwrap()).unwrap();
+        let events = vec![(at(10, 0), at(11, 0), "Meeting".to_string())];
+
+        let ranges: Vec<String> = calculate_free_blocks(&hours, &monday, &events).iter()
+            .map(|b| b.format_time_range())
+            .collect();
+        assert_eq!(ranges, vec!["9:00 AM-9:45 AM", "11:15 AM-12:00 PM", "1:00 PM-5:00 PM"]);
+
+        let saturday = chrono::NaiveDate::from_ymd_opt(2026, 3, 7).unwrap();
+        assert!(calculate_free_blocks(&hours, &saturday, &[]).is_empty());
+
+        // A 2h task becomes a 90m block and a 30m block after a break
+        let task = Task::parse_with_config("[2026-03-02][P0][WORK]{2h} Deep work", "Tasks", None);
+        let free = calculate_free_blocks(&hours, &monday, &[]);
+        let config = Config { work_hours: hours, ..create_default_config() };
+        let blocks: Vec<String> = schedule_tasks_into_blocks(&config, vec![&task], &free, &std::collections::HashMap::new()).iter()
+            .map(|s| s.block.format_time_range())
+            .collect();
+        assert_eq!(blocks, vec!["9:00 AM-10:30 AM", "10:45 AM-11:15 AM"]);
+    }
+
+    #[test]
+    fn test_schedule_respects_preferences() {
+        let hours = WorkHours {
+            hours: "09:00-17:00".into(),
+            preferences: [("@deep", "09:00-11:00"), ("@errands", "17:30-19:00"), ("P0", "after 14:00")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..Default::default()
+        };
+        let monday = chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let free = calculate_free_blocks(&hours, &monday, &[]);
+        let tasks = [
+            Task::parse_with_config("[2026-03-02][P1][WORK][@errands]{30m} Pick up parcel", "Tasks", None),
+            Task::parse_with_config("[2026-03-02][P1][WORK]{1h} Write report", "Tasks", None),
+            Task::parse_with_config("[2026-03-02][P1][WORK][@deep]{2h} Design review", "Tasks", None),
+            Task::parse_with_config("[2026-03-02][P0][WORK]{1h} Ship fix", "Tasks", None),
+        ];
+        let config = Config { work_hours: hours, ..create_default_config() };
+        let placed: Vec<String> = schedule_tasks_into_blocks(&config, tasks.iter().collect(), &free, &std::collections::HashMap::new()).iter()
+            .map(|s| format!("{} {}", s.block.format_time_range(), s.task.title))
+            .collect();
+        assert_eq!(placed, vec![
+            "2:00 PM-3:00 PM Ship fix",
+            "9:00 AM-11:00 AM Design review",
+            "5:30 PM-6:00 PM Pick up parcel",
+            "11:00 AM-12:00 PM Write report",
+        ]);
+    }
+
+    #[test]
+    fn test_schedule_order() {
+        let config = Config { work_hours: WorkHours { hours: "09:00-13:00".into(), ..Default::default() }, ..create_default_config() };
+        let monday = chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let free = calculate_free_blocks(&config.work_hours, &monday, &[]);
+        let task = |id: &str, title: &str| {
+            let mut task = Task::parse_with_config(title, "Tasks", None);
+            task.id = Some(id.to_string());
+            task
+        };
+        let tasks = [
+            task("launch", "[2026-03-02][P0][WORK]{1h} Launch"),
+            task("later", "[2026-03-06][P1][WORK]{1h} Review roadmap"),
+            task("overdue", "[2026-02-27][P1][WORK]{30m} Expense report"),
+            task("copy", "[2026-03-02][P1][WORK]{30m} Write copy"),
+        ];
+        // Launch waits on its subtask; the overdue task beats one due Friday
+        let parents = std::collections::HashMap::from([("copy".to_string(), "launch".to_string())]);
+        let placed: Vec<String> = schedule_tasks_into_blocks(&config, tasks.iter().collect(), &free, &parents).iter()
+            .map(|s| format!("{} {}", s.block.format_time_range(), s.task.title))
+            .collect();
+        assert_eq!(placed, vec![
+            "9:00 AM-9:30 AM Write copy",
+            "9:30 AM-10:30 AM Launch",
+            "10:30 AM-11:00 AM Expense report",
+            "11:00 AM-12:00 PM Review roadmap",
+        ]);
+
+        // Weighting the deadline over priority lets the overdue task go first
+        let config = Config { schedule_weights: ScheduleWeights { deadline: 2000, ..Default::default() }, ..config };
+        let first = schedule_order(&config, &tasks.iter().collect::<Vec<_>>(), &free, &parents)[0];
+        assert_eq!(first.title, "Expense report");
+    }
+
+    #[test]
+    fn test_structured_projects() {
+        let mut config: Config = serde_json::from_value(serde_json::json!({
+            "format": "[{date}][{priority}][{project}]{status}{context}{time} {title}",
+            "date_format": "%Y-%m-%d",
+            "google_account": "me@example.com",
+            "projects": {
+                "LIFE": "Personal life tasks",
+                "WORK": {"description": "Work tasks", "priority": "P1", "context": "@office", "hours": "after 13:00"}
+            }
+        })).unwrap();
+        assert_eq!(config.projects["LIFE"].description, "Personal life tasks");
+        assert_eq!(config.project(Some("work")).unwrap().context.as_deref(), Some("@office"));
+
+        // Triage fills only what's missing
+        let mut task = Task::parse_with_config("[2026-03-02][P0][WORK]{1h} Ship fix", "Tasks", None);
+        assert_eq!(apply_project_defaults(&config, &mut task), vec!["@office"]);
+        assert_eq!(task.priority.as_deref(), Some("P0"));
+
+        // WORK tasks wait for the afternoon; a context preference still wins
+        config.work_hours = WorkHours {
+            hours: "09:00-17:00".into(),
+            preferences: [("@deep".to_string(), "09:00-11:00".to_string())].into_iter().collect(),
+            ..Default::default()
+        };
+        let monday = chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let free = calculate_free_blocks(&config.work_hours, &monday, &[]);
+        let tasks = [
+            Task::parse_with_config("[2026-03-02][P1][WORK]{1h} Write report", "Tasks", None),
+            Task::parse_with_config("[2026-03-02][P1][WORK][@deep]{1h} Design review", "Tasks", None),
+            Task::parse_with_config("[2026-03-02][P1][LIFE]{30m} Call bank", "Tasks", None),
+        ];
+        let placed: Vec<String> = schedule_tasks_into_blocks(&config, tasks.iter().collect(), &free, &std::collections::HashMap::new()).iter()
+            .map(|s| format!("{} {}", s.block.format_time_range(), s.task.title))
+            .collect();
+        assert_eq!(placed, vec![
+            "1:00 PM-2:00 PM Write report",
+            "9:00 AM-10:00 AM Design review",
+            "10:00 AM-10:30 AM Call bank",
+        ]);
+    }
+
+    #[test]
+    fn test_suggest_estimate() {
+        let config = create_default_config();
+        let done = |title: &str| {
+            let mut task = Task::parse_with_config(title, "Tasks", None);
+            task.completed = true;
+            task
+        };
+        let history = vec![
+            done("[2026-03-02][P1][WORK]{1h} Write weekly report"),
+            done("[2026-03-09][P1][WORK]{45m} Write weekly report"),
+            done("[2026-03-16][P1][WORK]{2h} Write the weekly report"),
+            done("[2026-03-03][P1][WORK]{8h} Migrate billing #infra"),
+            Task::parse_with_config("[2026-03-23][P1][WORK]{15m} Write weekly report", "Tasks", None),
+        ];
+
+        // Median of 45m/1h/2h; the open task doesn't count
+        let task = Task::parse_with_config("[2026-03-23][P1][WORK] Write weekly report", "Tasks", None);
+        assert_eq!(suggest_estimate(&config, &task, &history), Some(("1h".to_string(), 3)));
+
+        // No similar title: fall back to a shared tag in the project, snapped to an option
+        let task = Task::parse_with_config("[2026-03-23][P1][WORK] Rotate certificates #infra", "Tasks", None);
+        assert_eq!(suggest_estimate(&config, &task, &history), Some(("8h".to_string(), 1)));
+
+        let task = Task::parse_with_config("[2026-03-23][P1][LIFE] Plant tomatoes", "Tasks", None);
+        assert_eq!(suggest_estimate(&config, &task, &history), None);
+    }
+
+    #[test]
+    fn test_estimate_factor() {
+        let db = SyncManager::open(std::path::Path::new(":memory:")).unwrap();
+        db.log_time("a", 3600, "timer").unwrap();
+        db.log_time("a", 1800, "focus").unwrap();
+        db.log_time("b", 1800, "timer").unwrap();
+        db.log_time("open", 600, "timer").unwrap();
+        let tracked = db.get_tracked_seconds().unwrap();
+        assert_eq!(tracked["a"], 5400);
+
+        let task = |id: &str, title: &str, completed: bool| {
+            let mut task = Task::parse_with_config(title, "Tasks", None);
+            task.id = Some(id.to_string());
+            task.completed = completed;
+            task
+        };
+        let tasks = vec![
+            task("a", "[2026-03-02][P1][WORK]{1h} Write report", true),
+            task("b", "[2026-03-02][P1][LIFE]{1h} Call bank", true),
+            task("c", "[2026-03-02][P1][WORK]{1h} Never timed", true),
+            task("open", "[2026-03-02][P1][WORK]{1h} Still going", false),
+        ];
+        let samples = estimate_samples(&tasks, &tracked);
+        let ids: Vec<_> = samples.iter().map(|(t, _, _)| t.id.as_deref().unwrap()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        // 2h estimated, 2h tracked
+        assert_eq!(estimate_factor(&samples), Some(1.0));
+        assert_eq!(estimate_factor(&samples[..1]), Some(1.5));
+        assert_eq!(estimate_factor(&[]), None);
+    }
+
+    #[test]
+    fn test_day_capacity_flags_overcommitted_days() {
+        let hours = WorkHours { hours: "09:00-17:00".into(), ..Default::default() };
+        let monday = chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let at = |h, m| Local.from_local_datetime(&monday.and_hms_opt(h, m, 0).unwrap()).unwrap();
+        // The breakfast meeting only counts from 9:00
+        let events = vec![
+            (at(8, 0), at(10, 0), "Breakfast".to_string()),
+            (at(13, 0), at(16, 0), "Workshop".to_string()),
+        ];
+        let tasks = [
+            Task::parse_with_config("[2026-03-02][P0][WORK]{2h} Report", "Tasks", None),
+            Task::parse_with_config("[2026-03-02][P1][WORK]{3h} Slides", "Tasks", None),
+            Task::parse_with_config("[2026-03-02][DONE][WORK]{4h} Old thing", "Tasks", None),
+        ];
+        let task_refs: Vec<&Task> = tasks.iter().collect();
+
+        let capacity = day_capacity(&hours, &monday, &events, &task_refs);
+        assert_eq!(capacity, DayCapacity { meeting_minutes: 240, task_minutes: 300, free_minutes: 240 });
+        assert!(capacity.is_overcommitted());
+    }
+
+    #[test]
+    fn test_next_task_score() {
+        let today = "2026-03-06";
+        let task = |title: &str| Task::parse_with_config(title, "Tasks", None);
+        let config = create_default_config();
+        let score = |t: &Task, minutes, context| next_task_score(&config, t, today, minutes, context);
+
+        let p0_long = task("[2026-03-06][P0][WORK]{2h} Big push");
+        let p1_late = task("[2026-03-01][P1][WORK][@home]{30m} Call plumber");
+        let done = task("[2026-03-06][DONE][WORK]{30m} Old");
+        let future = task("[2026-03-09][P0][WORK]{30m} Later");
+
+        assert!(score(&p0_long, None, None) > score(&p1_late, None, None));
+        assert_eq!(score(&done, None, None), None);
+        assert_eq!(score(&future, None, None), None);
+        // Too long for a 30 minute window, or the wrong place
+        assert_eq!(score(&p0_long, Some(30), None), None);
+        assert_eq!(score(&p1_late, None, Some("@work")), None);
+        assert!(score(&p1_late, Some(30), Some("home")).is_some());
+    }
+
+    #[test]
+    fn test_custom_priority_scheme() {
+        let priority = |name: &str, key, done| Priority { name: name.into(), key, done, ..Default::default() };
+        let config = Config {
+            priorities: vec![
+                priority("MUST", 'm', false),
+                priority("SHOULD", 's', false),
+                priority("COULD", 'c', false),
+                priority("WONT", 'w', true),
+            ],
+            ..create_default_config()
+        };
+        let task = |title: &str| Task::parse_with_config(title, "Tasks", Some(&config));
+        let tasks = [
+            task("[2026-03-05][COULD][WORK] Tidy the wiki"),
+            task("[2026-03-05][WONT][WORK] Rewrite in Go"),
+            task("[2026-03-04][SHOULD][WORK] Review PR"),
+            task("[2026-03-05][MUST][WORK] Ship release"),
+        ];
+
+        let mut sorted: Vec<&Task> = tasks.iter().collect();
+        sorted.sort_by_key(|t| config.priority_rank(t.priority.as_deref()));
+        let names: Vec<&str> = sorted.iter().map(|t| t.priority.as_deref().unwrap()).collect();
+        assert_eq!(names, vec!["MUST", "SHOULD", "COULD", "WONT"]);
+
+        assert!(tasks[1].completed && !tasks[0].completed);
+        assert_eq!(config.done_priority(), "WONT");
+        assert_eq!(higher_priority(&config, Some("COULD"), Some("SHOULD")).as_deref(), Some("SHOULD"));
+        assert_eq!(config.priority_emoji(Some("MUST")), "🔴");
+        assert_eq!(config.priority_emoji(Some("WONT")), "✅");
+        let ascii = Config { theme: output::Theme { ascii: true, ..Default::default() }, ..create_default_config() };
+        assert_eq!((ascii.priority_emoji(Some("P0")).as_str(), ascii.priority_emoji(Some("P9")).as_str()), ("(!)", "( )"));
+
+        let focus: Vec<&str> = focus_tasks(&config, &tasks, "2026-03-05", false, false, None).iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(focus, vec!["Ship release", "Review PR"]);
+        assert!(is_schedulable(&config, &Task { time: Some("1h".into()), ..tasks[3].clone() }, "2026-03-05", "2026-03-05"));
+        assert!(!is_schedulable(&config, &Task { time: Some("1h".into()), ..tasks[0].clone() }, "2026-03-05", "2026-03-05"));
+    }
+
+    #[test]
+    fn test_status_workflow() {
+        let status = |name: &str, next: &[&str]| StatusOption {
+            name: name.into(),
+            next: next.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+        let config = Config {
+            statuses: vec![
+                status("todo", &["progress"]),
+                status("progress", &["review", "todo"]),
+                status("review", &["progress", "done"]),
+                StatusOption { done: true, ..status("done", &[]) },
+            ],
+            ..create_default_config()
+        };
+        let names = |current| config.next_statuses(current).iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(Some("todo")), vec!["progress"]);
+        assert_eq!(names(Some("progress")), vec!["todo", "review"]);
+        // Untriaged tasks and statuses with no transitions can go anywhere
+        assert_eq!(names(None).len(), 4);
+        assert_eq!(names(Some("done")), vec!["todo", "progress", "review"]);
+
+        assert!(config.allows_transition(Some("review"), "done"));
+        assert!(!config.allows_transition(Some("todo"), "done"));
+        assert!(config.allows_transition(Some("todo"), "todo"));
+        assert!(config.allows_transition(Some("todo"), "waiting"));
+        assert!(config.is_done_status(Some("done")) && !config.is_done_status(Some("review")));
+    }
+
+    #[test]
+    fn test_wip_limits() {
+        let limits = WipLimits {
+            status_per_project: [("progress".to_string(), 1)].into_iter().collect(),
+            priority_total: [("P0".to_string(), 2)].into_iter().collect(),
+        };
+        let task = |id: &str, title: &str| {
+            let mut t = Task::parse_with_config(title, "Tasks", None);
+            t.id = Some(id.to_string());
+            t
+        };
+        let tasks = vec![
+            task("a", "[2026-03-06][P0][WORK][progress]{1h} One"),
+            task("b", "[2026-03-06][P0][LIFE]{1h} Two"),
+            task("c", "[2026-03-06][P1][WORK]{1h} Three"),
+            task("d", "[2026-03-06][DONE][WORK][progress]{1h} Finished"),
+        ];
+        assert!(limits.warnings(&tasks).is_empty());
+
+        let mut started = tasks[2].clone();
+        started.status = Some("progress".to_string());
+        assert_eq!(limits.breach(&tasks, &tasks[2], &started).as_deref(), Some("WORK would have 2 tasks in progress (limit 1)"));
+
+        let mut urgent = tasks[2].clone();
+        urgent.priority = Some("P0".to_string());
+        assert!(limits.breach(&tasks, &tasks[2], &urgent).is_some());
+        // Re-saving an unchanged field never trips a limit
+        assert_eq!(limits.breach(&tasks, &tasks[0], &tasks[0]), None);
+
+        let over = vec![tasks[0].clone(), started, urgent];
+        assert_eq!(limits.warnings(&over), vec!["WORK has 2 tasks in progress (limit 1)"]);
+    }
+
+    #[test]
+    fn test_escalation_rules() {
+        let policy = EscalationPolicy {
+            rules: vec![
+                EscalationRule { from: "P2".into(), to: "P1".into(), older_than_days: 14 },
+                EscalationRule { from: "P3".into(), to: "P2".into(), older_than_days: 30 },
+            ],
+            flag_overdue_days: Some(7),
+        };
+        let p2 = Task::parse_with_config("[2026-03-01][P2][WORK] Tidy", "Tasks", None);
+        assert!(policy.rule_for(&p2, 13).is_none());
+        assert_eq!(policy.rule_for(&p2, 14).map(|r| r.to.as_str()), Some("P1"));
+        let p0 = Task::parse_with_config("[2026-03-01][P0][WORK] Fire", "Tasks", None);
+        assert!(policy.rule_for(&p0, 100).is_none());
+    }
+
+    #[test]
+    fn test_rollover_skips_pinned_tasks() {
+        let config = create_default_config();
+        let task = |title: &str| Task::parse_with_config(title, "Tasks", Some(&config));
+        let today = "2026-03-06";
+        assert!(is_rollover_candidate(&config, &task("[2026-03-05][P1][WORK] Late"), today));
+        assert!(!is_rollover_candidate(&config, &task("[2026-03-05][P1][WORK] Filing #harddeadline"), today));
+        assert!(!is_rollover_candidate(&config, &task("[2026-03-05][DONE][WORK] Finished"), today));
+        assert!(!is_rollover_candidate(&config, &task("[2026-03-06][P1][WORK] Today"), today));
+    }
+
+    #[test]
+    fn test_plan_range() {
+        let day = |m, d| chrono::NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        let friday = day(3, 6);
+        let mut config = create_default_config();
+        let range = |next_week, from, to, days| {
+            plan_range(&config, friday, next_week, from, to, days).map(|(start, end, _)| (start, end)).ok()
+        };
+
+        assert_eq!(range(false, None, None, None), Some((day(3, 2), day(3, 8))));
+        assert_eq!(range(true, None, None, None), Some((day(3, 9), day(3, 15))));
+        assert_eq!(range(false, None, None, Some(3)), Some((friday, day(3, 8))));
+        assert_eq!(range(false, Some(day(4, 1)), None, None), Some((day(4, 1), day(4, 7))));
+        assert_eq!(range(false, None, Some(day(3, 10)), None), Some((friday, day(3, 10))));
+        assert_eq!(range(false, Some(day(3, 10)), Some(day(3, 9)), None), None);
+
+        config.week_starts_on = "Sunday".into();
+        let sunday_week = plan_range(&config, friday, false, None, None, None).unwrap();
+        assert_eq!((sunday_week.0, sunday_week.1), (day(3, 1), day(3, 7)));
+        assert_eq!(plan_range(&config, friday, true, None, None, None).unwrap().0, day(3, 8));
+    }
+
+    #[test]
+    fn test_format_date() {
+        let mut config = create_default_config();
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 3, 6).unwrap();
+        assert_eq!(config.format_date(date, "%A, %b %d"), "Friday, Mar 06");
+        config.date_names = DateNames {
+            weekdays: ["Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag", "Sonntag"].map(String::from).to_vec(),
+            months: ["Januar", "Februar", "März", "April", "Mai", "Juni", "Juli", "August", "September", "Oktober", "November", "Dezember"]
+                .map(String::from).to_vec(),
+        };
+        assert_eq!(config.format_date(date, "%A, %b %d"), "Freitag, Mär 06");
+        assert_eq!(config.format_date(date, "%a %d. %B"), "Fre 06. März");
+    }
+
+    #[test]
+    fn test_distribute_by_capacity() {
+        let day = |d| chrono::NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let tasks = [
+            Task::parse_with_config("[2026-02-20][P0][WORK]{2h} Big", "Tasks", None),
+            Task::parse_with_config("[2026-02-21][P1][WORK]{1h} Medium", "Tasks", None),
+            Task::parse_with_config("[2026-02-22][P1][WORK] Unestimated", "Tasks", None),
+            Task::parse_with_config("[2026-02-23][P2][WORK]{4h} Too big", "Tasks", None),
+        ];
+        let task_refs: Vec<&Task> = tasks.iter().collect();
+        let days = [(day(4), 150), (day(5), 60)];
+
+        let assigned = distribute_by_capacity(&task_refs, &days);
+        // Big fills the 4th, Medium fits the 5th, the rest overflow onto the roomiest day
+        assert_eq!(assigned, vec![Some(day(4)), Some(day(5)), Some(day(4)), Some(day(4))]);
+        assert_eq!(distribute_by_capacity(&task_refs, &[]), vec![None; 4]);
+    }
+
+    #[test]
+    fn test_next_free_slot_skips_taken_time() {
+        let day = chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let at = |h, m| Local.from_local_datetime(&day.and_hms_opt(h, m, 0).unwrap()).unwrap();
+        let block = |start, end| TimeBlock { start, end, duration_minutes: (end - start).num_minutes() };
+        let free = vec![block(at(9, 0), at(11, 0)), block(at(13, 0), at(17, 0))];
+        let current = block(at(9, 0), at(10, 0));
+        let other = block(at(13, 0), at(14, 30));
+
+        let next = next_free_slot(&free, &[&other], &current).unwrap();
+        assert_eq!(next.format_time_range(), "2:30 PM-3:30 PM");
+        assert!(slot_is_free(&free, &[&other], &current));
+        assert!(!slot_is_free(&free, &[&other], &block(at(14, 0), at(15, 0))));
+    }
+
+    #[test]
+    fn test_parse_date_relative_weeks() {
+        let in_two_weeks = (Local::now() + chrono::Duration::days(14)).format("%Y-%m-%d").to_string();
+        assert_eq!(parse_date_from_text("in 2 weeks").as_deref(), Some(in_two_weeks.as_str()));
+        assert_eq!(parse_date_from_text("ask me in two weeks").as_deref(), Some(in_two_weeks.as_str()));
+    }
+
+    #[test]
+    fn test_chronic_bumps() {
+        let with_id = |title, id: &str| {
+            let mut t = Task::parse_with_config(title, "Tasks", None);
+            t.id = Some(id.to_string());
+            t
+        };
+        let tasks = [
+            with_id("[2026-03-05][P2][WORK] Taxes", "a"),
+            with_id("[2026-03-05][P1][WORK] Dentist", "b"),
+            with_id("[2026-03-05][DONE][WORK] Gym", "c"),
+            with_id("[2026-03-05][P3][WORK] Inbox zero", "d"),
+        ];
+        let counts: std::collections::HashMap<String, i64> =
+            [("a", 5), ("b", 9), ("c", 12), ("d", 2)].iter().map(|(k, v)| (k.to_string(), *v)).collect();
+
+        let chronic: Vec<(&str, i64)> = chronic_bumps(&tasks, &counts, 5).into_iter().map(|(t, n)| (t.title.as_str(), n)).collect();
+        assert_eq!(chronic, vec![("Dentist", 9), ("Taxes", 5)]);
+    }
+
+    #[test]
+    fn test_due_date_from_title() {
+        assert_eq!(due_date_from_title("[2026-03-05][P1][WORK] Review PR").as_deref(), Some("2026-03-05"));
+        assert_eq!(due_date_from_title("[--][P1][WORK] Review PR"), None);
+        assert_eq!(due_date_from_title("Buy milk"), None);
+        let tomorrow = (Local::now() + chrono::Duration::days(1)).format("%Y-%m-%d").to_string();
+        assert_eq!(due_date_from_title("Call the bank tomorrow").as_deref(), Some(tomorrow.as_str()));
+    }
+
+    #[test]
+    fn test_focus_hides_future_starts() {
+        let mut later = Task::parse_with_config("[2026-03-05][P0][WORK] Quarterly filing", "Tasks", None);
+        later.start = Some("2026-03-10".to_string());
+        let now = Task::parse_with_config("[2026-03-05][P0][WORK] Ship release", "Tasks", None);
+        let tasks = [later, now];
+
+        let titles = |include_future| focus_tasks(&create_default_config(), &tasks, "2026-03-05", include_future, false, None).iter().map(|t| t.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(false), vec!["Ship release"]);
+        assert_eq!(titles(true).len(), 2);
+        assert!(!starts_later(&tasks[0], "2026-03-10"));
+        assert_eq!(parse_start_date("2026-03-10").unwrap(), "2026-03-10");
+        assert!(parse_start_date("someday").is_err());
+    }
+
+    #[test]
+    fn test_retag_title() {
+        assert_eq!(retag_title("Fix #urgent bug #Work", "urgent", Some("asap")).as_deref(), Some("Fix #asap bug #Work"));
+        assert_eq!(retag_title("Fix bug #Work", "work", None).as_deref(), Some("Fix bug"));
+        assert_eq!(retag_title("Fix bug #Work #urgent", "urgent", Some("work")).as_deref(), Some("Fix bug #Work"));
+        assert_eq!(retag_title("Fix bug #Workday", "work", None), None);
+    }
+
+    #[test]
+    fn test_in_context() {
+        let task = |title| Task::parse_with_config(title, "Tasks", None);
+        assert!(in_context(&task("[2026-03-05][P1][LIFE][@home] Laundry"), "home"));
+        assert!(in_context(&task("[2026-03-05][P1][LIFE][@Home] Laundry"), "@home"));
+        assert!(!in_context(&task("[2026-03-05][P1][WORK][@work] Deploy"), "@home"));
+        assert!(in_context(&task("[2026-03-05][P1][WORK] Think about Q3"), "@home"));
+    }
+
+    #[test]
+    fn test_local_date() {
+        // The same instant written in two zones lands on the same local day
+        assert_eq!(local_date("2026-03-05T23:30:00-08:00"), local_date("2026-03-06T07:30:00Z"));
+        assert!(local_date("2026-03-06T07:30:00Z").is_some());
+        assert_eq!(local_date("2026-03-06"), None);
+    }
+
+    #[test]
+    fn test_context_rules() {
+        let rule = |context: &str, hours: &str, days: &[&str]| ContextRule {
+            context: context.into(),
+            hours: hours.into(),
+            days: days.iter().map(|d| d.to_string()).collect(),
+        };
+        let config = Config {
+            context_rules: vec![rule("work", "09:00-17:00", &["weekdays"]), rule("@home", "07:00-22:00", &[])],
+            ..create_default_config()
+        };
+        // 2026-03-05 is a Thursday
+        let at = |date: &str, time: &str| chrono::NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M").unwrap();
+        let context = |at| config.context_rule_at(at).map(ContextRule::context);
+        assert_eq!(context(at("2026-03-05", "09:00")).as_deref(), Some("@work"));
+        assert_eq!(context(at("2026-03-05", "17:00")).as_deref(), Some("@home"));
+        assert_eq!(context(at("2026-03-07", "10:00")).as_deref(), Some("@home"));
+        assert_eq!(context(at("2026-03-05", "23:00")), None);
+        assert!(rule("@gym", "06:00-07:00", &["Mon", "thursday"]).applies_at(at("2026-03-05", "06:30")));
+        assert_eq!(config.context_rules[0].describe(), "weekdays 09:00-17:00");
+    }
+
+    #[test]
+    fn test_syncs_project() {
+        let mut config = create_default_config();
+        config.projects.get_mut("WORK").unwrap().sync_to = Some(vec!["clickup".into(), "Airtable".into()]);
+        config.projects.get_mut("LIFE").unwrap().sync_to = Some(vec![]);
+        assert!(config.syncs_project(Some("WORK"), "airtable"));
+        assert!(!config.syncs_project(Some("work"), "asana"));
+        assert!(!config.syncs_project(Some("LIFE"), "clickup"));
+        assert!(config.syncs_project(Some("SIDE"), "clickup"));
+        assert!(config.syncs_project(None, "clickup"));
+
+        let mut excluded = config.excluded_projects("asana");
+        excluded.sort();
+        assert_eq!(excluded, vec!["LIFE", "WORK"]);
+    }
+
+    #[test]
+    fn test_merge_notes() {
+        assert_eq!(merge_notes(Some("call first"), Some("bring receipts")).as_deref(), Some("call first\n\nbring receipts"));
+        assert_eq!(merge_notes(Some("same"), Some("same")).as_deref(), Some("same"));
+        assert_eq!(merge_notes(Some(""), Some("only")).as_deref(), Some("only"));
+        assert_eq!(merge_notes(None, None), None);
+    }
+
+    #[test]
+    fn test_parse_task_links() {
+        let json = r#"[{"type":"email","description":"Re: budget","link":"https://mail.google.com/x"},{"type":"doc","link":"https://docs.google.com/y"},{"type":"doc"}]"#;
+        assert_eq!(parse_task_links(json), vec![
+            ("Re: budget".to_string(), "https://mail.google.com/x".to_string()),
+            ("doc".to_string(), "https://docs.google.com/y".to_string()),
+        ]);
+        assert!(parse_task_links("not json").is_empty());
+
+        let notes = "Email: https://mail.google.com/mail/#all/1\nSee https://example.com/post too";
+        assert_eq!(note_links(notes), vec![
+            ("email".to_string(), "https://mail.google.com/mail/#all/1".to_string()),
+            ("link".to_string(), "https://example.com/post".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_page_title() {
+        let html = "<html><head><TITLE>\n  Rust &amp; SQLite:\n a &quot;guide&quot; </TITLE></head></html>";
+        assert_eq!(page_title(html).as_deref(), Some("Rust & SQLite: a \"guide\""));
+        assert_eq!(page_title("<title>  </title>"), None);
+        assert_eq!(page_title("<p>no title</p>"), None);
+    }
+
+    #[test]
+    fn test_describe_change() {
+        let change = |field: &str, old: Option<&str>, new: Option<&str>| TaskChange {
+            task_id: "t1".to_string(),
+            field: field.to_string(),
+            old_value: old.map(String::from),
+            new_value: new.map(String::from),
+            source: "bump".to_string(),
+            at: "2026-03-05T10:00:00+00:00".to_string(),
+        };
+        assert_eq!(describe_change(&change("due", Some("2026-03-05"), Some("2026-03-06"))), "due: 2026-03-05 → 2026-03-06");
+        assert_eq!(describe_change(&change("notes", Some("call first"), Some(""))), "notes: call first → —");
+        assert_eq!(describe_change(&change("created", None, Some("Buy milk"))), "created: Buy milk");
+        assert_eq!(format_change_time("not a time"), "not a time");
+    }
+
+    #[test]
+    fn test_search_texts() {
+        let mut task = Task::parse_with_config("Renew passport #travel", "Inbox", None);
+        task.notes = Some("Bring the old one".into());
+        let mut cached = CachedTask::parse_from_emoji_string("Renew passport", "t1", "list1");
+        cached.links = Some("https://travel.state.gov".into());
+
+        let fields = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        let searched = |names: &[&str]| -> Vec<&str> {
+            search_texts(&task, Some(&cached), &fields(names)).into_iter().map(|(field, _)| field).collect()
+        };
+        assert_eq!(searched(&["title", "notes"]), vec!["title", "notes"]);
+        assert_eq!(searched(&["tags", "links"]), vec!["tags", "links"]);
+        assert_eq!(searched(&["all"]), vec!["title", "notes", "tags", "description", "links"]);
+        assert_eq!(search_texts(&task, None, &fields(&["links"])).len(), 0);
+    }
+
+    #[test]
+    fn test_person() {
+        let mut task = Task::parse_with_config("[2026-03-04][P1][WORK] Ask +Bob about the budget", "Inbox", None);
+        assert_eq!(task.person(), Some("Bob"));
+        task.set_person("alice");
+        assert_eq!(task.title, "Ask +alice about the budget");
+
+        let mut task = Task::parse_with_config("Fix C++ build, +1 the RFC", "Inbox", None);
+        assert_eq!(task.person(), None);
+        task.set_person("sam");
+        assert_eq!(task.title, "Fix C++ build, +1 the RFC +sam");
+
+        let tasks = [
+            Task::parse_with_config("+bob 1:1 prep", "Inbox", None),
+            Task::parse_with_config("Review deck with +Bob", "Inbox", None),
+            Task::parse_with_config("Lunch with +ana", "Inbox", None),
+        ];
+        let filter = TaskFilter { person: Some("+BOB".into()), ..Default::default() };
+        let mut filtered: Vec<&Task> = tasks.iter().collect();
+        filter.retain(&mut filtered);
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(known_people(&tasks), vec!["bob", "ana"]);
+    }
+
+    #[test]
+    fn test_follow_up_task() {
+        let config = create_default_config();
+        let mut done = Task::parse_with_config("[2026-03-04][P1][WORK][@phone]{15m} Call the vendor", "Work", None);
+        done.id = Some("abcdef123456".into());
+
+        let follow_up = follow_up_task(&config, &done, "Send the signed contract");
+        assert_eq!(follow_up.project.as_deref(), Some("WORK"));
+        assert_eq!(follow_up.context.as_deref(), Some("@phone"));
+        assert_eq!(follow_up.list, "Work");
+        assert_eq!(follow_up.notes.as_deref(), Some("Follow-up to: Call the vendor (abcdef12)"));
+        assert!(follow_up.priority.is_none());
+
+        let own = follow_up_task(&config, &done, "[2026-03-06][P2][LIFE] Book a massage");
+        assert_eq!(own.project.as_deref(), Some("LIFE"));
+        assert_eq!(own.date, "2026-03-06");
+    }
+
+    #[test]
+    fn test_tasks_that_fit() {
+        let config = create_default_config();
+        let tasks = [
+            Task::parse_with_config("[2026-03-09][P1][WORK]{15m} Reply to Sam", "Inbox", None),
+            Task::parse_with_config("[2026-03-04][P1][WORK]{20m} Review PR", "Inbox", None),
+            Task::parse_with_config("[2026-03-10][P0][WORK]{10m} Renew domain", "Inbox", None),
+            Task::parse_with_config("[2026-03-04][P0][WORK]{1h} Big refactor", "Inbox", None),
+            Task::parse_with_config("[2026-03-04][P0][WORK] No estimate", "Inbox", None),
+        ];
+        let titles: Vec<&str> = tasks_that_fit(&config, &tasks, 20, "2026-03-04").iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Renew domain", "Review PR", "Reply to Sam"]);
+    }
+
+    #[test]
+    fn test_view_filter() {
+        let view: View = serde_json::from_str(r#"{ "max_time": "15m", "project": "WORK", "sort": "time" }"#).unwrap();
+        assert_eq!(view.sort.as_deref(), Some("time"));
+        assert!(!view.today);
+
+        let tasks = [
+            Task::parse_with_config("[2026-03-04][P1][WORK]{15m} Reply to Sam", "Inbox", None),
+            Task::parse_with_config("[2026-03-04][P1][WORK]{2h} Big refactor", "Inbox", None),
+            Task::parse_with_config("[2026-03-04][P1][LIFE]{10m} Water plants", "Inbox", None),
+            Task::parse_with_config("[2026-03-04][P1][WORK] No estimate", "Inbox", None),
+        ];
+        let mut filtered: Vec<&Task> = tasks.iter().collect();
+        view.filter.retain(&mut filtered);
+        let titles: Vec<&str> = filtered.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Reply to Sam"]);
+    }
+}
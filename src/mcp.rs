@@ -0,0 +1,358 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::Local;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+
+use crate::{
+    book_calendar_blocks, calculate_free_blocks, BlockOutcome, create_task_in_google, fetch_calendar_events,
+    find_tasks_by_id_prefix, get_tasks_from_cache, inflate_estimates, is_schedulable, parse_date_from_text,
+    push_dirty_tasks, schedule_tasks_into_blocks, update_task_locally, App, Task,
+};
+
+// Model Context Protocol over stdio: newline-delimited JSON-RPC 2.0 messages.
+// Everything written to stdout must be a protocol message, so tools never print.
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Run the MCP server until stdin closes
+pub fn run(app: &App) -> Result<()> {
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle_message(app, &message),
+            Err(e) => Some(error_response(Value::Null, -32700, &format!("Parse error: {}", e))),
+        };
+
+        if let Some(response) = response {
+            writeln!(stdout, "{}", response)?;
+            stdout.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle one JSON-RPC message. Notifications (no id) never get a response.
+fn handle_message(app: &App, message: &Value) -> Option<Value> {
+    let id = message.get("id").cloned()?;
+    let method = message["method"].as_str().unwrap_or("");
+
+    let result = match method {
+        "initialize" => json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": { "tools": {} },
+            "serverInfo": {
+                "name": "thegarden",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+        }),
+        "ping" => json!({}),
+        "tools/list" => json!({ "tools": tool_definitions() }),
+        "tools/call" => call_tool(app, &message["params"]),
+        _ => return Some(error_response(id, -32601, &format!("Method not found: {}", method))),
+    };
+
+    Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "list_tasks",
+            "description": "List tasks from the garden. Defaults to today's open tasks.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "all": { "type": "boolean", "description": "Include all dates, not just today" },
+                    "include_done": { "type": "boolean", "description": "Include completed tasks" },
+                    "priority": { "type": "string", "description": "Priority filter, e.g. P0 or \"P0,P1\"" },
+                    "project": { "type": "string", "description": "Project filter (substring)" },
+                    "status": { "type": "string", "description": "Status filter (substring)" },
+                    "context": { "type": "string", "description": "Context filter, e.g. @work" },
+                    "tag": { "type": "string", "description": "Tag filter (without #)" },
+                    "query": { "type": "string", "description": "Title substring to search for" }
+                }
+            }
+        },
+        {
+            "name": "add_task",
+            "description": "Add a new task to Google Tasks.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "title": { "type": "string", "description": "Task title (hashtags become tags)" },
+                    "priority": { "type": "string", "description": "Priority, e.g. P0-P3" },
+                    "project": { "type": "string", "description": "Project name" },
+                    "context": { "type": "string", "description": "Context, e.g. @work" },
+                    "time": { "type": "string", "description": "Time estimate, e.g. 30m or 2h" },
+                    "date": { "type": "string", "description": "Due date (YYYY-MM-DD or natural language like \"friday\")" }
+                },
+                "required": ["title"]
+            }
+        },
+        {
+            "name": "complete_task",
+            "description": "Mark a task as done by ID (a unique prefix is enough).",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string", "description": "Task ID or unique prefix" }
+                },
+                "required": ["id"]
+            }
+        },
+        {
+            "name": "schedule_day",
+            "description": "Suggest calendar blocks for a day's P0/P1 tasks, optionally booking them.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "date": { "type": "string", "description": "Day to schedule (YYYY-MM-DD, \"today\", or natural language). Defaults to today." },
+                    "book": { "type": "boolean", "description": "Create the calendar events" }
+                }
+            }
+        }
+    ])
+}
+
+fn call_tool(app: &App, params: &Value) -> Value {
+    let name = params["name"].as_str().unwrap_or("");
+    let args = &params["arguments"];
+
+    let outcome = match name {
+        "list_tasks" => list_tasks(app, args),
+        "add_task" => add_task(app, args),
+        "complete_task" => complete_task(app, args),
+        "schedule_day" => schedule_day(app, args),
+        _ => Err(anyhow!("Unknown tool: {}", name)),
+    };
+
+    match outcome {
+        Ok(text) => json!({ "content": [{ "type": "text", "text": text }] }),
+        Err(e) => json!({ "content": [{ "type": "text", "text": format!("{:#}", e) }], "isError": true }),
+    }
+}
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args[key].as_str().filter(|s| !s.trim().is_empty())
+}
+
+/// Case-insensitive substring match on an optional field; no filter matches everything
+fn field_matches(field: Option<&str>, filter: Option<&str>) -> bool {
+    match filter {
+        Some(wanted) => field
+            .map(|f| f.to_lowercase().contains(&wanted.to_lowercase()))
+            .unwrap_or(false),
+        None => true,
+    }
+}
+
+/// Resolve a date argument: "today", YYYY-MM-DD, or natural language
+fn resolve_date(input: Option<&str>) -> Result<String> {
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    match input {
+        None => Ok(today),
+        Some(s) if s.eq_ignore_ascii_case("today") => Ok(today),
+        Some(s) if chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() => Ok(s.to_string()),
+        Some(s) => parse_date_from_text(s).with_context(|| format!("Could not understand date '{}'", s)),
+    }
+}
+
+fn list_tasks(app: &App, args: &Value) -> Result<String> {
+    let tasks = get_tasks_from_cache(app)?;
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    let all = args["all"].as_bool().unwrap_or(false);
+    let include_done = args["include_done"].as_bool().unwrap_or(false);
+
+    let priorities: Option<Vec<String>> = str_arg(args, "priority")
+        .map(|p| p.split(',').map(|s| s.trim().to_uppercase()).collect());
+    let tag = str_arg(args, "tag").map(|t| t.trim_start_matches('#').to_lowercase());
+
+    let matching: Vec<&Task> = tasks.iter()
+        .filter(|t| all || t.date == today)
+        .filter(|t| include_done || !t.is_done())
+        .filter(|t| match &priorities {
+            Some(wanted) => t.priority.as_ref()
+                .map(|p| wanted.iter().any(|w| p.to_uppercase() == *w))
+                .unwrap_or(false),
+            None => true,
+        })
+        .filter(|t| field_matches(t.project.as_deref(), str_arg(args, "project")))
+        .filter(|t| field_matches(t.status.as_deref(), str_arg(args, "status")))
+        .filter(|t| field_matches(t.context.as_deref(), str_arg(args, "context")))
+        .filter(|t| field_matches(Some(&t.title), str_arg(args, "query")))
+        .filter(|t| match &tag {
+            Some(wanted) => t.tags.iter().any(|tg| tg.to_lowercase() == *wanted),
+            None => true,
+        })
+        .collect();
+
+    Ok(serde_json::to_string_pretty(&matching)?)
+}
+
+fn add_task(app: &App, args: &Value) -> Result<String> {
+    let config = &app.config;
+    let title = str_arg(args, "title").context("Missing required argument 'title'")?;
+    let date = resolve_date(str_arg(args, "date"))?;
+
+    let task = Task {
+        id: None,
+        list_id: None,
+        title: title.to_string(),
+        date,
+        priority: str_arg(args, "priority").map(|p| p.to_uppercase()),
+        project: str_arg(args, "project").map(|p| p.to_string()),
+        status: None,
+        context: str_arg(args, "context").map(|c| {
+            if c.starts_with('@') { c.to_string() } else { format!("@{}", c) }
+        }),
+        time: str_arg(args, "time").map(|t| t.to_string()),
+        list: "My Tasks".to_string(),
+        attachment_type: None,
+        tags: Vec::new(), // Hashtags stay in the title and are re-extracted on read
+        created: None,
+        start: None,
+        notes: None,
+        completed: false,
+    };
+    // Untriaged tasks keep a plain title so they show up in `triage`
+    let formatted_title = if task.priority.is_some() || task.project.is_some() {
+        task.format(config)
+    } else {
+        task.title.clone()
+    };
+
+    let task_id = create_task_in_google(app, "@default", &formatted_title, None)?;
+    Ok(format!("Created task {}: {}", task_id, formatted_title))
+}
+
+fn complete_task(app: &App, args: &Value) -> Result<String> {
+    let id = str_arg(args, "id").context("Missing required argument 'id'")?;
+    let tasks = get_tasks_from_cache(app)?;
+    let matching = find_tasks_by_id_prefix(&tasks, id);
+
+    let task = match matching.as_slice() {
+        [task] => *task,
+        [] => anyhow::bail!("No task found with ID starting with '{}'", id),
+        _ => anyhow::bail!(
+            "Multiple tasks match '{}': {}",
+            id,
+            matching.iter().map(|t| format!("{} ({})", t.short_id(), t.title)).collect::<Vec<_>>().join(", ")
+        ),
+    };
+
+    let mut done = task.clone();
+    done.completed = true;
+    update_task_locally(app, &done)?;
+
+    let (_, failed) = push_dirty_tasks(app)?;
+    if failed > 0 {
+        return Ok(format!("Marked '{}' done locally; push to Google failed and will retry on next sync", task.title));
+    }
+
+    Ok(format!("Completed: {}", task.title))
+}
+
+fn schedule_day(app: &App, args: &Value) -> Result<String> {
+    let config = &app.config;
+    let date_str = resolve_date(str_arg(args, "date"))?;
+    let date = chrono::NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")?;
+    let book = args["book"].as_bool().unwrap_or(false);
+
+    let events_by_day = fetch_calendar_events(app, &date_str, &date_str)?;
+    let day_events = events_by_day.get(&date_str).cloned().unwrap_or_default();
+    let free_blocks = calculate_free_blocks(&config.work_hours, &date, &day_events);
+
+    let tasks: Vec<Task> = get_tasks_from_cache(app)?.into_iter()
+        .filter(|t| is_schedulable(config, t, &date_str, &date_str))
+        .collect();
+    let tasks = inflate_estimates(app, tasks)?;
+    let day_tasks: Vec<&Task> = tasks.iter().collect();
+
+    let suggestions = schedule_tasks_into_blocks(config, day_tasks, &free_blocks, &app.db.get_parent_ids()?);
+    if suggestions.is_empty() {
+        return Ok(format!("Nothing to schedule on {} ({} free blocks)", date_str, free_blocks.len()));
+    }
+
+    let outcomes = if book {
+        Some(book_calendar_blocks(app, &suggestions, false)?.0)
+    } else {
+        None
+    };
+
+    let mut lines = vec![format!("Schedule for {}:", date_str)];
+    for (idx, suggestion) in suggestions.iter().enumerate() {
+        let mut line = format!(
+            "{} [{}] {} ({})",
+            suggestion.block.format_time_range(),
+            suggestion.task.priority.as_deref().unwrap_or("--"),
+            suggestion.task.title,
+            suggestion.task.short_id(),
+        );
+        if let Some(outcomes) = &outcomes {
+            line.push_str(match outcomes[idx] {
+                BlockOutcome::Created => " - booked",
+                BlockOutcome::Moved => " - moved",
+                BlockOutcome::Unchanged => " - already booked",
+                BlockOutcome::Failed => " - failed to book",
+            });
+        }
+        lines.push(line);
+    }
+
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_app() -> App {
+        App {
+            config: crate::create_default_config(),
+            db: crate::sync::SyncManager::open(std::path::Path::new(":memory:")).unwrap(),
+            google: Box::new(crate::provider::fake::FakeProvider::new()),
+        }
+    }
+
+    #[test]
+    fn test_initialize_and_tools_list() {
+        let app = test_app();
+        let init = handle_message(&app, &json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}})).unwrap();
+        assert_eq!(init["result"]["protocolVersion"], PROTOCOL_VERSION);
+        assert_eq!(init["id"], 1);
+
+        let list = handle_message(&app, &json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"})).unwrap();
+        let names: Vec<&str> = list["result"]["tools"].as_array().unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["list_tasks", "add_task", "complete_task", "schedule_day"]);
+    }
+
+    #[test]
+    fn test_notifications_and_unknown_methods() {
+        let app = test_app();
+        assert!(handle_message(&app, &json!({"jsonrpc": "2.0", "method": "notifications/initialized"})).is_none());
+
+        let resp = handle_message(&app, &json!({"jsonrpc": "2.0", "id": "x", "method": "bogus"})).unwrap();
+        assert_eq!(resp["error"]["code"], -32601);
+
+        let resp = handle_message(&app, &json!({"jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": {"name": "nope"}})).unwrap();
+        assert_eq!(resp["result"]["isError"], true);
+    }
+}
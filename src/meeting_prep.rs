@@ -0,0 +1,173 @@
+use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
+use colored::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{create_task_in_google, event_bounds, fetch_calendar_json, output, push_dirty_tasks, update_task_locally, App, Config, Task};
+
+// Meetings that need homework get it: after a sync, upcoming calendar events whose title
+// matches a rule get a prep task dated `hours_before` the start, with a reminder at
+// that time when it's still ahead. Each event gets one prep task, remembered by event ID
+// in sync_state, so renaming, finishing or deleting the task doesn't bring it back.
+
+/// sync_state key: calendar event ID → its prep task's ID
+const PREP_MAP: &str = "meeting_prep_id_map";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MeetingPrep {
+    pub enabled: bool,
+    /// How far ahead to look for meetings
+    pub days_ahead: i64,
+    pub rules: Vec<PrepRule>,
+}
+
+impl Default for MeetingPrep {
+    fn default() -> Self {
+        MeetingPrep { enabled: false, days_ahead: 3, rules: Vec::new() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrepRule {
+    /// "Interview" matches anywhere in the title; with `*` ("1:1 with *") it must match
+    /// the whole title, and the `*` part fills {match}
+    pub pattern: String,
+    /// {event}, {match} and {when} are filled in
+    #[serde(default = "default_title")]
+    pub title: String,
+    #[serde(default = "default_hours_before")]
+    pub hours_before: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Estimate, as in a title ("30m", "1h")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+}
+
+fn default_title() -> String {
+    "Prep: {event}".into()
+}
+
+fn default_hours_before() -> i64 {
+    24
+}
+
+/// What `*` matched ("" for a plain pattern), if the rule applies to `summary`
+fn match_rule(rule: &PrepRule, summary: &str) -> Option<String> {
+    if !rule.pattern.contains('*') {
+        return summary.to_lowercase().contains(&rule.pattern.to_lowercase()).then(String::new);
+    }
+    let parts: Vec<String> = rule.pattern.split('*').map(|p| regex::escape(p.trim_end())).collect();
+    let glob = Regex::new(&format!(r"(?i)^\s*{}\s*$", parts.join(r"\s*(.+?)"))).ok()?;
+    let caps = glob.captures(summary)?;
+    Some(caps.iter().skip(1).flatten().map(|m| m.as_str().trim()).collect::<Vec<_>>().join(" "))
+}
+
+/// The prep task for a meeting at `start`: dated `hours_before` it, never before `now`
+pub fn prep_task(config: &Config, rule: &PrepRule, summary: &str, matched: &str, start: DateTime<Local>, now: DateTime<Local>) -> Task {
+    let title = rule.title
+        .replace("{event}", summary)
+        .replace("{match}", matched)
+        .replace("{when}", &start.format("%a %b %-d %H:%M").to_string());
+    let mut task = Task::parse_with_config(&title, "Inbox", Some(config));
+    let due = (start - chrono::Duration::hours(rule.hours_before)).max(now);
+    task.date = due.format("%Y-%m-%d").to_string();
+    task.priority = rule.priority.clone().or(task.priority);
+    task.project = rule.project.clone().or(task.project);
+    task.time = rule.time.clone().or(task.time);
+    task
+}
+
+/// Create prep tasks for upcoming meetings that match a rule; returns how many
+pub fn create_prep_tasks(app: &App) -> Result<usize> {
+    let config = &app.config;
+    let prep = &config.meeting_prep;
+    if !prep.enabled || prep.rules.is_empty() || crate::sync::is_offline() {
+        return Ok(0);
+    }
+
+    let now = Local::now();
+    let (from, to) = (now.format("%Y-%m-%d").to_string(), (now + chrono::Duration::days(prep.days_ahead)).format("%Y-%m-%d").to_string());
+    let events = match fetch_calendar_json(config, &from, &to) {
+        Ok(events) => events,
+        Err(e) => {
+            tracing::warn!(error = %e, "meeting prep: couldn't fetch calendar");
+            return Ok(0);
+        }
+    };
+
+    let mut prepared = app.db.get_id_map(PREP_MAP)?;
+    let mut created = 0;
+    for event in events["events"].as_array().into_iter().flatten() {
+        let (Some(event_id), Some(summary)) = (event["id"].as_str(), event["summary"].as_str()) else { continue };
+        let Some((start, _, _)) = event_bounds(event) else { continue };
+        if start <= now || prepared.contains_key(event_id) {
+            continue;
+        }
+        let Some((rule, matched)) = prep.rules.iter().find_map(|r| match_rule(r, summary).map(|m| (r, m))) else { continue };
+
+        let mut task = prep_task(config, rule, summary, &matched, start, now);
+        task.notes = Some(format!("Prep for: {} ({})", summary, start.format("%a %b %-d %H:%M")));
+        let id = create_task_in_google(app, "@default", &task.format(config), None)?;
+        task.id = Some(id.clone());
+        task.list_id = Some("@default".into());
+        update_task_locally(app, &task)?;
+
+        let remind_at = start - chrono::Duration::hours(rule.hours_before);
+        if remind_at > now {
+            app.db.add_reminder(&id, &remind_at.with_timezone(&Utc).to_rfc3339())?;
+        }
+        prepared.insert(event_id.to_string(), id);
+        app.db.set_state(PREP_MAP, &serde_json::to_string(&prepared)?)?;
+        created += 1;
+    }
+
+    if created > 0 {
+        push_dirty_tasks(app)?;
+        output::note(format!("🗓  Added {} meeting prep task(s)", created).cyan());
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn rule(pattern: &str, title: &str) -> PrepRule {
+        PrepRule {
+            pattern: pattern.into(), title: title.into(), hours_before: 24,
+            priority: Some("P1".into()), project: None, time: Some("30m".into()),
+        }
+    }
+
+    #[test]
+    fn test_match_rule() {
+        assert_eq!(match_rule(&rule("Interview", ""), "Onsite interview: Dana").as_deref(), Some(""));
+        assert_eq!(match_rule(&rule("1:1 with *", ""), "1:1 with Sam").as_deref(), Some("Sam"));
+        assert_eq!(match_rule(&rule("1:1 with *", ""), "Weekly 1:1 with Sam"), None);
+        assert_eq!(match_rule(&rule("* / Board meeting", ""), "Q3 / board meeting").as_deref(), Some("Q3"));
+        assert_eq!(match_rule(&rule("Board meeting", ""), "Standup"), None);
+    }
+
+    #[test]
+    fn test_prep_task() {
+        let config = crate::create_default_config();
+        let now = Local.with_ymd_and_hms(2026, 3, 4, 10, 0, 0).unwrap();
+        let start = Local.with_ymd_and_hms(2026, 3, 6, 14, 0, 0).unwrap();
+
+        let task = prep_task(&config, &rule("1:1 with *", "Agenda for {match} ({when})"), "1:1 with Sam", "Sam", start, now);
+        assert_eq!(task.title, "Agenda for Sam (Fri Mar 6 14:00)");
+        assert_eq!(task.date, "2026-03-05");
+        assert_eq!(task.priority.as_deref(), Some("P1"));
+        assert_eq!(task.time.as_deref(), Some("30m"));
+
+        // Too close to give a day's notice: due today
+        let soon = Local.with_ymd_and_hms(2026, 3, 4, 15, 0, 0).unwrap();
+        assert_eq!(prep_task(&config, &rule("Interview", "Prep: {event}"), "Interview", "", soon, now).date, "2026-03-04");
+    }
+}
@@ -0,0 +1,380 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+
+// Versioned schema changes for cache.db. Each step runs once, in order, inside its
+// own transaction, and is recorded in schema_migrations; a failing step aborts the
+// open instead of leaving half-migrated rows behind. Steps up to BASELINE also run
+// against caches created before this table existed, so they must be idempotent.
+// Append new steps at the end and never renumber or edit an applied one.
+
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    apply: fn(&Connection) -> rusqlite::Result<()>,
+}
+
+/// A migration and when it was applied (None while pending)
+pub struct MigrationStatus {
+    pub version: u32,
+    pub name: &'static str,
+    pub applied_at: Option<String>,
+}
+
+pub const MIGRATIONS: &[Migration] = &[
+    Migration { version: 1, name: "create_tasks", apply: create_tasks },
+    Migration { version: 2, name: "backfill_task_ids_and_descriptions", apply: backfill_task_ids_and_descriptions },
+    Migration { version: 3, name: "task_indexes", apply: task_indexes },
+    Migration { version: 4, name: "sync_state_and_merge_tables", apply: sync_state_and_merge_tables },
+    Migration { version: 5, name: "inbox", apply: inbox },
+    Migration { version: 6, name: "calendar_blocks", apply: calendar_blocks },
+    Migration { version: 7, name: "task_history", apply: task_history },
+    Migration { version: 8, name: "priority_drift", apply: priority_drift },
+    Migration { version: 9, name: "time_entries", apply: time_entries },
+    Migration { version: 10, name: "snapshots", apply: snapshots },
+    Migration { version: 11, name: "goals", apply: goals },
+    Migration { version: 12, name: "reminders", apply: reminders },
+    Migration { version: 13, name: "calendar_block_reconciled", apply: calendar_block_reconciled },
+];
+
+/// Apply every pending migration. Returns the ones that ran.
+pub fn run(conn: &Connection) -> Result<Vec<&'static Migration>> {
+    let applied = applied_versions(conn)?;
+    if let Some(newest) = applied.keys().max().filter(|v| **v > latest_version()) {
+        anyhow::bail!(
+            "Cache schema is at version {} but this thegarden only knows up to {}; upgrade thegarden",
+            newest, latest_version()
+        );
+    }
+
+    let mut ran = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| !applied.contains_key(&m.version)) {
+        let tx = conn.unchecked_transaction()?;
+        (migration.apply)(&tx)
+            .and_then(|_| tx.execute(
+                "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![migration.version, migration.name, Utc::now().to_rfc3339()],
+            ))
+            .with_context(|| format!("Migration {:03} ({}) failed", migration.version, migration.name))?;
+        tx.commit()?;
+        tracing::info!(version = migration.version, name = migration.name, "applied migration");
+        ran.push(migration);
+    }
+    Ok(ran)
+}
+
+/// Every known migration with its applied time, without changing anything
+pub fn status(conn: &Connection) -> Result<Vec<MigrationStatus>> {
+    let mut applied = applied_versions(conn)?;
+    Ok(MIGRATIONS.iter()
+        .map(|m| MigrationStatus { version: m.version, name: m.name, applied_at: applied.remove(&m.version) })
+        .collect())
+}
+
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+fn applied_versions(conn: &Connection) -> Result<HashMap<u32, String>> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    let mut stmt = conn.prepare("SELECT version, applied_at FROM schema_migrations")?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+/// Add whichever of `columns` the table doesn't have yet
+fn add_missing_columns(conn: &Connection, table: &str, columns: &[(&str, &str)]) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let existing = stmt.query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (name, decl) in columns {
+        if !existing.iter().any(|c| c == name) {
+            conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, name, decl), [])?;
+        }
+    }
+    Ok(())
+}
+
+fn create_tasks(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS tasks (
+            id TEXT PRIMARY KEY,
+            unique_id TEXT NOT NULL UNIQUE,
+            list_id TEXT NOT NULL,
+            title TEXT NOT NULL,
+            status TEXT NOT NULL,
+            updated TEXT NOT NULL,
+            created TEXT,
+            links TEXT,
+            last_synced TEXT NOT NULL,
+            dirty INTEGER DEFAULT 0,
+
+            -- Structured properties
+            priority TEXT,
+            project TEXT,
+            context TEXT,
+            duration TEXT,
+            due_date TEXT,
+            start_date TEXT,
+            scheduled_date TEXT,
+            tags TEXT,
+            user_description TEXT,
+            bump_count INTEGER DEFAULT 0,
+            parent_id TEXT,
+
+            -- Derived field (immutable, always regenerated)
+            taskgarden_description TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Caches from before the structured properties only have the first few columns
+    add_missing_columns(conn, "tasks", &[
+        ("unique_id", "TEXT"),
+        ("dirty", "INTEGER DEFAULT 0"),
+        ("created", "TEXT"),
+        ("priority", "TEXT"),
+        ("project", "TEXT"),
+        ("context", "TEXT"),
+        ("duration", "TEXT"),
+        ("due_date", "TEXT"),
+        ("start_date", "TEXT"),
+        ("scheduled_date", "TEXT"),
+        ("tags", "TEXT"),
+        ("user_description", "TEXT"),
+        ("taskgarden_description", "TEXT"),
+        ("bump_count", "INTEGER DEFAULT 0"),
+        ("parent_id", "TEXT"),
+    ])
+}
+
+fn backfill_task_ids_and_descriptions(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE tasks SET unique_id = lower(hex(randomblob(16))) WHERE unique_id IS NULL",
+        [],
+    )?;
+    conn.execute(
+        "UPDATE tasks SET taskgarden_description = title WHERE taskgarden_description IS NULL OR taskgarden_description = ''",
+        [],
+    )?;
+    Ok(())
+}
+
+fn task_indexes(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_dirty ON tasks(dirty)", [])?;
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_status ON tasks(status)", [])?;
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_list_id ON tasks(list_id)", [])?;
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_created ON tasks(created)", [])?;
+    Ok(())
+}
+
+fn sync_state_and_merge_tables(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_state (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Duplicate pairs the user said are not duplicates
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS dismissed_pairs (
+            task_id_1 TEXT NOT NULL,
+            task_id_2 TEXT NOT NULL,
+            dismissed_at TEXT NOT NULL,
+            PRIMARY KEY (task_id_1, task_id_2)
+        )",
+        [],
+    )?;
+
+    // Near-duplicate pairs found during sync, waiting for the interactive merge
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS merge_queue (
+            task_id_1 TEXT NOT NULL,
+            task_id_2 TEXT NOT NULL,
+            queued_at TEXT NOT NULL,
+            PRIMARY KEY (task_id_1, task_id_2)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Quick captures from `in`; task_id is set once pushed, row removed once triaged
+fn inbox(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS inbox (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            title TEXT NOT NULL,
+            created TEXT NOT NULL,
+            task_id TEXT
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Calendar events created by `schedule`, so re-runs move them instead of duplicating
+fn calendar_blocks(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS calendar_blocks (
+            event_id TEXT PRIMARY KEY,
+            task_id TEXT NOT NULL,
+            start TEXT NOT NULL,
+            end TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Field-level change log for `history`
+fn task_history(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS task_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id TEXT NOT NULL,
+            field TEXT NOT NULL,
+            old_value TEXT,
+            new_value TEXT,
+            source TEXT NOT NULL,
+            at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_history_task ON task_history(task_id)", [])?;
+    Ok(())
+}
+
+/// Priority escalations and overdue flags from the aging policy, one per task and reason
+fn priority_drift(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS priority_drift (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id TEXT NOT NULL,
+            title TEXT NOT NULL,
+            from_priority TEXT,
+            to_priority TEXT,
+            reason TEXT NOT NULL,
+            at TEXT NOT NULL,
+            UNIQUE (task_id, reason)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Time actually spent on tasks, from the `next` timer and `focus --watch`
+fn time_entries(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS time_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id TEXT NOT NULL,
+            seconds INTEGER NOT NULL,
+            source TEXT NOT NULL,
+            at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_time_task ON time_entries(task_id)", [])?;
+    Ok(())
+}
+
+/// Open tasks and estimated minutes per project at each sync, for `burndown`
+fn snapshots(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS snapshots (
+            at TEXT NOT NULL,
+            project TEXT NOT NULL,
+            open_tasks INTEGER NOT NULL,
+            estimated_minutes INTEGER NOT NULL,
+            PRIMARY KEY (at, project)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Quarterly goals, and which goal each task rolls up to
+fn goals(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS goals (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            title TEXT NOT NULL,
+            quarter TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS goal_links (
+            task_id TEXT PRIMARY KEY,
+            goal_id INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Times to notify about a task, set with `remind` and fired by `notify`
+fn reminders(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS reminders (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id TEXT NOT NULL,
+            remind_at TEXT NOT NULL,
+            fired INTEGER NOT NULL DEFAULT 0
+        )",
+        [],
+    )?;
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_reminders_at ON reminders(remind_at)", [])?;
+    Ok(())
+}
+
+/// Whether `reconcile` has asked about a calendar block that has passed
+fn calendar_block_reconciled(conn: &Connection) -> rusqlite::Result<()> {
+    add_missing_columns(conn, "calendar_blocks", &[("reconciled", "INTEGER NOT NULL DEFAULT 0")])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_are_sequential() {
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version as usize, i + 1, "{}", migration.name);
+        }
+    }
+
+    #[test]
+    fn test_run_upgrades_pre_migration_cache_once() {
+        let conn = Connection::open_in_memory().unwrap();
+        // A cache from before the structured properties, missing most columns
+        conn.execute_batch(
+            "CREATE TABLE tasks (id TEXT PRIMARY KEY, list_id TEXT NOT NULL, title TEXT NOT NULL,
+                status TEXT NOT NULL, updated TEXT NOT NULL, links TEXT, last_synced TEXT NOT NULL);
+             INSERT INTO tasks VALUES ('t1', 'l', 'Old task', 'needsAction', '2025-01-01', NULL, '2025-01-01');",
+        ).unwrap();
+
+        assert_eq!(run(&conn).unwrap().len(), MIGRATIONS.len());
+        let (unique_id, description): (Option<String>, String) = conn.query_row(
+            "SELECT unique_id, taskgarden_description FROM tasks WHERE id = 't1'", [], |r| Ok((r.get(0)?, r.get(1)?)),
+        ).unwrap();
+        assert!(unique_id.is_some());
+        assert_eq!(description, "Old task");
+
+        assert!(run(&conn).unwrap().is_empty());
+        assert!(status(&conn).unwrap().iter().all(|s| s.applied_at.is_some()));
+    }
+}
@@ -0,0 +1,275 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::{focus_tasks, get_tasks_from_cache, output, parse_time_to_minutes, App, Config, Task};
+
+// Garden health over MQTT for a home dashboard: after each sync the overdue count, open
+// top-priority count and total focus estimate are published, retained, under
+// `topic_prefix`, one topic each plus a JSON `state`. With Home Assistant discovery on,
+// the three also show up as sensors without any YAML. Only QoS 0 publishing is needed,
+// so this speaks just enough MQTT 3.1.1 to connect, publish and disconnect.
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+const KEEP_ALIVE_SECS: u16 = 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MqttConfig {
+    pub enabled: bool,
+    pub host: String,
+    /// 1883, or 8883 with TLS, when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    pub client_id: String,
+    /// Topics are <prefix>/overdue, <prefix>/p0_open, <prefix>/focus_minutes and <prefix>/state
+    pub topic_prefix: String,
+    pub retain: bool,
+    pub tls: TlsConfig,
+    /// Publish Home Assistant discovery configs under this prefix ("homeassistant")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discovery_prefix: Option<String>,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        MqttConfig {
+            enabled: false,
+            host: "localhost".into(),
+            port: None,
+            username: None,
+            password: None,
+            client_id: "thegarden".into(),
+            topic_prefix: "thegarden".into(),
+            retain: true,
+            tls: TlsConfig::default(),
+            discovery_prefix: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TlsConfig {
+    pub enabled: bool,
+    /// PEM file with the broker's CA, for a private CA
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ca_file: Option<PathBuf>,
+    /// Accept any certificate (self-signed brokers on the LAN)
+    pub insecure: bool,
+}
+
+/// What the dashboard tile shows
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Health {
+    pub overdue: usize,
+    pub p0_open: usize,
+    pub focus_minutes: i64,
+}
+
+pub fn health(config: &Config, tasks: &[Task], today: &str) -> Health {
+    let top = config.top_priority();
+    Health {
+        overdue: tasks.iter().filter(|t| !t.is_done() && !t.date.is_empty() && t.date.as_str() < today).count(),
+        p0_open: tasks.iter().filter(|t| !t.is_done() && t.priority.is_some() && t.priority.as_deref() == top).count(),
+        focus_minutes: focus_tasks(config, tasks, today, false, false, None).iter()
+            .filter_map(|t| t.time.as_deref())
+            .map(|time| parse_time_to_minutes(time) as i64)
+            .sum(),
+    }
+}
+
+/// MQTT's variable-length "remaining length"
+fn remaining_length(mut len: usize, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+}
+
+fn string(value: &str, out: &mut Vec<u8>) {
+    out.extend((value.len() as u16).to_be_bytes());
+    out.extend(value.as_bytes());
+}
+
+fn packet(header: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = vec![header];
+    remaining_length(body.len(), &mut out);
+    out.extend(body);
+    out
+}
+
+pub fn connect_packet(settings: &MqttConfig) -> Result<Vec<u8>> {
+    // MQTT 3.1.1 (3.1.2.9) has no password without a user name; brokers drop the connection
+    if settings.password.is_some() && settings.username.is_none() {
+        anyhow::bail!("mqtt.password is set without mqtt.username; MQTT needs both");
+    }
+    let mut body = Vec::new();
+    string("MQTT", &mut body);
+    body.push(4); // protocol level 3.1.1
+    let mut flags = 0x02; // clean session
+    if settings.username.is_some() {
+        flags |= 0x80;
+    }
+    if settings.password.is_some() {
+        flags |= 0x40;
+    }
+    body.push(flags);
+    body.extend(KEEP_ALIVE_SECS.to_be_bytes());
+    string(&settings.client_id, &mut body);
+    for value in [&settings.username, &settings.password].into_iter().flatten() {
+        string(value, &mut body);
+    }
+    Ok(packet(0x10, &body))
+}
+
+pub fn publish_packet(topic: &str, payload: &str, retain: bool) -> Vec<u8> {
+    let mut body = Vec::new();
+    string(topic, &mut body);
+    body.extend(payload.as_bytes());
+    packet(0x30 | u8::from(retain), &body)
+}
+
+trait Stream: Read + Write {}
+impl<T: Read + Write> Stream for T {}
+
+fn open(settings: &MqttConfig) -> Result<Box<dyn Stream>> {
+    let port = settings.port.unwrap_or(if settings.tls.enabled { 8883 } else { 1883 });
+    let tcp = TcpStream::connect((settings.host.as_str(), port))
+        .with_context(|| format!("Couldn't reach MQTT broker {}:{}", settings.host, port))?;
+    tcp.set_read_timeout(Some(TIMEOUT))?;
+    tcp.set_write_timeout(Some(TIMEOUT))?;
+    if !settings.tls.enabled {
+        return Ok(Box::new(tcp));
+    }
+
+    let mut builder = native_tls::TlsConnector::builder();
+    if let Some(ref path) = settings.tls.ca_file {
+        let pem = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        builder.add_root_certificate(native_tls::Certificate::from_pem(&pem)?);
+    }
+    if settings.tls.insecure {
+        builder.danger_accept_invalid_certs(true).danger_accept_invalid_hostnames(true);
+    }
+    let tls = builder.build()?.connect(&settings.host, tcp)
+        .map_err(|e| anyhow::anyhow!("TLS handshake with {} failed: {}", settings.host, e))?;
+    Ok(Box::new(tls))
+}
+
+/// Connect, publish each (topic, payload), disconnect
+fn send(settings: &MqttConfig, messages: &[(String, String)]) -> Result<()> {
+    let mut stream = open(settings)?;
+    stream.write_all(&connect_packet(settings)?)?;
+    let mut connack = [0u8; 4];
+    stream.read_exact(&mut connack).context("No CONNACK from MQTT broker")?;
+    match connack {
+        [0x20, 2, _, 0] => {}
+        [0x20, 2, _, 4] | [0x20, 2, _, 5] => anyhow::bail!("MQTT broker refused the username or password"),
+        [0x20, 2, _, code] => anyhow::bail!("MQTT broker refused the connection (code {})", code),
+        _ => anyhow::bail!("Unexpected reply from MQTT broker"),
+    }
+    for (topic, payload) in messages {
+        stream.write_all(&publish_packet(topic, payload, settings.retain))?;
+    }
+    stream.write_all(&[0xE0, 0])?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Home Assistant discovery configs for the three sensors
+fn discovery(settings: &MqttConfig, discovery_prefix: &str) -> Vec<(String, String)> {
+    let node = settings.client_id.replace(|c: char| !c.is_ascii_alphanumeric() && c != '_', "_");
+    [("overdue", "Overdue tasks", "mdi:alert-circle", None), ("p0_open", "P0 open", "mdi:fire", None), ("focus_minutes", "Focus time", "mdi:timer-sand", Some("min"))]
+        .into_iter()
+        .map(|(key, name, icon, unit)| {
+            let mut config = serde_json::json!({
+                "name": name,
+                "unique_id": format!("{}_{}", node, key),
+                "state_topic": format!("{}/{}", settings.topic_prefix, key),
+                "icon": icon,
+                "state_class": "measurement",
+                "device": { "identifiers": [node], "name": "TaskGarden" },
+            });
+            if let Some(unit) = unit {
+                config["unit_of_measurement"] = unit.into();
+            }
+            (format!("{}/sensor/{}/{}/config", discovery_prefix, node, key), config.to_string())
+        })
+        .collect()
+}
+
+/// After a sync: publish the garden's health. Broker trouble is a warning, not a failed sync.
+pub fn publish(app: &App) -> Result<()> {
+    let settings = &app.config.mqtt;
+    if !settings.enabled || crate::sync::is_offline() {
+        return Ok(());
+    }
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    let health = health(&app.config, &get_tasks_from_cache(app)?, &today);
+
+    let prefix = &settings.topic_prefix;
+    let mut messages = settings.discovery_prefix.as_deref().map(|p| discovery(settings, p)).unwrap_or_default();
+    messages.extend([
+        (format!("{}/overdue", prefix), health.overdue.to_string()),
+        (format!("{}/p0_open", prefix), health.p0_open.to_string()),
+        (format!("{}/focus_minutes", prefix), health.focus_minutes.to_string()),
+        (format!("{}/state", prefix), serde_json::to_string(&health)?),
+    ]);
+    match send(settings, &messages) {
+        Ok(()) => output::note(format!("📡 Published garden health to {}", settings.host).dimmed()),
+        Err(e) => {
+            tracing::warn!(error = %e, "mqtt: publish failed");
+            eprintln!("{}", format!("⚠ MQTT publish failed: {:#}", e).yellow());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packets() {
+        let settings = MqttConfig { username: Some("u".into()), password: Some("p".into()), client_id: "tg".into(), ..Default::default() };
+        assert_eq!(connect_packet(&settings).unwrap(), vec![
+            0x10, 20, 0, 4, b'M', b'Q', b'T', b'T', 4, 0xC2, 0, 60, 0, 2, b't', b'g', 0, 1, b'u', 0, 1, b'p',
+        ]);
+        let no_user = MqttConfig { username: None, ..settings };
+        assert!(connect_packet(&no_user).is_err());
+        assert_eq!(publish_packet("a/b", "7", true), vec![0x31, 6, 0, 3, b'a', b'/', b'b', b'7']);
+
+        let mut out = Vec::new();
+        remaining_length(321, &mut out);
+        assert_eq!(out, vec![0xC1, 0x02]);
+    }
+
+    #[test]
+    fn test_health() {
+        let config = crate::create_default_config();
+        let task = |text: &str| Task::parse_with_config(text, "Inbox", None);
+        let tasks = vec![
+            task("[2026-03-04][P0][WORK]{2h} Ship it"),
+            task("[2026-03-02][P1][WORK]{30m} Review deck"),
+            task("[2026-03-01][P3][LIFE] Taxes"),
+            task("[2026-03-09][P0][LIFE]{1h} Plan trip"),
+        ];
+        assert_eq!(health(&config, &tasks, "2026-03-04"), Health { overdue: 2, p0_open: 2, focus_minutes: 210 });
+    }
+}
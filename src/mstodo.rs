@@ -0,0 +1,462 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{NaiveDate, Utc};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::IsTerminal;
+
+use crate::logging::LoggedRequest;
+use crate::{get_tasks_from_cache, App, Config, Task};
+
+// Microsoft To Do as a mirror, like Airtable and ClickUp: `sync --mstodo` pushes open
+// tasks to To Do lists through Microsoft Graph, with the due date, an importance from the
+// priority, and the notes as the body. A task goes to its project's `todo_list`, else the
+// configured `list`, else the default "Tasks" list; missing lists are created. Signing in
+// uses the device-code flow once, at a terminal; the refresh token is kept in sync_state.
+// Completed tasks are marked completed in To Do and then forgotten.
+
+/// sync_state key: Google task ID → "<To Do list ID> <To Do task ID>"
+const ID_MAP: &str = "mstodo_id_map";
+/// sync_state key: the Graph access and refresh tokens
+pub const TOKEN_STATE: &str = "mstodo_token";
+const SCOPE: &str = "Tasks.ReadWrite offline_access";
+const GRAPH_URL: &str = "https://graph.microsoft.com/v1.0";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MsTodoConfig {
+    pub enabled: bool,
+    /// Application ID of an Azure app registration with public client flows allowed
+    pub client_id: String,
+    /// "common", "consumers", "organizations" or a tenant ID
+    pub tenant: String,
+    /// To Do list for tasks whose project names none; None is the default "Tasks" list
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list: Option<String>,
+    /// Priority name → "high", "normal" or "low", ahead of the mapping by rank
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub importance: HashMap<String, String>,
+}
+
+impl Default for MsTodoConfig {
+    fn default() -> Self {
+        MsTodoConfig {
+            enabled: false,
+            client_id: String::new(),
+            tenant: "common".into(),
+            list: None,
+            importance: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Token {
+    access_token: String,
+    refresh_token: Option<String>,
+    /// Unix seconds, a minute early
+    expires_at: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceCode {
+    device_code: String,
+    message: String,
+    interval: u64,
+    expires_in: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OAuthError {
+    error: String,
+    #[serde(default)]
+    error_description: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TodoList {
+    id: String,
+    display_name: String,
+    #[serde(default)]
+    wellknown_list_name: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct PushStats {
+    pub created: usize,
+    pub updated: usize,
+    pub completed: usize,
+    pub errors: usize,
+}
+
+/// "high" for the two highest priorities, "low" below the third, "normal" otherwise
+pub fn importance(config: &Config, settings: &MsTodoConfig, priority: Option<&str>) -> &'static str {
+    if let Some(mapped) = priority.and_then(|p| settings.importance.get(p)) {
+        return match mapped.to_lowercase().as_str() {
+            "high" => "high",
+            "low" => "low",
+            _ => "normal",
+        };
+    }
+    if config.is_critical(priority) {
+        return "high";
+    }
+    match priority {
+        Some(_) if config.priority_rank(priority) >= 3 && config.priority_rank(priority) < config.priorities.len() => "low",
+        _ => "normal",
+    }
+}
+
+/// Graph's dateTimeTimeZone for a YYYY-MM-DD due date
+fn due_date_time(date: &str) -> Option<serde_json::Value> {
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    Some(json!({ "dateTime": format!("{}T00:00:00", date.format("%Y-%m-%d")), "timeZone": "UTC" }))
+}
+
+/// The todoTask body for a task; a null due date clears one set earlier
+pub fn todo_task(config: &Config, settings: &MsTodoConfig, task: &Task) -> serde_json::Value {
+    json!({
+        "title": task.title,
+        "importance": importance(config, settings, task.priority.as_deref()),
+        "status": if task.is_done() { "completed" } else { "notStarted" },
+        "dueDateTime": due_date_time(&task.date),
+        "body": { "content": task.notes.clone().unwrap_or_default(), "contentType": "text" },
+    })
+}
+
+/// The To Do list name a task belongs in; None for the default list
+fn list_name<'a>(config: &'a Config, settings: &'a MsTodoConfig, task: &Task) -> Option<&'a str> {
+    config.project(task.project.as_deref())
+        .and_then(|p| p.todo_list.as_deref())
+        .or(settings.list.as_deref())
+}
+
+fn split_entry(entry: &str) -> Option<(&str, &str)> {
+    entry.split_once(' ')
+}
+
+struct Graph {
+    http: reqwest::blocking::Client,
+    token: String,
+    lists: Vec<TodoList>,
+}
+
+impl Graph {
+    fn check(response: reqwest::blocking::Response, what: &str) -> Result<reqwest::blocking::Response> {
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let status = response.status();
+        let body = response.text().unwrap_or_default();
+        Err(anyhow!("Microsoft To Do {} error {}: {}", what, status, body))
+    }
+
+    fn fetch_lists(&mut self) -> Result<()> {
+        let mut url = format!("{}/me/todo/lists", GRAPH_URL);
+        loop {
+            let response = self.http.get(&url).bearer_auth(&self.token).send_logged()
+                .context("Failed to fetch Microsoft To Do lists")?;
+            let page: serde_json::Value = Self::check(response, "lists")?.json()?;
+            let lists: Vec<TodoList> = serde_json::from_value(page["value"].clone()).unwrap_or_default();
+            self.lists.extend(lists);
+            match page["@odata.nextLink"].as_str() {
+                Some(next) => url = next.to_string(),
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// The ID of the named list (or the default one), creating it if needed
+    fn list_id(&mut self, name: Option<&str>) -> Result<String> {
+        let found = self.lists.iter().find(|l| match name {
+            Some(name) => l.display_name.eq_ignore_ascii_case(name),
+            None => l.wellknown_list_name.as_deref() == Some("defaultList"),
+        });
+        if let Some(list) = found {
+            return Ok(list.id.clone());
+        }
+        let name = name.context("Microsoft To Do has no default list")?;
+        let response = self.http.post(format!("{}/me/todo/lists", GRAPH_URL))
+            .bearer_auth(&self.token)
+            .json(&json!({ "displayName": name }))
+            .send_logged()
+            .context("Failed to create Microsoft To Do list")?;
+        let list: TodoList = Self::check(response, "create list")?.json()?;
+        println!("{}", format!("  + list {}", list.display_name).dimmed());
+        let id = list.id.clone();
+        self.lists.push(list);
+        Ok(id)
+    }
+
+    fn create_task(&self, list_id: &str, body: &serde_json::Value) -> Result<String> {
+        let response = self.http.post(format!("{}/me/todo/lists/{}/tasks", GRAPH_URL, list_id))
+            .bearer_auth(&self.token)
+            .json(body)
+            .send_logged()
+            .context("Failed to create task in Microsoft To Do")?;
+        let created: serde_json::Value = Self::check(response, "create")?.json()?;
+        created["id"].as_str().map(str::to_string).context("Microsoft To Do returned no task ID")
+    }
+
+    /// Ok(false) when the task is gone from To Do
+    fn update_task(&self, list_id: &str, task_id: &str, body: &serde_json::Value) -> Result<bool> {
+        let response = self.http.patch(format!("{}/me/todo/lists/{}/tasks/{}", GRAPH_URL, list_id, task_id))
+            .bearer_auth(&self.token)
+            .json(body)
+            .send_logged()
+            .context("Failed to update task in Microsoft To Do")?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        Self::check(response, "update")?;
+        Ok(true)
+    }
+
+    fn delete_task(&self, list_id: &str, task_id: &str) -> Result<()> {
+        let response = self.http.delete(format!("{}/me/todo/lists/{}/tasks/{}", GRAPH_URL, list_id, task_id))
+            .bearer_auth(&self.token)
+            .send_logged()
+            .context("Failed to delete task in Microsoft To Do")?;
+        if response.status() != reqwest::StatusCode::NOT_FOUND {
+            Self::check(response, "delete")?;
+        }
+        Ok(())
+    }
+}
+
+fn authority(settings: &MsTodoConfig) -> String {
+    format!("https://login.microsoftonline.com/{}/oauth2/v2.0", settings.tenant)
+}
+
+fn save_token(app: &App, response: TokenResponse, previous_refresh: Option<String>) -> Result<Token> {
+    let token = Token {
+        access_token: response.access_token,
+        refresh_token: response.refresh_token.or(previous_refresh),
+        expires_at: Utc::now().timestamp() + response.expires_in - 60,
+    };
+    app.db.set_state(TOKEN_STATE, &serde_json::to_string(&token)?)?;
+    Ok(token)
+}
+
+/// Sign in with a device code: show Microsoft's instructions, then poll until approved
+fn device_login(app: &App, http: &reqwest::blocking::Client, settings: &MsTodoConfig) -> Result<Token> {
+    let response = http.post(format!("{}/devicecode", authority(settings)))
+        .form(&[("client_id", settings.client_id.as_str()), ("scope", SCOPE)])
+        .send_logged()
+        .context("Failed to start Microsoft sign-in")?;
+    let code: DeviceCode = Graph::check(response, "sign-in")?.json()?;
+    println!("{}", code.message.cyan());
+
+    let mut interval = code.interval.max(1);
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(code.expires_in);
+    while std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_secs(interval));
+        let response = http.post(format!("{}/token", authority(settings)))
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ("client_id", settings.client_id.as_str()),
+                ("device_code", code.device_code.as_str()),
+            ])
+            .send_logged()
+            .context("Failed to finish Microsoft sign-in")?;
+        if response.status().is_success() {
+            println!("{}", "✓ Signed in to Microsoft To Do".green());
+            return save_token(app, response.json()?, None);
+        }
+        let error: OAuthError = response.json().context("Unexpected Microsoft sign-in response")?;
+        match error.error.as_str() {
+            "authorization_pending" => {}
+            "slow_down" => interval += 5,
+            _ => anyhow::bail!("Microsoft sign-in failed: {} {}", error.error, error.error_description),
+        }
+    }
+    anyhow::bail!("Microsoft sign-in timed out")
+}
+
+fn refresh(app: &App, http: &reqwest::blocking::Client, settings: &MsTodoConfig, refresh_token: &str) -> Result<Token> {
+    let response = http.post(format!("{}/token", authority(settings)))
+        .form(&[
+            ("grant_type", "refresh_token"),
+            ("client_id", settings.client_id.as_str()),
+            ("refresh_token", refresh_token),
+            ("scope", SCOPE),
+        ])
+        .send_logged()
+        .context("Failed to refresh Microsoft token")?;
+    save_token(app, Graph::check(response, "token refresh")?.json()?, Some(refresh_token.to_string()))
+}
+
+/// A current access token: the stored one, a refreshed one, or a new sign-in at a terminal
+fn access_token(app: &App, http: &reqwest::blocking::Client, settings: &MsTodoConfig) -> Result<String> {
+    let stored: Option<Token> = app.db.get_state(TOKEN_STATE)?.and_then(|json| serde_json::from_str(&json).ok());
+    if let Some(token) = &stored {
+        if token.expires_at > Utc::now().timestamp() {
+            return Ok(token.access_token.clone());
+        }
+        if let Some(refresh_token) = &token.refresh_token {
+            match refresh(app, http, settings, refresh_token) {
+                Ok(token) => return Ok(token.access_token),
+                Err(e) => tracing::warn!(error = %e, "mstodo: token refresh failed"),
+            }
+        }
+    }
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!("Not signed in to Microsoft To Do; run `thegarden sync --mstodo` at a terminal");
+    }
+    Ok(device_login(app, http, settings)?.access_token)
+}
+
+/// Push tasks to Microsoft To Do; open ones are created or updated, mirrored ones that
+/// were completed are marked completed and dropped from the map
+fn push(app: &App, settings: &MsTodoConfig, dry_run: bool) -> Result<PushStats> {
+    let config = &app.config;
+    let mut map = app.db.get_id_map(ID_MAP)?;
+    let tasks: Vec<Task> = get_tasks_from_cache(app)?.into_iter()
+        .filter(|t| t.id.as_ref().is_some_and(|id| !t.is_done() || map.contains_key(id)))
+        .filter(|t| config.syncs_project(t.project.as_deref(), "mstodo"))
+        .collect();
+    let mut stats = PushStats::default();
+
+    if dry_run {
+        for task in &tasks {
+            let exists = map.contains_key(task.id.as_deref().unwrap_or_default());
+            let (label, count) = match (exists, task.is_done()) {
+                (_, true) => ("✓ complete", &mut stats.completed),
+                (true, false) => ("~ update", &mut stats.updated),
+                (false, false) => ("+ create", &mut stats.created),
+            };
+            println!("  {} {} → {}", label, task.title, list_name(config, settings, task).unwrap_or("Tasks"));
+            *count += 1;
+        }
+        return Ok(stats);
+    }
+
+    let http = reqwest::blocking::Client::new();
+    let token = access_token(app, &http, settings)?;
+    let mut graph = Graph { http, token, lists: Vec::new() };
+    graph.fetch_lists()?;
+
+    for task in &tasks {
+        let google_id = task.id.clone().unwrap_or_default();
+        let body = todo_task(config, settings, task);
+        let result = (|| -> Result<()> {
+            let list_id = graph.list_id(list_name(config, settings, task))?;
+            let existing = map.get(&google_id).and_then(|entry| split_entry(entry)).map(|(l, t)| (l.to_string(), t.to_string()));
+            match existing {
+                // To Do can't move a task between lists, so a new project means a new task
+                Some((old_list, todo_id)) if old_list != list_id && !task.is_done() => {
+                    graph.delete_task(&old_list, &todo_id)?;
+                    let id = graph.create_task(&list_id, &body)?;
+                    map.insert(google_id.clone(), format!("{} {}", list_id, id));
+                    stats.updated += 1;
+                }
+                Some((old_list, todo_id)) => {
+                    let found = graph.update_task(&old_list, &todo_id, &body)?;
+                    if task.is_done() {
+                        map.remove(&google_id);
+                        stats.completed += usize::from(found);
+                    } else if found {
+                        stats.updated += 1;
+                    } else {
+                        // Deleted in To Do: mirror it again
+                        let id = graph.create_task(&list_id, &body)?;
+                        map.insert(google_id.clone(), format!("{} {}", list_id, id));
+                        stats.created += 1;
+                    }
+                }
+                None => {
+                    let id = graph.create_task(&list_id, &body)?;
+                    map.insert(google_id.clone(), format!("{} {}", list_id, id));
+                    stats.created += 1;
+                }
+            }
+            Ok(())
+        })();
+        if let Err(e) = result {
+            eprintln!("Failed to sync task {} to Microsoft To Do: {}", google_id, e);
+            stats.errors += 1;
+        }
+        app.db.set_state(ID_MAP, &serde_json::to_string(&map)?)?;
+    }
+    Ok(stats)
+}
+
+pub fn sync_to_mstodo(app: &App, dry_run: bool) -> Result<()> {
+    let settings = &app.config.microsoft_todo;
+    if !settings.enabled {
+        println!("{}", "ℹ️  Microsoft To Do not enabled. Set 'microsoft_todo.enabled' in config.json".yellow());
+        return Ok(());
+    }
+    if settings.client_id.is_empty() {
+        anyhow::bail!("microsoft_todo.client_id is empty; see the README for registering an app");
+    }
+
+    println!("{}", if dry_run { "🔎 Microsoft To Do (dry run)" } else { "📤 Pushing tasks to Microsoft To Do..." }.cyan());
+    let stats = push(app, settings, dry_run)?;
+
+    if dry_run {
+        println!("{}", format!("Would create {}, update {}, complete {}", stats.created, stats.updated, stats.completed).cyan());
+    } else if stats.created > 0 || stats.updated > 0 || stats.completed > 0 {
+        println!("{}", format!(
+            "✓ Created: {}, Updated: {}, Completed: {}, Errors: {}",
+            stats.created, stats.updated, stats.completed, stats.errors
+        ).green());
+    } else {
+        println!("{}", "✓ No changes to push".dimmed());
+    }
+    if stats.errors > 0 {
+        println!("{}", format!("⚠️  {} tasks failed to sync", stats.errors).yellow());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_importance() {
+        let config = crate::create_default_config();
+        let mut settings = MsTodoConfig::default();
+        assert_eq!(importance(&config, &settings, Some("P0")), "high");
+        assert_eq!(importance(&config, &settings, Some("P1")), "high");
+        assert_eq!(importance(&config, &settings, Some("P2")), "normal");
+        assert_eq!(importance(&config, &settings, Some("P3")), "low");
+        assert_eq!(importance(&config, &settings, None), "normal");
+        assert_eq!(importance(&config, &settings, Some("Someday")), "normal");
+
+        settings.importance.insert("P2".into(), "High".into());
+        assert_eq!(importance(&config, &settings, Some("P2")), "high");
+    }
+
+    #[test]
+    fn test_todo_task() {
+        let config = crate::create_default_config();
+        let settings = MsTodoConfig::default();
+        let mut task = Task::parse_with_config("[2026-03-04][P0][WORK] Ship it", "Inbox", Some(&config));
+        task.notes = Some("Changelog first".into());
+
+        let body = todo_task(&config, &settings, &task);
+        assert_eq!(body["title"], "Ship it");
+        assert_eq!(body["importance"], "high");
+        assert_eq!(body["status"], "notStarted");
+        assert_eq!(body["dueDateTime"], json!({ "dateTime": "2026-03-04T00:00:00", "timeZone": "UTC" }));
+        assert_eq!(body["body"]["content"], "Changelog first");
+
+        task.completed = true;
+        task.date = String::new();
+        let body = todo_task(&config, &settings, &task);
+        assert_eq!(body["status"], "completed");
+        assert!(body["dueDateTime"].is_null());
+        assert_eq!(split_entry("list-1 task-2"), Some(("list-1", "task-2")));
+    }
+}
@@ -0,0 +1,296 @@
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::logging::LoggedRequest;
+use crate::{
+    focus_tasks, format_time_from_minutes, get_tasks_from_cache, output, parse_time_to_minutes, parse_time_window,
+    starts_later, App, Config, Task,
+};
+
+// Chat notifications for an accountability partner: a Discord webhook or a Matrix room
+// gets the `digest` when it runs, a message when a task reaches the top priority, and
+// one when today's estimates no longer fit the working day. The last two come from the
+// post-sync pass; the first sync with notifiers configured only notes the current top
+// priority tasks, so setting one up doesn't announce the whole backlog.
+
+/// sync_state key: open top-priority task IDs already announced
+const TOP_KEY: &str = "notify_top_priority_ids";
+/// sync_state key: the last day an overloaded message went out
+const OVERLOADED_KEY: &str = "notify_overloaded_day";
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+/// Discord rejects longer messages
+const DISCORD_LIMIT: usize = 2000;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Target {
+    Discord {
+        webhook_url: String,
+    },
+    Matrix {
+        /// e.g. "https://matrix.org"
+        homeserver: String,
+        /// "!abc123:matrix.org"
+        room_id: String,
+        access_token: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Notifier {
+    #[serde(flatten)]
+    pub target: Target,
+    /// Events to post: digest, top_priority, overloaded (all when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Event {
+    Digest,
+    /// A task reached the top priority (P0 by default)
+    TopPriority,
+    /// Today's estimates add up to more than the working day
+    Overloaded,
+}
+
+impl Event {
+    fn name(self) -> &'static str {
+        match self {
+            Event::Digest => "digest",
+            Event::TopPriority => "top_priority",
+            Event::Overloaded => "overloaded",
+        }
+    }
+}
+
+impl Notifier {
+    fn wants(&self, event: Event) -> bool {
+        self.events.is_empty() || self.events.iter().any(|e| e.eq_ignore_ascii_case(event.name()))
+    }
+
+    fn send(&self, client: &reqwest::blocking::Client, text: &str) -> Result<()> {
+        let request = match &self.target {
+            Target::Discord { webhook_url } => {
+                let content: String = text.chars().take(DISCORD_LIMIT).collect();
+                client.post(webhook_url).json(&serde_json::json!({ "content": content }))
+            }
+            Target::Matrix { homeserver, room_id, access_token } => {
+                let txn = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default().to_string();
+                let mut url = reqwest::Url::parse(homeserver)?;
+                url.path_segments_mut()
+                    .map_err(|_| anyhow::anyhow!("Bad Matrix homeserver URL {}", homeserver))?
+                    .pop_if_empty()
+                    .extend(["_matrix", "client", "v3", "rooms", room_id, "send", "m.room.message", &txn]);
+                client.put(url)
+                    .bearer_auth(access_token)
+                    .json(&serde_json::json!({ "msgtype": "m.text", "body": text }))
+            }
+        };
+        // A Discord webhook URL is its own credential
+        let response = match &self.target {
+            Target::Discord { .. } => request.send_redacted()?,
+            Target::Matrix { .. } => request.send_logged()?,
+        };
+        if !response.status().is_success() {
+            anyhow::bail!("HTTP {}: {}", response.status(), response.text().unwrap_or_default());
+        }
+        Ok(())
+    }
+
+    fn label(&self) -> &str {
+        match &self.target {
+            Target::Discord { .. } => "Discord",
+            Target::Matrix { room_id, .. } => room_id,
+        }
+    }
+}
+
+/// Post `text` to every notifier that wants `event`; failures are reported, not returned.
+/// Returns how many went out.
+pub fn post(app: &App, event: Event, text: &str) -> Result<usize> {
+    let notifiers: Vec<&Notifier> = app.config.notifiers.iter().filter(|n| n.wants(event)).collect();
+    if notifiers.is_empty() {
+        return Ok(0);
+    }
+    let client = reqwest::blocking::Client::builder().timeout(TIMEOUT).build()?;
+    let mut sent = 0;
+    for notifier in notifiers {
+        match notifier.send(&client, text) {
+            Ok(()) => sent += 1,
+            Err(e) => eprintln!("{}", format!("⚠ Notification to {} failed: {}", notifier.label(), e).yellow()),
+        }
+    }
+    tracing::info!(event = event.name(), sent, "notifications posted");
+    Ok(sent)
+}
+
+/// Minutes of estimates on open tasks due today or earlier, and minutes of working time today
+pub fn load(config: &Config, tasks: &[Task], today: NaiveDate) -> (i64, i64) {
+    let today_str = today.format("%Y-%m-%d").to_string();
+    let planned = tasks.iter()
+        .filter(|t| !t.is_done() && !t.date.is_empty() && t.date <= today_str && !starts_later(t, &today_str))
+        .filter_map(|t| t.time.as_deref())
+        .map(|time| parse_time_to_minutes(time) as i64)
+        .sum();
+    let lunch = config.work_hours.lunch.as_deref().and_then(parse_time_window)
+        .map(|(start, end)| (end - start).num_minutes())
+        .unwrap_or_default();
+    let available = config.work_hours.window_for(&today)
+        .map(|(start, end)| ((end - start).num_minutes() - lunch).max(0))
+        .unwrap_or_default();
+    (planned, available)
+}
+
+/// "2h 30m", or "0h" where format_time_from_minutes would say "no estimate"
+fn hours(minutes: i64) -> String {
+    if minutes == 0 { "0h".to_string() } else { format_time_from_minutes(minutes as i32) }
+}
+
+fn overloaded_line(planned: i64, available: i64) -> String {
+    format!("⚠️ Overloaded: {} planned for today, {} of working time", hours(planned), hours(available))
+}
+
+/// The daily digest as plain text: focus, load, overdue and WIP warnings
+pub fn digest_text(config: &Config, tasks: &[Task], today: NaiveDate) -> String {
+    let today_str = today.format("%Y-%m-%d").to_string();
+    let focus = focus_tasks(config, tasks, &today_str, false, false, None);
+    let overdue = tasks.iter().filter(|t| !t.is_done() && !t.date.is_empty() && t.date < today_str).count();
+    let (planned, available) = load(config, tasks, today);
+
+    let mut lines = vec![format!("🌱 Daily digest, {}", today.format("%a %b %-d"))];
+    lines.push(format!("Focus: {} task(s)", focus.len()));
+    for task in focus.iter().take(5) {
+        let priority = task.priority.as_ref().map(|p| format!("[{}] ", p)).unwrap_or_default();
+        lines.push(format!("• {}{}", priority, task.title));
+    }
+    if focus.len() > 5 {
+        lines.push(format!("• …and {} more", focus.len() - 5));
+    }
+    if overdue > 0 {
+        lines.push(format!("Overdue: {}", overdue));
+    }
+    if planned > available {
+        lines.push(overloaded_line(planned, available));
+    } else {
+        lines.push(format!("Load: {} planned, {} available", hours(planned), hours(available)));
+    }
+    for warning in config.wip_limits.warnings(tasks) {
+        lines.push(format!("🚧 {}", warning));
+    }
+    lines.join("\n")
+}
+
+/// Open tasks at the top priority, by ID
+fn top_priority_ids(config: &Config, tasks: &[Task]) -> Vec<String> {
+    let top = config.top_priority();
+    tasks.iter()
+        .filter(|t| !t.is_done() && t.priority.is_some() && t.priority.as_deref() == top)
+        .filter_map(|t| t.id.clone())
+        .collect()
+}
+
+/// After a sync: announce new top-priority tasks, and an overloaded day once
+pub fn after_sync(app: &App) -> Result<()> {
+    let config = &app.config;
+    if config.notifiers.is_empty() || crate::sync::is_offline() {
+        return Ok(());
+    }
+    let tasks = get_tasks_from_cache(app)?;
+    let top = top_priority_ids(config, &tasks);
+
+    let Some(known) = app.db.get_state(TOP_KEY)? else {
+        app.db.set_state(TOP_KEY, &serde_json::to_string(&top)?)?;
+        return Ok(());
+    };
+    let known: Vec<String> = serde_json::from_str(&known).unwrap_or_default();
+    let mut sent = 0;
+    for task in tasks.iter().filter(|t| t.id.as_ref().is_some_and(|id| top.contains(id) && !known.contains(id))) {
+        let priority = task.priority.as_deref().unwrap_or_default();
+        let due = if task.date.is_empty() { String::new() } else { format!(" (due {})", task.date) };
+        sent += post(app, Event::TopPriority, &format!("🚨 New {}: {}{}", priority, task.title, due))?;
+    }
+    app.db.set_state(TOP_KEY, &serde_json::to_string(&top)?)?;
+
+    let today = Local::now().date_naive();
+    let today_str = today.format("%Y-%m-%d").to_string();
+    let (planned, available) = load(config, &tasks, today);
+    if planned > available && app.db.get_state(OVERLOADED_KEY)?.as_deref() != Some(today_str.as_str()) {
+        sent += post(app, Event::Overloaded, &overloaded_line(planned, available))?;
+        app.db.set_state(OVERLOADED_KEY, &today_str)?;
+    }
+
+    if sent > 0 {
+        output::note(format!("💬 Posted {} notification(s)", sent).dimmed());
+    }
+    Ok(())
+}
+
+pub fn cmd_digest(app: &App, dry_run: bool) -> Result<()> {
+    let tasks = get_tasks_from_cache(app)?;
+    let text = digest_text(&app.config, &tasks, Local::now().date_naive());
+    println!("{}", text);
+    if dry_run {
+        return Ok(());
+    }
+    let sent = post(app, Event::Digest, &text)?;
+    if sent > 0 {
+        println!("{}", format!("💬 Posted to {} notifier(s)", sent).dimmed());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, text: &str) -> Task {
+        let mut task = Task::parse_with_config(text, "Inbox", None);
+        task.id = Some(id.into());
+        task
+    }
+
+    #[test]
+    fn test_webhook_url_stays_out_of_errors() {
+        let url = "http://127.0.0.1:9/api/webhooks/123/s3cr3t-token";
+        assert_eq!(crate::logging::redact_url(url), "127.0.0.1/…");
+        let notifier = Notifier { target: Target::Discord { webhook_url: url.into() }, events: vec![] };
+        let client = reqwest::blocking::Client::builder().timeout(TIMEOUT).build().unwrap();
+        let error = notifier.send(&client, "hi").unwrap_err();
+        assert!(!format!("{:#}", error).contains("s3cr3t"), "{:#}", error);
+    }
+
+    #[test]
+    fn test_digest() {
+        let config = crate::create_default_config();
+        let today = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let tasks = vec![
+            task("a", "[2026-03-04][P0][WORK]{8h} Ship it"),
+            task("b", "[2026-03-02][P1][WORK]{3h} Review deck"),
+            task("c", "[2026-03-09][P2][LIFE]{1h} Taxes"),
+        ];
+        // 08:00-18:00 by default
+        assert_eq!(load(&config, &tasks, today), (660, 600));
+
+        let text = digest_text(&config, &tasks, today);
+        assert!(text.starts_with("🌱 Daily digest, Wed Mar 4\nFocus: 2 task(s)\n• [P0] Ship it\n• [P1] Review deck\n"));
+        assert!(text.contains("Overdue: 1\n⚠️ Overloaded: 11h planned for today, 10h of working time"));
+        assert_eq!(top_priority_ids(&config, &tasks), vec!["a"]);
+    }
+
+    #[test]
+    fn test_notifier_config() {
+        let notifiers: Vec<Notifier> = serde_json::from_value(serde_json::json!([
+            {"type": "discord", "webhook_url": "https://discord.com/api/webhooks/1/x", "events": ["digest"]},
+            {"type": "matrix", "homeserver": "https://matrix.org", "room_id": "!abc:matrix.org", "access_token": "t"},
+        ])).unwrap();
+        assert!(notifiers[0].wants(Event::Digest));
+        assert!(!notifiers[0].wants(Event::TopPriority));
+        assert!(notifiers[1].wants(Event::Overloaded));
+        assert_eq!(notifiers[1].label(), "!abc:matrix.org");
+    }
+}
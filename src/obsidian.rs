@@ -0,0 +1,227 @@
+use anyhow::{Context, Result};
+use chrono::{Local, NaiveDate};
+use colored::*;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{focus_tasks, get_tasks_from_cache, local_date, push_dirty_tasks, update_task_locally, App, Config, Task};
+
+// `obsidian` writes today's focus list and what got done into the daily note, as
+// checkboxes under one heading. Re-running replaces that section rather than adding
+// another. Each line ends in a hidden %%tg:<id>%% comment, so with read-back on, boxes
+// ticked in Obsidian complete their tasks the next time it runs.
+
+/// sync_state key: the daily note written last, checked for ticked boxes on the next run
+const LAST_NOTE: &str = "obsidian_last_note";
+
+static CHECKED_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^\s*[-*] \[[xX]\] .*%%tg:([\w-]+)%%").unwrap());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ObsidianConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vault: Option<PathBuf>,
+    /// Daily notes folder inside the vault ("" for the vault root)
+    pub folder: String,
+    /// chrono format of daily note names, as set in Obsidian's Daily notes plugin
+    pub date_format: String,
+    /// Vault-relative note used to create a missing daily note
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    pub heading: String,
+    /// Complete tasks whose boxes were ticked in the note
+    pub read_back: bool,
+}
+
+impl Default for ObsidianConfig {
+    fn default() -> Self {
+        ObsidianConfig {
+            vault: None,
+            folder: String::new(),
+            date_format: "%Y-%m-%d".into(),
+            template: None,
+            heading: "## 🌱 TaskGarden".into(),
+            read_back: false,
+        }
+    }
+}
+
+pub fn daily_note_path(settings: &ObsidianConfig, vault: &Path, day: NaiveDate) -> PathBuf {
+    vault.join(&settings.folder).join(format!("{}.md", day.format(&settings.date_format)))
+}
+
+fn checkbox(task: &Task) -> String {
+    let mark = if task.is_done() { "x" } else { " " };
+    let priority = task.priority.as_ref().map(|p| format!("[{}] ", p)).unwrap_or_default();
+    format!("- [{}] {}{} %%tg:{}%%", mark, priority, task.title, task.id.as_deref().unwrap_or_default())
+}
+
+/// The heading, then focus tasks and the ones finished today
+pub fn render_section(heading: &str, focus: &[&Task], done: &[&Task]) -> String {
+    let mut lines = vec![heading.to_string(), String::new(), "**Focus**".to_string()];
+    lines.extend(focus.iter().map(|t| checkbox(t)));
+    if focus.is_empty() {
+        lines.push("Nothing critical today.".to_string());
+    }
+    if !done.is_empty() {
+        lines.push(String::new());
+        lines.push("**Done**".to_string());
+        lines.extend(done.iter().map(|t| checkbox(t)));
+    }
+    lines.join("\n") + "\n"
+}
+
+/// `note` with the section under `heading` swapped for `section`, or `section` appended.
+/// The old section runs to the next heading at the same level or higher.
+pub fn replace_section(note: &str, heading: &str, section: &str) -> String {
+    let level = heading.chars().take_while(|c| *c == '#').count();
+    let lines: Vec<&str> = note.lines().collect();
+    let Some(start) = lines.iter().position(|l| l.trim_end() == heading) else {
+        let note = note.trim_end();
+        return if note.is_empty() { section.to_string() } else { format!("{}\n\n{}", note, section) };
+    };
+    let end = lines[start + 1..].iter()
+        .position(|l| {
+            let hashes = l.chars().take_while(|c| *c == '#').count();
+            hashes > 0 && hashes <= level && l[hashes..].starts_with(' ')
+        })
+        .map(|i| start + 1 + i)
+        .unwrap_or(lines.len());
+
+    let mut out: Vec<String> = lines[..start].iter().map(|l| l.to_string()).collect();
+    out.push(section.trim_end().to_string());
+    if end < lines.len() {
+        out.push(String::new());
+        out.extend(lines[end..].iter().map(|l| l.to_string()));
+    }
+    out.join("\n") + "\n"
+}
+
+/// Task IDs of ticked boxes written by `obsidian`
+pub fn checked_ids(note: &str) -> Vec<String> {
+    CHECKED_REGEX.captures_iter(note).map(|c| c[1].to_string()).collect()
+}
+
+/// A new daily note: the template with {{date}} and {{title}} filled in, or just a title
+fn new_note(settings: &ObsidianConfig, vault: &Path, day: NaiveDate) -> Result<String> {
+    let title = day.format(&settings.date_format).to_string();
+    match &settings.template {
+        Some(template) => {
+            let path = vault.join(template);
+            let path = if path.extension().is_none() { path.with_extension("md") } else { path };
+            let text = fs::read_to_string(&path).with_context(|| format!("Failed to read template {}", path.display()))?;
+            Ok(text.replace("{{date}}", &day.format("%Y-%m-%d").to_string()).replace("{{title}}", &title))
+        }
+        None => Ok(format!("# {}\n", title)),
+    }
+}
+
+/// Complete open tasks ticked in `notes`; returns how many
+fn read_back(app: &App, tasks: &[Task], notes: &[PathBuf]) -> Result<usize> {
+    let ids: Vec<String> = notes.iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|note| checked_ids(&note))
+        .collect();
+
+    let mut completed = 0;
+    for task in tasks.iter().filter(|t| !t.is_done() && t.id.as_ref().is_some_and(|id| ids.contains(id))) {
+        let mut done = task.clone();
+        done.completed = true;
+        update_task_locally(app, &done)?;
+        println!("{}", format!("✓ Ticked in Obsidian: {}", task.title).green());
+        completed += 1;
+    }
+    if completed > 0 {
+        push_dirty_tasks(app)?;
+    }
+    Ok(completed)
+}
+
+pub fn cmd_obsidian(app: &App, vault: Option<PathBuf>, read_back_flag: bool) -> Result<()> {
+    let config: &Config = &app.config;
+    let settings = &config.obsidian;
+    let vault = vault.or_else(|| settings.vault.clone())
+        .context("No vault: pass --vault or set obsidian.vault in config")?;
+    if !vault.is_dir() {
+        anyhow::bail!("Vault {} isn't a directory", vault.display());
+    }
+
+    let today = Local::now().date_naive();
+    let today_str = today.format("%Y-%m-%d").to_string();
+    let path = daily_note_path(settings, &vault, today);
+
+    if read_back_flag || settings.read_back {
+        let mut notes = vec![path.clone()];
+        notes.extend(app.db.get_state(LAST_NOTE)?.map(PathBuf::from).filter(|p| *p != path));
+        read_back(app, &get_tasks_from_cache(app)?, &notes)?;
+    }
+
+    let tasks = get_tasks_from_cache(app)?;
+    let focus = focus_tasks(config, &tasks, &today_str, false, false, None);
+    // The log is in UTC; a day early covers completions before UTC midnight
+    let yesterday = (today - chrono::Duration::days(1)).format("%Y-%m-%d").to_string();
+    let done_ids: Vec<String> = app.db.get_history_since(&yesterday)?.into_iter()
+        .filter(|c| c.field == "status" && c.new_value.as_deref() == Some("completed"))
+        .filter(|c| local_date(&c.at) == Some(today))
+        .map(|c| c.task_id)
+        .collect();
+    let done: Vec<&Task> = tasks.iter()
+        .filter(|t| t.is_done() && t.id.as_ref().is_some_and(|id| done_ids.contains(id)))
+        .collect();
+
+    let existing = match fs::read_to_string(&path) {
+        Ok(note) => note,
+        Err(_) => new_note(settings, &vault, today)?,
+    };
+    let section = render_section(&settings.heading, &focus, &done);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, replace_section(&existing, &settings.heading, &section))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    app.db.set_state(LAST_NOTE, &path.to_string_lossy())?;
+
+    println!("{}", format!("📓 {} focus, {} done → {}", focus.len(), done.len(), path.display()).green());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, text: &str, done: bool) -> Task {
+        let mut task = Task::parse_with_config(text, "Inbox", None);
+        task.id = Some(id.into());
+        task.completed = done;
+        task
+    }
+
+    #[test]
+    fn test_daily_note_section() {
+        let (a, b) = (task("abc123", "[2026-03-04][P0][WORK] Ship it", false), task("def456", "[2026-03-04][P2][LIFE] Call mom", true));
+        let section = render_section("## Garden", &[&a], &[&b]);
+        assert_eq!(section, "## Garden\n\n**Focus**\n- [ ] [P0] Ship it %%tg:abc123%%\n\n**Done**\n- [x] [P2] Call mom %%tg:def456%%\n");
+
+        let note = "# 2026-03-04\n\nMorning pages\n";
+        let first = replace_section(note, "## Garden", &section);
+        assert_eq!(first, format!("# 2026-03-04\n\nMorning pages\n\n{}", section));
+
+        // Running again replaces the section and keeps what follows it
+        let edited = format!("{}\n## Journal\nFelt good\n", first.trim_end());
+        let again = replace_section(&edited, "## Garden", "## Garden\n\n**Focus**\nNothing critical today.\n");
+        assert_eq!(again, "# 2026-03-04\n\nMorning pages\n\n## Garden\n\n**Focus**\nNothing critical today.\n\n## Journal\nFelt good\n");
+
+        let settings = ObsidianConfig { folder: "Daily".into(), ..Default::default() };
+        let day = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        assert_eq!(daily_note_path(&settings, Path::new("/vault"), day), Path::new("/vault/Daily/2026-03-04.md"));
+    }
+
+    #[test]
+    fn test_checked_ids() {
+        let note = "- [x] [P0] Ship it %%tg:abc123%%\n- [ ] Call mom %%tg:def456%%\n  - [X] Nested %%tg:ghi789%%\n- [x] Not ours\n";
+        assert_eq!(checked_ids(note), vec!["abc123", "ghi789"]);
+    }
+}
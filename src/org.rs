@@ -0,0 +1,260 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::export::{self, ExportedTask, ImportStats};
+use crate::{App, Config, Task};
+
+// Org-mode export: one TODO/DONE heading per task, its due date as DEADLINE and start
+// date as SCHEDULED, the first two priorities as [#A]/[#B] and the rest as [#C], and a
+// properties drawer with the IDs and the fields org has no place for. `import --format
+// org` reads the file back: headings with an :ID: update that task, ones without are
+// created. A priority cookie changed in org wins over the exact name in the drawer.
+
+static HEADING_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\*+\s+(?:(TODO|DONE)\s+)?(?:\[#([A-Z])\]\s+)?(.*?)(?:\s+(:[\w@#%:]+:))?\s*$").unwrap()
+});
+static PLANNING_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(DEADLINE|SCHEDULED):\s*<(\d{4}-\d{2}-\d{2})[^>]*>").unwrap());
+static PROPERTY_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*:([A-Za-z_]+):\s*(.*?)\s*$").unwrap());
+
+/// [#A] for the top priority, [#B] for the next, [#C] for the rest
+fn cookie(config: &Config, priority: &str) -> char {
+    match config.priority_rank(Some(priority)) {
+        0 => 'A',
+        1 => 'B',
+        _ => 'C',
+    }
+}
+
+fn timestamp(date: &str) -> Option<String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok().map(|d| format!("<{}>", d.format("%Y-%m-%d %a")))
+}
+
+/// "45m" or "2h" as org's H:MM effort
+fn effort(time: &str) -> Option<String> {
+    let minutes = crate::parse_time_to_minutes(time);
+    (minutes > 0).then(|| format!("{}:{:02}", minutes / 60, minutes % 60))
+}
+
+/// H:MM effort back to a title estimate ("45m", "2h")
+fn estimate(effort: &str) -> Option<String> {
+    let (hours, minutes) = effort.split_once(':')?;
+    let minutes = hours.trim().parse::<i64>().ok()? * 60 + minutes.trim().parse::<i64>().ok()?;
+    match minutes {
+        0 => None,
+        m if m % 60 == 0 => Some(format!("{}h", m / 60)),
+        m => Some(format!("{}m", m)),
+    }
+}
+
+/// Org tags are letters, digits, _ @ # %
+fn org_tag(tag: &str) -> String {
+    tag.replace(|c: char| !c.is_alphanumeric() && !"_@#%".contains(c), "_")
+}
+
+pub fn to_org(config: &Config, tasks: &[ExportedTask]) -> String {
+    let mut out = String::from("#+TITLE: thegarden\n#+TODO: TODO | DONE\n\n");
+    for task in tasks {
+        let keyword = if task.completed { "DONE" } else { "TODO" };
+        let priority = task.priority.as_deref().map(|p| format!("[#{}] ", cookie(config, p))).unwrap_or_default();
+        let tags: Vec<String> = task.tags.iter().map(|t| org_tag(t)).collect();
+        let tags = if tags.is_empty() { String::new() } else { format!(" :{}:", tags.join(":")) };
+        let name = if task.name.is_empty() { &task.title } else { &task.name };
+        out.push_str(&format!("* {} {}{}{}\n", keyword, priority, name, tags));
+
+        let planning: Vec<String> = [
+            task.due_date.as_deref().and_then(timestamp).map(|t| format!("DEADLINE: {}", t)),
+            task.start_date.as_deref().and_then(timestamp).map(|t| format!("SCHEDULED: {}", t)),
+        ].into_iter().flatten().collect();
+        if !planning.is_empty() {
+            out.push_str(&format!("  {}\n", planning.join(" ")));
+        }
+
+        out.push_str("  :PROPERTIES:\n");
+        let properties = [
+            ("ID", Some(task.id.clone())),
+            ("UNIQUE_ID", task.unique_id.clone()),
+            ("PRIORITY_NAME", task.priority.clone()),
+            ("PROJECT", task.project.clone()),
+            ("STATUS", task.status.clone()),
+            ("CONTEXT", task.context.clone()),
+            ("EFFORT", task.time.as_deref().and_then(effort)),
+        ];
+        for (name, value) in properties {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                out.push_str(&format!("  :{}: {}\n", name, value));
+            }
+        }
+        out.push_str("  :END:\n");
+
+        // Indented, so a note line starting with "*" isn't read as a heading
+        for line in task.notes.as_deref().unwrap_or_default().lines() {
+            if line.is_empty() {
+                out.push('\n');
+            } else {
+                out.push_str(&format!("  {}\n", line));
+            }
+        }
+    }
+    out
+}
+
+/// The tasks in an org file, as export records without the stored `title`
+pub fn parse_org(config: &Config, contents: &str) -> Vec<ExportedTask> {
+    let mut tasks: Vec<ExportedTask> = Vec::new();
+    let mut cookies: Vec<Option<char>> = Vec::new();
+    let mut notes: Vec<Vec<String>> = Vec::new();
+    let mut in_drawer = false;
+
+    for line in contents.lines() {
+        if let Some(caps) = HEADING_REGEX.captures(line) {
+            tasks.push(ExportedTask {
+                name: caps[3].trim().to_string(),
+                tags: caps.get(4).map(|t| t.as_str().split(':').filter(|t| !t.is_empty()).map(str::to_string).collect()).unwrap_or_default(),
+                completed: caps.get(1).is_some_and(|k| k.as_str() == "DONE"),
+                ..Default::default()
+            });
+            cookies.push(caps.get(2).and_then(|c| c.as_str().chars().next()));
+            notes.push(Vec::new());
+            in_drawer = false;
+            continue;
+        }
+        let Some(task) = tasks.last_mut() else { continue };
+
+        let trimmed = line.trim();
+        if trimmed == ":PROPERTIES:" {
+            in_drawer = true;
+        } else if in_drawer && trimmed == ":END:" {
+            in_drawer = false;
+        } else if in_drawer {
+            let Some(caps) = PROPERTY_REGEX.captures(line) else { continue };
+            let value = Some(caps[2].to_string()).filter(|v| !v.is_empty());
+            match caps[1].to_uppercase().as_str() {
+                "ID" => task.id = value.unwrap_or_default(),
+                "UNIQUE_ID" => task.unique_id = value,
+                "PRIORITY_NAME" => task.priority = value,
+                "PROJECT" => task.project = value,
+                "STATUS" => task.status = value,
+                "CONTEXT" => task.context = value,
+                "EFFORT" => task.time = value.as_deref().and_then(estimate),
+                _ => {}
+            }
+        } else if PLANNING_REGEX.is_match(trimmed) && trimmed.starts_with(['D', 'S']) {
+            for caps in PLANNING_REGEX.captures_iter(trimmed) {
+                match &caps[1] {
+                    "DEADLINE" => task.due_date = Some(caps[2].to_string()),
+                    _ => task.start_date = Some(caps[2].to_string()),
+                }
+            }
+        } else if !line.starts_with('#') {
+            notes.last_mut().unwrap().push(line.strip_prefix("  ").unwrap_or(line).to_string());
+        }
+    }
+
+    for ((task, cookie_letter), lines) in tasks.iter_mut().zip(cookies).zip(notes) {
+        // Keep the drawer's exact priority unless the cookie was changed in org
+        task.priority = match (cookie_letter, task.priority.take()) {
+            (Some(letter), Some(name)) if cookie(config, &name) == letter => Some(name),
+            (Some(letter), _) => {
+                let rank = (letter as u8).saturating_sub(b'A') as usize;
+                config.ranked_priorities().nth(rank).map(|p| p.name.clone())
+            }
+            (None, _) => None,
+        };
+        let text = lines.join("\n").trim().to_string();
+        task.notes = Some(text).filter(|t| !t.is_empty());
+    }
+    tasks
+}
+
+/// The fields a title carries
+fn title_fields(task: &Task) -> impl PartialEq + '_ {
+    let mut tags: Vec<&String> = task.tags.iter().collect();
+    tags.sort();
+    (&task.title, &task.date, &task.priority, &task.project, &task.status, &task.context, &task.time, tags)
+}
+
+/// Load an org export: each heading becomes a title in the configured format, except
+/// that a task whose fields didn't change keeps its stored title exactly
+pub fn import_org(app: &App, contents: &str) -> Result<ImportStats> {
+    let config = &app.config;
+    let mut records = parse_org(config, contents);
+    for record in &mut records {
+        let mut task = Task::parse_with_config(&record.name, "Tasks", Some(config));
+        task.title = record.name.clone();
+        if let Some(ref due) = record.due_date {
+            task.date = due.clone();
+        }
+        task.priority = record.priority.clone();
+        task.project = record.project.clone();
+        task.status = record.status.clone();
+        task.context = record.context.clone();
+        task.time = record.time.clone();
+        for tag in &record.tags {
+            // "q1-close" in the title comes back from org as "q1_close"
+            if !task.tags.iter().any(|t| org_tag(t) == *tag) {
+                task.tags.push(tag.clone());
+            }
+        }
+        record.title = task.format(config);
+
+        if let Some(row) = app.db.get_task_by_id(&record.id)? {
+            let mut stored = Task::parse_with_config(&row.title, "Tasks", Some(config));
+            if let Some(due) = row.due_date.clone() {
+                stored.date = due;
+            }
+            if title_fields(&stored) == title_fields(&task) {
+                record.title = row.title;
+            }
+        }
+    }
+    export::import_records(app, &records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_org_round_trip() {
+        let config = crate::create_default_config();
+        let task: ExportedTask = serde_json::from_value(serde_json::json!({
+            "id": "abc",
+            "unique_id": "u-1",
+            "title": "[2026-03-04][P2][WORK][blocked]{45m} Send report",
+            "name": "Send report",
+            "due_date": "2026-03-04",
+            "start_date": "2026-03-01",
+            "priority": "P2",
+            "project": "WORK",
+            "status": "blocked",
+            "time": "45m",
+            "tags": ["q1-close"],
+            "notes": "* not a heading\nsecond line",
+        })).unwrap();
+
+        let org = to_org(&config, std::slice::from_ref(&task));
+        assert!(org.contains("* TODO [#C] Send report :q1_close:\n  DEADLINE: <2026-03-04 Wed> SCHEDULED: <2026-03-01 Sun>\n"));
+        assert!(org.contains("  :ID: abc\n  :UNIQUE_ID: u-1\n  :PRIORITY_NAME: P2\n"));
+        assert!(org.contains("  :EFFORT: 0:45\n  :END:\n  * not a heading\n  second line\n"));
+
+        let parsed = parse_org(&config, &org);
+        assert_eq!(parsed.len(), 1);
+        let back = &parsed[0];
+        assert_eq!((back.id.as_str(), back.name.as_str()), ("abc", "Send report"));
+        assert_eq!(back.priority.as_deref(), Some("P2"));
+        assert_eq!(back.time.as_deref(), Some("45m"));
+        assert_eq!(back.due_date.as_deref(), Some("2026-03-04"));
+        assert_eq!(back.start_date.as_deref(), Some("2026-03-01"));
+        assert_eq!(back.notes.as_deref(), Some("* not a heading\nsecond line"));
+
+        // Changing the cookie in org changes the priority
+        let edited = parse_org(&config, &org.replace("[#C]", "[#A]").replace("TODO", "DONE"));
+        assert_eq!(edited[0].priority.as_deref(), Some("P0"));
+        assert!(edited[0].completed);
+
+        let added = parse_org(&config, "* TODO New idea\n");
+        assert_eq!((added[0].id.as_str(), added[0].priority.as_ref()), ("", None));
+    }
+}
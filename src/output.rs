@@ -0,0 +1,191 @@
+use colored::{Color, ColoredString, Colorize};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use crate::Task;
+
+// How much the CLI says. Quiet drops colors and the sync/progress chatter around a
+// command so only its result is printed; porcelain also swaps the task views for
+// one tab-separated line per task that scripts can rely on.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Normal,
+    Quiet,
+    Porcelain,
+}
+
+static MODE: AtomicU8 = AtomicU8::new(Mode::Normal as u8);
+
+/// `focus --quiet/--porcelain` exit code when an overdue P0 needs attention
+pub const EXIT_OVERDUE: i32 = 2;
+
+/// `next --quiet/--porcelain` exit code when no task fits
+pub const EXIT_NOTHING: i32 = 1;
+
+pub fn set_mode(mode: Mode) {
+    MODE.store(mode as u8, Ordering::Relaxed);
+    if mode != Mode::Normal {
+        colored::control::set_override(false);
+    }
+}
+
+fn mode() -> Mode {
+    match MODE.load(Ordering::Relaxed) {
+        m if m == Mode::Quiet as u8 => Mode::Quiet,
+        m if m == Mode::Porcelain as u8 => Mode::Porcelain,
+        _ => Mode::Normal,
+    }
+}
+
+/// `theme` in config.json. NO_COLOR in the environment also turns colors off.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Theme {
+    /// false turns every color off
+    #[serde(default = "default_color")]
+    pub color: bool,
+    /// Plain-text markers instead of emoji, for terminals and logs that can't show them
+    #[serde(default)]
+    pub ascii: bool,
+    /// Color of view headings (default cyan)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headers: Option<String>,
+    /// Color per priority, ahead of the priority's own `color`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub priorities: HashMap<String, String>,
+    /// Color per status (default cyan)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub statuses: HashMap<String, String>,
+}
+
+fn default_color() -> bool {
+    true
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme { color: true, ascii: false, headers: None, priorities: HashMap::new(), statuses: HashMap::new() }
+    }
+}
+
+impl Theme {
+    pub fn is_default(&self) -> bool {
+        self.color && !self.ascii && self.headers.is_none() && self.priorities.is_empty() && self.statuses.is_empty()
+    }
+
+    /// A configured color for `name` in `colors`, matched case-insensitively
+    pub fn color_for(colors: &HashMap<String, String>, name: &str) -> Option<Color> {
+        colors.iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .and_then(|(_, color)| color.parse().ok())
+    }
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+pub fn set_theme(theme: &Theme) {
+    if !theme.color {
+        colored::control::set_override(false);
+    }
+    let _ = THEME.set(theme.clone());
+}
+
+fn theme() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
+
+pub fn is_ascii() -> bool {
+    theme().ascii
+}
+
+/// In ascii mode, drop a leading emoji (and the spaces after it) from a line,
+/// keeping any color codes in front of it
+pub fn plain(text: &str) -> String {
+    if !is_ascii() {
+        return text.to_string();
+    }
+    strip_leading_emoji(text)
+}
+
+fn strip_leading_emoji(text: &str) -> String {
+    let mut start = 0;
+    while text[start..].starts_with("\x1b[") {
+        match text[start..].find('m') {
+            Some(end) => start += end + 1,
+            None => break,
+        }
+    }
+    match text[start..].split_once(' ') {
+        Some((first, rest)) if !first.is_ascii() && !first.chars().any(char::is_alphanumeric) => format!("{}{}", &text[..start], rest.trim_start()),
+        _ => text.to_string(),
+    }
+}
+
+/// A bold view heading in the theme's header color
+pub fn header(text: &str) -> ColoredString {
+    let color = theme().headers.as_deref().and_then(|c| c.parse().ok()).unwrap_or(Color::Cyan);
+    plain(text).color(color).bold()
+}
+
+/// Quiet or porcelain: no chatter, and exit codes carry meaning
+pub fn is_quiet() -> bool {
+    mode() != Mode::Normal
+}
+
+pub fn is_porcelain() -> bool {
+    mode() == Mode::Porcelain
+}
+
+/// Print a progress or status line, unless quiet
+pub fn note(message: impl Display) {
+    if !is_quiet() {
+        println!("{}", plain(&message.to_string()));
+    }
+}
+
+/// id, date, priority, project, status, context, time, title, separated by tabs ("-" when unset)
+pub fn porcelain_line(task: &Task) -> String {
+    let field = |value: Option<&str>| value.filter(|v| !v.is_empty()).unwrap_or("-").to_string();
+    [
+        field(task.id.as_deref()),
+        field(Some(&task.date)),
+        field(task.priority.as_deref()),
+        field(task.project.as_deref()),
+        field(task.status.as_deref()),
+        field(task.context.as_deref()),
+        field(task.time.as_deref()),
+        task.title.replace(['\t', '\n'], " "),
+    ].join("\t")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_leading_emoji() {
+        assert_eq!(strip_leading_emoji("🗓️  Schedule for today"), "Schedule for today");
+        assert_eq!(strip_leading_emoji("\x1b[36m🔄 First sync\x1b[0m"), "\x1b[36mFirst sync\x1b[0m");
+        assert_eq!(strip_leading_emoji("The Garden"), "The Garden");
+        assert_eq!(strip_leading_emoji("Café opens"), "Café opens");
+    }
+
+    #[test]
+    fn test_theme_colors() {
+        let theme: Theme = serde_json::from_str(r#"{"ascii": true, "statuses": {"Blocked": "red", "review": "nope"}}"#).unwrap();
+        assert!(theme.color && theme.ascii && !theme.is_default());
+        assert_eq!(Theme::color_for(&theme.statuses, "blocked"), Some(Color::Red));
+        assert_eq!(Theme::color_for(&theme.statuses, "review"), None);
+        assert!(Theme::default().is_default());
+    }
+
+    #[test]
+    fn test_porcelain_line_fills_missing_fields() {
+        let mut task = Task::parse_with_config("[2026-01-21][P0][WORK]{2h} Fix\tbug", "Tasks", None);
+        task.id = Some("abc123".into());
+        assert_eq!(porcelain_line(&task), "abc123\t2026-01-21\tP0\tWORK\t-\t-\t2h\tFix bug");
+    }
+}
@@ -0,0 +1,191 @@
+use anyhow::{Context, Result};
+use colored::*;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// Where thegarden keeps its files, first match wins:
+//   --data-dir / $THEGARDEN_HOME   config and cache together in that directory
+//   ~/.thegarden                   only if its config.json sets "legacy_paths": true
+//   XDG                            $XDG_CONFIG_HOME/thegarden and $XDG_DATA_HOME/thegarden
+// `--config` picks the config file on top of any of these.
+
+/// Directory given with `--data-dir`, if any
+static DATA_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Config file given with `--config`, if any
+static CONFIG_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Use `dir` for the cache and default config instead of `$THEGARDEN_HOME` or the XDG directories
+pub fn set_data_dir(dir: PathBuf) {
+    *DATA_DIR.lock().unwrap() = Some(dir);
+}
+
+/// Read and write the config at `path` instead of the default location
+pub fn set_config_path(path: PathBuf) {
+    *CONFIG_PATH.lock().unwrap() = Some(path);
+}
+
+fn home_dir() -> Result<PathBuf> {
+    dirs::home_dir().context("Could not find home directory")
+}
+
+/// The pre-XDG location for both config and cache
+fn legacy_dir() -> Result<PathBuf> {
+    Ok(home_dir()?.join(".thegarden"))
+}
+
+/// One directory for everything, from `--data-dir` or `$THEGARDEN_HOME`
+fn override_dir() -> Option<PathBuf> {
+    if let Some(dir) = DATA_DIR.lock().unwrap().clone() {
+        return Some(dir);
+    }
+    std::env::var_os("THEGARDEN_HOME").filter(|h| !h.is_empty()).map(PathBuf::from)
+}
+
+/// `$<var>/thegarden`, or `~/<fallback>/thegarden` when the variable is unset or relative (per the XDG spec)
+fn xdg_dir(value: Option<OsString>, home: &Path, fallback: &str) -> PathBuf {
+    value.map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .unwrap_or_else(|| home.join(fallback))
+        .join("thegarden")
+}
+
+fn xdg_config_dir() -> Result<PathBuf> {
+    Ok(xdg_dir(std::env::var_os("XDG_CONFIG_HOME"), &home_dir()?, ".config"))
+}
+
+fn xdg_data_dir() -> Result<PathBuf> {
+    Ok(xdg_dir(std::env::var_os("XDG_DATA_HOME"), &home_dir()?, ".local/share"))
+}
+
+/// Whether ~/.thegarden's config opts out of XDG with `"legacy_paths": true`
+fn keeps_legacy_paths(legacy: &Path) -> bool {
+    fs::read_to_string(legacy.join("config.json")).ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        .and_then(|c| c["legacy_paths"].as_bool())
+        .unwrap_or(false)
+}
+
+/// The legacy directory, when the user asked to stay there
+fn pinned_legacy_dir() -> Result<Option<PathBuf>> {
+    let legacy = legacy_dir()?;
+    Ok(keeps_legacy_paths(&legacy).then_some(legacy))
+}
+
+/// Where the cache lives
+pub fn data_dir() -> Result<PathBuf> {
+    if let Some(dir) = override_dir() {
+        return Ok(dir);
+    }
+    match pinned_legacy_dir()? {
+        Some(legacy) => Ok(legacy),
+        None => xdg_data_dir(),
+    }
+}
+
+/// Where config.json lives unless `--config` says otherwise
+fn config_dir() -> Result<PathBuf> {
+    if let Some(dir) = override_dir() {
+        return Ok(dir);
+    }
+    match pinned_legacy_dir()? {
+        Some(legacy) => Ok(legacy),
+        None => xdg_config_dir(),
+    }
+}
+
+/// `--config`, otherwise config.json in the config directory
+pub fn config_path() -> Result<PathBuf> {
+    if let Some(path) = CONFIG_PATH.lock().unwrap().clone() {
+        return Ok(path);
+    }
+    Ok(config_dir()?.join("config.json"))
+}
+
+/// Move ~/.thegarden into the XDG directories the first time we run without it pinned.
+/// Does nothing when an override is in effect or the XDG locations are already in use.
+pub fn migrate_legacy() -> Result<()> {
+    if override_dir().is_some() {
+        return Ok(());
+    }
+    let legacy = legacy_dir()?;
+    if !legacy.is_dir() || keeps_legacy_paths(&legacy) {
+        return Ok(());
+    }
+    let (config_dir, data_dir) = (xdg_config_dir()?, xdg_data_dir()?);
+    if migrate(&legacy, &config_dir, &data_dir)? {
+        eprintln!("{}", format!("Moved {} to {} (config) and {} (cache)", legacy.display(), config_dir.display(), data_dir.display()).yellow());
+        eprintln!("{}", "To stay in the old location, move both back and set \"legacy_paths\": true in config.json.\n".yellow());
+    }
+    Ok(())
+}
+
+/// Move config.json into `config_dir` and everything else into `data_dir`, then
+/// remove `legacy` if it ended up empty. Returns false if there was nothing to do.
+fn migrate(legacy: &Path, config_dir: &Path, data_dir: &Path) -> Result<bool> {
+    if config_dir.join("config.json").exists() || data_dir.join("cache.db").exists() {
+        return Ok(false);
+    }
+    let entries: Vec<fs::DirEntry> = fs::read_dir(legacy)?.collect::<std::io::Result<_>>()?;
+    if entries.is_empty() {
+        return Ok(false);
+    }
+
+    fs::create_dir_all(config_dir).with_context(|| format!("Failed to create {}", config_dir.display()))?;
+    fs::create_dir_all(data_dir).with_context(|| format!("Failed to create {}", data_dir.display()))?;
+    for entry in entries {
+        let name = entry.file_name();
+        let target = if name == "config.json" { config_dir } else { data_dir }.join(&name);
+        move_file(&entry.path(), &target)?;
+    }
+    let _ = fs::remove_dir(legacy);
+    Ok(true)
+}
+
+/// Rename, falling back to copy-and-delete when the target is on another filesystem
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to).with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))?;
+    fs::remove_file(from)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xdg_dir_ignores_relative_values() {
+        let home = Path::new("/home/me");
+        assert_eq!(xdg_dir(None, home, ".config"), PathBuf::from("/home/me/.config/thegarden"));
+        assert_eq!(xdg_dir(Some("rel/dir".into()), home, ".config"), PathBuf::from("/home/me/.config/thegarden"));
+        assert_eq!(xdg_dir(Some("/xdg/data".into()), home, ".local/share"), PathBuf::from("/xdg/data/thegarden"));
+    }
+
+    #[test]
+    fn test_migrate_splits_config_and_cache() {
+        let root = std::env::temp_dir().join(format!("thegarden-migrate-{}", uuid::Uuid::new_v4()));
+        let legacy = root.join(".thegarden");
+        let (config_dir, data_dir) = (root.join("config/thegarden"), root.join("data/thegarden"));
+        fs::create_dir_all(&legacy).unwrap();
+        fs::write(legacy.join("config.json"), "{}").unwrap();
+        fs::write(legacy.join("cache.db"), "db").unwrap();
+
+        assert!(migrate(&legacy, &config_dir, &data_dir).unwrap());
+        assert!(config_dir.join("config.json").exists());
+        assert!(data_dir.join("cache.db").exists());
+        assert!(!legacy.exists());
+
+        // Already migrated: a second run leaves things alone
+        fs::create_dir_all(&legacy).unwrap();
+        fs::write(legacy.join("cache.db"), "stale").unwrap();
+        assert!(!migrate(&legacy, &config_dir, &data_dir).unwrap());
+        assert_eq!(fs::read_to_string(data_dir.join("cache.db")).unwrap(), "db");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,116 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::sync::CachedTask;
+
+// What team-visible tools (Airtable, ClickUp) get to see. Tasks carrying an excluded tag
+// are never pushed; the rest go out with the configured fields stripped and private tags
+// taken off the title. Both pushes build their records from the title and notes, so
+// filtering the cached task first covers every field they send.
+
+static CONTEXT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[@[^\]]*\]").unwrap());
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Field {
+    Notes,
+    Links,
+    Context,
+    /// Every hashtag in the title
+    Tags,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrivacyConfig {
+    /// Fields left out of every exported task
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub strip_fields: Vec<Field>,
+    /// Hashtags taken off exported titles ("private" or "#private")
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub redact_tags: Vec<String>,
+    /// Tasks with any of these hashtags aren't exported at all
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude_tags: Vec<String>,
+}
+
+fn is_tag(word: &str, tags: &[String]) -> bool {
+    word.strip_prefix('#')
+        .is_some_and(|tag| tags.iter().any(|t| t.trim_start_matches('#').eq_ignore_ascii_case(tag)))
+}
+
+impl PrivacyConfig {
+    pub fn excludes(&self, task: &CachedTask) -> bool {
+        task.title.split_whitespace().any(|word| is_tag(word, &self.exclude_tags))
+    }
+
+    fn hides(&self, word: &str) -> bool {
+        (self.strip_fields.contains(&Field::Tags) && word.starts_with('#')) || is_tag(word, &self.redact_tags)
+    }
+
+    /// The task as a team export may show it
+    pub fn apply(&self, task: &CachedTask) -> CachedTask {
+        let mut task = task.clone();
+        let strip = |field| self.strip_fields.contains(&field);
+        if strip(Field::Notes) {
+            task.user_description = None;
+        }
+        if strip(Field::Links) {
+            task.links = None;
+        }
+        task.title = self.apply_title(&task.title);
+        task
+    }
+
+    /// A title as a team export may show it
+    pub fn apply_title(&self, title: &str) -> String {
+        let title = if self.strip_fields.contains(&Field::Context) {
+            CONTEXT_REGEX.replace(title, "")
+        } else {
+            title.into()
+        };
+        title.split_whitespace()
+            .filter(|word| !self.hides(word))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Hashtags in `title` that exports leave out, so a name edited elsewhere can get them back
+    pub fn hidden_tags<'a>(&self, title: &'a str) -> Vec<&'a str> {
+        title.split_whitespace().filter(|word| self.hides(word)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_privacy_filter() {
+        let task = |title: &str| {
+            let mut task = CachedTask::parse_from_emoji_string(title, "g1", "list");
+            task.title = title.into();
+            task.user_description = Some("Call the bank".into());
+            task
+        };
+        let privacy = PrivacyConfig {
+            strip_fields: vec![Field::Notes, Field::Context],
+            redact_tags: vec!["#private".into()],
+            exclude_tags: vec!["personal".into()],
+        };
+
+        assert!(privacy.excludes(&task("[2026-01-27][P1][LIFE] Dentist #Personal")));
+        assert!(!privacy.excludes(&task("[2026-01-27][P1][WORK] Review #personalization")));
+
+        let exported = privacy.apply(&task("[2026-01-27][P1][WORK][progress][@home]{1h} Fix bug #private #DeepWork"));
+        assert_eq!(exported.title, "[2026-01-27][P1][WORK][progress]{1h} Fix bug #DeepWork");
+        assert_eq!(exported.user_description, None);
+        assert_eq!(privacy.hidden_tags("Fix bug #private #DeepWork"), ["#private"]);
+
+        let no_tags = PrivacyConfig { strip_fields: vec![Field::Tags], ..Default::default() };
+        let exported = no_tags.apply(&task("[2026-01-27][P1][WORK] Fix bug #private #DeepWork"));
+        assert_eq!(exported.title, "[2026-01-27][P1][WORK] Fix bug");
+        assert_eq!(exported.user_description.as_deref(), Some("Call the bank"));
+    }
+}
@@ -0,0 +1,495 @@
+use anyhow::{Context, Result};
+use futures_util::future::LocalBoxFuture;
+use futures_util::{stream, StreamExt};
+use std::process::Command;
+
+use crate::logging::{LoggedAsyncCommand, LoggedCommand};
+use crate::Config;
+
+// Google Tasks sits behind `TaskProvider` so the sync path can run against
+// something other than the real account. `Gog` shells out to the gog CLI; tasks go
+// in and out as Google's JSON (id, title, status, notes, updated, ...), which is what
+// gog prints. Built with the `mock-provider` feature, setting $THEGARDEN_FAKE_GOOGLE
+// to a JSON file swaps in an in-memory Google kept in that file, for trying things
+// without an account. Tests use the same fake. `update_all` is the one async call:
+// sync pushes every queued change at once, and gog can run several of those in parallel.
+
+/// Remote task storage: the calls sync, push and the task commands make to Google
+pub trait TaskProvider {
+    /// IDs of the account's task lists
+    fn list_ids(&self) -> Result<Vec<String>>;
+    /// Tasks in a list, only those updated since `updated_min` (RFC 3339) when given
+    fn tasks(&self, list_id: &str, updated_min: Option<&str>) -> Result<Vec<serde_json::Value>>;
+    /// Create a task and return it as stored
+    fn create(&self, list_id: &str, title: &str, parent: Option<&str>) -> Result<serde_json::Value>;
+    /// Overwrite the title, and the notes when given; `completed` also closes it
+    fn update(&self, list_id: &str, task_id: &str, title: &str, notes: Option<&str>, completed: bool) -> Result<()>;
+    /// Mark a completed task as not completed
+    fn reopen(&self, list_id: &str, task_id: &str) -> Result<()>;
+    fn delete(&self, list_id: &str, task_id: &str) -> Result<()>;
+    /// `update` for many tasks, up to `limit` at a time; results in the order given
+    fn update_all<'a>(&'a self, updates: &'a [Update], _limit: usize) -> LocalBoxFuture<'a, Vec<Result<()>>> {
+        Box::pin(async move {
+            updates.iter().map(|u| self.update(&u.list_id, &u.task_id, &u.title, u.notes.as_deref(), u.completed)).collect()
+        })
+    }
+}
+
+/// One task's arguments to `update`
+pub struct Update {
+    pub list_id: String,
+    pub task_id: String,
+    pub title: String,
+    pub notes: Option<String>,
+    pub completed: bool,
+}
+
+/// The provider for this run: the gog CLI, or the fake when asked for
+pub fn from_config(config: &Config) -> Result<Box<dyn TaskProvider>> {
+    #[cfg(feature = "mock-provider")]
+    if let Some(path) = std::env::var_os("THEGARDEN_FAKE_GOOGLE").filter(|p| !p.is_empty()) {
+        return Ok(Box::new(fake::FakeProvider::open(path.into())?));
+    }
+    Ok(Box::new(Gog { account: config.google_account.clone() }))
+}
+
+/// Google Tasks through the gog CLI
+pub struct Gog {
+    pub account: String,
+}
+
+impl Gog {
+    /// Run `gog tasks ...` for this account and return stdout
+    fn run(&self, args: &[&str]) -> Result<Vec<u8>> {
+        let output = Command::new("gog")
+            .arg("tasks")
+            .args(args)
+            .args(["--account", &self.account])
+            .logged_output()
+            .context("Failed to run gog command. Make sure 'gog' CLI is installed.")?;
+        if !output.status.success() {
+            anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(output.stdout)
+    }
+
+    async fn run_async(&self, args: &[&str]) -> Result<Vec<u8>> {
+        let output = tokio::process::Command::new("gog")
+            .arg("tasks")
+            .args(args)
+            .args(["--account", &self.account])
+            .logged_output()
+            .await
+            .context("Failed to run gog command. Make sure 'gog' CLI is installed.")?;
+        if !output.status.success() {
+            anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(output.stdout)
+    }
+}
+
+fn update_args<'a>(list_id: &'a str, task_id: &'a str, title: &'a str, notes: Option<&'a str>, completed: bool) -> Vec<&'a str> {
+    let mut args = vec!["update", list_id, task_id, "--title", title];
+    if let Some(notes) = notes {
+        args.extend(["--notes", notes]);
+    }
+    if completed {
+        args.extend(["--status", "completed"]);
+    }
+    args
+}
+
+impl TaskProvider for Gog {
+    fn list_ids(&self) -> Result<Vec<String>> {
+        let lists: serde_json::Value = serde_json::from_slice(&self.run(&["lists", "list", "--json"])?)?;
+        Ok(lists["tasklists"].as_array().into_iter().flatten()
+            .filter_map(|list| list["id"].as_str().map(|id| id.to_string()))
+            .collect())
+    }
+
+    fn tasks(&self, list_id: &str, updated_min: Option<&str>) -> Result<Vec<serde_json::Value>> {
+        let mut args = vec!["list", list_id, "--json"];
+        if let Some(min_time) = updated_min {
+            args.extend(["--updated-min", min_time]);
+        }
+        let tasks: serde_json::Value = serde_json::from_slice(&self.run(&args)?)?;
+        Ok(tasks["tasks"].as_array().cloned().unwrap_or_default())
+    }
+
+    fn create(&self, list_id: &str, title: &str, parent: Option<&str>) -> Result<serde_json::Value> {
+        let mut args = vec!["add", list_id, "--title", title, "--json"];
+        if let Some(parent_id) = parent {
+            args.extend(["--parent", parent_id]);
+        }
+        let created: serde_json::Value = serde_json::from_slice(&self.run(&args)?)?;
+        Ok(if created["task"].is_object() { created["task"].clone() } else { created })
+    }
+
+    fn update(&self, list_id: &str, task_id: &str, title: &str, notes: Option<&str>, completed: bool) -> Result<()> {
+        self.run(&update_args(list_id, task_id, title, notes, completed)).map(|_| ())
+    }
+
+    fn reopen(&self, list_id: &str, task_id: &str) -> Result<()> {
+        self.run(&["update", list_id, task_id, "--status", "needsAction"]).map(|_| ())
+    }
+
+    fn delete(&self, list_id: &str, task_id: &str) -> Result<()> {
+        self.run(&["delete", list_id, task_id]).map(|_| ())
+    }
+
+    fn update_all<'a>(&'a self, updates: &'a [Update], limit: usize) -> LocalBoxFuture<'a, Vec<Result<()>>> {
+        let runs = updates.iter().map(move |u| async move {
+            self.run_async(&update_args(&u.list_id, &u.task_id, &u.title, u.notes.as_deref(), u.completed)).await.map(|_| ())
+        });
+        Box::pin(stream::iter(runs).buffered(limit.max(1)).collect())
+    }
+}
+
+#[cfg(any(test, feature = "mock-provider"))]
+pub mod fake {
+    use super::TaskProvider;
+    use anyhow::{Context, Result};
+    use chrono::{DateTime, SecondsFormat, Utc};
+    use serde::{Deserialize, Serialize};
+    use std::cell::RefCell;
+    use std::path::PathBuf;
+    use std::rc::Rc;
+
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    struct State {
+        lists: Vec<String>,
+        /// (list ID, task as Google stores it), in creation order
+        tasks: Vec<(String, serde_json::Value)>,
+        next_id: u64,
+        /// Every change made through the provider, e.g. "update task0001 completed"
+        #[serde(skip)]
+        calls: Vec<String>,
+    }
+
+    /// An in-memory Google Tasks account. Clones share the same account, so a test
+    /// can keep a handle after giving one to the App.
+    #[derive(Clone, Default)]
+    pub struct FakeProvider {
+        state: Rc<RefCell<State>>,
+        /// Where the account is saved after each change, if anywhere
+        path: Option<PathBuf>,
+    }
+
+    impl FakeProvider {
+        /// Load the account from `path`, starting a new one if it doesn't exist yet
+        pub fn open(path: PathBuf) -> Result<Self> {
+            let state = match std::fs::read_to_string(&path) {
+                Ok(contents) => serde_json::from_str(&contents)
+                    .with_context(|| format!("{} isn't a fake Google Tasks account", path.display()))?,
+                Err(_) => State { lists: vec!["list".to_string()], ..Default::default() },
+            };
+            Ok(FakeProvider { state: Rc::new(RefCell::new(state)), path: Some(path) })
+        }
+
+        /// Change the account, record the call and save
+        fn change<T>(&self, call: String, f: impl FnOnce(&mut State) -> Result<T>) -> Result<T> {
+            let mut state = self.state.borrow_mut();
+            let result = f(&mut state)?;
+            state.calls.push(call);
+            if let Some(ref path) = self.path {
+                std::fs::write(path, serde_json::to_string_pretty(&*state)?)?;
+            }
+            Ok(result)
+        }
+
+        fn edit(&self, call: String, task_id: &str, f: impl FnOnce(&mut serde_json::Value)) -> Result<()> {
+            self.change(call, |state| {
+                let (_, task) = state.tasks.iter_mut().find(|(_, t)| t["id"] == task_id)
+                    .with_context(|| format!("No task {}", task_id))?;
+                f(task);
+                task["updated"] = now().into();
+                Ok(())
+            })
+        }
+    }
+
+    #[cfg(test)]
+    impl FakeProvider {
+        /// An account with one empty list, "list"
+        pub fn new() -> Self {
+            let fake = FakeProvider::default();
+            fake.state.borrow_mut().lists.push("list".to_string());
+            fake
+        }
+
+        /// Add an empty list
+        pub fn add_list(&self, list_id: &str) {
+            self.state.borrow_mut().lists.push(list_id.to_string());
+        }
+
+        /// Add a task as if it had been created in Google's own app; returns its ID
+        pub fn seed(&self, title: &str) -> String {
+            let mut state = self.state.borrow_mut();
+            state.next_id += 1;
+            let id = task_id(state.next_id);
+            state.tasks.push(("list".to_string(), task_json(&id, title, None)));
+            id
+        }
+
+        /// A task as Google holds it now
+        pub fn task(&self, task_id: &str) -> Option<serde_json::Value> {
+            self.state.borrow().tasks.iter().find(|(_, t)| t["id"] == task_id).map(|(_, t)| t.clone())
+        }
+
+        /// Changes made through the provider so far, oldest first
+        pub fn calls(&self) -> Vec<String> {
+            self.state.borrow().calls.clone()
+        }
+    }
+
+    /// IDs as long as the 8-character short IDs the CLI prints
+    fn task_id(n: u64) -> String {
+        format!("task{:04}", n)
+    }
+
+    fn now() -> String {
+        Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+    }
+
+    fn task_json(id: &str, title: &str, parent: Option<&str>) -> serde_json::Value {
+        let now = now();
+        let mut task = serde_json::json!({
+            "id": id, "title": title, "status": "needsAction", "created": now, "updated": now,
+        });
+        if let Some(parent) = parent {
+            task["parent"] = parent.into();
+        }
+        task
+    }
+
+    impl TaskProvider for FakeProvider {
+        fn list_ids(&self) -> Result<Vec<String>> {
+            Ok(self.state.borrow().lists.clone())
+        }
+
+        fn tasks(&self, list_id: &str, updated_min: Option<&str>) -> Result<Vec<serde_json::Value>> {
+            let since = updated_min.and_then(|t| DateTime::parse_from_rfc3339(t).ok());
+            let updated = |task: &serde_json::Value| task["updated"].as_str().and_then(|t| DateTime::parse_from_rfc3339(t).ok());
+            Ok(self.state.borrow().tasks.iter()
+                .filter(|(list, _)| list == list_id)
+                .filter(|(_, task)| since.is_none() || updated(task) >= since)
+                .map(|(_, task)| task.clone())
+                .collect())
+        }
+
+        fn create(&self, list_id: &str, title: &str, parent: Option<&str>) -> Result<serde_json::Value> {
+            self.change(format!("create {}", title), |state| {
+                if !state.lists.iter().any(|l| l == list_id) && list_id != "@default" {
+                    anyhow::bail!("No list {}", list_id);
+                }
+                state.next_id += 1;
+                let task = task_json(&task_id(state.next_id), title, parent);
+                let list = if list_id == "@default" { state.lists[0].clone() } else { list_id.to_string() };
+                state.tasks.push((list, task.clone()));
+                Ok(task)
+            })
+        }
+
+        fn update(&self, _list_id: &str, task_id: &str, title: &str, notes: Option<&str>, completed: bool) -> Result<()> {
+            let call = format!("update {}{}", task_id, if completed { " completed" } else { "" });
+            self.edit(call, task_id, |task| {
+                task["title"] = title.into();
+                if let Some(notes) = notes {
+                    task["notes"] = notes.into();
+                }
+                if completed {
+                    task["status"] = "completed".into();
+                }
+            })
+        }
+
+        fn reopen(&self, _list_id: &str, task_id: &str) -> Result<()> {
+            self.edit(format!("reopen {}", task_id), task_id, |task| task["status"] = "needsAction".into())
+        }
+
+        fn delete(&self, _list_id: &str, task_id: &str) -> Result<()> {
+            self.change(format!("delete {}", task_id), |state| {
+                state.tasks.retain(|(_, t)| t["id"] != task_id);
+                Ok(())
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fake::FakeProvider;
+    use crate::sync::SyncManager;
+    use crate::*;
+
+    // The sync path end to end: pull from the fake, edit the way triage, bump and
+    // merge do, then push. A local edit leaves the task dirty until Google has it.
+
+    fn app_with(fake: &FakeProvider) -> App {
+        let dir = std::env::temp_dir().join(format!("thegarden-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        App {
+            config: create_default_config(),
+            db: SyncManager::open(&dir.join("cache.db")).unwrap(),
+            google: Box::new(fake.clone()),
+        }
+    }
+
+    fn cached(app: &App, id: &str) -> Task {
+        get_tasks_from_cache(app).unwrap().into_iter().find(|t| t.id.as_deref() == Some(id)).unwrap()
+    }
+
+    fn is_dirty(app: &App, id: &str) -> bool {
+        app.db.get_dirty_tasks().unwrap().iter().any(|t| t.id == id)
+    }
+
+    fn remote_title(fake: &FakeProvider, id: &str) -> String {
+        fake.task(id).unwrap()["title"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_triage_round_trip() {
+        let fake = FakeProvider::new();
+        let id = fake.seed("Call the bank");
+        let app = app_with(&fake);
+        assert_eq!(sync_with_google(&app, false).unwrap(), vec![id.clone()]);
+        assert!(!is_dirty(&app, &id));
+
+        let mut task = cached(&app, &id);
+        task.date = "2026-01-21".into();
+        task.priority = Some("P1".into());
+        task.project = Some("LIFE".into());
+        task.time = Some("15m".into());
+        save_triage_step(&app, &task, "Saving");
+        assert!(is_dirty(&app, &id));
+        assert_eq!(remote_title(&fake, &id), "Call the bank");
+
+        assert_eq!(push_dirty_tasks(&app).unwrap(), (1, 0));
+        assert!(!is_dirty(&app, &id));
+        assert_eq!(remote_title(&fake, &id), "[2026-01-21][P1][LIFE]{15m} Call the bank");
+
+        // Pulling our own push back changes nothing
+        sync_with_google(&app, false).unwrap();
+        assert_eq!(cached(&app, &id).priority.as_deref(), Some("P1"));
+        assert!(!is_dirty(&app, &id));
+    }
+
+    #[test]
+    fn test_bump_round_trip() {
+        let fake = FakeProvider::new();
+        let ids = [fake.seed("[2026-01-20][P2][WORK] Draft the memo"), fake.seed("[2026-01-20][P3][LIFE] Book a haircut")];
+        let app = app_with(&fake);
+        sync_with_google(&app, false).unwrap();
+
+        let mut tasks: Vec<Task> = ids.iter().map(|id| cached(&app, id)).collect();
+        bump_tasks(&app, &mut tasks, "2026-01-22").unwrap();
+        assert_eq!(app.db.get_dirty_tasks().unwrap().len(), 2);
+        assert_eq!(app.db.get_bump_counts().unwrap().get(&ids[0]), Some(&1));
+
+        assert_eq!(push_dirty_tasks(&app).unwrap(), (2, 0));
+        assert!(app.db.get_dirty_tasks().unwrap().is_empty());
+        assert_eq!(remote_title(&fake, &ids[0]), "[2026-01-22][P2][WORK] Draft the memo");
+        assert_eq!(remote_title(&fake, &ids[1]), "[2026-01-22][P3][LIFE] Book a haircut");
+    }
+
+    #[test]
+    fn test_merge_round_trip() {
+        let fake = FakeProvider::new();
+        let keep_id = fake.seed("[2026-01-21][P2][WORK] Email Dana the budget");
+        let drop_id = fake.seed("[2026-01-19][P0][WORK] email dana budget");
+        let app = app_with(&fake);
+        sync_with_google(&app, false).unwrap();
+
+        let (keep, other) = (cached(&app, &keep_id), cached(&app, &drop_id));
+        let merged = merge_task_fields(&app.config, &keep, &other, keep.title.clone());
+        update_task_locally(&app, &merged).unwrap();
+        delete_task_everywhere(&app, &other).unwrap();
+        assert!(fake.task(&drop_id).is_none());
+        assert!(app.db.get_task_by_id(&drop_id).unwrap().is_none());
+
+        push_dirty_tasks_to_google(&app).unwrap();
+        assert!(!is_dirty(&app, &keep_id));
+        assert_eq!(remote_title(&fake, &keep_id), merged.format(&app.config));
+        assert_eq!(fake.calls(), vec![format!("delete {}", drop_id), format!("update {}", keep_id)]);
+    }
+
+    #[test]
+    fn test_sync_dedupe_only_merges_exact_new_copies() {
+        let fake = FakeProvider::new();
+        let old_id = fake.seed("[2026-03-04][P0][WORK] Call mom");
+        let app = app_with(&fake);
+        assert_eq!(sync_with_google(&app, false).unwrap(), vec![old_id.clone()]);
+
+        let other_id = fake.seed("[2026-04-10][P3][LIFE] Call mom");
+        let copy_id = fake.seed("[2026-03-04][P0][WORK] Call mom");
+        // A forced sync rewrites the old task too, but only the two new ones count
+        let created = sync_with_google(&app, true).unwrap();
+        assert_eq!(created, vec![other_id.clone(), copy_id.clone()]);
+        dedupe_synced_tasks(&app, &created).unwrap();
+
+        assert!(fake.task(&copy_id).is_none());
+        assert!(fake.task(&old_id).is_some());
+        // Same name under another date, priority and project is left for `merge`
+        assert!(fake.task(&other_id).is_some());
+        let queued = app.db.get_merge_queue().unwrap();
+        assert!(queued.iter().any(|(a, b)| [a, b].contains(&&old_id) && [a, b].contains(&&other_id)));
+    }
+
+    #[test]
+    fn test_completion_and_failed_push() {
+        let fake = FakeProvider::new();
+        let id = fake.seed("[2026-01-21][P1][LIFE] Pay rent");
+        let app = app_with(&fake);
+        sync_with_google(&app, false).unwrap();
+
+        let mut task = cached(&app, &id);
+        task.completed = true;
+        update_task_locally(&app, &task).unwrap();
+        // A task Google no longer has can't be pushed, so it stays dirty for the next try
+        let mut orphan = Task::parse_with_config("[2026-01-21][P2][WORK] Gone remotely", "Tasks", Some(&app.config));
+        orphan.id = Some("missing01".into());
+        orphan.list_id = Some("list".into());
+        update_task_locally(&app, &orphan).unwrap();
+
+        assert_eq!(push_dirty_tasks(&app).unwrap(), (1, 1));
+        assert_eq!(fake.task(&id).unwrap()["status"], "completed");
+        assert_eq!(fake.calls(), vec![format!("update {} completed", id)]);
+        assert!(!is_dirty(&app, &id));
+        assert!(is_dirty(&app, "missing01"));
+
+        task.completed = false;
+        update_task_locally(&app, &task).unwrap();
+        reopen_task_in_google(&app, &task).unwrap();
+        assert_eq!(fake.task(&id).unwrap()["status"], "needsAction");
+    }
+
+    #[test]
+    fn test_update_args() {
+        assert_eq!(super::update_args("l", "t", "Ship it", None, false), vec!["update", "l", "t", "--title", "Ship it"]);
+        assert_eq!(
+            super::update_args("l", "t", "Ship it", Some("notes"), true),
+            vec!["update", "l", "t", "--title", "Ship it", "--notes", "notes", "--status", "completed"]
+        );
+    }
+
+    #[test]
+    fn test_fake_account_persists() {
+        let path = std::env::temp_dir().join(format!("thegarden-fake-{}.json", uuid::Uuid::new_v4()));
+        let created = FakeProvider::open(path.clone()).unwrap().create("@default", "Water the plants", None).unwrap();
+        let reopened = FakeProvider::open(path.clone()).unwrap();
+        let tasks = reopened.tasks("list", None).unwrap();
+        assert_eq!(tasks, vec![created]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_create_caches_the_new_task() {
+        let fake = FakeProvider::new();
+        let app = app_with(&fake);
+        let id = create_task_in_google(&app, "list", "[2026-01-21][P2][WORK] Write tests", None).unwrap();
+        assert_eq!(remote_title(&fake, &id), "[2026-01-21][P2][WORK] Write tests");
+        assert!(app.db.get_task_by_id(&id).unwrap().is_some());
+        assert!(!is_dirty(&app, &id));
+        assert!(app.db.get_dirty_tasks().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use colored::*;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::io::{IsTerminal, Write};
+
+use crate::{
+    complete_task, format_time_from_minutes, get_tasks_from_cache, output, parse_date_from_text,
+    parse_time_to_minutes, push_dirty_tasks, record_bumps, update_task_locally, App, CalendarBlock, Task,
+};
+
+// Calendar blocks booked by `schedule` come back once they've ended: `reconcile` (and an
+// explicit `sync` at a terminal) asks how each went. Done completes the task, partial
+// shrinks its estimate to what's left, reschedule moves its date. Time spent is logged
+// against the task with source "calendar", like a timer session. Blocks for tasks that
+// are already done, or gone, are settled without asking.
+
+static SPENT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:(\d+)\s*h)?\s*(?:(\d+)\s*m?)?$").unwrap());
+
+#[derive(Debug, PartialEq)]
+enum Answer {
+    Done,
+    Partial,
+    Reschedule,
+    Skip,
+    Later,
+    Quit,
+}
+
+fn parse_answer(input: &str) -> Answer {
+    match input.trim().to_lowercase().as_str() {
+        "d" | "done" => Answer::Done,
+        "p" | "partial" => Answer::Partial,
+        "r" | "reschedule" => Answer::Reschedule,
+        "s" | "skip" => Answer::Skip,
+        "q" | "quit" => Answer::Quit,
+        _ => Answer::Later,
+    }
+}
+
+/// "45", "45m", "2h" or "1h30m" as minutes; empty means `default`
+fn parse_minutes(input: &str, default: i64) -> Option<i64> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return Some(default);
+    }
+    let caps = SPENT_REGEX.captures(&input)?;
+    let (hours, minutes) = (caps.get(1), caps.get(2));
+    if hours.is_none() && minutes.is_none() {
+        return None;
+    }
+    let number = |m: Option<regex::Match>| m.map_or(Ok(0), |m| m.as_str().parse::<i64>()).ok();
+    Some(number(hours)? * 60 + number(minutes)?)
+}
+
+/// Minutes as a title estimate ("2h" or "90m"), which is all the title parser reads back
+fn estimate_label(minutes: i64) -> String {
+    if minutes % 60 == 0 { format!("{}h", minutes / 60) } else { format!("{}m", minutes) }
+}
+
+fn parse_block_time(raw: &str) -> Option<DateTime<Local>> {
+    DateTime::parse_from_rfc3339(raw).ok().map(|dt| dt.with_timezone(&Local))
+}
+
+fn block_minutes(block: &CalendarBlock) -> i64 {
+    match (parse_block_time(&block.start), parse_block_time(&block.end)) {
+        (Some(start), Some(end)) => (end - start).num_minutes().max(0),
+        _ => 0,
+    }
+}
+
+/// "Tue Mar 3 09:00–10:00"
+fn block_label(block: &CalendarBlock) -> String {
+    match (parse_block_time(&block.start), parse_block_time(&block.end)) {
+        (Some(start), Some(end)) => format!("{}–{}", start.format("%a %b %-d %H:%M"), end.format("%H:%M")),
+        _ => block.start.clone(),
+    }
+}
+
+/// Blocks that have ended and still need an answer, with their tasks; blocks for done
+/// or deleted tasks are marked reconciled on the way
+fn pending(app: &App) -> Result<Vec<(CalendarBlock, Task)>> {
+    let blocks = app.db.get_unreconciled_blocks(&Utc::now().to_rfc3339())?;
+    if blocks.is_empty() {
+        return Ok(Vec::new());
+    }
+    let tasks = get_tasks_from_cache(app)?;
+    let mut open = Vec::new();
+    for block in blocks {
+        match tasks.iter().find(|t| t.id.as_deref() == Some(block.task_id.as_str())) {
+            Some(task) if !task.is_done() => open.push((block, task.clone())),
+            _ => app.db.mark_block_reconciled(&block.event_id)?,
+        }
+    }
+    Ok(open)
+}
+
+fn ask(prompt: &str) -> Result<String> {
+    print!("{} ", prompt);
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_string())
+}
+
+/// Ask for minutes until the answer parses
+fn ask_minutes(prompt: &str, default: i64) -> Result<i64> {
+    loop {
+        if let Some(minutes) = parse_minutes(&ask(prompt)?, default) {
+            return Ok(minutes);
+        }
+        println!("{}", "  Try 45, 45m, 2h or 1h30m".dimmed());
+    }
+}
+
+/// Log `minutes` against the task and say how that compares to its estimate
+fn log_spent(app: &App, task: &Task, minutes: i64) -> Result<()> {
+    let Some(task_id) = task.id.as_deref() else { return Ok(()) };
+    if minutes > 0 {
+        app.db.log_time(task_id, minutes * 60, "calendar")?;
+    }
+    let total = app.db.get_tracked_seconds()?.get(task_id).copied().unwrap_or_default() / 60;
+    let estimate = task.time.as_deref().map(parse_time_to_minutes).unwrap_or_default();
+    let so_far = format_time_from_minutes(total as i32);
+    let line = match estimate {
+        0 => format!("  Logged {} ({} tracked)", format_time_from_minutes(minutes as i32), so_far),
+        est => format!("  Logged {} ({} of {} estimate)", format_time_from_minutes(minutes as i32), so_far, format_time_from_minutes(est)),
+    };
+    let line = if total > estimate as i64 && estimate > 0 { line.yellow() } else { line.dimmed() };
+    println!("{}", line);
+    Ok(())
+}
+
+/// Ask about each block; returns how many were settled
+fn prompt_all(app: &App, pending: Vec<(CalendarBlock, Task)>) -> Result<usize> {
+    let total = pending.len();
+    println!("{}", output::header(&format!("📅 {} calendar block(s) have passed\n", total)));
+    let mut settled = 0;
+    for (block, task) in pending {
+        let planned = block_minutes(&block);
+        let estimate = task.time.as_ref().map(|t| format!(" {{{}}}", t)).unwrap_or_default();
+        println!("{} {}{}", block_label(&block).cyan(), task.title, estimate.dimmed());
+        let answer = parse_answer(&ask(&format!("  {}", "d=done  p=partial  r=reschedule  s=skip  Enter=later  q=quit:".bold()))?);
+
+        match answer {
+            Answer::Done => {
+                let spent = ask_minutes(&format!("  Time spent? (Enter = {}):", format_time_from_minutes(planned as i32)), planned)?;
+                log_spent(app, &task, spent)?;
+                complete_task(app, &task)?;
+            }
+            Answer::Partial => {
+                let spent = ask_minutes(&format!("  Time spent? (Enter = {}):", format_time_from_minutes(planned as i32)), planned)?;
+                log_spent(app, &task, spent)?;
+                let estimate = task.time.as_deref().map(parse_time_to_minutes).unwrap_or_default() as i64;
+                let left = (estimate - spent).max(0);
+                let prompt = if left > 0 {
+                    format!("  Time left? (Enter = {}):", format_time_from_minutes(left as i32))
+                } else {
+                    "  Time left? (Enter to keep the estimate):".to_string()
+                };
+                let left = ask_minutes(&prompt, left)?;
+                if left > 0 {
+                    let mut updated = task.clone();
+                    updated.time = Some(estimate_label(left));
+                    update_task_locally(app, &updated)?;
+                    push_dirty_tasks(app)?;
+                    println!("{}", format!("  ◐ {} left: {}", estimate_label(left), task.title).green());
+                }
+            }
+            Answer::Reschedule => {
+                let input = ask("  New date? (e.g. tomorrow, fri, 2026-03-10):")?;
+                let date = NaiveDate::parse_from_str(&input, "%Y-%m-%d").ok()
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .or_else(|| parse_date_from_text(&input));
+                let Some(date) = date else {
+                    println!("{}", format!("  Couldn't read a date from '{}'; asking again next time", input).yellow());
+                    continue;
+                };
+                let mut moved = task.clone();
+                moved.date = date.clone();
+                record_bumps(&app.db, std::slice::from_ref(&moved))?;
+                update_task_locally(app, &moved)?;
+                push_dirty_tasks(app)?;
+                println!("{}", format!("  → {}: {}", date, task.title).green());
+            }
+            Answer::Skip => {}
+            Answer::Later => continue,
+            Answer::Quit => break,
+        }
+        app.db.mark_block_reconciled(&block.event_id)?;
+        settled += 1;
+    }
+    Ok(settled)
+}
+
+pub fn cmd_reconcile(app: &App) -> Result<()> {
+    let pending = pending(app)?;
+    if pending.is_empty() {
+        println!("{}", "No passed calendar blocks to reconcile.".dimmed());
+        return Ok(());
+    }
+    if !std::io::stdin().is_terminal() {
+        for (block, task) in &pending {
+            println!("{} {} {}", task.short_id().dimmed(), block_label(block), task.title);
+        }
+        return Ok(());
+    }
+    prompt_all(app, pending)?;
+    Ok(())
+}
+
+/// After an explicit sync at a terminal, ask about blocks that have passed since the last one
+pub fn after_sync(app: &App) -> Result<()> {
+    if !std::io::stdin().is_terminal() || output::is_quiet() {
+        return Ok(());
+    }
+    let pending = pending(app)?;
+    if !pending.is_empty() {
+        prompt_all(app, pending)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_minutes() {
+        assert_eq!(parse_minutes("", 60), Some(60));
+        assert_eq!(parse_minutes("45", 60), Some(45));
+        assert_eq!(parse_minutes("45m", 60), Some(45));
+        assert_eq!(parse_minutes("2H", 60), Some(120));
+        assert_eq!(parse_minutes("1h30m", 60), Some(90));
+        assert_eq!(parse_minutes("1h 30", 60), Some(90));
+        assert_eq!(parse_minutes("soon", 60), None);
+        assert_eq!(estimate_label(120), "2h");
+        assert_eq!(estimate_label(90), "90m");
+        assert_eq!(parse_answer("D"), Answer::Done);
+        assert_eq!(parse_answer(""), Answer::Later);
+    }
+
+    #[test]
+    fn test_unreconciled_blocks() {
+        let db = crate::sync::SyncManager::open(std::path::Path::new(":memory:")).unwrap();
+        let block = |id: &str, start: &str, end: &str| CalendarBlock {
+            event_id: id.into(), task_id: "t".into(), start: start.into(), end: end.into(),
+        };
+        db.save_calendar_block(&block("a", "2026-03-04T09:00:00+00:00", "2026-03-04T10:00:00+00:00")).unwrap();
+        db.save_calendar_block(&block("b", "2026-03-04T13:00:00+00:00", "2026-03-04T14:00:00+00:00")).unwrap();
+        assert_eq!(block_minutes(&block("a", "2026-03-04T09:00:00+00:00", "2026-03-04T10:30:00+00:00")), 90);
+
+        let ids = |db: &crate::sync::SyncManager| db.get_unreconciled_blocks("2026-03-04T12:00:00+00:00").unwrap()
+            .into_iter().map(|b| b.event_id).collect::<Vec<_>>();
+        assert_eq!(ids(&db), vec!["a"]);
+        db.mark_block_reconciled("a").unwrap();
+        assert!(ids(&db).is_empty());
+
+        // Moving a reconciled block makes it pending again once it passes
+        db.save_calendar_block(&block("a", "2026-03-04T10:00:00+00:00", "2026-03-04T11:00:00+00:00")).unwrap();
+        assert_eq!(ids(&db), vec!["a"]);
+    }
+}
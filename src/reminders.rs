@@ -0,0 +1,206 @@
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use colored::*;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::process::Command;
+
+use crate::{find_date_in_text, find_tasks_by_id_prefix, get_tasks_from_cache, App, Task};
+
+// Reminders are cache-only timestamps on a task. `notify` fires the ones that are
+// due as desktop notifications (notify-send, or osascript on macOS) and marks them
+// so they fire once; `notify --watch` keeps checking.
+
+/// How often `notify --watch` checks for due reminders
+const WATCH_INTERVAL_SECS: u64 = 30;
+
+/// Time of day when a reminder names only a date
+const DEFAULT_HOUR: u32 = 9;
+
+static IN_N_MINUTES_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bin (\d+|an?|one) ?(m|mins?|minutes?|h|hrs?|hours?)\b").unwrap()
+});
+static CLOCK_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(\d{1,2})(?::(\d{2}))?\s*(am|pm)\b|\b(\d{1,2}):(\d{2})\b|\b(noon)\b").unwrap()
+});
+static ISO_DATE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(\d{4}-\d{2}-\d{2})\b").unwrap());
+
+/// When "tomorrow 9am", "friday 14:30", "2026-03-05 8pm", "in 20 minutes" or "5pm"
+/// falls, relative to `now`. A bare time that has passed today means tomorrow.
+pub fn parse_when(text: &str, now: DateTime<Local>) -> Option<DateTime<Local>> {
+    if let Some(caps) = IN_N_MINUTES_REGEX.captures(text) {
+        let n = match caps[1].to_lowercase().as_str() {
+            "a" | "an" | "one" => 1,
+            digits => digits.parse::<i64>().ok()?,
+        };
+        let minutes = if caps[2].to_lowercase().starts_with('h') { n * 60 } else { n };
+        return Some(now + chrono::Duration::minutes(minutes));
+    }
+
+    let mut rest = text.to_string();
+    let time = match CLOCK_REGEX.captures(text) {
+        Some(caps) => {
+            let time = if caps.get(6).is_some() {
+                NaiveTime::from_hms_opt(12, 0, 0)
+            } else if let Some(meridiem) = caps.get(3) {
+                let hour: u32 = caps[1].parse().ok()?;
+                let minute: u32 = caps.get(2).map_or(Ok(0), |m| m.as_str().parse()).ok()?;
+                if !(1..=12).contains(&hour) {
+                    return None;
+                }
+                let pm = meridiem.as_str().eq_ignore_ascii_case("pm");
+                NaiveTime::from_hms_opt(hour % 12 + if pm { 12 } else { 0 }, minute, 0)
+            } else {
+                NaiveTime::from_hms_opt(caps[4].parse().ok()?, caps[5].parse().ok()?, 0)
+            };
+            rest.replace_range(caps.get(0).unwrap().range(), " ");
+            Some(time?)
+        }
+        None => None,
+    };
+
+    let date = match ISO_DATE_REGEX.captures(&rest) {
+        Some(caps) => Some(NaiveDate::parse_from_str(&caps[1], "%Y-%m-%d").ok()?),
+        None => find_date_in_text(&rest).and_then(|(date, _)| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok()),
+    };
+
+    let at = match (date, time) {
+        (Some(date), time) => date.and_time(time.unwrap_or(NaiveTime::from_hms_opt(DEFAULT_HOUR, 0, 0)?)),
+        (None, Some(time)) => {
+            let today = now.date_naive().and_time(time);
+            if today > now.naive_local() { today } else { today + chrono::Duration::days(1) }
+        }
+        (None, None) => return None,
+    };
+    Local.from_local_datetime(&at).earliest()
+}
+
+/// "Mon Mar 2 09:00" for a stored RFC3339 time
+fn format_remind_at(at: &str) -> String {
+    DateTime::parse_from_rfc3339(at)
+        .map(|at| at.with_timezone(&Local).format("%a %b %-d %H:%M").to_string())
+        .unwrap_or_else(|_| at.to_string())
+}
+
+/// Pending reminders for one task, formatted for `show`
+pub fn pending_for(app: &App, task_id: &str) -> Result<Vec<String>> {
+    Ok(app.db.get_pending_reminders()?.into_iter()
+        .filter(|r| r.task_id == task_id)
+        .map(|r| format_remind_at(&r.remind_at))
+        .collect())
+}
+
+pub fn cmd_remind(app: &App, id: &str, when: &[String], clear: bool) -> Result<()> {
+    let tasks = get_tasks_from_cache(app)?;
+    let matching = find_tasks_by_id_prefix(&tasks, id);
+    let task = match matching.len() {
+        0 => anyhow::bail!("No task found with ID starting with '{}'", id),
+        1 => matching[0],
+        n => anyhow::bail!("{} tasks match '{}'. Please be more specific", n, id),
+    };
+    let task_id = task.id.as_deref().unwrap_or_default();
+
+    if clear {
+        let cleared = app.db.clear_reminders(task_id)?;
+        println!("{}", format!("🔕 Cleared {} reminder(s) for: {}", cleared, task.title).green());
+        return Ok(());
+    }
+
+    let when = when.join(" ");
+    let now = Local::now();
+    let Some(at) = parse_when(&when, now) else {
+        anyhow::bail!("Couldn't read a time from '{}' (try \"tomorrow 9am\", \"friday 14:30\" or \"in 20 minutes\")", when);
+    };
+    if at <= now {
+        anyhow::bail!("{} is in the past", at.format("%a %b %-d %H:%M"));
+    }
+    app.db.add_reminder(task_id, &at.with_timezone(&Utc).to_rfc3339())?;
+    println!("{}", format!("⏰ Reminder set for {}: {}", at.format("%a %b %-d %H:%M"), task.title).green());
+    Ok(())
+}
+
+/// Show a desktop notification, falling back to the terminal when there's no notifier
+fn notify(title: &str, body: &str) {
+    let shown = if cfg!(target_os = "macos") {
+        let script = format!("display notification {:?} with title {:?}", body, title);
+        Command::new("osascript").args(["-e", &script]).status()
+    } else {
+        Command::new("notify-send").args([title, body]).status()
+    };
+    if !shown.is_ok_and(|s| s.success()) {
+        println!("🔔 {}: {}", title.bold(), body);
+    }
+}
+
+/// Fire reminders due by now; returns how many fired. Reminders for tasks that are
+/// done or gone are dropped quietly.
+fn fire_due(app: &App, tasks: &[Task]) -> Result<usize> {
+    let now = Utc::now().to_rfc3339();
+    let mut fired = 0;
+    for reminder in app.db.get_pending_reminders()?.into_iter().filter(|r| r.remind_at <= now) {
+        let task = tasks.iter().find(|t| t.id.as_deref() == Some(reminder.task_id.as_str()));
+        if let Some(task) = task.filter(|t| !t.is_done()) {
+            let due = if task.date.is_empty() { String::new() } else { format!(" (due {})", task.date) };
+            notify("thegarden reminder", &format!("{}{}", task.title, due));
+            fired += 1;
+        }
+        app.db.mark_reminder_fired(reminder.id)?;
+    }
+    Ok(fired)
+}
+
+pub fn cmd_notify(app: &App, watch: bool) -> Result<()> {
+    if !watch {
+        let fired = fire_due(app, &get_tasks_from_cache(app)?)?;
+        if fired == 0 {
+            println!("{}", "No reminders due.".dimmed());
+        }
+        return Ok(());
+    }
+
+    println!("{}", format!("⏰ Watching for reminders every {}s (Ctrl-C to stop)", WATCH_INTERVAL_SECS).cyan());
+    loop {
+        fire_due(app, &get_tasks_from_cache(app)?)?;
+        std::thread::sleep(std::time::Duration::from_secs(WATCH_INTERVAL_SECS));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_when() {
+        let now = Local.with_ymd_and_hms(2026, 3, 4, 10, 0, 0).unwrap();
+        let at = |text: &str| parse_when(text, now).map(|t| t.format("%Y-%m-%d %H:%M").to_string());
+
+        assert_eq!(at("in 20 minutes").as_deref(), Some("2026-03-04 10:20"));
+        assert_eq!(at("in an hour").as_deref(), Some("2026-03-04 11:00"));
+        assert_eq!(at("5pm").as_deref(), Some("2026-03-04 17:00"));
+        // Already past today, so tomorrow
+        assert_eq!(at("9:30").as_deref(), Some("2026-03-05 09:30"));
+        assert_eq!(at("2026-03-10 8pm").as_deref(), Some("2026-03-10 20:00"));
+        assert_eq!(at("2026-03-10").as_deref(), Some("2026-03-10 09:00"));
+        assert_eq!(at("12am 2026-03-10").as_deref(), Some("2026-03-10 00:00"));
+        assert_eq!(at("noon").as_deref(), Some("2026-03-04 12:00"));
+        assert_eq!(at("13pm"), None);
+        assert_eq!(at("whenever"), None);
+
+        let tomorrow = (Local::now() + chrono::Duration::days(1)).format("%Y-%m-%d 09:00").to_string();
+        assert_eq!(parse_when("tomorrow 9am", Local::now()).map(|t| t.format("%Y-%m-%d %H:%M").to_string()), Some(tomorrow));
+    }
+
+    #[test]
+    fn test_pending_reminders() {
+        let db = crate::sync::SyncManager::open(std::path::Path::new(":memory:")).unwrap();
+        db.add_reminder("a", "2026-03-05T09:00:00+00:00").unwrap();
+        let first = db.add_reminder("a", "2026-03-04T09:00:00+00:00").unwrap();
+        db.add_reminder("b", "2026-03-06T09:00:00+00:00").unwrap();
+
+        db.mark_reminder_fired(first).unwrap();
+        let pending: Vec<String> = db.get_pending_reminders().unwrap().into_iter().map(|r| r.remind_at).collect();
+        assert_eq!(pending, vec!["2026-03-05T09:00:00+00:00", "2026-03-06T09:00:00+00:00"]);
+        assert_eq!(db.clear_reminders("a").unwrap(), 1);
+        assert_eq!(db.get_pending_reminders().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,244 @@
+use anyhow::Result;
+use chrono::{NaiveDate, Weekday};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::sync::TaskChange;
+use crate::goals;
+use crate::{format_time_from_minutes, get_tasks_from_cache, local_date, parse_time_to_minutes, start_of_week, App, Task};
+
+// `report --week` sums up a week from the cache and its change log:
+// what got done (by project) and how long it took, what is still open from the week,
+// what came in, and what kept getting pushed. Markdown pastes into a status update;
+// HTML drops into an email.
+
+pub const FORMATS: [&str; 2] = ["markdown", "html"];
+
+pub struct WeeklyReport {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Finished during the week, by project
+    pub completed: BTreeMap<String, Vec<Task>>,
+    pub estimated_minutes: i64,
+    pub tracked_minutes: i64,
+    /// Still open and due during the week or before it
+    pub carried_over: Vec<Task>,
+    /// Created during the week
+    pub created: Vec<Task>,
+    /// Tasks whose due date was pushed later during the week, most pushed first
+    pub bumped: Vec<(Task, usize)>,
+    /// Goals none of whose tasks changed during the week
+    pub stale_goals: Vec<String>,
+}
+
+/// First and last day of the week `weeks_ago` weeks before the one containing `today`
+pub fn week_bounds(today: NaiveDate, weeks_ago: i64, first: Weekday) -> (NaiveDate, NaiveDate) {
+    let start = start_of_week(today, first) - chrono::Duration::days(7 * weeks_ago);
+    (start, start + chrono::Duration::days(6))
+}
+
+pub fn weekly(app: &App, start: NaiveDate, end: NaiveDate) -> Result<WeeklyReport> {
+    let tasks = get_tasks_from_cache(app)?;
+    // The log is in UTC; a day early covers a week that started before UTC midnight
+    let history = app.db.get_history_since(&(start - chrono::Duration::days(1)).format("%Y-%m-%d").to_string())?;
+    let tracked = app.db.get_tracked_seconds()?;
+    let stale_goals = goals::stale(app, &tasks, &start.format("%Y-%m-%d").to_string())?.into_iter()
+        .map(|a| format!("#{} {} ({})", a.goal.id, a.goal.title, a.goal.quarter))
+        .collect();
+    Ok(WeeklyReport { stale_goals, ..build(tasks, &history, &tracked, start, end) })
+}
+
+fn build(tasks: Vec<Task>, history: &[TaskChange], tracked: &HashMap<String, i64>, start: NaiveDate, end: NaiveDate) -> WeeklyReport {
+    let end_str = end.format("%Y-%m-%d").to_string();
+    // `created` is RFC3339 from Google, or a bare date
+    let created_in_week = |at: &str| {
+        local_date(at)
+            .or_else(|| NaiveDate::parse_from_str(at, "%Y-%m-%d").ok())
+            .is_some_and(|day| day >= start && day <= end)
+    };
+    let changes: Vec<&TaskChange> = history.iter()
+        .filter(|c| local_date(&c.at).is_some_and(|day| day >= start && day <= end))
+        .collect();
+
+    let completed_ids: Vec<&str> = changes.iter()
+        .filter(|c| c.field == "status" && c.new_value.as_deref() == Some("completed"))
+        .map(|c| c.task_id.as_str())
+        .collect();
+    let mut pushes: HashMap<&str, usize> = HashMap::new();
+    for change in changes.iter().filter(|c| c.field == "due") {
+        if let (Some(old), Some(new)) = (&change.old_value, &change.new_value) {
+            if new > old {
+                *pushes.entry(change.task_id.as_str()).or_default() += 1;
+            }
+        }
+    }
+
+    let mut report = WeeklyReport {
+        start,
+        end,
+        completed: BTreeMap::new(),
+        estimated_minutes: 0,
+        tracked_minutes: 0,
+        carried_over: Vec::new(),
+        created: Vec::new(),
+        bumped: Vec::new(),
+        stale_goals: Vec::new(),
+    };
+    for task in tasks {
+        let id = task.id.clone().unwrap_or_default();
+        if let Some(&count) = pushes.get(id.as_str()) {
+            report.bumped.push((task.clone(), count));
+        }
+        if task.created.as_deref().is_some_and(created_in_week) {
+            report.created.push(task.clone());
+        }
+        if task.completed && completed_ids.contains(&id.as_str()) {
+            report.estimated_minutes += task.time.as_deref().map_or(0, parse_time_to_minutes) as i64;
+            report.tracked_minutes += tracked.get(&id).copied().unwrap_or(0) / 60;
+            let project = task.project.clone().unwrap_or_else(|| "No Project".to_string());
+            report.completed.entry(project).or_default().push(task);
+        } else if !task.completed && !task.date.is_empty() && task.date <= end_str {
+            report.carried_over.push(task);
+        }
+    }
+    report.carried_over.sort_by(|a, b| a.date.cmp(&b.date));
+    report.bumped.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.title.cmp(&b.0.title)));
+    report
+}
+
+impl WeeklyReport {
+    fn completed_count(&self) -> usize {
+        self.completed.values().map(Vec::len).sum()
+    }
+
+    fn headline(&self) -> String {
+        let minutes = |m: i64| if m > 0 { format_time_from_minutes(m as i32) } else { "0m".to_string() };
+        format!("Done: {} tasks · {} estimated · {} tracked · carried over: {} · new: {} · bumped: {} tasks, {} times",
+            self.completed_count(), minutes(self.estimated_minutes), minutes(self.tracked_minutes),
+            self.carried_over.len(), self.created.len(),
+            self.bumped.len(), self.bumped.iter().map(|(_, n)| n).sum::<usize>())
+    }
+
+    fn title(&self) -> String {
+        format!("Week of {} – {}", self.start.format("%b %-d"), self.end.format("%b %-d, %Y"))
+    }
+
+    /// Sections as (heading, lines), skipping empty ones
+    fn sections(&self) -> Vec<(String, Vec<String>)> {
+        let label = |t: &Task| {
+            let prefix = [t.priority.as_deref(), t.project.as_deref()].iter().flatten()
+                .map(|p| format!("[{}]", p))
+                .collect::<String>();
+            if prefix.is_empty() { t.title.clone() } else { format!("{} {}", prefix, t.title) }
+        };
+        let mut sections = Vec::new();
+        for (project, tasks) in &self.completed {
+            let lines: Vec<String> = tasks.iter()
+                .map(|t| match t.time.as_deref() {
+                    Some(time) => format!("{} ({})", t.title, time),
+                    None => t.title.clone(),
+                })
+                .collect();
+            sections.push((format!("Completed: {} ({})", project, tasks.len()), lines));
+        }
+        sections.push(("Carried over".to_string(), self.carried_over.iter().map(|t| format!("{} — due {}", label(t), t.date)).collect()));
+        sections.push(("New this week".to_string(), self.created.iter().map(label).collect()));
+        sections.push(("Bumped".to_string(), self.bumped.iter().map(|(t, n)| format!("{} ↻{}", label(t), n)).collect()));
+        sections.push(("Goals with no activity".to_string(), self.stale_goals.clone()));
+        sections.into_iter().filter(|(_, lines)| !lines.is_empty()).collect()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n{}\n", self.title(), self.headline());
+        for (heading, lines) in self.sections() {
+            out.push_str(&format!("\n## {}\n\n", heading));
+            for line in lines {
+                out.push_str(&format!("- {}\n", line));
+            }
+        }
+        out
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut out = format!("<h1>{}</h1>\n<p>{}</p>\n", escape_html(&self.title()), escape_html(&self.headline()));
+        for (heading, lines) in self.sections() {
+            out.push_str(&format!("<h2>{}</h2>\n<ul>\n", escape_html(&heading)));
+            for line in lines {
+                out.push_str(&format!("  <li>{}</li>\n", escape_html(&line)));
+            }
+            out.push_str("</ul>\n");
+        }
+        out
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(task_id: &str, field: &str, old: Option<&str>, new: &str, at: &str) -> TaskChange {
+        TaskChange {
+            task_id: task_id.to_string(),
+            field: field.to_string(),
+            old_value: old.map(str::to_string),
+            new_value: Some(new.to_string()),
+            source: "cli".to_string(),
+            at: at.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_week_bounds() {
+        let thursday = NaiveDate::from_ymd_opt(2026, 3, 5).unwrap();
+        assert_eq!(week_bounds(thursday, 0, Weekday::Mon), (NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(), NaiveDate::from_ymd_opt(2026, 3, 8).unwrap()));
+        assert_eq!(week_bounds(thursday, 1, Weekday::Mon).0, NaiveDate::from_ymd_opt(2026, 2, 23).unwrap());
+        assert_eq!(week_bounds(thursday, 0, Weekday::Sun), (NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(), NaiveDate::from_ymd_opt(2026, 3, 7).unwrap()));
+    }
+
+    #[test]
+    fn test_weekly_report() {
+        let task = |id: &str, title: &str, completed: bool, created: &str| {
+            let mut task = Task::parse_with_config(title, "Tasks", None);
+            task.id = Some(id.to_string());
+            task.completed = completed;
+            task.created = Some(created.to_string());
+            task
+        };
+        let tasks = vec![
+            task("a", "[2026-03-03][P1][WORK]{1h} Write <report>", true, "2026-02-20"),
+            task("b", "[2026-03-04][P2][LIFE]{30m} Call bank", true, "2026-03-02"),
+            task("old", "[2026-02-10][P2][LIFE]{30m} Finished long ago", true, "2026-02-01"),
+            task("c", "[2026-03-06][P0][WORK] Ship fix", false, "2026-03-03"),
+            task("d", "[2026-03-20][P3][SIDE] Someday", false, "2026-03-04"),
+            // Created on the last day of the week, with a time; and undated
+            task("e", "Idea from Sunday", false, "2026-03-08T10:00:00Z"),
+        ];
+        let history = vec![
+            change("old", "status", Some("needsAction"), "completed", "2026-02-11T10:00:00Z"),
+            change("a", "status", Some("needsAction"), "completed", "2026-03-03T16:00:00Z"),
+            change("b", "status", Some("needsAction"), "completed", "2026-03-05T09:00:00Z"),
+            change("c", "due", Some("2026-03-04"), "2026-03-05", "2026-03-04T18:00:00Z"),
+            change("c", "due", Some("2026-03-05"), "2026-03-06", "2026-03-05T18:00:00Z"),
+            change("d", "due", Some("2026-03-21"), "2026-03-20", "2026-03-05T18:00:00Z"),
+        ];
+        let tracked = HashMap::from([("a".to_string(), 5400)]);
+        let (start, end) = (NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(), NaiveDate::from_ymd_opt(2026, 3, 8).unwrap());
+        let report = build(tasks, &history, &tracked, start, end);
+
+        assert_eq!(report.completed.keys().collect::<Vec<_>>(), vec!["LIFE", "WORK"]);
+        assert_eq!((report.estimated_minutes, report.tracked_minutes), (90, 90));
+        assert_eq!(report.carried_over.iter().map(|t| t.title.as_str()).collect::<Vec<_>>(), vec!["Ship fix"]);
+        assert_eq!(report.created.iter().map(|t| t.id.as_deref().unwrap()).collect::<Vec<_>>(), vec!["b", "c", "d", "e"]);
+        // Pulling a date earlier isn't a bump
+        assert_eq!(report.bumped.iter().map(|(t, n)| (t.title.as_str(), *n)).collect::<Vec<_>>(), vec![("Ship fix", 2)]);
+
+        let markdown = report.to_markdown();
+        assert!(markdown.starts_with("# Week of Mar 2 – Mar 8, 2026\n\nDone: 2 tasks · 1h 30m estimated · 1h 30m tracked"));
+        assert!(markdown.contains("## Completed: WORK (1)\n\n- Write <report> (1h)\n"));
+        assert!(markdown.contains("- [P0][WORK] Ship fix ↻2\n"));
+        assert!(report.to_html().contains("<li>Write &lt;report&gt; (1h)</li>"));
+    }
+}
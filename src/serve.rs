@@ -0,0 +1,269 @@
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use colored::*;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::io::Read;
+
+use crate::clickup::{self, ClickUpClient, ClickUpTaskResponse, ClickUpWebhook};
+use crate::clickup_sync::ClickUpSync;
+use crate::privacy::PrivacyConfig;
+use crate::{block_on, get_tasks_from_cache, push_dirty_tasks_to_google, sync, update_task_locally, App, Config, Task};
+
+// `serve --webhooks` registers a ClickUp webhook for the workspace and applies the
+// events it delivers to the cache as they arrive, then pushes the result to Google
+// Tasks. Only tasks that `sync --clickup` created are touched (the clickup_id_map
+// links them). ClickUp has to reach the endpoint, so `--url` is the public address
+// (a tunnel or reverse proxy) in front of the local port. The registration is kept
+// in sync_state and reused until the URL changes.
+
+pub const DEFAULT_PORT: u16 = 8787;
+
+/// sync_state key for the registered ClickUp webhook
+pub const WEBHOOK_KEY: &str = "clickup_webhook";
+
+/// Largest delivery read; ClickUp's are a few KB, and the port is open to the network
+const MAX_BODY: u64 = 1024 * 1024;
+
+/// Events ClickUp sends us
+const EVENTS: [&str; 7] = [
+    "taskUpdated", "taskDeleted", "taskStatusUpdated", "taskPriorityUpdated",
+    "taskDueDateUpdated", "taskTimeEstimateUpdated", "taskTagUpdated",
+];
+
+/// Listen for ClickUp webhook deliveries until interrupted
+pub fn run(app: &App, port: u16, url: Option<&str>) -> Result<()> {
+    let clickup_config = match &app.config.clickup {
+        Some(cfg) if cfg.enabled => cfg,
+        _ => anyhow::bail!("ClickUp isn't set up; add an enabled 'clickup' section to config.json"),
+    };
+    let client = ClickUpClient::new(clickup::ClickUpConfig {
+        api_token: clickup_config.api_token.clone(),
+        list_id: clickup_config.list_id.clone().unwrap_or_default(),
+    })?;
+    let webhook = ensure_webhook(app, &client, url)?;
+
+    let server = tiny_http::Server::http(("0.0.0.0", port))
+        .map_err(|e| anyhow::anyhow!("Can't listen on port {}: {}", port, e))?;
+    sync::set_change_source("clickup");
+    println!("{}", format!("👂 Listening on port {} for ClickUp events sent to {}", port, webhook.endpoint).cyan());
+    println!("{}", "   Ctrl-C to stop".dimmed());
+    tracing::info!(port, endpoint = %webhook.endpoint, "serving ClickUp webhooks");
+
+    for mut request in server.incoming_requests() {
+        let mut body = Vec::new();
+        let status = if *request.method() != tiny_http::Method::Post {
+            405
+        } else if request.as_reader().take(MAX_BODY + 1).read_to_end(&mut body).is_err() {
+            400
+        } else if body.len() as u64 > MAX_BODY {
+            413
+        } else {
+            let signature = request.headers().iter()
+                .find(|h| h.field.equiv("X-Signature"))
+                .map(|h| h.value.as_str().to_string());
+            match signature {
+                Some(signature) if verify_signature(&webhook.secret, &body, &signature) => handle(app, &client, &body),
+                _ => {
+                    tracing::warn!("rejected webhook delivery with a bad signature");
+                    401
+                }
+            }
+        };
+        let _ = request.respond(tiny_http::Response::empty(status));
+    }
+    Ok(())
+}
+
+/// Reuse the stored registration unless `url` asks for a different endpoint
+fn ensure_webhook(app: &App, client: &ClickUpClient, url: Option<&str>) -> Result<ClickUpWebhook> {
+    let stored: Option<ClickUpWebhook> = app.db.get_state(WEBHOOK_KEY)?.and_then(|s| serde_json::from_str(&s).ok());
+    let url = match (stored, url) {
+        (Some(webhook), None) => return Ok(webhook),
+        (Some(webhook), Some(url)) if webhook.endpoint == url => return Ok(webhook),
+        (Some(old), Some(url)) => {
+            if let Err(e) = block_on(client.delete_webhook(&old.id)) {
+                eprintln!("{}", format!("⚠ Couldn't remove the old webhook for {}: {}", old.endpoint, e).yellow());
+            }
+            url
+        }
+        (None, Some(url)) => url,
+        (None, None) => anyhow::bail!(
+            "Pass --url with the public address ClickUp should send events to (forwarded to this port)"
+        ),
+    };
+
+    let webhook = block_on(async { client.create_webhook(&client.get_team_id().await?, url, &EVENTS).await })?;
+    app.db.set_state(WEBHOOK_KEY, &serde_json::to_string(&webhook)?)?;
+    println!("{}", format!("✓ Registered ClickUp webhook {}", webhook.id).green());
+    Ok(webhook)
+}
+
+/// ClickUp signs each body with HMAC-SHA256 under the webhook's secret, hex-encoded.
+/// The comparison is constant-time, so response timing says nothing about the signature.
+fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(signature) = decode_hex(signature.trim()) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok()).collect()
+}
+
+/// Apply one delivery; the status code tells ClickUp whether to retry
+fn handle(app: &App, client: &ClickUpClient, body: &[u8]) -> u16 {
+    let Ok(event) = serde_json::from_slice::<serde_json::Value>(body) else {
+        return 400;
+    };
+    let (Some(name), Some(clickup_id)) = (event["event"].as_str(), event["task_id"].as_str()) else {
+        return 200; // e.g. the test ping ClickUp sends on registration
+    };
+    match apply_event(app, client, name, clickup_id) {
+        Ok(Some(message)) => {
+            println!("  {}", message);
+            200
+        }
+        Ok(None) => 200,
+        Err(e) => {
+            eprintln!("{}", format!("  ⚠ {} for ClickUp task {} failed: {:#}", name, clickup_id, e).yellow());
+            tracing::warn!(event = name, clickup_id, error = %e, "webhook event failed");
+            500
+        }
+    }
+}
+
+/// Bring the linked task up to date with ClickUp. Returns what changed, if anything.
+fn apply_event(app: &App, client: &ClickUpClient, event: &str, clickup_id: &str) -> Result<Option<String>> {
+    let links: HashMap<String, String> = app.db.get_id_map("clickup_id_map")?;
+    let Some(task_id) = links.iter().find(|(_, c)| c.as_str() == clickup_id).map(|(g, _)| g.clone()) else {
+        return Ok(None); // Not a task we pushed
+    };
+    let Some(mut task) = get_tasks_from_cache(app)?.into_iter().find(|t| t.id.as_deref() == Some(task_id.as_str())) else {
+        return Ok(None);
+    };
+    let before = task.format(&app.config);
+
+    let message = if event == "taskDeleted" {
+        // Unlink and close it rather than deleting anything in Google Tasks
+        let mut links = links;
+        links.remove(&task_id);
+        app.db.set_state("clickup_id_map", &serde_json::to_string(&links)?)?;
+        task.completed = true;
+        format!("{} {} {}", "✗".red(), task.title, "(deleted in ClickUp; marked done)".dimmed())
+    } else {
+        let remote = block_on(client.get_task(clickup_id)).context("Failed to fetch the task from ClickUp")?;
+        apply_remote(&app.config, &app.config.privacy, &mut task, &remote);
+        format!("{} {}", "↓".cyan(), task.format(&app.config))
+    };
+
+    if task.format(&app.config) == before {
+        return Ok(None);
+    }
+    update_task_locally(app, &task)?;
+    push_dirty_tasks_to_google(app)?;
+    tracing::info!(event, task_id, clickup_id, "applied ClickUp event");
+    Ok(Some(message))
+}
+
+/// Copy what ClickUp holds into the task: name, priority, status, due date and estimate.
+/// Fields ClickUp leaves empty keep their current value.
+fn apply_remote(config: &Config, privacy: &PrivacyConfig, task: &mut Task, remote: &ClickUpTaskResponse) {
+    // The name went out through the privacy filter; only a rename in ClickUp replaces the
+    // title, and the tags the filter held back stay on it
+    let name = remote.name.trim();
+    if !name.is_empty() && name != privacy.apply_title(&task.title) {
+        let hidden = privacy.hidden_tags(&task.title).into_iter()
+            .filter(|tag| !name.split_whitespace().any(|word| word == *tag));
+        task.title = std::iter::once(name).chain(hidden).collect::<Vec<_>>().join(" ");
+    }
+    if let Some(ref priority) = remote.priority {
+        task.priority = ClickUpSync::priority_from_clickup(config, &priority.id, task.priority.as_deref());
+    }
+    if ClickUpSync::is_closed_status(&config.statuses, &remote.status.status) {
+        task.completed = true;
+    } else {
+        task.status = ClickUpSync::status_from_clickup(&config.statuses, &remote.status.status, task.status.as_deref());
+    }
+    if let Some(date) = remote.due_date.as_deref()
+        .and_then(|ms| ms.parse::<i64>().ok())
+        .and_then(DateTime::from_timestamp_millis)
+    {
+        task.date = date.format("%Y-%m-%d").to_string();
+    }
+    if let Some(time) = remote.time_estimate.and_then(|ms| crate::importers::estimate(ms / 60_000)) {
+        task.time = Some(time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_signature() {
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(b"{}");
+        let good: String = mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+        assert!(verify_signature("secret", b"{}", &good));
+        assert!(!verify_signature("secret", b"{ }", &good));
+        assert!(!verify_signature("other", b"{}", &good));
+        assert!(verify_signature("secret", b"{}", &good.to_uppercase()));
+        assert!(!verify_signature("secret", b"{}", &good[..62]));
+        assert!(!verify_signature("secret", b"{}", "zz"));
+        assert_eq!(decode_hex("00fF10"), Some(vec![0, 255, 16]));
+        assert_eq!(decode_hex("abc"), None);
+    }
+
+    #[test]
+    fn test_apply_remote() {
+        let config = crate::create_default_config();
+        let mut task = Task::parse_with_config("[2026-01-21][P5][WORK][needsTriage]{30m} Ask Sam about specs", "Tasks", Some(&config));
+        let remote: ClickUpTaskResponse = serde_json::from_value(serde_json::json!({
+            "id": "cu1",
+            "name": "Ask Sam about the specs",
+            "status": { "status": "to do" },
+            "priority": { "id": "4", "priority": "low" },
+            "due_date": "1769299200000",
+            "time_estimate": 3600000,
+            "tags": [],
+        })).unwrap();
+        apply_remote(&config, &PrivacyConfig::default(), &mut task, &remote);
+        assert_eq!(task.format(&config), "[2026-01-25][P5][WORK][needsTriage]{1h} Ask Sam about the specs");
+
+        let closed: ClickUpTaskResponse = serde_json::from_value(serde_json::json!({
+            "id": "cu1", "name": "Ask Sam about the specs", "status": { "status": "complete" }, "tags": [],
+        })).unwrap();
+        apply_remote(&config, &PrivacyConfig::default(), &mut task, &closed);
+        assert!(task.completed);
+    }
+
+    #[test]
+    fn test_apply_remote_keeps_redacted_tags() {
+        let config = crate::create_default_config();
+        let privacy = PrivacyConfig { redact_tags: vec!["private".into()], ..Default::default() };
+        let mut task = Task::parse_with_config("[2026-01-21][P1][WORK] Salary review #private #hr", "Tasks", Some(&config));
+        let remote = |name: &str| -> ClickUpTaskResponse {
+            serde_json::from_value(serde_json::json!({
+                "id": "cu1", "name": name, "status": { "status": "to do" }, "tags": [],
+            })).unwrap()
+        };
+
+        // The name as pushed: nothing changed
+        apply_remote(&config, &privacy, &mut task, &remote("Salary review #hr"));
+        assert_eq!(task.title, "Salary review #private #hr");
+
+        // Renamed in ClickUp: the new name, with the redacted tag kept
+        apply_remote(&config, &privacy, &mut task, &remote("Salary review with Sam #hr"));
+        assert_eq!(task.title, "Salary review with Sam #hr #private");
+    }
+}
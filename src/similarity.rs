@@ -0,0 +1,239 @@
+use anyhow::{anyhow, Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use crate::logging::LoggedRequest;
+
+// Leading [date][priority][project]...{time} prefix and the ClickUp 🔃 marker
+static PREFIX_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:🔃\s*)?(?:\[[^\]]*\]|\{[^}]*\})*\s*").unwrap()
+});
+
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "into", "is", "it",
+    "me", "my", "of", "on", "or", "our", "re", "regarding", "so", "the", "their", "this",
+    "to", "up", "with", "about", "via", "w", "we", "you", "your",
+];
+
+// Verbs that mean the same thing in a task list collapse to one token
+const SYNONYMS: &[(&str, &str)] = &[
+    ("email", "message"), ("mail", "message"), ("send", "message"), ("sent", "message"),
+    ("ping", "message"), ("text", "message"), ("msg", "message"), ("note", "message"),
+    ("call", "call"), ("phone", "call"), ("ring", "call"),
+    ("fix", "fix"), ("repair", "fix"), ("debug", "fix"),
+    ("review", "review"), ("check", "review"), ("look", "review"),
+    ("buy", "buy"), ("order", "buy"), ("purchase", "buy"),
+    ("meet", "meet"), ("meeting", "meet"), ("sync", "meet"),
+    ("write", "write"), ("draft", "write"),
+    ("schedule", "schedule"), ("book", "schedule"),
+];
+
+/// Inputs per embeddings request; providers cap a request's inputs and tokens
+const EMBED_BATCH: usize = 100;
+
+/// How `merge` scores a pair of titles
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Method {
+    /// Normalized words
+    #[value(alias = "t")]
+    Token,
+    /// Raw characters
+    #[value(alias = "b")]
+    Bigram,
+    /// Vectors from the configured embeddings endpoint
+    #[value(alias = "e")]
+    Embedding,
+}
+
+impl std::fmt::Display for Method {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Method::Token => "token",
+            Method::Bigram => "bigram",
+            Method::Embedding => "embedding",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Optional OpenAI-compatible embeddings endpoint used by `merge --method embedding`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EmbeddingConfig {
+    /// e.g. https://api.openai.com/v1/embeddings or http://localhost:11434/v1/embeddings
+    pub url: String,
+    pub model: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+/// Lowercase and strip the bracket prefix, hashtags markers, and punctuation
+pub fn normalize_title(title: &str) -> String {
+    let stripped = PREFIX_REGEX.replace(title, "");
+    stripped
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Very small suffix stemmer - good enough to line up "emails"/"emailing"/"emailed"
+fn stem(word: &str) -> String {
+    for suffix in ["ing", "ed", "es", "s"] {
+        if let Some(base) = word.strip_suffix(suffix) {
+            if base.chars().count() >= 3 && !base.ends_with('s') {
+                return base.to_string();
+            }
+        }
+    }
+    word.to_string()
+}
+
+/// Normalized content tokens: stopwords removed, stemmed, synonyms collapsed
+pub fn tokens(title: &str) -> Vec<String> {
+    normalize_title(title)
+        .split_whitespace()
+        .filter(|w| !STOPWORDS.contains(w))
+        .map(|w| {
+            let stemmed = stem(w);
+            SYNONYMS.iter()
+                .find(|(from, _)| *from == w || *from == stemmed)
+                .map(|(_, to)| to.to_string())
+                .unwrap_or(stemmed)
+        })
+        .collect()
+}
+
+/// Calculate similarity between two strings using bigrams (Dice coefficient)
+pub fn string_similarity(s1: &str, s2: &str) -> f64 {
+    let s1_lower = s1.to_lowercase();
+    let s2_lower = s2.to_lowercase();
+
+    if s1_lower == s2_lower {
+        return 1.0;
+    }
+
+    let bigrams1: HashSet<(char, char)> = s1_lower
+        .chars()
+        .zip(s1_lower.chars().skip(1))
+        .collect();
+    let bigrams2: HashSet<(char, char)> = s2_lower
+        .chars()
+        .zip(s2_lower.chars().skip(1))
+        .collect();
+
+    if bigrams1.is_empty() || bigrams2.is_empty() {
+        return 0.0;
+    }
+
+    let intersection = bigrams1.intersection(&bigrams2).count();
+    (2.0 * intersection as f64) / (bigrams1.len() + bigrams2.len()) as f64
+}
+
+/// Dice coefficient over normalized token sets. Falls back to bigrams on the
+/// normalized titles when either side has no content words.
+pub fn token_similarity(s1: &str, s2: &str) -> f64 {
+    let t1: HashSet<String> = tokens(s1).into_iter().collect();
+    let t2: HashSet<String> = tokens(s2).into_iter().collect();
+
+    if t1.is_empty() || t2.is_empty() {
+        return string_similarity(&normalize_title(s1), &normalize_title(s2));
+    }
+
+    let intersection = t1.intersection(&t2).count();
+    (2.0 * intersection as f64) / (t1.len() + t2.len()) as f64
+}
+
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    let dot: f64 = a.iter().zip(b).map(|(x, y)| (*x as f64) * (*y as f64)).sum();
+    let norm_a: f64 = a.iter().map(|x| (*x as f64).powi(2)).sum::<f64>().sqrt();
+    let norm_b: f64 = b.iter().map(|x| (*x as f64).powi(2)).sum::<f64>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+/// Fetch embeddings for any number of texts (one vector per input, same order),
+/// EMBED_BATCH per request
+pub fn embed_all(config: &EmbeddingConfig, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+    let client = reqwest::blocking::Client::new();
+    let mut vectors = Vec::with_capacity(texts.len());
+    for batch in texts.chunks(EMBED_BATCH) {
+        vectors.extend(embed_batch(&client, config, batch)?);
+    }
+    Ok(vectors)
+}
+
+fn embed_batch(client: &reqwest::blocking::Client, config: &EmbeddingConfig, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+    let mut request = client
+        .post(&config.url)
+        .header("Content-Type", "application/json")
+        .json(&serde_json::json!({ "model": config.model, "input": texts }));
+    if let Some(ref key) = config.api_key {
+        request = request.header("Authorization", format!("Bearer {}", key));
+    }
+
+    let response = request.send_logged().context("Failed to reach embeddings endpoint")?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().unwrap_or_default();
+        return Err(anyhow!("Embeddings API error {}: {}", status, body));
+    }
+
+    #[derive(Deserialize)]
+    struct EmbeddingResponse {
+        data: Vec<EmbeddingData>,
+    }
+
+    #[derive(Deserialize)]
+    struct EmbeddingData {
+        embedding: Vec<f32>,
+    }
+
+    let parsed: EmbeddingResponse = response
+        .json()
+        .context("Failed to parse embeddings response")?;
+
+    if parsed.data.len() != texts.len() {
+        return Err(anyhow!("Embeddings API returned {} vectors for {} inputs", parsed.data.len(), texts.len()));
+    }
+
+    Ok(parsed.data.into_iter().map(|d| d.embedding).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_strips_prefix() {
+        assert_eq!(
+            normalize_title("[2026-01-27][P0][WORK][progress][@work]{2h} Fix editor bug! #DeepWork"),
+            "fix editor bug deepwork"
+        );
+        assert_eq!(normalize_title("🔃 [2026-01-27][P1][LIFE] Call mom"), "call mom");
+    }
+
+    #[test]
+    fn test_token_similarity_catches_rephrasing() {
+        let sim = token_similarity("email Bob re budget", "send budget note to Bob");
+        assert!(sim >= 0.8, "expected a duplicate, got {}", sim);
+        assert!(string_similarity("email Bob re budget", "send budget note to Bob") < 0.8);
+    }
+
+    #[test]
+    fn test_token_similarity_rejects_shared_stopwords() {
+        let sim = token_similarity("Call the plumber about the sink", "Read the book about the war");
+        assert!(sim < 0.5, "expected unrelated, got {}", sim);
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[1.0, 0.0]) - 1.0).abs() < 1e-9);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+    }
+}
@@ -1,10 +1,135 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
 use rusqlite::{params, Connection};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::encryption::{self, Cipher};
+
+/// A calendar event TaskGarden created for a task; start/end are RFC3339
+#[derive(Debug, Clone)]
+pub struct CalendarBlock {
+    pub event_id: String,
+    pub task_id: String,
+    pub start: String,
+    pub end: String,
+}
+
+/// One logged priority escalation (or flag, when `to_priority` is None)
+#[derive(Debug, Clone)]
+pub struct PriorityDrift {
+    pub task_id: String,
+    pub title: String,
+    pub from_priority: Option<String>,
+    pub to_priority: Option<String>,
+    pub reason: String,
+    pub at: String,
+}
+
+/// One recorded field change on a cached task
+#[derive(Debug, Clone)]
+pub struct TaskChange {
+    pub task_id: String,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub source: String,
+    pub at: String,
+}
+
+/// Open work at one sync, summed over the projects asked for
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub at: String,
+    pub open_tasks: i64,
+    pub estimated_minutes: i64,
+}
+
+/// A quarterly goal tasks can roll up to
+#[derive(Debug, Clone)]
+pub struct Goal {
+    pub id: i64,
+    pub title: String,
+    /// e.g. "2026-Q2"
+    pub quarter: String,
+}
+
+/// A pending notification for a task
+#[derive(Debug, Clone)]
+pub struct Reminder {
+    pub id: i64,
+    pub task_id: String,
+    /// RFC3339, UTC
+    pub remind_at: String,
+}
+
+/// How long a write waits for another process holding the cache before giving up
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// sync_state key marking the cache as encrypted; its value is a known string sealed with
+/// the key, so a wrong key is caught on open instead of at the first task read
+const ENCRYPTION_CHECK: &str = "encryption_check";
+const ENCRYPTION_CHECK_VALUE: &str = "thegarden";
+
+/// What `encrypt_cache` seals: table, columns, and which rows
+const SEALED_COLUMNS: [(&str, &[&str], &str); 5] = [
+    ("tasks", &["title", "links", "user_description", "taskgarden_description"], ""),
+    ("task_history", &["old_value", "new_value"], "WHERE field IN ('created', 'title', 'notes')"),
+    ("inbox", &["title"], ""),
+    ("priority_drift", &["title"], ""),
+    ("goals", &["title"], ""),
+];
+/// sync_state entries holding credentials (the To Do OAuth tokens, the ClickUp webhook secret,
+/// outbound webhook URLs waiting for a retry)
+const SEALED_STATE_KEYS: [&str; 3] = [crate::mstodo::TOKEN_STATE, crate::serve::WEBHOOK_KEY, crate::webhooks::RETRY_KEY];
+const SEALED_HISTORY_FIELDS: [&str; 3] = ["created", "title", "notes"];
+
+/// What local cache writes are attributed to in task_history ("cli" unless a command says otherwise)
+static CHANGE_SOURCE: std::sync::Mutex<&str> = std::sync::Mutex::new("cli");
+
+/// Attribute the following local writes to `source` (triage, merge, bump, auto, ...)
+pub fn set_change_source(source: &'static str) {
+    *CHANGE_SOURCE.lock().unwrap() = source;
+}
+
+fn change_source() -> &'static str {
+    *CHANGE_SOURCE.lock().unwrap()
+}
+
+/// Whether this run must stay off the network (`--offline` or `"offline": true`)
+static OFFLINE: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Work from the cache only: writes stay dirty and are pushed by a later online sync
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// The assignee for a pushed task from a map keyed by "+person" or project name: the
+/// title's +person first, then its project, then `default`
+pub fn pick_assignee<'a, T>(assignees: &'a HashMap<String, T>, default: Option<&'a T>, title: &str, project: Option<&str>) -> Option<&'a T> {
+    let person = crate::PERSON_REGEX.captures(title).map(|caps| format!("+{}", &caps[1]));
+    let find = |key: &str| assignees.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, v)| v);
+    person.as_deref().and_then(find)
+        .or_else(|| project.and_then(find))
+        .or(default)
+}
+
+/// SHA-256 of a record as JSON, to tell whether a provider already has this version
+pub fn content_hash<T: serde::Serialize>(record: &T) -> String {
+    use sha2::{Digest, Sha256};
+    let json = serde_json::to_vec(record).unwrap_or_default();
+    Sha256::digest(json).iter().map(|b| format!("{:02x}", b)).collect()
+}
 
 pub struct SyncManager {
     conn: Connection,
+    /// Set when the cache is encrypted
+    cipher: Option<Cipher>,
 }
 
 #[derive(Debug, Clone)]
@@ -109,6 +234,7 @@ impl CachedTask {
     
     /// Parse emoji format string into properties
     /// This is for importing tasks that were created externally
+    #[allow(dead_code)]
     pub fn parse_from_emoji_string(input: &str, id: &str, list_id: &str) -> Self {
         use regex::Regex;
         
@@ -198,106 +324,177 @@ impl CachedTask {
 
 impl SyncManager {
     pub fn new() -> Result<Self> {
-        let db_path = Self::get_db_path()?;
-        let conn = Connection::open(&db_path)
-            .context("Failed to open cache database")?;
+        Self::open(&Self::get_db_path()?)
+    }
 
-        // Create tables if they don't exist
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS tasks (
-                id TEXT PRIMARY KEY,
-                unique_id TEXT NOT NULL UNIQUE,
-                list_id TEXT NOT NULL,
-                title TEXT NOT NULL,
-                status TEXT NOT NULL,
-                updated TEXT NOT NULL,
-                created TEXT,
-                links TEXT,
-                last_synced TEXT NOT NULL,
-                dirty INTEGER DEFAULT 0,
-                
-                -- Structured properties
-                priority TEXT,
-                project TEXT,
-                context TEXT,
-                duration TEXT,
-                due_date TEXT,
-                start_date TEXT,
-                scheduled_date TEXT,
-                tags TEXT,
-                user_description TEXT,
-                
-                -- Derived field (immutable, always regenerated)
-                taskgarden_description TEXT NOT NULL
-            )",
-            [],
-        )?;
+    /// Open (and if needed create) the cache at `db_path`, bringing its schema up to date;
+    /// ":memory:" gives a throwaway one
+    pub fn open(db_path: &Path) -> Result<Self> {
+        let conn = Self::connect(db_path)?;
+        crate::migrations::run(&conn)?;
+        let mut db = Self { conn, cipher: None };
+        if let Some(check) = db.get_state(ENCRYPTION_CHECK)? {
+            let cipher = Cipher::new(&encryption::load_key(false)?);
+            if cipher.decrypt(&check)? != ENCRYPTION_CHECK_VALUE {
+                anyhow::bail!("Can't decrypt the cache: the key doesn't match the one it was encrypted with");
+            }
+            db.cipher = Some(cipher);
+        }
+        Ok(db)
+    }
 
-        // Migration: add new columns if they don't exist
-        let migrations = vec![
-            "ALTER TABLE tasks ADD COLUMN unique_id TEXT",
-            "ALTER TABLE tasks ADD COLUMN dirty INTEGER DEFAULT 0",
-            "ALTER TABLE tasks ADD COLUMN created TEXT",
-            "ALTER TABLE tasks ADD COLUMN priority TEXT",
-            "ALTER TABLE tasks ADD COLUMN project TEXT",
-            "ALTER TABLE tasks ADD COLUMN context TEXT",
-            "ALTER TABLE tasks ADD COLUMN duration TEXT",
-            "ALTER TABLE tasks ADD COLUMN due_date TEXT",
-            "ALTER TABLE tasks ADD COLUMN start_date TEXT",
-            "ALTER TABLE tasks ADD COLUMN scheduled_date TEXT",
-            "ALTER TABLE tasks ADD COLUMN tags TEXT",
-            "ALTER TABLE tasks ADD COLUMN user_description TEXT",
-            "ALTER TABLE tasks ADD COLUMN taskgarden_description TEXT",
-        ];
-        
-        for migration in migrations {
-            let _ = conn.execute(migration, []);
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    /// Encrypt (or decrypt) the titles, notes and links already in the cache, and everything
+    /// written from now on. Returns false if the cache was already that way.
+    pub fn set_encrypted(&mut self, encrypt: bool) -> Result<bool> {
+        if encrypt == self.is_encrypted() {
+            return Ok(false);
         }
-        
-        // Generate unique_ids for existing tasks that don't have one
-        conn.execute(
-            "UPDATE tasks SET unique_id = lower(hex(randomblob(16))) WHERE unique_id IS NULL",
-            [],
-        )?;
-        
-        // Generate taskgarden_description for existing tasks
-        conn.execute(
-            "UPDATE tasks SET taskgarden_description = title WHERE taskgarden_description IS NULL OR taskgarden_description = ''",
-            [],
-        )?;
+        let cipher = if encrypt { Some(Cipher::new(&encryption::load_key(true)?)) } else { None };
+        self.reseal(cipher)?;
+        Ok(true)
+    }
 
-        // Create indexes for faster queries
-        conn.execute("CREATE INDEX IF NOT EXISTS idx_dirty ON tasks(dirty)", [])?;
-        conn.execute("CREATE INDEX IF NOT EXISTS idx_status ON tasks(status)", [])?;
-        conn.execute("CREATE INDEX IF NOT EXISTS idx_list_id ON tasks(list_id)", [])?;
-        conn.execute("CREATE INDEX IF NOT EXISTS idx_created ON tasks(created)", [])?;
-
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS sync_state (
-                key TEXT PRIMARY KEY,
-                value TEXT NOT NULL
-            )",
-            [],
-        )?;
+    /// Rewrite every sealed column from the current cipher (or plaintext) to `cipher`
+    fn reseal(&mut self, cipher: Option<Cipher>) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for (table, columns, filter) in SEALED_COLUMNS {
+            let rows = {
+                let mut stmt = tx.prepare(&format!("SELECT rowid, {} FROM {} {}", columns.join(", "), table, filter))?;
+                let rows = stmt
+                    .query_map([], |row| {
+                        let values = (1..=columns.len()).map(|i| row.get(i)).collect::<rusqlite::Result<Vec<Option<String>>>>()?;
+                        Ok((row.get::<_, i64>(0)?, values))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+                rows
+            };
+            let assignments: Vec<String> = columns.iter().enumerate().map(|(i, column)| format!("{} = ?{}", column, i + 2)).collect();
+            let update = format!("UPDATE {} SET {} WHERE rowid = ?1", table, assignments.join(", "));
+            for (rowid, values) in rows {
+                let values = values.into_iter()
+                    .map(|value| {
+                        let plain = self.unseal(value)?;
+                        Ok(match &cipher {
+                            Some(cipher) => cipher.encrypt_opt(plain.as_deref()),
+                            None => plain,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let mut params: Vec<&dyn rusqlite::ToSql> = vec![&rowid];
+                params.extend(values.iter().map(|value| value as &dyn rusqlite::ToSql));
+                tx.execute(&update, params.as_slice())?;
+            }
+        }
+        for key in SEALED_STATE_KEYS {
+            let value: Option<String> = tx
+                .query_row("SELECT value FROM sync_state WHERE key = ?1", params![key], |row| row.get(0))
+                .ok();
+            if let Some(plain) = self.unseal(value)? {
+                let value = match &cipher {
+                    Some(cipher) => cipher.encrypt(&plain),
+                    None => plain,
+                };
+                tx.execute("UPDATE sync_state SET value = ?2 WHERE key = ?1", params![key, value])?;
+            }
+        }
+        match &cipher {
+            Some(cipher) => self.set_state(ENCRYPTION_CHECK, &cipher.encrypt(ENCRYPTION_CHECK_VALUE))?,
+            None => self.delete_state(ENCRYPTION_CHECK)?,
+        }
+        tx.commit()?;
+        self.cipher = cipher;
 
-        // Table for tracking dismissed duplicate pairs
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS dismissed_pairs (
-                task_id_1 TEXT NOT NULL,
-                task_id_2 TEXT NOT NULL,
-                dismissed_at TEXT NOT NULL,
-                PRIMARY KEY (task_id_1, task_id_2)
-            )",
-            [],
-        )?;
+        // Rewrite the file and empty the WAL so no copy of the old values is left in free pages
+        self.conn.execute_batch("VACUUM")?;
+        self.conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        Ok(())
+    }
 
-        Ok(Self { conn })
+    fn seal(&self, value: Option<&str>) -> Option<String> {
+        match &self.cipher {
+            Some(cipher) => cipher.encrypt_opt(value),
+            None => value.map(str::to_string),
+        }
+    }
+
+    fn unseal(&self, value: Option<String>) -> Result<Option<String>> {
+        match &self.cipher {
+            Some(cipher) => cipher.decrypt_opt(value),
+            None => Ok(value),
+        }
+    }
+
+    /// A task row as read, with its sealed columns decrypted
+    fn unseal_task(&self, mut task: CachedTask) -> Result<CachedTask> {
+        if let Some(cipher) = &self.cipher {
+            task.title = cipher.decrypt(&task.title)?;
+            task.links = cipher.decrypt_opt(task.links)?;
+            task.user_description = cipher.decrypt_opt(task.user_description)?;
+            task.taskgarden_description = cipher.decrypt(&task.taskgarden_description)?;
+        }
+        Ok(task)
+    }
+
+    /// Schema migrations of the default cache and whether each has run, without applying any
+    pub fn migration_status() -> Result<Vec<crate::migrations::MigrationStatus>> {
+        crate::migrations::status(&Self::connect(&Self::get_db_path()?)?)
+    }
+
+    /// Write a consistent copy of the cache (WAL included) to `path`, which must not exist
+    pub fn snapshot_to(&self, path: &Path) -> Result<()> {
+        self.conn.execute("VACUUM INTO ?1", params![path.to_string_lossy()])
+            .with_context(|| format!("Failed to snapshot the cache to {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Delete the plaintext values of `keys` from sync_state in the cache file at `path` (a
+    /// snapshot); sealed values stay, since they're unreadable without the keychain key
+    pub fn strip_state(path: &Path, keys: &[&str]) -> Result<()> {
+        let conn = Self::connect(path)?;
+        for key in keys {
+            conn.execute(
+                "DELETE FROM sync_state WHERE key = ?1 AND value NOT LIKE ?2",
+                params![key, format!("{}%", encryption::PREFIX)],
+            )?;
+        }
+        // Deleted rows linger in free pages until the file is rebuilt
+        conn.execute_batch("VACUUM")?;
+        Ok(())
     }
 
-    fn get_db_path() -> Result<PathBuf> {
-        let home = dirs::home_dir().context("Could not find home directory")?;
-        let cache_dir = home.join(".thegarden");
-        std::fs::create_dir_all(&cache_dir)?;
+    /// Copy `keys` from sync_state into the cache file at `path` where it has none, as stored
+    pub fn carry_over_state(&self, path: &Path, keys: &[&str]) -> Result<()> {
+        let conn = Self::connect(path)?;
+        for key in keys {
+            let value: Option<String> = self.conn
+                .query_row("SELECT value FROM sync_state WHERE key = ?1", params![key], |row| row.get(0))
+                .ok();
+            if let Some(value) = value {
+                conn.execute("INSERT OR IGNORE INTO sync_state (key, value) VALUES (?1, ?2)", params![key, value])?;
+            }
+        }
+        Ok(())
+    }
+
+    fn connect(db_path: &Path) -> Result<Connection> {
+        let conn = Connection::open(db_path)
+            .context("Failed to open cache database")?;
+
+        // WAL lets a sync in one terminal run while another reads; writers wait
+        // for each other instead of failing with "database is locked"
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        Ok(conn)
+    }
+
+    pub fn get_db_path() -> Result<PathBuf> {
+        let cache_dir = crate::paths::data_dir()?;
+        std::fs::create_dir_all(&cache_dir)
+            .with_context(|| format!("Failed to create {}", cache_dir.display()))?;
         Ok(cache_dir.join("cache.db"))
     }
 
@@ -331,11 +528,15 @@ impl SyncManager {
                 |row| row.get(0),
             )
             .ok();
+        if SEALED_STATE_KEYS.contains(&key) {
+            return self.unseal(result);
+        }
         Ok(result)
     }
 
     /// Generic set state value
     pub fn set_state(&self, key: &str, value: &str) -> Result<()> {
+        let value = if SEALED_STATE_KEYS.contains(&key) { self.seal(Some(value)) } else { Some(value.to_string()) };
         self.conn.execute(
             "INSERT OR REPLACE INTO sync_state (key, value) VALUES (?1, ?2)",
             params![key, value],
@@ -343,6 +544,19 @@ impl SyncManager {
         Ok(())
     }
 
+    /// A Google task ID → provider record ID map kept in sync_state (empty if unset or unreadable)
+    pub fn get_id_map(&self, key: &str) -> Result<std::collections::HashMap<String, String>> {
+        Ok(self.get_state(key)?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default())
+    }
+
+    /// Generic delete state value
+    pub fn delete_state(&self, key: &str) -> Result<()> {
+        self.conn.execute("DELETE FROM sync_state WHERE key = ?1", params![key])?;
+        Ok(())
+    }
+
     pub fn get_all_cached_tasks(&self) -> Result<Vec<CachedTask>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, unique_id, list_id, title, status, updated, created, links, dirty,
@@ -381,34 +595,47 @@ impl SyncManager {
             })?
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(tasks)
+        tasks.into_iter().map(|task| self.unseal_task(task)).collect()
     }
 
     pub fn upsert_task(&self, task: &CachedTask) -> Result<()> {
+        self.write_row(&self.conn, task, task.dirty, change_source())
+    }
+
+    /// Write every column of a task row, regenerating the derived description,
+    /// and log what changed to task_history
+    fn write_row(&self, conn: &Connection, task: &CachedTask, dirty: bool, source: &str) -> Result<()> {
         let now = Utc::now().to_rfc3339();
-        let dirty_val: i32 = if task.dirty { 1 } else { 0 };
+        let dirty_val: i32 = if dirty { 1 } else { 0 };
         
         // Always regenerate taskgarden_description from properties
         let mut task_to_save = task.clone();
         task_to_save.taskgarden_description = task_to_save.generate_taskgarden_description();
-        
-        self.conn.execute(
-            "INSERT OR REPLACE INTO tasks (
+
+        self.record_changes(conn, &task_to_save, source, &now)?;
+        let title = self.seal(Some(&task_to_save.title));
+        let links = self.seal(task_to_save.links.as_deref());
+        let user_description = self.seal(task_to_save.user_description.as_deref());
+        let taskgarden_description = self.seal(Some(&task_to_save.taskgarden_description));
+
+        conn.execute(            "INSERT OR REPLACE INTO tasks (
                 id, unique_id, list_id, title, status, updated, created, links, 
                 last_synced, dirty, priority, project, context, duration, 
                 due_date, start_date, scheduled_date, tags, user_description, 
-                taskgarden_description
+                taskgarden_description, bump_count, parent_id
              )
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20)",
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20,
+                     COALESCE((SELECT bump_count FROM tasks WHERE id = ?1), 0),
+                     (SELECT parent_id FROM tasks WHERE id = ?1))",
             params![
                 &task_to_save.id,
                 &task_to_save.unique_id,
                 &task_to_save.list_id,
-                &task_to_save.title,
+                &title,
                 &task_to_save.status,
                 &task_to_save.updated,
                 &task_to_save.created,
-                &task_to_save.links,
+                &links,
                 &now,
                 &dirty_val,
                 &task_to_save.priority,
@@ -419,13 +646,91 @@ impl SyncManager {
                 &task_to_save.start_date,
                 &task_to_save.scheduled_date,
                 &task_to_save.tags,
-                &task_to_save.user_description,
-                &task_to_save.taskgarden_description,
+                &user_description,
+                &taskgarden_description,
             ],
         )?;
         Ok(())
     }
 
+    /// Log each tracked field that differs from the stored row (or the creation, if there is none)
+    fn record_changes(&self, conn: &Connection, task: &CachedTask, source: &str, at: &str) -> Result<()> {
+        type Fields = [Option<String>; 5];
+        let before: Option<Fields> = match conn.query_row(
+            "SELECT title, status, due_date, start_date, user_description FROM tasks WHERE id = ?1",
+            params![&task.id],
+            |row| Ok([row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?]),
+        ) {
+            Ok([title, status, due, start, notes]) => Some([self.unseal(title)?, status, due, start, self.unseal(notes)?]),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        let log = |field: &str, old: Option<&str>, new: Option<&str>| {
+            let (old, new) = if SEALED_HISTORY_FIELDS.contains(&field) {
+                (self.seal(old), self.seal(new))
+            } else {
+                (old.map(str::to_string), new.map(str::to_string))
+            };
+            conn.execute(
+                "INSERT INTO task_history (task_id, field, old_value, new_value, source, at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![&task.id, field, old, new, source, at],
+            )
+        };
+
+        let Some(before) = before else {
+            log("created", None, Some(&task.title))?;
+            return Ok(());
+        };
+        let after: Fields = [
+            Some(task.title.clone()),
+            Some(task.status.clone()),
+            task.due_date.clone(),
+            task.start_date.clone(),
+            task.user_description.clone(),
+        ];
+        for (field, (old, new)) in ["title", "status", "due", "start", "notes"].iter().zip(before.iter().zip(after.iter())) {
+            if old != new {
+                log(field, old.as_deref(), new.as_deref())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Change history for one task, oldest first
+    pub fn get_task_history(&self, task_id: &str) -> Result<Vec<TaskChange>> {
+        self.query_history("WHERE task_id = ?1", task_id)
+    }
+
+    /// Every recorded change since an RFC3339 timestamp (or date), oldest first
+    pub fn get_history_since(&self, since: &str) -> Result<Vec<TaskChange>> {
+        self.query_history("WHERE at >= ?1", since)
+    }
+
+    fn query_history(&self, filter: &str, param: &str) -> Result<Vec<TaskChange>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT task_id, field, old_value, new_value, source, at FROM task_history {} ORDER BY at, id",
+            filter
+        ))?;
+        let changes = stmt
+            .query_map(params![param], |row| {
+                Ok(TaskChange {
+                    task_id: row.get(0)?,
+                    field: row.get(1)?,
+                    old_value: row.get(2)?,
+                    new_value: row.get(3)?,
+                    source: row.get(4)?,
+                    at: row.get(5)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        changes.into_iter()
+            .map(|change| Ok(TaskChange { old_value: self.unseal(change.old_value)?, new_value: self.unseal(change.new_value)?, ..change }))
+            .collect()
+    }
+
+    #[allow(dead_code)]
     pub fn delete_task(&self, task_id: &str) -> Result<()> {
         self.conn.execute("DELETE FROM tasks WHERE id = ?1", params![task_id])?;
         Ok(())
@@ -468,7 +773,7 @@ impl SyncManager {
             },
         );
         match result {
-            Ok(task) => Ok(Some(task)),
+            Ok(task) => Ok(Some(self.unseal_task(task)?)),
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(e.into()),
         }
@@ -513,7 +818,7 @@ impl SyncManager {
             })?
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(tasks)
+        tasks.into_iter().map(|task| self.unseal_task(task)).collect()
     }
 
     /// Mark a task as clean (after successfully pushing to Google)
@@ -528,54 +833,60 @@ impl SyncManager {
     /// Upsert a task from remote (Google) - skips if local task is dirty
     pub fn upsert_task_from_remote(&self, task: &CachedTask) -> Result<bool> {
         // Check if local task exists and is dirty
-        if let Some(existing) = self.get_task_by_id(&task.id)? {
-            if existing.dirty {
-                // Don't overwrite dirty local changes
-                return Ok(false);
-            }
+        let existing = self.get_task_by_id(&task.id)?;
+        if existing.as_ref().map(|t| t.dirty).unwrap_or(false) {
+            // Don't overwrite dirty local changes
+            return Ok(false);
         }
 
-        let now = Utc::now().to_rfc3339();
-        self.conn.execute(
-            "INSERT OR REPLACE INTO tasks (id, list_id, title, status, updated, links, last_synced, dirty, created)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, ?8)",
-            params![
-                &task.id,
-                &task.list_id,
-                &task.title,
-                &task.status,
-                &task.updated,
-                &task.links,
-                &now,
-                &task.created,
-            ],
-        )?;
+        // Keep the TaskGarden UUID stable across syncs
+        let mut task = task.clone();
+        if let Some(existing) = existing {
+            task.unique_id = existing.unique_id;
+            // Start dates live only in the cache; Google doesn't know about them
+            if task.start_date.is_none() {
+                task.start_date = existing.start_date;
+            }
+        }
+        self.write_row(&self.conn, &task, false, "sync-remote")?;
         Ok(true)
     }
 
+    /// Upsert remote tasks (each with its Google parent ID) in a single transaction,
+    /// skipping dirty ones like `upsert_task_from_remote`. Returns the IDs written.
+    pub fn upsert_tasks_batch(&self, tasks: &[(CachedTask, Option<String>)]) -> Result<Vec<String>> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut written = Vec::new();
+        for (task, parent_id) in tasks {
+            if self.upsert_task_from_remote(task)? {
+                self.set_task_parent(&task.id, parent_id.as_deref())?;
+                written.push(task.id.clone());
+            }
+        }
+        tx.commit()?;
+        Ok(written)
+    }
+
     /// Upsert a task locally and mark it as dirty
     pub fn upsert_task_locally(&self, task: &CachedTask) -> Result<()> {
-        let now = Utc::now().to_rfc3339();
-        self.conn.execute(
-            "INSERT OR REPLACE INTO tasks (id, list_id, title, status, updated, links, last_synced, dirty, created)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 1, ?8)",
-            params![
-                &task.id,
-                &task.list_id,
-                &task.title,
-                &task.status,
-                &task.updated,
-                &task.links,
-                &now,
-                &task.created,
-            ],
-        )?;
+        self.write_row(&self.conn, task, true, change_source())
+    }
+
+    /// Upsert many tasks locally in a single transaction, marking them all dirty
+    pub fn upsert_tasks_locally(&self, tasks: &[CachedTask]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for task in tasks {
+            self.write_row(&tx, task, true, change_source())?;
+        }
+        tx.commit()?;
         Ok(())
     }
 
+
+    #[allow(dead_code)]
     pub fn clear_cache(&self) -> Result<()> {
         self.conn.execute("DELETE FROM tasks", [])?;
-        self.conn.execute("DELETE FROM sync_state", [])?;
+        self.conn.execute("DELETE FROM sync_state WHERE key != ?1", params![ENCRYPTION_CHECK])?;
         Ok(())
     }
 
@@ -608,14 +919,488 @@ impl SyncManager {
         Ok(())
     }
 
+    /// All dismissed pairs
+    pub fn get_dismissed_pairs(&self) -> Result<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare("SELECT task_id_1, task_id_2 FROM dismissed_pairs")?;
+        let pairs = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(pairs)
+    }
+
+    /// Forget that a pair was dismissed
+    pub fn undismiss_pair(&self, id1: &str, id2: &str) -> Result<()> {
+        let (a, b) = if id1 < id2 { (id1, id2) } else { (id2, id1) };
+        self.conn.execute(
+            "DELETE FROM dismissed_pairs WHERE task_id_1 = ?1 AND task_id_2 = ?2",
+            params![a, b],
+        )?;
+        Ok(())
+    }
+
+    /// Queue a pair of tasks for review in the interactive merge
+    pub fn queue_merge_pair(&self, id1: &str, id2: &str) -> Result<()> {
+        let (a, b) = if id1 < id2 { (id1, id2) } else { (id2, id1) };
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "INSERT OR IGNORE INTO merge_queue (task_id_1, task_id_2, queued_at) VALUES (?1, ?2, ?3)",
+            params![a, b, now],
+        )?;
+        Ok(())
+    }
+
+    /// Get all queued merge pairs (oldest first)
+    pub fn get_merge_queue(&self) -> Result<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT task_id_1, task_id_2 FROM merge_queue ORDER BY queued_at"
+        )?;
+        let pairs = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(pairs)
+    }
+
+    /// Remove a pair from the merge queue (after it was merged, dismissed, or skipped)
+    pub fn remove_queued_pair(&self, id1: &str, id2: &str) -> Result<()> {
+        let (a, b) = if id1 < id2 { (id1, id2) } else { (id2, id1) };
+        self.conn.execute(
+            "DELETE FROM merge_queue WHERE task_id_1 = ?1 AND task_id_2 = ?2",
+            params![a, b],
+        )?;
+        Ok(())
+    }
+
+    /// Each task's stored unique_id, NULL included (get_all_cached_tasks fills those in)
+    pub fn get_unique_ids(&self) -> Result<Vec<(String, Option<String>)>> {
+        let mut stmt = self.conn.prepare("SELECT id, unique_id FROM tasks ORDER BY id")?;
+        let ids = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ids)
+    }
+
+    pub fn set_unique_id(&self, task_id: &str, unique_id: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE tasks SET unique_id = ?2 WHERE id = ?1",
+            params![task_id, unique_id],
+        )?;
+        Ok(())
+    }
+
     /// Delete a task by ID
     pub fn delete_task_by_id(&self, task_id: &str) -> Result<()> {
-        self.conn.execute("DELETE FROM tasks WHERE id = ?1", params![task_id])?;
-        // Also clean up any dismissed pairs involving this task
+        let deleted = self.conn.execute("DELETE FROM tasks WHERE id = ?1", params![task_id])?;
+        if deleted > 0 {
+            self.conn.execute(
+                "INSERT INTO task_history (task_id, field, old_value, new_value, source, at)
+                 VALUES (?1, 'deleted', NULL, NULL, ?2, ?3)",
+                params![task_id, change_source(), Utc::now().to_rfc3339()],
+            )?;
+        }
+        // Also clean up any dismissed or queued pairs involving this task
         self.conn.execute(
             "DELETE FROM dismissed_pairs WHERE task_id_1 = ?1 OR task_id_2 = ?1",
             params![task_id],
         )?;
+        self.conn.execute(
+            "DELETE FROM merge_queue WHERE task_id_1 = ?1 OR task_id_2 = ?1",
+            params![task_id],
+        )?;
+        self.remove_inbox_task(task_id)?;
+        Ok(())
+    }
+
+    /// Capture a thought into the local inbox (no network)
+    pub fn add_inbox_item(&self, title: &str) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "INSERT INTO inbox (title, created) VALUES (?1, ?2)",
+            params![self.seal(Some(title)), now],
+        )?;
+        Ok(())
+    }
+
+    /// Inbox items not yet created in Google, oldest first: (row id, title)
+    pub fn get_unpushed_inbox(&self) -> Result<Vec<(i64, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, title FROM inbox WHERE task_id IS NULL ORDER BY id"
+        )?;
+        let items = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<(i64, String)>, _>>()?;
+        items.into_iter().map(|(id, title)| Ok((id, self.unseal(Some(title))?.unwrap_or_default()))).collect()
+    }
+
+    /// Record the Google task an inbox item became
+    pub fn mark_inbox_pushed(&self, row_id: i64, task_id: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE inbox SET task_id = ?1 WHERE id = ?2",
+            params![task_id, row_id],
+        )?;
+        Ok(())
+    }
+
+    /// Task IDs of pushed inbox items still waiting for triage, oldest first
+    pub fn get_inbox_task_ids(&self) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT task_id FROM inbox WHERE task_id IS NOT NULL ORDER BY id"
+        )?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ids)
+    }
+
+    /// Take a task out of the inbox lane
+    pub fn remove_inbox_task(&self, task_id: &str) -> Result<()> {
+        self.conn.execute("DELETE FROM inbox WHERE task_id = ?1", params![task_id])?;
+        Ok(())
+    }
+
+    /// All calendar blocks created by TaskGarden, ordered by start
+    pub fn get_calendar_blocks(&self) -> Result<Vec<CalendarBlock>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT event_id, task_id, start, end FROM calendar_blocks ORDER BY start"
+        )?;
+        let blocks = stmt
+            .query_map([], |row| {
+                Ok(CalendarBlock {
+                    event_id: row.get(0)?,
+                    task_id: row.get(1)?,
+                    start: row.get(2)?,
+                    end: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(blocks)
+    }
+
+    /// Record (or move) a calendar block; a moved block is asked about again once it passes
+    pub fn save_calendar_block(&self, block: &CalendarBlock) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "INSERT INTO calendar_blocks (event_id, task_id, start, end, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(event_id) DO UPDATE SET task_id = ?2, start = ?3, end = ?4, reconciled = 0",
+            params![block.event_id, block.task_id, block.start, block.end, now],
+        )?;
+        Ok(())
+    }
+
+    /// Blocks that ended by `now` (RFC3339 UTC) and haven't been reconciled, oldest first
+    pub fn get_unreconciled_blocks(&self, now: &str) -> Result<Vec<CalendarBlock>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT event_id, task_id, start, end FROM calendar_blocks
+             WHERE reconciled = 0 AND end <= ?1 ORDER BY start"
+        )?;
+        let blocks = stmt
+            .query_map(params![now], |row| {
+                Ok(CalendarBlock {
+                    event_id: row.get(0)?,
+                    task_id: row.get(1)?,
+                    start: row.get(2)?,
+                    end: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(blocks)
+    }
+
+    /// Stop asking about a passed calendar block
+    pub fn mark_block_reconciled(&self, event_id: &str) -> Result<()> {
+        self.conn.execute("UPDATE calendar_blocks SET reconciled = 1 WHERE event_id = ?1", params![event_id])?;
+        Ok(())
+    }
+
+    /// Forget a calendar block (after its event was deleted)
+    pub fn remove_calendar_block(&self, event_id: &str) -> Result<()> {
+        self.conn.execute("DELETE FROM calendar_blocks WHERE event_id = ?1", params![event_id])?;
+        Ok(())
+    }
+
+    /// Remember which task (if any) a task is nested under in Google Tasks
+    pub fn set_task_parent(&self, task_id: &str, parent_id: Option<&str>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE tasks SET parent_id = ?2 WHERE id = ?1",
+            params![task_id, parent_id],
+        )?;
+        Ok(())
+    }
+
+    /// Task ID → parent task ID, for every subtask
+    pub fn get_parent_ids(&self) -> Result<std::collections::HashMap<String, String>> {
+        let mut stmt = self.conn.prepare("SELECT id, parent_id FROM tasks WHERE parent_id IS NOT NULL")?;
+        let parents = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<std::collections::HashMap<_, _>, _>>()?;
+        Ok(parents)
+    }
+
+    /// IDs of the tasks nested under `parent_id`
+    pub fn get_subtask_ids(&self, parent_id: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare("SELECT id FROM tasks WHERE parent_id = ?1")?;
+        let ids = stmt
+            .query_map(params![parent_id], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(ids)
+    }
+
+    /// Record that these tasks were manually pushed to a later date
+    pub fn increment_bump_counts(&self, task_ids: &[&str]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare("UPDATE tasks SET bump_count = COALESCE(bump_count, 0) + 1 WHERE id = ?1")?;
+            for task_id in task_ids {
+                stmt.execute(params![task_id])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Bump counts for every task that has been bumped at least once
+    pub fn get_bump_counts(&self) -> Result<std::collections::HashMap<String, i64>> {
+        let mut stmt = self.conn.prepare("SELECT id, bump_count FROM tasks WHERE bump_count > 0")?;
+        let counts = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<std::collections::HashMap<_, _>, _>>()?;
+        Ok(counts)
+    }
+
+    /// Log an escalation (to_priority set) or flag (to_priority None).
+    /// Returns false if this task was already logged for the same reason.
+    pub fn log_priority_drift(&self, task_id: &str, title: &str, from: Option<&str>, to: Option<&str>, reason: &str) -> Result<bool> {
+        let now = Utc::now().to_rfc3339();
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO priority_drift (task_id, title, from_priority, to_priority, reason, at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![task_id, self.seal(Some(title)), from, to, reason, now],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Drift entries logged since an RFC3339 timestamp, newest first
+    pub fn get_priority_drift(&self, since: &str) -> Result<Vec<PriorityDrift>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT task_id, title, from_priority, to_priority, reason, at FROM priority_drift
+             WHERE at >= ?1 ORDER BY at DESC"
+        )?;
+        let entries = stmt
+            .query_map(params![since], |row| {
+                Ok(PriorityDrift {
+                    task_id: row.get(0)?,
+                    title: row.get(1)?,
+                    from_priority: row.get(2)?,
+                    to_priority: row.get(3)?,
+                    reason: row.get(4)?,
+                    at: row.get(5)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        entries.into_iter()
+            .map(|entry| Ok(PriorityDrift { title: self.unseal(Some(entry.title))?.unwrap_or_default(), ..entry }))
+            .collect()
+    }
+
+    /// Record time spent on a task; source is "timer", "focus" or "calendar"
+    pub fn log_time(&self, task_id: &str, seconds: i64, source: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO time_entries (task_id, seconds, source, at) VALUES (?1, ?2, ?3, ?4)",
+            params![task_id, seconds, source, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Store one snapshot: (project, open tasks, estimated minutes) rows taken at `at`
+    pub fn save_snapshot(&self, at: &str, rows: &[(String, i64, i64)]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO snapshots (at, project, open_tasks, estimated_minutes) VALUES (?1, ?2, ?3, ?4)"
+            )?;
+            for (project, open, minutes) in rows {
+                stmt.execute(params![at, project, open, minutes])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Snapshots since an RFC3339 timestamp (or date), oldest first, for one project or all.
+    /// A project missing from a snapshot had nothing open then.
+    pub fn get_snapshots(&self, since: &str, project: Option<&str>) -> Result<Vec<Snapshot>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT at,
+                    COALESCE(SUM(CASE WHEN ?2 IS NULL OR project = ?2 THEN open_tasks END), 0),
+                    COALESCE(SUM(CASE WHEN ?2 IS NULL OR project = ?2 THEN estimated_minutes END), 0)
+             FROM snapshots WHERE at >= ?1 GROUP BY at ORDER BY at"
+        )?;
+        let snapshots = stmt
+            .query_map(params![since, project], |row| {
+                Ok(Snapshot { at: row.get(0)?, open_tasks: row.get(1)?, estimated_minutes: row.get(2)? })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(snapshots)
+    }
+
+    /// Add a goal and return its ID
+    pub fn add_goal(&self, title: &str, quarter: &str) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO goals (title, quarter, created_at) VALUES (?1, ?2, ?3)",
+            params![self.seal(Some(title)), quarter, Utc::now().to_rfc3339()],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Every goal, oldest first
+    pub fn get_goals(&self) -> Result<Vec<Goal>> {
+        let mut stmt = self.conn.prepare("SELECT id, title, quarter FROM goals ORDER BY id")?;
+        let goals = stmt
+            .query_map([], |row| Ok(Goal { id: row.get(0)?, title: row.get(1)?, quarter: row.get(2)? }))?
+            .collect::<Result<Vec<Goal>, _>>()?;
+        goals.into_iter()
+            .map(|goal| Ok(Goal { title: self.unseal(Some(goal.title))?.unwrap_or_default(), ..goal }))
+            .collect()
+    }
+
+    /// Roll a task up to a goal (replacing any goal it had)
+    pub fn link_goal(&self, task_id: &str, goal_id: i64) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO goal_links (task_id, goal_id) VALUES (?1, ?2)",
+            params![task_id, goal_id],
+        )?;
         Ok(())
     }
+
+    /// Goal ID per linked task ID
+    pub fn get_goal_links(&self) -> Result<std::collections::HashMap<String, i64>> {
+        let mut stmt = self.conn.prepare("SELECT task_id, goal_id FROM goal_links")?;
+        let links = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_, _>>()?;
+        Ok(links)
+    }
+
+    pub fn add_reminder(&self, task_id: &str, remind_at: &str) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO reminders (task_id, remind_at) VALUES (?1, ?2)",
+            params![task_id, remind_at],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Reminders not fired yet, soonest first
+    pub fn get_pending_reminders(&self) -> Result<Vec<Reminder>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, task_id, remind_at FROM reminders WHERE fired = 0 ORDER BY remind_at"
+        )?;
+        let reminders = stmt
+            .query_map([], |row| Ok(Reminder { id: row.get(0)?, task_id: row.get(1)?, remind_at: row.get(2)? }))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(reminders)
+    }
+
+    pub fn mark_reminder_fired(&self, id: i64) -> Result<()> {
+        self.conn.execute("UPDATE reminders SET fired = 1 WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Drop a task's pending reminders; returns how many there were
+    pub fn clear_reminders(&self, task_id: &str) -> Result<usize> {
+        Ok(self.conn.execute("DELETE FROM reminders WHERE task_id = ?1 AND fired = 0", params![task_id])?)
+    }
+
+    /// Total tracked seconds per task ID
+    pub fn get_tracked_seconds(&self) -> Result<std::collections::HashMap<String, i64>> {
+        let mut stmt = self.conn.prepare("SELECT task_id, SUM(seconds) FROM time_entries GROUP BY task_id")?;
+        let totals = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_, _>>()?;
+        Ok(totals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote_task(id: &str, title: &str) -> CachedTask {
+        CachedTask::parse_from_emoji_string(title, id, "list")
+    }
+
+    #[test]
+    fn test_pick_assignee() {
+        let assignees = HashMap::from([("+Sam".to_string(), 1), ("WORK".to_string(), 2)]);
+        let pick = |title, project| pick_assignee(&assignees, Some(&9), title, project).copied();
+        assert_eq!(pick("[2026-01-27][P1][WORK] Review deck +sam", Some("WORK")), Some(1));
+        assert_eq!(pick("[2026-01-27][P1][WORK] Review deck +alex", Some("work")), Some(2));
+        assert_eq!(pick("[2026-01-27][P1][LIFE] Taxes", Some("LIFE")), Some(9));
+        assert_eq!(pick_assignee(&assignees, None, "Taxes", None), None);
+    }
+
+    #[test]
+    fn test_upsert_tasks_batch_skips_dirty_tasks() {
+        let db = SyncManager::open(Path::new(":memory:")).unwrap();
+        let mut local = remote_task("b", "Edited locally");
+        local.dirty = true;
+        db.upsert_task_locally(&local).unwrap();
+
+        let batch = vec![
+            (remote_task("a", "Parent"), None),
+            (remote_task("b", "Stale remote title"), None),
+            (remote_task("c", "Child"), Some("a".to_string())),
+        ];
+        assert_eq!(db.upsert_tasks_batch(&batch).unwrap(), vec!["a", "c"]);
+        assert_eq!(db.get_task_by_id("b").unwrap().unwrap().title, "Edited locally");
+        assert_eq!(db.get_subtask_ids("a").unwrap(), vec!["c"]);
+    }
+
+    #[test]
+    fn test_encrypted_cache() {
+        let mut db = SyncManager::open(Path::new(":memory:")).unwrap();
+        let mut task = remote_task("a", "Salary review");
+        task.user_description = Some("Ask for 10%".into());
+        db.upsert_task_locally(&task).unwrap();
+        db.add_inbox_item("Call the lawyer").unwrap();
+        db.add_goal("Get promoted", "2026-Q3").unwrap();
+        db.set_state(crate::mstodo::TOKEN_STATE, r#"{"refresh_token":"M.R3_secret"}"#).unwrap();
+        db.set_state(crate::serve::WEBHOOK_KEY, r#"{"secret":"hmac_secret"}"#).unwrap();
+        let raw_state = |db: &SyncManager, key: &str| -> String {
+            db.conn.query_row("SELECT value FROM sync_state WHERE key = ?1", params![key], |row| row.get(0)).unwrap()
+        };
+        let raw = |db: &SyncManager| -> (String, String) {
+            db.conn.query_row("SELECT title, user_description FROM tasks WHERE id = 'a'", [], |row| Ok((row.get(0)?, row.get(1)?))).unwrap()
+        };
+
+        db.reseal(Some(Cipher::new(&[3; 32]))).unwrap();
+        let (title, notes) = raw(&db);
+        assert!(!title.contains("Salary") && !notes.contains("10%"));
+        let inbox: String = db.conn.query_row("SELECT title FROM inbox", [], |row| row.get(0)).unwrap();
+        assert!(!inbox.contains("lawyer"));
+        let goal: String = db.conn.query_row("SELECT title FROM goals", [], |row| row.get(0)).unwrap();
+        assert!(!goal.contains("promoted"));
+        assert!(!raw_state(&db, crate::mstodo::TOKEN_STATE).contains("M.R3_secret"));
+        assert!(!raw_state(&db, crate::serve::WEBHOOK_KEY).contains("hmac_secret"));
+        assert_eq!(db.get_goals().unwrap()[0].title, "Get promoted");
+        assert_eq!(db.get_state(crate::serve::WEBHOOK_KEY).unwrap().as_deref(), Some(r#"{"secret":"hmac_secret"}"#));
+
+        // Credentials saved while encrypted are sealed too
+        db.set_state(crate::mstodo::TOKEN_STATE, r#"{"refresh_token":"M.R3_newer"}"#).unwrap();
+        assert!(!raw_state(&db, crate::mstodo::TOKEN_STATE).contains("M.R3_newer"));
+
+        // Reads and change tracking see plaintext
+        task.title = "Salary review (moved)".into();
+        db.upsert_task_locally(&task).unwrap();
+        let stored = db.get_task_by_id("a").unwrap().unwrap();
+        assert_eq!((stored.title.as_str(), stored.user_description.as_deref()), ("Salary review (moved)", Some("Ask for 10%")));
+        let history = db.get_task_history("a").unwrap();
+        let fields: Vec<_> = history.iter().map(|c| (c.field.as_str(), c.new_value.as_deref())).collect();
+        assert_eq!(fields, [("created", Some("Salary review")), ("title", Some("Salary review (moved)"))]);
+        assert_eq!(db.get_unpushed_inbox().unwrap()[0].1, "Call the lawyer");
+
+        db.reseal(None).unwrap();
+        assert_eq!(raw(&db), ("Salary review (moved)".into(), "Ask for 10%".into()));
+        assert_eq!(raw_state(&db, crate::mstodo::TOKEN_STATE), r#"{"refresh_token":"M.R3_newer"}"#);
+        assert!(!db.is_encrypted());
+    }
 }
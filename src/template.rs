@@ -0,0 +1,200 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+
+use crate::Task;
+
+// Format templates: `format` (the stored title), `formats.<name>` for `list --template`
+// and `formats.focus` for the focus view. A template is parsed once into literal text
+// and `{field}` placeholders, so a title that happens to contain "{date}" is left alone.
+// `{{` and `}}` are literal braces; an unknown `{name}` renders as written.
+// Views render `{date}` relative to today ("in 3d") unless asked for `--absolute`.
+
+pub const FIELDS: [&str; 13] = [
+    "date", "priority", "project", "status", "context", "time", "title",
+    "id", "age", "due_in", "tags", "list", "person",
+];
+
+/// Built in for `list --template` when config has no `formats` entry of the same name
+pub const BUILTIN: [(&str, &str); 2] = [
+    ("compact", "{id} {priority} {title}"),
+    ("detailed", "{id} [{date}][{priority}][{project}]{status}{context}{time} {title} · {due_in} · {age} old · {list}"),
+];
+
+#[derive(Debug, PartialEq)]
+enum Piece<'a> {
+    Literal(&'a str),
+    Field(&'a str),
+}
+
+fn tokenize(template: &str) -> Vec<Piece<'_>> {
+    let mut pieces = Vec::new();
+    let mut rest = template;
+    while !rest.is_empty() {
+        let next = rest.find(['{', '}']).unwrap_or(rest.len());
+        if next > 0 {
+            pieces.push(Piece::Literal(&rest[..next]));
+            rest = &rest[next..];
+            continue;
+        }
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            pieces.push(Piece::Literal(&rest[..1]));
+            rest = &rest[2..];
+        } else if let Some(end) = rest.starts_with('{').then(|| rest.find('}')).flatten() {
+            let name = &rest[1..end];
+            if FIELDS.contains(&name) {
+                pieces.push(Piece::Field(name));
+            } else {
+                pieces.push(Piece::Literal(&rest[..=end]));
+            }
+            rest = &rest[end + 1..];
+        } else {
+            pieces.push(Piece::Literal(&rest[..1]));
+            rest = &rest[1..];
+        }
+    }
+    pieces
+}
+
+/// Fail on a placeholder that isn't a field, naming the ones that are
+pub fn validate(template: &str) -> Result<()> {
+    let mut rest = template.replace("{{", "").replace("}}", "");
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else { break };
+        let name = &rest[start + 1..start + len];
+        if !FIELDS.contains(&name) {
+            anyhow::bail!("Unknown placeholder '{{{}}}' in template (fields: {})", name, FIELDS.join(", "));
+        }
+        rest = rest[start + len + 1..].to_string();
+    }
+    Ok(())
+}
+
+/// A number of days as "3d", "2w", "4mo" or "1y"
+pub fn span(days: i64) -> String {
+    let days = days.abs();
+    match days {
+        0..=13 => format!("{}d", days),
+        14..=59 => format!("{}w", days / 7),
+        60..=364 => format!("{}mo", days / 30),
+        _ => format!("{}y", days / 365),
+    }
+}
+
+/// "today", "tomorrow", "yesterday", "in 3d" or "2w ago"
+pub fn relative_day(day: NaiveDate, today: NaiveDate) -> String {
+    match (day - today).num_days() {
+        0 => "today".into(),
+        1 => "tomorrow".into(),
+        -1 => "yesterday".into(),
+        n if n > 0 => format!("in {}", span(n)),
+        n => format!("{} ago", span(n)),
+    }
+}
+
+/// A YYYY-MM-DD day as shown in views: relative unless `absolute` (anything else as-is)
+pub fn day_label(date: &str, today: NaiveDate, absolute: bool) -> String {
+    match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        Ok(day) if !absolute => relative_day(day, today),
+        _ => date.to_string(),
+    }
+}
+
+fn field(task: &Task, name: &str, today: NaiveDate, relative: bool) -> String {
+    let day = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+    match name {
+        "date" => day_label(&task.date, today, !relative),
+        "priority" => task.priority.clone().unwrap_or_else(|| "--".into()),
+        "project" => task.project.clone().unwrap_or_else(|| "---".into()),
+        "status" => task.status.as_ref().map(|s| format!("[{}]", s)).unwrap_or_default(),
+        "context" => task.context.as_ref().map(|c| format!("[{}]", c)).unwrap_or_default(),
+        // Time keeps its braces so the title parses back ({2h})
+        "time" => task.time.as_ref().map(|t| format!("{{{}}}", t)).unwrap_or_default(),
+        "title" => {
+            // Hashtags parsed from the title are still in it; only append ones that aren't
+            let missing_tags: Vec<String> = task.tags.iter()
+                .map(|t| format!("#{}", t))
+                .filter(|t| !task.title.contains(t.as_str()))
+                .collect();
+            if missing_tags.is_empty() {
+                task.title.clone()
+            } else {
+                format!("{} {}", task.title, missing_tags.join(" "))
+            }
+        }
+        "id" => task.short_id(),
+        "age" => task.created.as_deref().and_then(day)
+            .map(|created| span((today - created).num_days().max(0)))
+            .unwrap_or_else(|| "?".into()),
+        "due_in" => match day(&task.date).map(|due| (due - today).num_days()) {
+            None => "no date".into(),
+            Some(0) => "today".into(),
+            Some(n) if n > 0 => format!("in {}", span(n)),
+            Some(n) => format!("{} overdue", span(n)),
+        },
+        "tags" => task.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" "),
+        "list" => task.list.clone(),
+        "person" => task.person().map(|p| format!("+{}", p)).unwrap_or_default(),
+        _ => format!("{{{}}}", name),
+    }
+}
+
+/// Fill in `template` for one task; `today` anchors {age} and {due_in}
+pub fn render(template: &str, task: &Task, today: NaiveDate) -> String {
+    render_with(template, task, today, false)
+}
+
+/// Like `render`, with {date} relative to today
+pub fn render_relative(template: &str, task: &Task, today: NaiveDate) -> String {
+    render_with(template, task, today, true)
+}
+
+fn render_with(template: &str, task: &Task, today: NaiveDate, relative: bool) -> String {
+    tokenize(template).into_iter()
+        .map(|piece| match piece {
+            Piece::Literal(text) => text.to_string(),
+            Piece::Field(name) => field(task, name, today, relative),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let mut task = Task::parse_with_config("[2026-03-06][P1][WORK]{30m} Ship {date} fix #infra", "Inbox", None);
+        task.id = Some("abcdef123456".into());
+        task.created = Some("2026-03-01".into());
+        task.tags.push("urgent".into());
+        let today = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+
+        assert_eq!(render("{id} {priority} {title}", &task, today), "abcdef12 P1 Ship {date} fix #infra #urgent");
+        assert_eq!(render("{due_in} · {age} · {tags} · {list}", &task, today), "in 2d · 3d · #infra #urgent · Inbox");
+        assert_eq!(render("{{id}} {nope} {time}", &task, today), "{id} {nope} {30m}");
+
+        task.date = "2026-03-01".into();
+        task.created = None;
+        assert_eq!(render("{due_in} {age}", &task, today), "3d overdue ?");
+
+        assert_eq!(render_relative("[{date}] {title}", &task, today), "[3d ago] Ship {date} fix #infra #urgent");
+
+        assert!(validate("{id} {{literal}} {due_in}").is_ok());
+        assert!(validate("{id} {nope}").is_err());
+    }
+
+    #[test]
+    fn test_relative_day() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let rel = |y, m, d| relative_day(NaiveDate::from_ymd_opt(y, m, d).unwrap(), today);
+        assert_eq!(rel(2026, 3, 4), "today");
+        assert_eq!(rel(2026, 3, 5), "tomorrow");
+        assert_eq!(rel(2026, 3, 3), "yesterday");
+        assert_eq!(rel(2026, 3, 7), "in 3d");
+        assert_eq!(rel(2026, 2, 18), "2w ago");
+        assert_eq!(rel(2026, 6, 4), "in 3mo");
+        assert_eq!(rel(2025, 1, 1), "1y ago");
+        assert_eq!(day_label("2026-03-07", today, true), "2026-03-07");
+        assert_eq!(day_label("someday", today, false), "someday");
+    }
+}
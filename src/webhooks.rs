@@ -0,0 +1,337 @@
+use anyhow::Result;
+use chrono::{Local, Utc};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+
+use crate::logging::{redact_url, LoggedRequest};
+use crate::sync::TaskChange;
+use crate::{export, get_tasks_from_cache, output, App, Config, Task};
+
+// Outbound webhooks. After each sync, task events are POSTed as JSON to every
+// configured URL whose filter matches. Created/completed/escalated come from
+// task_history, so changes made by commands between syncs go out with the next
+// sync; overdue comes from a pass over the cache, once per task and due date. The
+// first sync with webhooks configured only notes where things stand, so adding a
+// webhook doesn't replay the past.
+
+/// sync_state key: `at` of the newest task_history entry already turned into events
+const CURSOR_KEY: &str = "webhooks_history_cursor";
+/// sync_state key: task ID → due date, for overdue tasks already announced
+const OVERDUE_KEY: &str = "webhooks_overdue";
+/// sync_state key: deliveries that failed every attempt, retried on the next sync
+pub const RETRY_KEY: &str = "webhooks_retry";
+/// Failed deliveries kept for retry; the oldest are dropped past this
+const RETRY_LIMIT: usize = 500;
+
+/// Tries per delivery; waits 1s, 2s, ... between them
+const ATTEMPTS: u32 = 3;
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Webhook {
+    pub url: String,
+    /// Events to send: created, completed, escalated, overdue (all when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+    /// Extra request headers, e.g. {"Authorization": "Bearer ..."}
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+}
+
+impl Webhook {
+    fn wants(&self, event: Event) -> bool {
+        self.events.is_empty()
+            || self.events.iter().any(|e| e.trim_start_matches("task.").eq_ignore_ascii_case(event.name()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Event {
+    Created,
+    Completed,
+    /// Priority changed to the top one (P0 by default)
+    Escalated,
+    Overdue,
+}
+
+impl Event {
+    fn name(self) -> &'static str {
+        match self {
+            Event::Created => "created",
+            Event::Completed => "completed",
+            Event::Escalated => "escalated",
+            Event::Overdue => "overdue",
+        }
+    }
+}
+
+/// An event about to be sent: which task, and its title at the time
+#[derive(Debug, PartialEq)]
+struct Pending {
+    event: Event,
+    task_id: String,
+    title: String,
+    source: String,
+}
+
+/// One POST to one webhook, as queued for retry
+#[derive(Debug, Serialize, Deserialize)]
+struct Delivery {
+    url: String,
+    payload: serde_json::Value,
+}
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    /// "task.created", "task.completed", "task.escalated" or "task.overdue"
+    event: String,
+    at: &'a str,
+    /// What made the change (cli, triage, auto, ...), or "sync" for overdue
+    source: &'a str,
+    /// The task as `export` writes it (just id and title if it has left the cache)
+    task: serde_json::Value,
+}
+
+/// Send events for everything that happened since the last call. Delivery failures
+/// are retried, then reported and queued for the next sync; they never fail the sync.
+pub fn fire(app: &App) -> Result<()> {
+    let hooks = &app.config.webhooks;
+    if hooks.is_empty() {
+        return Ok(());
+    }
+
+    let db = &app.db;
+    let now = Utc::now().to_rfc3339();
+    let tasks = get_tasks_from_cache(app)?;
+    let overdue = overdue_tasks(&tasks);
+
+    let Some(cursor) = db.get_state(CURSOR_KEY)? else {
+        // First run: start from here
+        db.set_state(CURSOR_KEY, &now)?;
+        db.set_state(OVERDUE_KEY, &serde_json::to_string(&overdue)?)?;
+        return Ok(());
+    };
+
+    let changes: Vec<TaskChange> = db.get_history_since(&cursor)?.into_iter().filter(|c| c.at > cursor).collect();
+    let by_id: HashMap<&str, &Task> = tasks.iter().filter_map(|t| Some((t.id.as_deref()?, t))).collect();
+    let mut pending = events_from_history(&changes, &app.config);
+
+    let announced: HashMap<String, String> = db.get_state(OVERDUE_KEY)?
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    for (id, date) in &overdue {
+        if announced.get(id) != Some(date) {
+            let title = by_id.get(id.as_str()).map(|t| t.title.clone()).unwrap_or_default();
+            pending.push(Pending { event: Event::Overdue, task_id: id.clone(), title, source: "sync".to_string() });
+        }
+    }
+
+    // Earlier failures go first, so each webhook still gets events in order
+    let mut deliveries: Vec<Delivery> = db.get_state(RETRY_KEY)?
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    for item in &pending {
+        let hooks: Vec<&Webhook> = hooks.iter().filter(|h| h.wants(item.event)).collect();
+        if hooks.is_empty() {
+            continue;
+        }
+        let task = match by_id.get(item.task_id.as_str()) {
+            Some(task) => export::describe(app, &[task])?.into_iter().next().map(serde_json::to_value).transpose()?,
+            None => None,
+        };
+        let payload = Payload {
+            event: format!("task.{}", item.event.name()),
+            at: &now,
+            source: &item.source,
+            task: task.unwrap_or_else(|| serde_json::json!({ "id": item.task_id, "title": item.title })),
+        };
+        let payload = serde_json::to_value(&payload)?;
+        deliveries.extend(hooks.iter().map(|hook| Delivery { url: hook.url.clone(), payload: payload.clone() }));
+    }
+
+    let client = reqwest::blocking::Client::builder().timeout(TIMEOUT).build()?;
+    let (sent, mut failed) = deliver_all(&client, hooks, deliveries);
+    if failed.len() > RETRY_LIMIT {
+        let dropped = failed.drain(..failed.len() - RETRY_LIMIT).count();
+        eprintln!("{}", format!("⚠ Dropped {} webhook events that kept failing", dropped).yellow());
+    }
+    if failed.is_empty() {
+        db.delete_state(RETRY_KEY)?;
+    } else {
+        eprintln!("{}", format!("⚠ {} webhook events will be retried on the next sync", failed.len()).yellow());
+        db.set_state(RETRY_KEY, &serde_json::to_string(&failed)?)?;
+    }
+
+    if let Some(last) = changes.last() {
+        db.set_state(CURSOR_KEY, &last.at)?;
+    }
+    db.set_state(OVERDUE_KEY, &serde_json::to_string(&overdue)?)?;
+
+    tracing::info!(sent, failed = failed.len(), "webhooks fired");
+    if sent > 0 {
+        output::note(format!("🪝 Sent {} webhook events", sent).dimmed());
+    }
+    Ok(())
+}
+
+/// Open tasks dated before today, by ID → date
+fn overdue_tasks(tasks: &[Task]) -> HashMap<String, String> {
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    tasks.iter()
+        .filter(|t| !t.date.is_empty() && t.date < today && !t.is_done())
+        .filter_map(|t| Some((t.id.clone()?, t.date.clone())))
+        .collect()
+}
+
+/// Events in these history entries, oldest first. Completion is the status changing to
+/// completed; a title gaining a done priority (or the old [DONE]) counts too.
+fn events_from_history(changes: &[TaskChange], config: &Config) -> Vec<Pending> {
+    let priority = |title: &str| Task::parse_with_config(title, "Tasks", Some(config)).priority;
+    let mut pending: Vec<Pending> = Vec::new();
+    for change in changes {
+        let new = change.new_value.clone().unwrap_or_default();
+        let event = match change.field.as_str() {
+            "created" => Event::Created,
+            "status" if new == "completed" => Event::Completed,
+            "title" => {
+                let before = change.old_value.as_deref().and_then(priority);
+                let done = |p: Option<&str>| p.is_some_and(|p| config.is_done_priority(p));
+                match priority(&new) {
+                    Some(p) if done(Some(&p)) && !done(before.as_deref()) => Event::Completed,
+                    Some(p) if Some(p.as_str()) == config.top_priority() && before != Some(p.clone()) => Event::Escalated,
+                    _ => continue,
+                }
+            }
+            _ => continue,
+        };
+        if pending.iter().any(|p| p.event == event && p.task_id == change.task_id) {
+            continue;
+        }
+        let title = if change.field == "status" { String::new() } else { new };
+        pending.push(Pending { event, task_id: change.task_id.clone(), title, source: change.source.clone() });
+    }
+    pending
+}
+
+/// Try each delivery in order. Returns how many went out and the ones that failed; once a
+/// webhook fails, the rest of its deliveries wait for the next sync without being tried.
+/// Deliveries to webhooks no longer configured are dropped.
+fn deliver_all(client: &reqwest::blocking::Client, hooks: &[Webhook], deliveries: Vec<Delivery>) -> (usize, Vec<Delivery>) {
+    let mut sent = 0;
+    let mut failed = Vec::new();
+    let mut down: HashSet<String> = HashSet::new();
+    for delivery in deliveries {
+        let Some(hook) = hooks.iter().find(|h| h.url == delivery.url) else {
+            continue;
+        };
+        if down.contains(&hook.url) {
+            failed.push(delivery);
+            continue;
+        }
+        match deliver(client, hook, &delivery.payload) {
+            Ok(()) => sent += 1,
+            Err(e) => {
+                let event = delivery.payload["event"].as_str().unwrap_or_default();
+                eprintln!("{}", format!("⚠ Webhook {} failed for {}: {}", redact_url(&hook.url), event, e).yellow());
+                down.insert(hook.url.clone());
+                failed.push(delivery);
+            }
+        }
+    }
+    (sent, failed)
+}
+
+/// POST the payload, retrying network errors, 429s and 5xxs
+fn deliver(client: &reqwest::blocking::Client, hook: &Webhook, payload: &serde_json::Value) -> Result<()> {
+    let mut attempt = 1;
+    loop {
+        let mut request = client.post(&hook.url).json(payload);
+        for (name, value) in &hook.headers {
+            request = request.header(name, value);
+        }
+        // Zapier and n8n hook URLs are secrets in themselves
+        let error = match request.send_redacted() {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) if response.status().is_server_error() || response.status().as_u16() == 429 => {
+                format!("HTTP {}", response.status())
+            }
+            Ok(response) => anyhow::bail!("HTTP {}", response.status()),
+            Err(e) => e.to_string(),
+        };
+        if attempt == ATTEMPTS {
+            anyhow::bail!("{} (after {} attempts)", error, ATTEMPTS);
+        }
+        std::thread::sleep(Duration::from_secs(1 << (attempt - 1)));
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(task_id: &str, field: &str, old: Option<&str>, new: Option<&str>) -> TaskChange {
+        TaskChange {
+            task_id: task_id.into(),
+            field: field.into(),
+            old_value: old.map(Into::into),
+            new_value: new.map(Into::into),
+            source: "cli".into(),
+            at: "2026-01-21T09:00:00+00:00".into(),
+        }
+    }
+
+    #[test]
+    fn test_events_from_history() {
+        let config = crate::create_default_config();
+        let changes = vec![
+            change("a", "created", None, Some("Call the bank")),
+            change("b", "title", Some("[2026-01-21][P2][WORK] Ship it"), Some("[2026-01-21][P0][WORK] Ship it")),
+            change("b", "title", Some("[2026-01-21][P0][WORK] Ship it"), Some("[2026-01-21][P0][WORK] Ship it now")),
+            change("c", "title", Some("[2026-01-21][P1][LIFE] Taxes"), Some("[2026-01-21][DONE][LIFE] Taxes")),
+            change("c", "status", Some("needsAction"), Some("completed")),
+            change("d", "status", Some("needsAction"), Some("completed")),
+            change("e", "due", Some("2026-01-20"), Some("2026-01-21")),
+        ];
+        let pending = events_from_history(&changes, &config);
+        let events: Vec<(Event, &str)> = pending.iter().map(|p| (p.event, p.task_id.as_str())).collect();
+        assert_eq!(events, vec![
+            (Event::Created, "a"),
+            (Event::Escalated, "b"),
+            (Event::Completed, "c"),
+            (Event::Completed, "d"),
+        ]);
+    }
+
+    #[test]
+    fn test_event_filter() {
+        let hook = |events: &[&str]| Webhook {
+            url: "http://localhost/hook".into(),
+            events: events.iter().map(|e| e.to_string()).collect(),
+            headers: BTreeMap::new(),
+        };
+        assert!(hook(&[]).wants(Event::Overdue));
+        assert!(hook(&["task.completed", "Escalated"]).wants(Event::Escalated));
+        assert!(!hook(&["completed"]).wants(Event::Created));
+    }
+
+    #[test]
+    fn test_failed_deliveries_are_kept() {
+        let hook = |url: &str| Webhook { url: url.into(), events: vec![], headers: BTreeMap::new() };
+        let delivery = |url: &str, event: &str| Delivery { url: url.into(), payload: serde_json::json!({ "event": event }) };
+        // Nothing listens on port 9
+        let hooks = [hook("http://127.0.0.1:9/hooks/abc")];
+        let deliveries = vec![
+            delivery("http://127.0.0.1:9/hooks/abc", "task.created"),
+            delivery("http://removed.example/hook", "task.created"),
+            delivery("http://127.0.0.1:9/hooks/abc", "task.completed"),
+        ];
+        let client = reqwest::blocking::Client::builder().timeout(TIMEOUT).build().unwrap();
+        let (sent, failed) = deliver_all(&client, &hooks, deliveries);
+        assert_eq!(sent, 0);
+        let events: Vec<_> = failed.iter().map(|d| d.payload["event"].as_str().unwrap()).collect();
+        assert_eq!(events, ["task.created", "task.completed"]);
+    }
+}